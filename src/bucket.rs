@@ -1,8 +1,11 @@
 //! Bucket management for WenPM
 //!
-//! Buckets are remote manifest sources that can be added to WenPM.
+//! Buckets are manifest sources that can be added to WenPM: an HTTP(S) URL, a
+//! `file://` URL or a path to a manifest on disk (e.g. on a network drive).
 //! They use the same manifest format as local sources.
 
+use crate::utils::network::NetworkSettings;
+use crate::utils::HttpClient;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -30,10 +33,37 @@ fn default_enabled() -> bool {
     true
 }
 
-fn default_priority() -> u32 {
+/// Priority given to buckets that don't set one
+pub fn default_priority() -> u32 {
     100
 }
 
+/// Manifest file a bucket URL points at, for `file://` URLs and filesystem paths
+///
+/// Returns None for URLs of any other scheme (`http://`, `https://`).
+pub fn local_manifest_path(url: &str) -> Option<PathBuf> {
+    if let Some(path) = url.strip_prefix("file://") {
+        // file:///C:/buckets/main.json names C:/buckets/main.json
+        let path = match path.strip_prefix('/') {
+            Some(rest) if cfg!(windows) && rest.get(1..2) == Some(":") => rest,
+            _ => path,
+        };
+        return Some(PathBuf::from(path));
+    }
+    (!url.contains("://")).then(|| PathBuf::from(url))
+}
+
+/// Read the manifest of a bucket, from disk or over HTTP
+pub fn fetch_manifest_text(url: &str, settings: &NetworkSettings) -> Result<String> {
+    match local_manifest_path(url) {
+        Some(path) => fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read bucket manifest {}", path.display())),
+        None => HttpClient::with_settings(None, settings)?
+            .get_text(url)
+            .with_context(|| format!("Failed to fetch bucket from {}", url)),
+    }
+}
+
 /// Bucket configuration file
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BucketConfig {
@@ -142,7 +172,8 @@ impl BucketConfig {
     }
 
     /// Set bucket enabled state
-    #[allow(dead_code)]
+    ///
+    /// Returns false when no bucket has this name.
     pub fn set_enabled(&mut self, name: &str, enabled: bool) -> bool {
         if let Some(bucket) = self.find_bucket_mut(name) {
             bucket.enabled = enabled;
@@ -163,6 +194,30 @@ impl Default for BucketConfig {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_local_manifest_path() {
+        assert_eq!(
+            local_manifest_path("/mnt/share/bucket.json"),
+            Some(PathBuf::from("/mnt/share/bucket.json"))
+        );
+        assert_eq!(
+            local_manifest_path("file:///mnt/share/bucket.json"),
+            Some(PathBuf::from("/mnt/share/bucket.json"))
+        );
+        assert_eq!(
+            local_manifest_path("buckets/main.json"),
+            Some(PathBuf::from("buckets/main.json"))
+        );
+        assert_eq!(
+            local_manifest_path("https://example.com/manifest.json"),
+            None
+        );
+        assert_eq!(
+            local_manifest_path("http://example.com/manifest.json"),
+            None
+        );
+    }
+
     #[test]
     fn test_bucket_config_new() {
         let config = BucketConfig::new();
@@ -230,5 +285,12 @@ mod tests {
         let enabled = config.enabled_buckets();
         assert_eq!(enabled.len(), 1);
         assert_eq!(enabled[0].name, "bucket1");
+
+        assert!(config.set_enabled("bucket2", true));
+        assert!(config.set_enabled("bucket1", false));
+        assert!(!config.set_enabled("nonexistent", true));
+        let enabled = config.enabled_buckets();
+        assert_eq!(enabled.len(), 1);
+        assert_eq!(enabled[0].name, "bucket2");
     }
 }
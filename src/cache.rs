@@ -8,7 +8,7 @@ use crate::core::manifest::{Package, PackageSource, ScriptItem, SourceManifest};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::PathBuf;
 
@@ -157,8 +157,15 @@ impl ManifestCache {
     }
 
     /// Add a package to cache
-    pub fn add_package(&mut self, package: Package, source: PackageSource) {
+    ///
+    /// Install hooks from an existing entry are kept when the new package info
+    /// (e.g. fetched from the GitHub API) doesn't declare any.
+    pub fn add_package(&mut self, mut package: Package, source: PackageSource) {
         let repo = package.repo.clone();
+        if let Some(existing) = self.packages.get(&repo) {
+            package.inherit_install_hooks(&existing.package);
+            package.inherit_aliases(&existing.package);
+        }
         self.packages
             .insert(repo, CachedPackage { package, source });
     }
@@ -241,54 +248,72 @@ impl Default for ManifestCache {
     }
 }
 
+/// Build a cache from fetched bucket manifests
+///
+/// Buckets are merged by descending priority. When two buckets provide the same
+/// repo (or script name), the higher-priority one wins and the other entry is
+/// skipped, so each source's `package_count` only counts what it contributed.
 pub fn build_cache_from_results(
-    buckets_with_results: Vec<(Bucket, Result<SourceManifest>)>,
+    mut buckets_with_results: Vec<(Bucket, Result<SourceManifest>)>,
 ) -> ManifestCache {
     let mut cache = ManifestCache::new();
     cache.last_updated = Utc::now();
 
+    // Higher priority first; the sort is stable, so equal priorities keep their order
+    buckets_with_results.sort_by_key(|(bucket, _)| std::cmp::Reverse(bucket.priority));
+
     for (bucket, result) in buckets_with_results {
         let source_key = format!("bucket:{}", bucket.name);
         let now = Utc::now();
+        let source = PackageSource::Bucket {
+            name: bucket.name.clone(),
+        };
 
         match result {
             Ok(manifest) => {
-                let package_count = manifest.packages.len();
-                let script_count = manifest.scripts.len();
-                let total_count = package_count + script_count;
+                let mut retained = 0;
 
                 for package in manifest.packages {
-                    cache.add_package(
-                        package,
-                        PackageSource::Bucket {
-                            name: bucket.name.clone(),
-                        },
-                    );
+                    if let Some(kept) = cache.packages.get(&package.repo) {
+                        log::debug!(
+                            "Skipping {} from bucket '{}': '{}' already provides {}",
+                            package.name,
+                            bucket.name,
+                            source_label(&kept.source),
+                            package.repo
+                        );
+                        continue;
+                    }
+                    cache.add_package(package, source.clone());
+                    retained += 1;
                 }
 
                 for script in manifest.scripts {
-                    cache.add_script(
-                        script,
-                        PackageSource::Bucket {
-                            name: bucket.name.clone(),
-                        },
-                    );
+                    if let Some(kept) = cache.scripts.get(&script.name) {
+                        log::debug!(
+                            "Skipping script {} from bucket '{}': '{}' already provides it",
+                            script.name,
+                            bucket.name,
+                            source_label(&kept.source)
+                        );
+                        continue;
+                    }
+                    cache.add_script(script, source.clone());
+                    retained += 1;
                 }
 
                 cache.sources.insert(
                     source_key,
                     CachedSourceInfo {
-                        source: PackageSource::Bucket {
-                            name: bucket.name.clone(),
-                        },
-                        package_count: total_count,
+                        source,
+                        package_count: retained,
                         last_fetched: Some(now),
                         url: Some(bucket.url.clone()),
                     },
                 );
             }
             Err(e) => {
-                log::warn!("Failed to fetch bucket '{}': {}", bucket.name, e);
+                log::warn!("Failed to fetch bucket '{}': {:#}", bucket.name, e);
             }
         }
     }
@@ -296,6 +321,103 @@ pub fn build_cache_from_results(
     cache
 }
 
+/// Bucket name, repo URL or script origin of a source
+fn source_label(source: &PackageSource) -> &str {
+    match source {
+        PackageSource::Bucket { name } => name,
+        PackageSource::DirectRepo { url } => url,
+        PackageSource::Script { origin, .. } => origin,
+    }
+}
+
+/// What changed in one bucket between two caches
+#[derive(Debug, Default, PartialEq)]
+pub struct BucketDiff {
+    /// Names of packages and scripts that appeared
+    pub added: Vec<String>,
+    /// Names of packages and scripts that disappeared
+    pub removed: Vec<String>,
+    /// Packages whose declared version changed: (name, old, new)
+    pub updated: Vec<(String, Option<String>, Option<String>)>,
+}
+
+impl BucketDiff {
+    /// Whether nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.updated.is_empty()
+    }
+}
+
+/// Entries of each bucket in a cache: bucket name -> entry key -> (name, version)
+type BucketEntries<'a> = BTreeMap<&'a str, BTreeMap<String, (&'a str, Option<&'a str>)>>;
+
+/// Group the bucket entries of a cache, keying packages by repo URL and scripts by name
+fn bucket_entries(cache: &ManifestCache) -> BucketEntries<'_> {
+    let mut entries: BucketEntries = BTreeMap::new();
+    for (key, cp) in &cache.packages {
+        if let PackageSource::Bucket { name } = &cp.source {
+            entries.entry(name).or_default().insert(
+                format!("package:{}", key),
+                (&cp.package.name, cp.package.version.as_deref()),
+            );
+        }
+    }
+    for (key, cs) in &cache.scripts {
+        if let PackageSource::Bucket { name } = &cs.source {
+            entries
+                .entry(name)
+                .or_default()
+                .insert(format!("script:{}", key), (&cs.script.name, None));
+        }
+    }
+    entries
+}
+
+/// Compare two caches per bucket
+///
+/// Only buckets present in both caches' sources are compared, so a bucket that
+/// failed to fetch (or was fetched for the first time) is not reported as having
+/// lost or gained everything.
+pub fn diff_by_bucket(old: &ManifestCache, new: &ManifestCache) -> BTreeMap<String, BucketDiff> {
+    let old_entries = bucket_entries(old);
+    let new_entries = bucket_entries(new);
+    let empty = BTreeMap::new();
+
+    let mut diffs = BTreeMap::new();
+    for (source_key, info) in &new.sources {
+        let PackageSource::Bucket { name } = &info.source else {
+            continue;
+        };
+        if !old.sources.contains_key(source_key) {
+            continue;
+        }
+
+        let before = old_entries.get(name.as_str()).unwrap_or(&empty);
+        let after = new_entries.get(name.as_str()).unwrap_or(&empty);
+
+        let mut diff = BucketDiff::default();
+        for (key, (entry_name, version)) in after {
+            match before.get(key) {
+                None => diff.added.push(entry_name.to_string()),
+                Some((_, old_version)) if old_version != version => diff.updated.push((
+                    entry_name.to_string(),
+                    old_version.map(str::to_string),
+                    version.map(str::to_string),
+                )),
+                Some(_) => {}
+            }
+        }
+        for (key, (entry_name, _)) in before {
+            if !after.contains_key(key) {
+                diff.removed.push(entry_name.to_string());
+            }
+        }
+
+        diffs.insert(name.clone(), diff);
+    }
+    diffs
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -315,12 +437,15 @@ mod tests {
 
         let package = Package {
             name: "test".to_string(),
+            aliases: Vec::new(),
             description: "Test package".to_string(),
             repo: "https://github.com/test/test".to_string(),
             homepage: None,
             license: None,
             version: None,
             platforms: HashMap::new(),
+            pre_install: Vec::new(),
+            post_install: Vec::new(),
         };
 
         let source = PackageSource::Bucket {
@@ -334,6 +459,146 @@ mod tests {
         assert_eq!(cached.source, source);
     }
 
+    #[test]
+    fn test_diff_by_bucket() {
+        fn package(name: &str, version: Option<&str>) -> Package {
+            Package {
+                name: name.to_string(),
+                aliases: Vec::new(),
+                description: String::new(),
+                repo: format!("https://github.com/o/{}", name),
+                homepage: None,
+                license: None,
+                version: version.map(str::to_string),
+                platforms: HashMap::new(),
+                pre_install: Vec::new(),
+                post_install: Vec::new(),
+            }
+        }
+        fn bucket(name: &str) -> Bucket {
+            Bucket {
+                name: name.to_string(),
+                url: format!("https://example.com/{}.json", name),
+                enabled: true,
+                priority: 100,
+            }
+        }
+        fn manifest(packages: Vec<Package>) -> Result<SourceManifest> {
+            Ok(SourceManifest {
+                packages,
+                scripts: Vec::new(),
+            })
+        }
+
+        let old = build_cache_from_results(vec![
+            (
+                bucket("main"),
+                manifest(vec![
+                    package("rg", Some("13.0.0")),
+                    package("fd", None),
+                    package("bat", None),
+                ]),
+            ),
+            (bucket("extra"), manifest(vec![package("jq", None)])),
+        ]);
+        let new = build_cache_from_results(vec![
+            (
+                bucket("main"),
+                manifest(vec![
+                    package("rg", Some("14.0.0")),
+                    package("fd", None),
+                    package("eza", None),
+                ]),
+            ),
+            // A failed fetch must not show up as everything removed
+            (bucket("extra"), Err(anyhow::anyhow!("offline"))),
+            // First fetch of a new bucket is not diffed either
+            (bucket("fresh"), manifest(vec![package("yq", None)])),
+        ]);
+
+        let diffs = diff_by_bucket(&old, &new);
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(
+            diffs["main"],
+            BucketDiff {
+                added: vec!["eza".to_string()],
+                removed: vec!["bat".to_string()],
+                updated: vec![(
+                    "rg".to_string(),
+                    Some("13.0.0".to_string()),
+                    Some("14.0.0".to_string())
+                )],
+            }
+        );
+
+        assert!(diff_by_bucket(&new, &new)
+            .values()
+            .all(BucketDiff::is_empty));
+    }
+
+    #[test]
+    fn test_build_cache_prefers_higher_priority() {
+        fn package(name: &str, repo: &str) -> Package {
+            Package {
+                name: name.to_string(),
+                aliases: Vec::new(),
+                description: String::new(),
+                repo: repo.to_string(),
+                homepage: None,
+                license: None,
+                version: None,
+                platforms: HashMap::new(),
+                pre_install: Vec::new(),
+                post_install: Vec::new(),
+            }
+        }
+        fn bucket(name: &str, priority: u32) -> Bucket {
+            Bucket {
+                name: name.to_string(),
+                url: format!("https://example.com/{}.json", name),
+                enabled: true,
+                priority,
+            }
+        }
+
+        // The low-priority bucket comes first and must still lose the shared repo
+        let cache = build_cache_from_results(vec![
+            (
+                bucket("community", 50),
+                Ok(SourceManifest {
+                    packages: vec![
+                        package("rg-fork", "https://github.com/BurntSushi/ripgrep"),
+                        package("fd", "https://github.com/sharkdp/fd"),
+                    ],
+                    scripts: Vec::new(),
+                }),
+            ),
+            (
+                bucket("main", 200),
+                Ok(SourceManifest {
+                    packages: vec![
+                        package("ripgrep", "https://github.com/BurntSushi/ripgrep"),
+                        // A repeated repo in the same bucket keeps its first entry
+                        package("ripgrep-dup", "https://github.com/BurntSushi/ripgrep"),
+                    ],
+                    scripts: Vec::new(),
+                }),
+            ),
+        ]);
+
+        assert_eq!(cache.packages.len(), 2);
+        let rg = &cache.packages["https://github.com/BurntSushi/ripgrep"];
+        assert_eq!(rg.package.name, "ripgrep");
+        assert_eq!(
+            rg.source,
+            PackageSource::Bucket {
+                name: "main".to_string()
+            }
+        );
+        assert_eq!(cache.sources["bucket:main"].package_count, 1);
+        assert_eq!(cache.sources["bucket:community"].package_count, 1);
+    }
+
     #[test]
     fn test_is_valid() {
         let mut cache = ManifestCache::new();
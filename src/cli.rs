@@ -1,6 +1,7 @@
 //! CLI argument parsing for Wenget
 
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "wenget")]
@@ -17,13 +18,47 @@ use clap::{Parser, Subcommand};
 
 {all-args}{after-help}
 ")]
+#[command(after_help = "\
+Exit codes:
+  0   Success, or nothing to do
+  1   Error, or every item failed
+  2   Partial failure: some items of add, update or del failed
+  update --check: 0 up to date, 10 update available, 2 not installed, 3 check failed")]
 pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
 
-    /// Enable verbose logging
-    #[arg(long, global = true)]
+    /// Enable verbose (debug) logging
+    #[arg(long, global = true, conflicts_with = "quiet")]
     pub verbose: bool,
+
+    /// Only log errors
+    #[arg(short = 'q', long, global = true)]
+    pub quiet: bool,
+
+    /// Also write log output to a file (e.g. to attach to a bug report)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub log_file: Option<PathBuf>,
+
+    /// Disable colored output (also set by NO_COLOR or when stdout is not a terminal)
+    #[arg(long, global = true)]
+    pub no_color: bool,
+
+    /// Wait for another running wenget operation to finish instead of failing
+    #[arg(long, global = true)]
+    pub wait: bool,
+
+    /// Network timeout in seconds for this run (overrides WENGET_TIMEOUT and config.toml)
+    #[arg(long, global = true, value_name = "SECS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub timeout: Option<u64>,
+
+    /// Retry failed requests this many times, default 3 (overrides WENGET_RETRIES and config.toml)
+    #[arg(long, global = true, value_name = "N")]
+    pub retries: Option<u32>,
+
+    /// Never use the network: use cached manifests and downloads only (also set by WENGET_OFFLINE=1)
+    #[arg(long, global = true)]
+    pub offline: bool,
 }
 
 #[derive(Subcommand)]
@@ -34,11 +69,30 @@ pub enum Commands {
         command: BucketCommands,
     },
 
+    /// Inspect or clear the manifest cache
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommands,
+    },
+
+    /// Remove leftover downloads and the manifest cache (installed apps are kept)
+    Clean {
+        /// Only remove leftover downloads
+        #[arg(long, conflicts_with = "cache_only")]
+        downloads_only: bool,
+
+        /// Only remove the manifest cache
+        #[arg(long)]
+        cache_only: bool,
+    },
+
     /// Install packages or scripts from buckets ,GitHub repo, URLs, or local files
     #[command(visible_alias = "install")]
     #[command(visible_alias = "a")]
     Add {
-        /// Package names, GitHub URLs, or script paths/URLs to add (supports wildcards *)
+        /// Package names, GitHub URLs, or script paths/URLs to add (supports wildcards *).
+        /// Append @<range> to a package to pin updates to a semver range (e.g., ripgrep@^13),
+        /// or @<tag> to install and pin exactly that release (e.g., ripgrep@14.1.0)
         names: Vec<String>,
 
         /// Skip confirmation prompts
@@ -49,7 +103,8 @@ pub enum Commands {
         #[arg(short = 'c', long = "command")]
         script_name: Option<String>,
 
-        /// Specify target platform (e.g., windows-x64, linux-x64, darwin-arm64)
+        /// Specify target platform (e.g., windows-x64, linux-x64, darwin-arm64);
+        /// `all` downloads the binaries of every platform without installing them
         #[arg(short = 'p', long = "platform")]
         platform: Option<String>,
 
@@ -57,13 +112,71 @@ pub enum Commands {
         #[arg(short = 'v', long = "ver")]
         pkg_version: Option<String>,
 
+        /// Install the newest release even when it is a prerelease
+        #[arg(long, conflicts_with = "pkg_version")]
+        pre: bool,
+
         /// Specify variant to install (e.g., baseline, profile)
         #[arg(long = "variant")]
         variant: Option<String>,
 
+        /// Install the build for this compiler/libc and keep it on updates
+        /// (reinstalls an installed package built with another one)
+        #[arg(long, value_parser = ["gnu", "musl", "msvc"], conflicts_with = "platform")]
+        compiler: Option<String>,
+
+        /// Install the release asset whose name contains this text instead of
+        /// the detected one (e.g. --asset x86_64-unknown-linux-musl)
+        #[arg(long, value_name = "NAME", conflicts_with_all = ["variant", "compiler"])]
+        asset: Option<String>,
+
         /// Don't append variant suffix to command name
         #[arg(long = "no-suffix")]
         no_suffix: bool,
+
+        /// Create launchers in this directory instead of the configured bin directory
+        #[arg(long = "bin-dir", value_name = "PATH")]
+        bin_dir: Option<PathBuf>,
+
+        /// Keep the downloaded archive in cache/archives/<name>/<version>/
+        /// instead of deleting it; installing that version again reuses it
+        #[arg(long = "keep-download", alias = "keep-archive")]
+        keep_download: bool,
+
+        /// Re-download and re-extract even when the same version is installed
+        /// (repairs an incomplete install in place)
+        #[arg(short = 'f', long)]
+        force: bool,
+
+        /// Always choose the executables interactively, even when one candidate is clear
+        #[arg(long, conflicts_with_all = ["yes", "exe"])]
+        select: bool,
+
+        /// Use this file from the archive as executable (path or file name, repeatable)
+        #[arg(long, value_name = "PATH")]
+        exe: Vec<String>,
+
+        /// Link every likely executable in the archive (e.g. ffmpeg and ffprobe) without asking
+        #[arg(long, conflicts_with_all = ["select", "exe"])]
+        all_bins: bool,
+
+        /// Write the packages this command installed (version, URL, asset, checksum)
+        /// to a JSON file
+        #[arg(long = "emit-manifest", value_name = "PATH")]
+        emit_manifest: Option<PathBuf>,
+
+        /// Merge into an existing --emit-manifest file instead of overwriting it
+        #[arg(long = "append-manifest", requires = "emit_manifest")]
+        append_manifest: bool,
+
+        /// Also install the shell completions shipped in the archive (bash, zsh, fish)
+        #[arg(long)]
+        completions: bool,
+
+        /// Show the asset, command name and install directory of each package
+        /// without downloading or installing anything
+        #[arg(long = "dry-run", conflicts_with = "emit_manifest")]
+        dry_run: bool,
     },
 
     /// List installed packages
@@ -72,6 +185,22 @@ pub enum Commands {
         /// Show all available packages from buckets (not just installed)
         #[arg(short = 'a', long = "all")]
         all: bool,
+
+        /// Group installed packages by bucket, direct URL or script origin
+        #[arg(long = "by-source", conflicts_with = "all")]
+        by_source: bool,
+
+        /// Only packages installed on or after this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long, value_name = "DATE", conflicts_with_all = ["all", "by_source"])]
+        since: Option<String>,
+
+        /// Only packages installed before this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long, value_name = "DATE", conflicts_with_all = ["all", "by_source"])]
+        before: Option<String>,
+
+        /// Print the installed packages as a JSON array (with --all, scripts included)
+        #[arg(long, conflicts_with = "by_source")]
+        json: bool,
     },
 
     /// Show package information from buckets or GitHub repo
@@ -79,6 +208,60 @@ pub enum Commands {
     Info {
         /// Package names or GitHub URLs to show (supports wildcards * for cache queries)
         names: Vec<String>,
+
+        /// Also show which asset would be installed on every common platform
+        #[arg(long = "all-platforms")]
+        all_platforms: bool,
+
+        /// Also list every asset of the latest release with its score on this platform
+        #[arg(long)]
+        assets: bool,
+    },
+
+    /// List the files an installed package put on disk
+    Files {
+        /// Installed package names or keys (e.g. ripgrep, bun::baseline)
+        names: Vec<String>,
+    },
+
+    /// Show the history of installs, updates and deletes
+    Log {
+        /// Number of entries to show (the most recent ones)
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// Write the installed packages to a list for `wenget import`
+    Export {
+        /// Output file (default: print to stdout)
+        #[arg(short, long, value_name = "PATH")]
+        output: Option<PathBuf>,
+
+        /// Write one `wenget add` input per line instead of JSON
+        #[arg(long)]
+        text: bool,
+    },
+
+    /// Install the packages of a list written by `wenget export`
+    Import {
+        /// File path or URL of the list
+        source: String,
+
+        /// Skip confirmation prompts
+        #[arg(short = 'y', long)]
+        yes: bool,
+
+        /// Resolve every entry and report what would be added, updated or
+        /// skipped, without installing anything
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+    },
+
+    /// List installed packages that have a newer release
+    Outdated {
+        /// Output the list as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Search for packages in buckets
@@ -86,6 +269,28 @@ pub enum Commands {
     Search {
         /// Package names to search (supports wildcards *)
         names: Vec<String>,
+
+        /// Output results as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Maximum number of results to show
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+
+        /// Pick from ranked results in a fuzzy finder, then show info or install
+        #[arg(short = 'i', long, conflicts_with = "json")]
+        interactive: bool,
+    },
+
+    /// Run a package once from a temporary directory without installing it
+    Try {
+        /// Package name or GitHub URL to run
+        name: String,
+
+        /// Arguments passed to the executable (put them after --)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
     },
 
     /// Upgrade installed packages
@@ -94,6 +299,10 @@ pub enum Commands {
         /// Package names to upgrade, or "all" for all packages (supports wildcards *)
         names: Vec<String>,
 
+        /// Upgrade every installed package and print a summary (same as no names)
+        #[arg(short = 'a', long, conflicts_with_all = ["names", "check"])]
+        all: bool,
+
         /// Skip confirmation prompts
         #[arg(short = 'y', long)]
         yes: bool,
@@ -101,6 +310,20 @@ pub enum Commands {
         /// Specify target platform (e.g., linux-x86_64-musl, aarch64-unknown-linux-musl)
         #[arg(short = 'p', long = "platform")]
         platform: Option<String>,
+
+        /// Only check one package for an update, without installing it.
+        /// Exit code: 0 up to date, 10 update available, 2 not installed, 3 unknown
+        #[arg(long)]
+        check: bool,
+
+        /// Upgrade to the newest release even when it is a prerelease
+        #[arg(long)]
+        pre: bool,
+
+        /// Only refresh the stored description and package info; nothing is
+        /// downloaded and the installed binary and version are kept
+        #[arg(long = "metadata-only", conflicts_with_all = ["check", "platform", "pre"])]
+        metadata_only: bool,
     },
 
     /// Delete (remove) installed packages
@@ -129,6 +352,10 @@ pub enum Commands {
         /// Skip confirmation prompts
         #[arg(short = 'y', long)]
         yes: bool,
+
+        /// Don't modify PATH (shell rc files or the Windows PATH variable)
+        #[arg(long = "no-path")]
+        no_path: bool,
     },
 
     /// Repair corrupted configuration files
@@ -136,13 +363,27 @@ pub enum Commands {
         /// Force rebuild all configuration files (not just corrupted ones)
         #[arg(short, long)]
         force: bool,
+
+        /// Recreate launchers (symlinks/shims) of all installed packages instead
+        #[arg(long)]
+        shims: bool,
+    },
+
+    /// Check installed packages for missing files and broken launchers
+    Verify {
+        /// Recreate missing or broken launchers
+        #[arg(long)]
+        repair: bool,
     },
 
-    /// Edit configuration file with default editor
+    /// Edit configuration file with default editor, or get/set single settings
     #[command(visible_alias = "c")]
-    Config,
+    Config {
+        #[command(subcommand)]
+        command: Option<ConfigCommands>,
+    },
 
-    /// Rename an installed command
+    /// Rename an installed command or package
     #[command(visible_alias = "mv")]
     #[command(visible_alias = "rn")]
     Rename {
@@ -151,9 +392,63 @@ pub enum Commands {
 
         /// New command name (if omitted, will prompt interactively)
         new_name: Option<String>,
+
+        /// Rename the package key in installed.json instead of a command
+        #[arg(long)]
+        package: bool,
+    },
+
+    /// Add an extra launcher name for an installed command, or list aliases
+    Alias {
+        /// Alias name (omit to list all aliases)
+        name: Option<String>,
+
+        /// Installed command the alias runs
+        command: Option<String>,
+
+        /// Remove the alias and its launcher
+        #[arg(short = 'r', long, requires = "name", conflicts_with = "command")]
+        remove: bool,
+    },
+
+    /// Switch a package back to a previous version kept by `keep_versions`
+    Rollback {
+        /// Installed package name
+        name: String,
+
+        /// Version to switch to (default: the one installed before the current one)
+        #[arg(long, value_name = "VERSION")]
+        to: Option<String>,
     },
 }
 
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    /// Print a setting (preferred_platform, custom_bin_path)
+    Get {
+        /// Setting name
+        key: String,
+    },
+
+    /// Change a setting (preferred_platform, custom_bin_path); an empty value unsets it
+    Set {
+        /// Setting name
+        key: String,
+
+        /// New value
+        value: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CacheCommands {
+    /// Show cache location, size, age and per-bucket counts
+    Info,
+
+    /// Remove the cache file; it is rebuilt from buckets on next use
+    Clear,
+}
+
 #[derive(Subcommand)]
 pub enum BucketCommands {
     /// Add a bucket
@@ -163,6 +458,18 @@ pub enum BucketCommands {
 
         /// URL to the manifest.json file
         url: String,
+
+        /// Bucket priority, higher = preferred [default: 100]
+        #[arg(long)]
+        priority: Option<u32>,
+
+        /// Add the bucket without enabling it
+        #[arg(long)]
+        disabled: bool,
+
+        /// Add the bucket even if its manifest can't be fetched or parsed
+        #[arg(short, long)]
+        force: bool,
     },
 
     /// Delete buckets
@@ -171,6 +478,18 @@ pub enum BucketCommands {
         names: Vec<String>,
     },
 
+    /// Enable a disabled bucket
+    Enable {
+        /// Bucket name
+        name: String,
+    },
+
+    /// Disable a bucket without deleting it (its packages leave the cache)
+    Disable {
+        /// Bucket name
+        name: String,
+    },
+
     /// List all buckets
     List,
 
@@ -195,13 +514,28 @@ pub enum BucketCommands {
         #[arg(short = 'o', long = "output")]
         output: Option<String>,
 
-        /// GitHub personal access token (or use GITHUB_TOKEN env var)
+        /// GitHub personal access token (or use WENGET_GITHUB_TOKEN / GITHUB_TOKEN env var)
         #[arg(short = 't', long = "token")]
         token: Option<String>,
 
         /// Update mode when output file exists
         #[arg(short = 'u', long = "update-mode", value_enum)]
         update_mode: Option<UpdateMode>,
+
+        /// Only record these platforms (comma-separated, e.g. linux,macos,windows or
+        /// linux-x86_64-musl); a package missing one of them is an error
+        #[arg(long, value_delimiter = ',', value_name = "PLATFORM")]
+        platforms: Vec<String>,
+    },
+
+    /// Check a bucket manifest for problems before publishing
+    Validate {
+        /// URL or local path of the manifest.json file
+        source: String,
+
+        /// Skip network checks (repo URL reachability)
+        #[arg(long)]
+        offline: bool,
     },
 }
 
@@ -229,4 +563,396 @@ mod tests {
         use clap::CommandFactory;
         Cli::command().debug_assert();
     }
+
+    #[test]
+    fn test_bucket_add_flags() {
+        let cli = Cli::parse_from(["wenget", "bucket", "add", "extra", "https://x/m.json"]);
+        let Some(Commands::Bucket {
+            command: BucketCommands::Add {
+                priority, disabled, ..
+            },
+        }) = cli.command
+        else {
+            panic!("expected bucket add");
+        };
+        assert_eq!(priority, None);
+        assert!(!disabled);
+
+        let cli = Cli::parse_from([
+            "wenget",
+            "bucket",
+            "add",
+            "extra",
+            "https://x/m.json",
+            "--priority",
+            "200",
+            "--disabled",
+            "--force",
+        ]);
+        let Some(Commands::Bucket {
+            command:
+                BucketCommands::Add {
+                    priority,
+                    disabled,
+                    force,
+                    ..
+                },
+        }) = cli.command
+        else {
+            panic!("expected bucket add");
+        };
+        assert_eq!(priority, Some(200));
+        assert!(disabled);
+        assert!(force);
+    }
+
+    #[test]
+    fn test_bucket_enable_disable() {
+        let cli = Cli::parse_from(["wenget", "bucket", "disable", "extra"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Bucket {
+                command: BucketCommands::Disable { ref name }
+            }) if name == "extra"
+        ));
+        let cli = Cli::parse_from(["wenget", "bucket", "enable", "extra"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Bucket {
+                command: BucketCommands::Enable { ref name }
+            }) if name == "extra"
+        ));
+        assert!(Cli::try_parse_from(["wenget", "bucket", "enable"]).is_err());
+    }
+
+    #[test]
+    fn test_help_documents_exit_codes() {
+        use clap::CommandFactory;
+        let help = Cli::command().render_help().to_string();
+        assert!(help.contains("Exit codes:"));
+        assert!(help.contains("Partial failure"));
+    }
+
+    #[test]
+    fn test_cache_subcommands() {
+        let cli = Cli::parse_from(["wenget", "cache", "info"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Cache {
+                command: CacheCommands::Info
+            })
+        ));
+
+        let cli = Cli::parse_from(["wenget", "cache", "clear"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Cache {
+                command: CacheCommands::Clear
+            })
+        ));
+    }
+
+    #[test]
+    fn test_config_subcommands() {
+        let cli = Cli::parse_from(["wenget", "config"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Config { command: None })
+        ));
+
+        let cli = Cli::parse_from(["wenget", "config", "get", "preferred_platform"]);
+        let Some(Commands::Config {
+            command: Some(ConfigCommands::Get { key }),
+        }) = cli.command
+        else {
+            panic!("expected config get");
+        };
+        assert_eq!(key, "preferred_platform");
+
+        let cli = Cli::parse_from(["wenget", "c", "set", "custom_bin_path", "/opt/bin"]);
+        let Some(Commands::Config {
+            command: Some(ConfigCommands::Set { key, value }),
+        }) = cli.command
+        else {
+            panic!("expected config set");
+        };
+        assert_eq!(
+            (key.as_str(), value.as_str()),
+            ("custom_bin_path", "/opt/bin")
+        );
+    }
+
+    #[test]
+    fn test_add_force_flag() {
+        for args in [
+            ["wenget", "add", "ripgrep", "--force"],
+            ["wenget", "add", "ripgrep", "-f"],
+        ] {
+            let Some(Commands::Add { force, .. }) = Cli::parse_from(args).command else {
+                panic!("expected add");
+            };
+            assert!(force);
+        }
+
+        let Some(Commands::Add { force, .. }) = Cli::parse_from(["wenget", "add", "rg"]).command
+        else {
+            panic!("expected add");
+        };
+        assert!(!force);
+    }
+
+    #[test]
+    fn test_add_compiler_flag() {
+        let cli = Cli::parse_from(["wenget", "add", "ripgrep", "--compiler", "gnu"]);
+        let Some(Commands::Add { compiler, .. }) = cli.command else {
+            panic!("expected add");
+        };
+        assert_eq!(compiler.as_deref(), Some("gnu"));
+
+        assert!(Cli::try_parse_from(["wenget", "add", "rg", "--compiler", "clang"]).is_err());
+        assert!(Cli::try_parse_from([
+            "wenget",
+            "add",
+            "rg",
+            "--compiler",
+            "gnu",
+            "-p",
+            "linux-x64"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_add_asset_flag() {
+        let cli = Cli::parse_from(["wenget", "add", "ripgrep", "--asset", "musl.tar.gz"]);
+        let Some(Commands::Add { asset, .. }) = cli.command else {
+            panic!("expected add");
+        };
+        assert_eq!(asset.as_deref(), Some("musl.tar.gz"));
+
+        // The asset already decides the variant and the compiler
+        assert!(
+            Cli::try_parse_from(["wenget", "add", "rg", "--asset", "x", "--variant", "y"]).is_err()
+        );
+        assert!(
+            Cli::try_parse_from(["wenget", "add", "rg", "--asset", "x", "--compiler", "gnu"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_pre_flag() {
+        let cli = Cli::parse_from(["wenget", "add", "tool", "--pre"]);
+        assert!(matches!(cli.command, Some(Commands::Add { pre: true, .. })));
+        let cli = Cli::parse_from(["wenget", "update", "tool", "--pre"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Update { pre: true, .. })
+        ));
+
+        // An explicit version leaves nothing to pick
+        assert!(Cli::try_parse_from(["wenget", "add", "tool", "--pre", "--ver", "1.0"]).is_err());
+    }
+
+    #[test]
+    fn test_update_all_flag() {
+        let cli = Cli::parse_from(["wenget", "update", "--all", "-y"]);
+        let Some(Commands::Update { names, all, .. }) = cli.command else {
+            panic!("expected update");
+        };
+        assert!(all);
+        assert!(names.is_empty());
+
+        assert!(Cli::try_parse_from(["wenget", "update", "--all", "ripgrep"]).is_err());
+        assert!(Cli::try_parse_from(["wenget", "update", "--all", "--check"]).is_err());
+    }
+
+    #[test]
+    fn test_add_emit_manifest() {
+        let cli = Cli::parse_from([
+            "wenget",
+            "add",
+            "rg",
+            "--emit-manifest",
+            "out/installed.json",
+            "--append-manifest",
+        ]);
+        let Some(Commands::Add {
+            emit_manifest,
+            append_manifest,
+            ..
+        }) = cli.command
+        else {
+            panic!("expected add");
+        };
+        assert_eq!(emit_manifest, Some(PathBuf::from("out/installed.json")));
+        assert!(append_manifest);
+
+        // --append-manifest needs a file to append to
+        assert!(Cli::try_parse_from(["wenget", "add", "rg", "--append-manifest"]).is_err());
+    }
+
+    #[test]
+    fn test_network_flags() {
+        let cli = Cli::parse_from([
+            "wenget",
+            "add",
+            "ripgrep",
+            "--timeout",
+            "120",
+            "--retries",
+            "3",
+        ]);
+        assert_eq!(cli.timeout, Some(120));
+        assert_eq!(cli.retries, Some(3));
+
+        let cli = Cli::parse_from(["wenget", "list"]);
+        assert_eq!(cli.timeout, None);
+        assert_eq!(cli.retries, None);
+        assert!(!cli.offline);
+
+        let cli = Cli::parse_from(["wenget", "info", "ripgrep", "--offline"]);
+        assert!(cli.offline);
+
+        assert!(Cli::try_parse_from(["wenget", "--timeout", "0", "list"]).is_err());
+    }
+
+    #[test]
+    fn test_try_passes_args_through() {
+        let cli = Cli::parse_from(["wenget", "try", "ripgrep", "--", "--version", "-i"]);
+        let Some(Commands::Try { name, args }) = cli.command else {
+            panic!("expected try");
+        };
+        assert_eq!(name, "ripgrep");
+        assert_eq!(args, vec!["--version", "-i"]);
+    }
+
+    #[test]
+    fn test_add_executable_selection_flags() {
+        let cli = Cli::parse_from([
+            "wenget", "add", "tool", "--exe", "bin/tool", "--exe", "helper",
+        ]);
+        let Some(Commands::Add { select, exe, .. }) = cli.command else {
+            panic!("expected add");
+        };
+        assert!(!select);
+        assert_eq!(exe, vec!["bin/tool", "helper"]);
+
+        let cli = Cli::parse_from(["wenget", "add", "tool", "--select"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Add { select: true, .. })
+        ));
+
+        // Prompting contradicts --yes and an explicit --exe
+        assert!(Cli::try_parse_from(["wenget", "add", "tool", "--select", "-y"]).is_err());
+        assert!(Cli::try_parse_from(["wenget", "add", "tool", "--select", "--exe", "x"]).is_err());
+    }
+
+    #[test]
+    fn test_list_by_source_flag() {
+        let cli = Cli::parse_from(["wenget", "list", "--by-source"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::List {
+                all: false,
+                by_source: true,
+                ..
+            })
+        ));
+
+        // Grouping only applies to installed packages
+        assert!(Cli::try_parse_from(["wenget", "list", "--all", "--by-source"]).is_err());
+    }
+
+    #[test]
+    fn test_list_json_flag() {
+        let cli = Cli::parse_from(["wenget", "list", "--json", "--all"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::List {
+                all: true,
+                json: true,
+                ..
+            })
+        ));
+        assert!(Cli::try_parse_from(["wenget", "list", "--json", "--by-source"]).is_err());
+    }
+
+    #[test]
+    fn test_list_date_filters() {
+        let cli = Cli::parse_from([
+            "wenget",
+            "list",
+            "--since",
+            "2024-01-01",
+            "--before",
+            "2024-07-01",
+        ]);
+        let Some(Commands::List { since, before, .. }) = cli.command else {
+            panic!("expected list");
+        };
+        assert_eq!(since.as_deref(), Some("2024-01-01"));
+        assert_eq!(before.as_deref(), Some("2024-07-01"));
+
+        assert!(Cli::try_parse_from(["wenget", "list", "--all", "--since", "2024-01-01"]).is_err());
+    }
+
+    #[test]
+    fn test_update_metadata_only_flag() {
+        let cli = Cli::parse_from(["wenget", "update", "ripgrep", "--metadata-only"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Update {
+                metadata_only: true,
+                check: false,
+                ..
+            })
+        ));
+
+        assert!(
+            Cli::try_parse_from(["wenget", "update", "rg", "--metadata-only", "--check"]).is_err()
+        );
+        assert!(
+            Cli::try_parse_from(["wenget", "update", "rg", "--metadata-only", "-p", "linux"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_info_all_platforms_flag() {
+        let cli = Cli::parse_from(["wenget", "info", "ripgrep", "--all-platforms"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Info {
+                all_platforms: true,
+                assets: false,
+                ..
+            })
+        ));
+
+        let cli = Cli::parse_from(["wenget", "info", "ripgrep", "--assets"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Info { assets: true, .. })
+        ));
+    }
+
+    #[test]
+    fn test_log_limit() {
+        let cli = Cli::parse_from(["wenget", "log"]);
+        assert!(matches!(cli.command, Some(Commands::Log { limit: 20 })));
+        let cli = Cli::parse_from(["wenget", "log", "-n", "5"]);
+        assert!(matches!(cli.command, Some(Commands::Log { limit: 5 })));
+    }
+
+    #[test]
+    fn test_rollback_to() {
+        let cli = Cli::parse_from(["wenget", "rollback", "ripgrep", "--to", "14.0.0"]);
+        let Some(Commands::Rollback { name, to }) = cli.command else {
+            panic!("expected rollback");
+        };
+        assert_eq!(name, "ripgrep");
+        assert_eq!(to.as_deref(), Some("14.0.0"));
+    }
 }
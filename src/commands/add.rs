@@ -1,24 +1,34 @@
 //! Add (Install) command implementation
 
+use crate::commands::install_manifest::{self, EmitManifest};
+use crate::commands::prefetch;
+use crate::commands::Outcome;
+use crate::core::events::{InstallEvent, ProgressSink};
+use crate::core::history;
 use crate::core::manifest::{PackageSource, ScriptType};
-use crate::core::{Config, InstalledPackage, Platform, WenPaths};
+use crate::core::platform::{Arch, ParsedAsset};
+use crate::core::version::{is_exact_tag, parse_constraint, pin_constraint, split_constraint};
+use crate::core::{Compiler, Config, InstalledPackage, Platform, Preferences, WenPaths};
 use crate::downloader;
 use crate::installer::{
-    create_script_shim, detect_script_type, download_script, extract_archive, extract_script_name,
-    find_executable_candidates,
+    command_file_name, completions, create_script_shim, detect_script_type, download_script,
+    extract_archive, extract_script_name, find_executable_candidates,
     input_detector::{detect_input_type, InputType},
     install_script,
     local::install_local_file,
-    normalize_command_name, read_local_script,
+    normalize_command_name, read_local_script, sanitize_command_name, script_checksum, versions,
 };
-use crate::package_resolver::{PackageInput, PackageResolver, ResolvedPackage};
+use crate::package_resolver::{from_bucket, PackageInput, PackageResolver, ResolvedPackage};
+use crate::providers::github::GitHubAsset;
 use crate::providers::{GitHubProvider, SourceProvider};
+use crate::utils::interrupt::CleanupGuard;
+use crate::utils::network::{NetworkOverrides, NetworkSettings};
 use anyhow::{Context, Result};
 use chrono::Utc;
 use colored::Colorize;
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[cfg(windows)]
 use crate::installer::create_shim;
@@ -26,27 +36,135 @@ use crate::installer::create_shim;
 #[cfg(unix)]
 use crate::installer::create_symlink;
 
+/// How to choose the executables of a package among the archive's candidates
+#[derive(Debug, Clone, Default)]
+pub struct ExeSelection {
+    /// Always prompt with the scored candidate list (`--select`)
+    pub interactive: bool,
+    /// Archive paths or file names to use instead of the heuristic (`--exe`)
+    pub paths: Vec<String>,
+    /// Link every likely executable without prompting (`--all-bins`)
+    pub all: bool,
+}
+
+/// Options of an `add` run, mirroring the `wenget add` flags
+#[derive(Debug, Clone, Default)]
+pub struct AddOptions {
+    /// Skip confirmation prompts (`--yes`)
+    pub yes: bool,
+    /// Command name to install under (`--command`)
+    pub script_name: Option<String>,
+    /// Target platform instead of the detected one (`--platform`)
+    pub platform: Option<String>,
+    /// Release to install (`--ver`)
+    pub version: Option<String>,
+    /// Consider prereleases (`--pre`)
+    pub pre: bool,
+    /// Only install this variant (`--variant`)
+    pub variant: Option<String>,
+    /// Compiler/libc variant to pin (`--compiler`)
+    pub compiler: Option<String>,
+    /// Release asset to install instead of the scored pick (`--asset`)
+    pub asset: Option<String>,
+    /// Don't suffix command names of variants (`--no-suffix`)
+    pub no_suffix: bool,
+    /// Reinstalling for `wenget update` rather than installing
+    pub update_mode: bool,
+    /// Directory for the launchers (`--bin-dir`)
+    pub bin_dir: Option<PathBuf>,
+    /// Keep the archive in cache/archives for reuse (`--keep-download`)
+    pub keep_download: bool,
+    /// Reinstall even when the version is already installed (`--force`)
+    pub force: bool,
+    /// Show what would be installed without installing (`--dry-run`)
+    pub dry_run: bool,
+    /// Install shell completions shipped in the archive (`--completions`)
+    pub completions: bool,
+    /// How the executables are chosen
+    pub exe_selection: ExeSelection,
+    /// Record the installed packages in a JSON file (`--emit-manifest`)
+    pub emit_manifest: Option<EmitManifest>,
+}
+
+/// Lowest candidate score linked by `--all-bins`
+///
+/// Exec permission alone (35), a native binary (60) or a file in `bin/` (40)
+/// reach it; a stray script deep in the archive does not.
+const ALL_BINS_MIN_SCORE: u32 = 35;
+
+/// Candidates linked by `--all-bins`, or the best one when none scores high enough
+fn all_bins(
+    candidates: &[crate::installer::extractor::ExecutableCandidate],
+) -> Vec<&crate::installer::extractor::ExecutableCandidate> {
+    let selected: Vec<_> = candidates
+        .iter()
+        .filter(|c| c.score >= ALL_BINS_MIN_SCORE)
+        .collect();
+    if selected.is_empty() {
+        candidates.iter().take(1).collect()
+    } else {
+        selected
+    }
+}
+
 /// Install packages (smart detection: package names from cache or GitHub URLs)
-#[allow(clippy::too_many_arguments)]
 pub fn run(
     names: Vec<String>,
-    yes: bool,
-    script_name: Option<String>,
-    platform: Option<String>,
-    version: Option<String>,
-    variant_filter: Option<String>,
-    no_suffix: bool,
-    update_mode: bool,
-) -> Result<()> {
-    let config = Config::new()?;
-    let paths = WenPaths::new()?;
+    options: AddOptions,
+    network: NetworkOverrides,
+    sink: &dyn ProgressSink,
+) -> Result<Outcome> {
+    let AddOptions {
+        yes,
+        script_name,
+        platform,
+        version,
+        pre,
+        variant: variant_filter,
+        compiler,
+        asset,
+        no_suffix,
+        update_mode,
+        bin_dir,
+        keep_download,
+        force,
+        dry_run,
+        completions,
+        exe_selection,
+        emit_manifest,
+    } = options;
+    let config = Config::with_network(network)?.with_prereleases(pre);
+    if update_mode && prefetch::is_all_platforms(platform.as_deref()) {
+        anyhow::bail!("--platform all only pre-fetches binaries; use it with wenget add");
+    }
+    let compiler = compiler
+        .map(|name| {
+            Compiler::from_name(&name).ok_or_else(|| anyhow::anyhow!("Unknown compiler: {}", name))
+        })
+        .transpose()?;
+    let paths = match bin_dir {
+        Some(dir) => {
+            // Relative paths are taken relative to the current working directory
+            let dir = if dir.is_relative() {
+                std::env::current_dir()
+                    .context("Failed to determine current directory")?
+                    .join(dir)
+            } else {
+                dir
+            };
+            config.paths().with_bin_dir(dir)
+        }
+        // `custom_bin_path` from config.toml, or the default bin directory
+        None => config.paths().clone(),
+    };
 
-    // Ensure initialized
-    if !config.is_initialized() {
+    // Ensure initialized (a dry run leaves the filesystem alone)
+    if !dry_run && !config.is_initialized() {
         config.init()?;
     }
 
-    let mut installed = config.get_or_create_installed()?;
+    let mut installed = config.load_installed()?;
+    let installed_before = install_manifest::snapshot(&installed);
 
     if names.is_empty() {
         println!("{}", "No package names or URLs provided".yellow());
@@ -61,7 +179,8 @@ pub fn run(
             "  wenget add https://raw.githubusercontent.com/.../script.sh  # Install remote script"
         );
         println!("  wenget add ripgrep -p linux-x64 # Install for specific platform");
-        return Ok(());
+        println!("  wenget add ripgrep --bin-dir ./bin  # Put the launcher in ./bin");
+        return Ok(Outcome::default());
     }
 
     // Categorize inputs
@@ -79,45 +198,81 @@ pub fn run(
         }
     }
 
+    let mut outcome = Outcome::default();
+
     // Handle script installations
     if !script_inputs.is_empty() {
-        install_scripts(
+        outcome = outcome.merge(install_scripts(
             &config,
             &paths,
             &mut installed,
             script_inputs,
             yes,
             script_name.as_deref(),
-        )?;
+            dry_run,
+        )?);
     }
 
     // Handle local file installations
     if !local_inputs.is_empty() {
-        install_local_files(
+        outcome = outcome.merge(install_local_files(
             &config,
             &paths,
             &mut installed,
             local_inputs,
             yes,
             script_name.as_deref(),
-        )?;
+            dry_run,
+        )?);
+    }
+
+    // GitHub release asset URLs install as packages of their repository
+    let (asset_inputs, url_inputs): (Vec<&String>, Vec<&String>) = url_inputs
+        .into_iter()
+        .partition(|url| GitHubProvider::parse_release_asset_url(url).is_some());
+    if !asset_inputs.is_empty() {
+        outcome = outcome.merge(install_release_assets(
+            &config,
+            &paths,
+            &mut installed,
+            asset_inputs,
+            yes,
+            script_name.as_deref(),
+            no_suffix,
+            keep_download,
+            dry_run,
+            completions,
+            &exe_selection,
+            sink,
+        )?);
     }
 
     // Handle direct URL installations
     if !url_inputs.is_empty() {
-        install_from_urls(
+        outcome = outcome.merge(install_from_urls(
             &config,
             &paths,
             &mut installed,
             url_inputs,
             yes,
             script_name.as_deref(),
-        )?;
+            keep_download,
+            dry_run,
+        )?);
+    }
+
+    // `name@range` inputs are pinned to the newest release satisfying their range,
+    // `name@tag` inputs to exactly that release
+    let (constrained_inputs, package_inputs): (Vec<&String>, Vec<&String>) = package_inputs
+        .into_iter()
+        .partition(|name| split_constraint(name).is_some());
+    if !constrained_inputs.is_empty() && version.is_some() {
+        anyhow::bail!("A version constraint (name@range) cannot be combined with --version");
     }
 
     // Handle package installations (existing logic)
     if !package_inputs.is_empty() {
-        install_packages(
+        outcome = outcome.merge(install_packages(
             &config,
             &paths,
             &mut installed,
@@ -127,12 +282,243 @@ pub fn run(
             platform.as_deref(),
             version.as_deref(),
             variant_filter.as_deref(),
+            compiler,
+            asset.as_deref(),
             no_suffix,
             update_mode,
-        )?;
+            keep_download,
+            force,
+            dry_run,
+            completions,
+            &exe_selection,
+            sink,
+        )?);
     }
 
-    Ok(())
+    for input in constrained_inputs {
+        let result = install_constrained_package(
+            &config,
+            &paths,
+            &mut installed,
+            input,
+            yes,
+            script_name.as_deref(),
+            platform.as_deref(),
+            variant_filter.as_deref(),
+            compiler,
+            asset.as_deref(),
+            no_suffix,
+            update_mode,
+            keep_download,
+            force,
+            dry_run,
+            completions,
+            &exe_selection,
+            sink,
+        );
+        match result {
+            Ok(part) => outcome = outcome.merge(part),
+            Err(e) => {
+                eprintln!("{} {}: {:#}", "✗".red(), input, e);
+                outcome = outcome.merge(Outcome::new(0, 1));
+            }
+        }
+    }
+
+    if !dry_run {
+        record_history(&paths, &installed, &installed_before, update_mode);
+    }
+
+    if let Some(emit) = &emit_manifest {
+        let records = install_manifest::installed_since(&installed, &installed_before);
+        let count = records.len();
+        install_manifest::write(emit, records)?;
+        println!(
+            "{} Recorded {} installed package(s) in {}",
+            "✓".green(),
+            count,
+            emit.path.display()
+        );
+    }
+
+    Ok(outcome)
+}
+
+/// Record the packages installed or reinstalled since `before` in the install history
+///
+/// Packages that were already installed count as updates.
+fn record_history(
+    paths: &WenPaths,
+    installed: &crate::core::InstalledManifest,
+    before: &HashMap<String, chrono::DateTime<Utc>>,
+    update_mode: bool,
+) {
+    let mut changed: Vec<(&String, &InstalledPackage)> = installed
+        .packages
+        .iter()
+        .filter(|(key, pkg)| before.get(*key) != Some(&pkg.installed_at))
+        .collect();
+    changed.sort_by_key(|(key, _)| *key);
+    for (key, pkg) in changed {
+        let action = if update_mode || before.contains_key(key) {
+            history::Action::Update
+        } else {
+            history::Action::Install
+        };
+        history::record(paths, action, key, pkg);
+    }
+}
+
+/// Install a `name@range` input at the newest release satisfying the range
+///
+/// The range is recorded on the installed package so `wenget update` stays within it.
+/// A `name@tag` input (e.g. `ripgrep@14.1.0`) installs that release and pins it to
+/// `=14.1.0`; tags that aren't versions are installed without a pin.
+#[allow(clippy::too_many_arguments)]
+fn install_constrained_package(
+    config: &Config,
+    paths: &WenPaths,
+    installed: &mut crate::core::InstalledManifest,
+    input: &str,
+    yes: bool,
+    custom_name: Option<&str>,
+    custom_platform: Option<&str>,
+    variant_filter: Option<&str>,
+    compiler: Option<Compiler>,
+    asset: Option<&str>,
+    no_suffix: bool,
+    update_mode: bool,
+    keep_download: bool,
+    force: bool,
+    dry_run: bool,
+    completions: bool,
+    exe_selection: &ExeSelection,
+    sink: &dyn ProgressSink,
+) -> Result<Outcome> {
+    let (name, constraint) = split_constraint(input)
+        .ok_or_else(|| anyhow::anyhow!("Missing version constraint: {}", input))?;
+    let req = if is_exact_tag(constraint) {
+        None
+    } else {
+        Some(parse_constraint(constraint)?)
+    };
+
+    let cache = config.get_or_rebuild_cache()?;
+    let resolver = PackageResolver::new(config, &cache)?;
+    let resolved = resolver
+        .resolve(&PackageInput::parse(name))
+        .with_context(|| format!("{}: Not found", name))?;
+    let repo = resolved
+        .first()
+        .map(|r| r.package.repo.clone())
+        .ok_or_else(|| anyhow::anyhow!("{}: Not found", name))?;
+
+    let github = GitHubProvider::from_config(config)?;
+    let (tag, recorded_constraint) = match &req {
+        Some(req) => {
+            let tag = github.fetch_matching_version(&repo, req)?.ok_or_else(|| {
+                anyhow::anyhow!("No release of {} satisfies {}", name, constraint)
+            })?;
+            println!(
+                "{} {} {} resolves to {}",
+                "ℹ".cyan(),
+                name,
+                constraint,
+                tag.green()
+            );
+            (tag, Some(constraint.to_string()))
+        }
+        None => {
+            // Fail before installing, since bucket packages would otherwise try a derived URL
+            if let Some(message) = github.missing_tag_message(&repo, constraint) {
+                anyhow::bail!(message);
+            }
+            (constraint.to_string(), pin_constraint(constraint))
+        }
+    };
+
+    let name = name.to_string();
+    let outcome = install_packages(
+        config,
+        paths,
+        installed,
+        vec![&name],
+        yes,
+        custom_name,
+        custom_platform,
+        Some(&tag),
+        variant_filter,
+        compiler,
+        asset,
+        no_suffix,
+        update_mode,
+        keep_download,
+        force,
+        dry_run,
+        completions,
+        exe_selection,
+        sink,
+    )?;
+
+    // Record the range (or pin) on the entries this install produced
+    let Some(recorded_constraint) = recorded_constraint.filter(|_| !dry_run) else {
+        return Ok(outcome);
+    };
+    let repo_names: Vec<&str> = resolved.iter().map(|r| r.package.name.as_str()).collect();
+    let installed_version = tag.trim_start_matches('v');
+    let mut recorded = false;
+    for pkg in installed.packages.values_mut() {
+        if repo_names.contains(&pkg.repo_name.as_str()) && pkg.version == installed_version {
+            pkg.version_constraint = Some(recorded_constraint.clone());
+            recorded = true;
+        }
+    }
+    if recorded {
+        config.save_installed(installed)?;
+    }
+
+    Ok(outcome)
+}
+
+/// Print what installing one package would do (`--dry-run`)
+fn print_dry_run(source: &str, command: &str, app_dir: &Path) {
+    println!("  Asset: {}", source);
+    println!("  Command: {}", command);
+    println!("  Install to: {}", app_dir.display());
+}
+
+/// Last component of a path or URL
+fn file_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+/// Command name a binary package is expected to get, without downloading it
+///
+/// An install names the command after the executable found in the archive; a dry
+/// run goes by the custom name or the package name, resolved against the commands
+/// of other packages the same way.
+fn expected_command_name(
+    installed: &crate::core::InstalledManifest,
+    installed_key: &str,
+    pkg_name: &str,
+    custom_name: Option<&str>,
+    no_suffix: bool,
+) -> Result<String> {
+    let taken = installed.command_name_set(Some(installed_key));
+    if let Some(custom) = custom_name {
+        let custom = sanitize_command_name(custom)?;
+        return Ok(resolve_command_name(&custom, None, &taken, true));
+    }
+    let variant = installed_key
+        .split_once("::")
+        .map(|(_, variant)| variant)
+        .filter(|_| !no_suffix);
+    Ok(resolve_command_name(
+        &normalize_command_name(pkg_name),
+        variant,
+        &taken,
+        false,
+    ))
 }
 
 /// Resolve command name to avoid conflicts
@@ -244,10 +630,16 @@ fn install_scripts(
     script_inputs: Vec<&String>,
     yes: bool,
     custom_name: Option<&str>,
-) -> Result<()> {
+    dry_run: bool,
+) -> Result<Outcome> {
+    // Reject unusable custom names before anything is written
+    let custom_name = custom_name.map(sanitize_command_name).transpose()?;
+    let custom_name = custom_name.as_deref();
+
     println!("{}", "Scripts to install:".bold());
 
     let mut scripts_to_install: Vec<(String, String, ScriptType, String)> = Vec::new(); // (name, content, type, origin)
+    let mut input_failures = 0;
 
     for input in script_inputs {
         // Determine if local or remote
@@ -255,10 +647,11 @@ fn install_scripts(
 
         // Get script content
         let content = if is_url {
-            match download_script(input) {
+            match download_script(input, config.network()) {
                 Ok(c) => c,
                 Err(e) => {
                     eprintln!("{} Failed to download {}: {}", "✗".red(), input, e);
+                    input_failures += 1;
                     continue;
                 }
             }
@@ -268,6 +661,7 @@ fn install_scripts(
                 Ok(c) => c,
                 Err(e) => {
                     eprintln!("{} Failed to read {}: {}", "✗".red(), input, e);
+                    input_failures += 1;
                     continue;
                 }
             }
@@ -278,6 +672,7 @@ fn install_scripts(
             Some(t) => t,
             None => {
                 eprintln!("{} Cannot detect script type for: {}", "✗".red(), input);
+                input_failures += 1;
                 continue;
             }
         };
@@ -291,6 +686,7 @@ fn install_scripts(
                 script_type.display_name(),
                 "not supported on this platform".yellow()
             );
+            input_failures += 1;
             continue;
         }
 
@@ -302,6 +698,7 @@ fn install_scripts(
                 Some(n) => n,
                 None => {
                     eprintln!("{} Cannot extract name from: {}", "✗".red(), input);
+                    input_failures += 1;
                     continue;
                 }
             }
@@ -331,7 +728,16 @@ fn install_scripts(
 
     if scripts_to_install.is_empty() {
         println!("{}", "No scripts to install".yellow());
-        return Ok(());
+        return Ok(Outcome::new(0, input_failures));
+    }
+
+    if dry_run {
+        println!();
+        for (name, _, _, origin) in &scripts_to_install {
+            println!("{} {}", "Would install".cyan(), name);
+            print_dry_run(origin, name, &paths.app_dir(name));
+        }
+        return Ok(Outcome::new(scripts_to_install.len(), input_failures));
     }
 
     // Show security warning
@@ -346,7 +752,7 @@ fn install_scripts(
     // Confirm installation
     if !yes && !crate::utils::confirm("\nProceed with installation?")? {
         println!("Installation cancelled");
-        return Ok(());
+        return Ok(Outcome::default());
     }
 
     println!();
@@ -404,11 +810,11 @@ fn install_scripts(
         );
     }
 
-    Ok(())
+    Ok(Outcome::new(success_count, fail_count + input_failures))
 }
 
 /// Install a single script
-fn install_single_script(
+pub(crate) fn install_single_script(
     paths: &WenPaths,
     name: &str,
     content: &str,
@@ -454,6 +860,13 @@ fn install_single_script(
         asset_name: format!("{}.{}", name, script_type.extension()),
         parent_package: None,
         download_url: None,
+        bin_dir: paths
+            .custom_bin_dir()
+            .map(|p| p.to_string_lossy().to_string()),
+        version_constraint: None,
+        compiler: None,
+        checksum: Some(script_checksum(content)),
+        files: Vec::new(),
     };
 
     Ok(inst_pkg)
@@ -467,16 +880,34 @@ fn install_local_files(
     files: Vec<&String>,
     yes: bool,
     custom_name: Option<&str>,
-) -> Result<()> {
+    dry_run: bool,
+) -> Result<Outcome> {
+    // Reject unusable custom names before anything is written
+    let custom_name = custom_name.map(sanitize_command_name).transpose()?;
+    let custom_name = custom_name.as_deref();
+
     println!("{}", "Local files to install:".bold());
 
     for file in &files {
         println!("  • {}", file);
     }
 
+    if dry_run {
+        println!();
+        for file in &files {
+            let name = match custom_name {
+                Some(custom) => custom.to_string(),
+                None => normalize_command_name(file_name(file)),
+            };
+            println!("{} {}", "Would install".cyan(), file);
+            print_dry_run(file, &name, &paths.app_dir(&name));
+        }
+        return Ok(Outcome::new(files.len(), 0));
+    }
+
     if !yes && !crate::utils::confirm("\nProceed with installation?")? {
         println!("Installation cancelled");
-        return Ok(());
+        return Ok(Outcome::default());
     }
 
     println!();
@@ -549,10 +980,214 @@ fn install_local_files(
         );
     }
 
-    Ok(())
+    Ok(Outcome::new(success_count, fail_count))
+}
+
+/// Install the exact assets behind GitHub release download URLs
+///
+/// The package is recorded with its repository as source and the asset name as
+/// template, so `wenget update` later picks the same asset from newer releases.
+#[allow(clippy::too_many_arguments)]
+fn install_release_assets(
+    config: &Config,
+    paths: &WenPaths,
+    installed: &mut crate::core::InstalledManifest,
+    urls: Vec<&String>,
+    yes: bool,
+    custom_name: Option<&str>,
+    no_suffix: bool,
+    keep_download: bool,
+    dry_run: bool,
+    completions: bool,
+    exe_selection: &ExeSelection,
+    sink: &dyn ProgressSink,
+) -> Result<Outcome> {
+    println!("{}", "Release assets to install:".bold());
+    for url in &urls {
+        println!("  • {}", url);
+    }
+
+    if !yes && !dry_run && !crate::utils::confirm("\nProceed with installation?")? {
+        println!("Installation cancelled");
+        return Ok(Outcome::default());
+    }
+
+    println!();
+
+    let github = GitHubProvider::from_config(config)?;
+    let mut successful: Vec<String> = Vec::new();
+    let mut failed: Vec<String> = Vec::new();
+
+    for url in urls {
+        let Some(asset) = GitHubProvider::parse_release_asset_url(url) else {
+            continue;
+        };
+        let repo_url = asset.repo_url();
+        let version = asset.version().to_string();
+
+        let (pkg, binary, platform_id) = release_asset_package(&github, &asset);
+        let variant =
+            crate::core::manifest::extract_variant_from_asset(&asset.asset_name, &asset.repo);
+        let installed_key =
+            crate::core::manifest::generate_installed_key(&asset.repo, variant.as_deref());
+        let platform_match = crate::core::platform::PlatformMatch {
+            platform_id,
+            is_exact: true,
+            fallback_type: None,
+            score: 0,
+        };
+        let source = PackageSource::DirectRepo {
+            url: repo_url.clone(),
+        };
+
+        if dry_run {
+            println!("{} {} v{}", "Would install".cyan(), installed_key, version);
+            let command = expected_command_name(
+                installed,
+                &installed_key,
+                &pkg.name,
+                custom_name,
+                no_suffix,
+            )?;
+            print_dry_run(&binary.url, &command, &paths.app_dir(&installed_key));
+            successful.push(installed_key);
+            println!();
+            continue;
+        }
+
+        println!("{} {} v{}...", "Installing".cyan(), installed_key, version);
+        println!("  {} From: {}", "ℹ".cyan(), asset.asset_name.dimmed());
+
+        match install_package(
+            installed,
+            paths,
+            &pkg,
+            &platform_match,
+            &binary,
+            &version,
+            &source,
+            &installed_key,
+            None,
+            custom_name,
+            yes,
+            no_suffix,
+            false,
+            keep_download,
+            completions,
+            exe_selection,
+            config.network(),
+            sink,
+        ) {
+            Ok(inst_pkg) => {
+                installed.upsert_package(installed_key.clone(), inst_pkg);
+                println!("  {} Installed successfully", "✓".green());
+                successful.push(installed_key);
+            }
+            Err(e) => {
+                println!("  {} Failed to install {}: {}", "✗".red(), installed_key, e);
+                failed.push(installed_key);
+            }
+        }
+        println!();
+    }
+
+    if dry_run {
+        println!(
+            "{} {} asset(s) would be installed: {}",
+            "ℹ".cyan(),
+            successful.len(),
+            successful.join(" ")
+        );
+        return Ok(Outcome::new(successful.len(), failed.len()));
+    }
+
+    if !successful.is_empty() {
+        config.save_installed(installed)?;
+    }
+
+    println!("{}", "Summary:".bold());
+    if !successful.is_empty() {
+        println!(
+            "  {} {} asset(s) installed: {}",
+            "✓".green(),
+            successful.len(),
+            successful.join(" ")
+        );
+    }
+    if !failed.is_empty() {
+        println!(
+            "  {} {} asset(s) failed: {}",
+            "✗".red(),
+            failed.len(),
+            failed.join(" ")
+        );
+    }
+
+    Ok(Outcome::new(successful.len(), failed.len()))
+}
+
+/// Build the package, binary and platform id for a release asset
+///
+/// Release metadata is used when GitHub can be reached; otherwise the asset is
+/// installed from its URL with the repository name as description.
+fn release_asset_package(
+    github: &GitHubProvider,
+    asset: &crate::providers::github::ReleaseAssetUrl,
+) -> (
+    crate::core::Package,
+    crate::core::manifest::PlatformBinary,
+    String,
+) {
+    let repo_url = asset.repo_url();
+    let pkg = match github.fetch_package_by_version(&repo_url, &asset.tag) {
+        Ok(pkg) => pkg,
+        Err(e) => {
+            log::debug!("No release metadata for {}: {:#}", repo_url, e);
+            crate::core::Package {
+                name: asset.repo.clone(),
+                aliases: Vec::new(),
+                description: asset.repo.clone(),
+                repo: repo_url.clone(),
+                homepage: None,
+                license: None,
+                version: Some(asset.version().to_string()),
+                platforms: HashMap::new(),
+                pre_install: Vec::new(),
+                post_install: Vec::new(),
+            }
+        }
+    };
+
+    let found = pkg.platforms.iter().find_map(|(platform_id, binaries)| {
+        binaries
+            .iter()
+            .find(|b| b.asset_name == asset.asset_name)
+            .map(|b| (b.clone(), platform_id.clone()))
+    });
+    let (binary, platform_id) = found.unwrap_or_else(|| {
+        (
+            crate::core::manifest::PlatformBinary {
+                url: format!(
+                    "{}/releases/download/{}/{}",
+                    repo_url, asset.tag, asset.asset_name
+                ),
+                size: 0,
+                checksum: None,
+                asset_name: asset.asset_name.clone(),
+            },
+            Platform::current()
+                .possible_identifiers()
+                .into_iter()
+                .next()
+                .unwrap_or_default(),
+        )
+    });
+
+    (pkg, binary, platform_id)
 }
 
 /// Install binary or archive from direct URLs
+#[allow(clippy::too_many_arguments)]
 fn install_from_urls(
     config: &Config,
     paths: &WenPaths,
@@ -560,16 +1195,36 @@ fn install_from_urls(
     urls: Vec<&String>,
     yes: bool,
     custom_name: Option<&str>,
-) -> Result<()> {
+    keep_download: bool,
+    dry_run: bool,
+) -> Result<Outcome> {
+    // Reject unusable custom names before anything is written
+    let custom_name = custom_name.map(sanitize_command_name).transpose()?;
+    let custom_name = custom_name.as_deref();
+
     println!("{}", "URLs to install:".bold());
 
     for url in &urls {
         println!("  • {}", url);
     }
 
+    if dry_run {
+        println!();
+        for url in &urls {
+            let filename = file_name(url.split('?').next().unwrap_or(url));
+            let name = match custom_name {
+                Some(custom) => custom.to_string(),
+                None => normalize_command_name(filename),
+            };
+            println!("{} {}", "Would install".cyan(), filename);
+            print_dry_run(url, &name, &paths.app_dir(&name));
+        }
+        return Ok(Outcome::new(urls.len(), 0));
+    }
+
     if !yes && !crate::utils::confirm("\nProceed with installation?")? {
         println!("Installation cancelled");
-        return Ok(());
+        return Ok(Outcome::default());
     }
 
     println!();
@@ -599,8 +1254,9 @@ fn install_from_urls(
         // Handle query parameters in URL
         let filename = filename.split('?').next().unwrap_or(filename);
         let download_path = temp_dir.join(filename);
+        let mut archive_path = None;
 
-        match downloader::download_file(url, &download_path) {
+        match downloader::download_file_cached(url, &download_path, 0, None, config.network()) {
             Ok(_) => {
                 println!("  {} Downloaded", "✓".green());
                 println!("{} {}...", "Installing".cyan(), filename);
@@ -623,6 +1279,7 @@ fn install_from_urls(
                             }
                         };
                         let display_names = inst_pkg.get_command_names().join(", ");
+                        archive_path = Some(paths.archive_path(&name, &inst_pkg.version, filename));
                         installed.upsert_package(name.clone(), inst_pkg);
                         println!(
                             "  {} Installed successfully as {}",
@@ -646,8 +1303,17 @@ fn install_from_urls(
             }
         }
 
-        // Clean up downloaded file
-        if download_path.exists() {
+        // Clean up downloaded file; kept archives go where package installs keep theirs
+        if let Some(archive_path) = archive_path.filter(|_| keep_download) {
+            match move_file(&download_path, &archive_path) {
+                Ok(()) => println!(
+                    "  {} Kept archive at {}",
+                    "ℹ".cyan(),
+                    archive_path.display()
+                ),
+                Err(e) => log::warn!("Failed to keep downloaded file: {:#}", e),
+            }
+        } else if download_path.exists() {
             if let Err(e) = fs::remove_file(&download_path) {
                 log::warn!(
                     "Failed to cleanup downloaded file: {}: {}",
@@ -683,7 +1349,7 @@ fn install_from_urls(
         );
     }
 
-    Ok(())
+    Ok(Outcome::new(success_count, fail_count))
 }
 
 /// Print available variant names for a package's binaries
@@ -715,7 +1381,9 @@ fn normalize_asset_for_matching(asset_name: &str) -> String {
         .trim_end_matches(".tar.xz")
         .trim_end_matches(".tgz")
         .trim_end_matches(".exe")
-        .trim_end_matches(".7z");
+        .trim_end_matches(".7z")
+        .trim_end_matches(".tar.lz4")
+        .trim_end_matches(".tar");
 
     // Split on both - and _, filter out version segments, rejoin
     name.split(['-', '_'])
@@ -815,17 +1483,37 @@ fn install_packages(
     custom_platform: Option<&str>,
     custom_version: Option<&str>,
     variant_filter: Option<&str>,
+    compiler: Option<Compiler>,
+    asset: Option<&str>,
     no_suffix: bool,
     update_mode: bool,
-) -> Result<()> {
-    // Get current platform (used for informational messages).
-    let current_platform = Platform::current();
+    keep_download: bool,
+    force: bool,
+    dry_run: bool,
+    completions: bool,
+    exe_selection: &ExeSelection,
+    sink: &dyn ProgressSink,
+) -> Result<Outcome> {
+    // `-p all` only downloads every platform's binaries
+    if prefetch::is_all_platforms(custom_platform) {
+        return prefetch::run(config, paths, names, custom_version, dry_run);
+    }
+
+    // Get current platform, honoring the `preferred_compiler` setting
+    let current_platform = Platform::current().prefer_compiler(
+        config
+            .preferences()
+            .preferred_compiler
+            .as_deref()
+            .and_then(Compiler::from_name),
+    );
 
     // Determine the effective platform override: the `-p/--platform` flag takes
     // precedence over the `preferred_platform` config setting. When neither is
     // set, auto-detection (`Platform::current`) is used.
-    let platform_override =
-        custom_platform.or_else(|| config.preferences().preferred_platform.as_deref());
+    let preferred_platform = config.preferences().valid_preferred_platform();
+    let platform_override = custom_platform.or(preferred_platform);
+    let native_arch = current_platform.arch;
 
     // Load cache once for both script lookup and package resolution
     let cache = config.get_or_rebuild_cache()?;
@@ -838,83 +1526,152 @@ fn install_packages(
         crate::core::platform::PlatformMatch,
     )> = Vec::new();
     let mut scripts_to_install: Vec<(String, String, ScriptType, String)> = Vec::new(); // (name, url, type, origin)
+                                                                                        // Inputs that could not be resolved or don't support this platform
+    let mut resolve_failures = 0;
 
     for original_name in &names {
         let input = PackageInput::parse(original_name);
+        let input_platform = input_platform_override(
+            installed,
+            original_name,
+            platform_override,
+            custom_platform,
+            update_mode,
+            native_arch,
+        );
+        let input_compiler = input_compiler(installed, original_name, compiler, update_mode);
 
         match resolver.resolve(&input) {
             Ok(resolved) => {
                 for pkg_resolved in resolved {
-                    // Use smart platform matching. When an override (flag or
-                    // config) is set, resolve against it; otherwise auto-detect.
-                    let matches = if let Some(override_str) = platform_override {
-                        Platform::match_override(override_str, &pkg_resolved.package.platforms)
-                    } else {
-                        current_platform.find_best_match(&pkg_resolved.package.platforms)
-                    };
-
-                    if matches.is_empty() {
-                        let target = platform_override
-                            .map(|s| s.to_string())
-                            .unwrap_or_else(|| current_platform.to_string());
-                        println!(
-                            "{} {} does not support platform {}",
-                            "Warning:".yellow(),
-                            pkg_resolved.package.name,
-                            target
-                        );
+                    // A package without any build isn't a platform problem
+                    if pkg_resolved.package.platforms.is_empty() {
                         println!(
-                            "  Available platforms: {}",
-                            pkg_resolved
-                                .package
-                                .platforms
-                                .keys()
-                                .cloned()
-                                .collect::<Vec<_>>()
-                                .join(", ")
+                            "{} {}",
+                            "✗".red(),
+                            GitHubProvider::source_only_message(&pkg_resolved.package.name)
                         );
+                        resolve_failures += 1;
                         continue;
                     }
 
-                    let best_match = &matches[0];
+                    // `--asset` picks the build by name once the release is fetched
+                    let best_match = if asset.is_some() {
+                        pinned_match(
+                            input_platform
+                                .clone()
+                                .unwrap_or_else(|| current_platform.to_string()),
+                        )
+                    } else {
+                        // Use smart platform matching. When an override (flag or
+                        // config) is set, resolve against it; otherwise auto-detect.
+                        let matches = match platform_matches_or_detect(
+                            &pkg_resolved.package.platforms,
+                            input_platform.as_deref(),
+                            preferred_platform,
+                            current_platform,
+                            input_compiler,
+                            &pkg_resolved.package.name,
+                        ) {
+                            Ok(matches) => matches,
+                            Err(e) => {
+                                println!(
+                                    "{} {}: {}",
+                                    "Warning:".yellow(),
+                                    pkg_resolved.package.name,
+                                    e
+                                );
+                                resolve_failures += 1;
+                                continue;
+                            }
+                        };
 
-                    // Check if fallback requires confirmation
-                    if let Some(fallback_type) = &best_match.fallback_type {
-                        if fallback_type.requires_confirmation() && !yes {
+                        if matches.is_empty() {
+                            let target = input_platform
+                                .clone()
+                                .unwrap_or_else(|| current_platform.to_string());
                             println!(
-                                "{} {} - no exact match for {}, but {} is available",
-                                "⚠".yellow(),
+                                "{} {} does not support platform {}",
+                                "Warning:".yellow(),
                                 pkg_resolved.package.name,
-                                current_platform,
-                                best_match.platform_id
+                                target
                             );
-                            println!("  This is a fallback: {}", fallback_type.description());
-
-                            if !crate::utils::prompt::confirm_no_default("  Install anyway?")? {
-                                println!("  Skipped");
-                                continue;
-                            }
-                        } else if !yes {
-                            // Fallback doesn't require confirmation, but inform user
                             println!(
-                                "{} Using fallback: {} ({})",
-                                "ℹ".cyan(),
-                                best_match.platform_id,
-                                fallback_type.description()
+                                "  Available platforms: {}",
+                                pkg_resolved
+                                    .package
+                                    .platforms
+                                    .keys()
+                                    .cloned()
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
                             );
+                            if input_platform.is_none() {
+                                print_installer_hint(
+                                    config,
+                                    &pkg_resolved.package,
+                                    current_platform,
+                                );
+                            }
+                            resolve_failures += 1;
+                            continue;
                         }
-                    }
 
-                    packages_to_install.push((
-                        original_name.to_string(),
-                        pkg_resolved,
-                        best_match.clone(),
-                    ));
+                        let best_match = matches[0].clone();
+
+                        // Check if fallback requires confirmation
+                        if let Some(fallback_type) = &best_match.fallback_type {
+                            if fallback_type.requires_confirmation() && !yes {
+                                println!(
+                                    "{} {} - no exact match for {}, but {} is available",
+                                    "⚠".yellow(),
+                                    pkg_resolved.package.name,
+                                    current_platform,
+                                    best_match.platform_id
+                                );
+                                println!("  This is a fallback: {}", fallback_type.description());
+
+                                if !crate::utils::prompt::confirm_no_default("  Install anyway?")? {
+                                    println!("  Skipped");
+                                    continue;
+                                }
+                            } else if !yes {
+                                // Fallback doesn't require confirmation, but inform user
+                                println!(
+                                    "{} Using fallback: {} ({})",
+                                    "ℹ".cyan(),
+                                    best_match.platform_id,
+                                    fallback_type.description()
+                                );
+                            }
+                        }
+                        best_match
+                    };
+
+                    // Inputs found by alias ("rg::musl") are keyed by the package name,
+                    // and bucket-qualified inputs ("extra/fd") by the bare name
+                    let unqualified = match input {
+                        PackageInput::BucketName { ref name, .. } => name,
+                        _ => original_name,
+                    };
+                    let original_name = match unqualified.split_once("::") {
+                        Some((base, variant))
+                            if !base.contains('*') && base != pkg_resolved.package.name =>
+                        {
+                            format!("{}::{}", pkg_resolved.package.name, variant)
+                        }
+                        _ => unqualified.to_string(),
+                    };
+
+                    packages_to_install.push((original_name, pkg_resolved, best_match));
                 }
             }
-            Err(_) => {
+            Err(e) => {
                 // If not found as package, check if it's a script in cache
-                if let Some(cached_script) = cache.find_script(original_name) {
+                if let Some(cached_script) = cache
+                    .find_script(input.name())
+                    .filter(|cs| from_bucket(&cs.source, input.bucket()))
+                {
                     let script = &cached_script.script;
 
                     // Get installable script for current platform (checks if interpreter exists)
@@ -938,9 +1695,11 @@ fn install_packages(
                             script.name,
                             script.platforms_display()
                         );
+                        resolve_failures += 1;
                     }
                 } else {
-                    eprintln!("{} {}: Not found", "Error".red().bold(), original_name);
+                    eprintln!("{} {}: {:#}", "Error".red().bold(), original_name, e);
+                    resolve_failures += 1;
                 }
             }
         }
@@ -948,12 +1707,12 @@ fn install_packages(
 
     if packages_to_install.is_empty() && scripts_to_install.is_empty() {
         println!("{}", "No packages or scripts to install".yellow());
-        return Ok(());
+        return Ok(Outcome::new(0, resolve_failures));
     }
 
     // Create GitHub provider to fetch versions (for packages)
     let github = if !packages_to_install.is_empty() {
-        Some(GitHubProvider::new()?)
+        Some(GitHubProvider::from_config(config)?)
     } else {
         None
     };
@@ -1031,37 +1790,90 @@ fn install_packages(
             "unknown".to_string()
         };
 
+        target_pkg.inherit_install_hooks(&resolved.package);
         resolved.package = target_pkg;
 
         // Recompute platform match for the new target package platforms
-        let matches = if let Some(override_str) = platform_override {
-            Platform::match_override(override_str, &resolved.package.platforms)
+        let input_platform = input_platform_override(
+            installed,
+            &original_name,
+            platform_override,
+            custom_platform,
+            update_mode,
+            native_arch,
+        );
+        let platform_match = if let Some(pattern) = asset {
+            // `--asset` replaces the scored builds with the named release asset
+            let binary =
+                match pinned_asset(github.as_ref(), &resolved.package, custom_version, pattern) {
+                    Ok(binary) => binary,
+                    Err(e) => {
+                        println!("{} {}: {:#}", "✗".red(), resolved.package.name, e);
+                        resolve_failures += 1;
+                        continue;
+                    }
+                };
+            let platform_id = input_platform.unwrap_or_else(|| current_platform.to_string());
+            resolved.package.platforms = HashMap::from([(platform_id.clone(), vec![binary])]);
+            pinned_match(platform_id)
         } else {
-            current_platform.find_best_match(&resolved.package.platforms)
-        };
+            let input_compiler = input_compiler(installed, &original_name, compiler, update_mode);
+            let matches = match platform_matches_or_detect(
+                &resolved.package.platforms,
+                input_platform.as_deref(),
+                preferred_platform,
+                current_platform,
+                input_compiler,
+                &resolved.package.name,
+            ) {
+                Ok(matches) => matches,
+                Err(e) => {
+                    println!(
+                        "{} {} v{}: {}",
+                        "Warning:".yellow(),
+                        resolved.package.name,
+                        version,
+                        e
+                    );
+                    resolve_failures += 1;
+                    continue;
+                }
+            };
 
-        if matches.is_empty() {
-            let target = platform_override
-                .map(|s| s.to_string())
-                .unwrap_or_else(|| current_platform.to_string());
-            println!(
-                "{} {} v{} does not support platform {}",
-                "Warning:".yellow(),
-                resolved.package.name,
-                version,
-                target
-            );
-            continue;
-        }
-        let platform_match = matches[0].clone();
+            if matches.is_empty() {
+                if input_platform.is_none() {
+                    print_installer_hint(config, &resolved.package, current_platform);
+                }
+                let target = input_platform.unwrap_or_else(|| current_platform.to_string());
+                println!(
+                    "{} {} v{} does not support platform {}",
+                    "Warning:".yellow(),
+                    resolved.package.name,
+                    version,
+                    target
+                );
+                resolve_failures += 1;
+                continue;
+            }
+            matches[0].clone()
+        };
 
         // Check if already installed
         // Determine which key to check based on input type and variant filter
+        // An explicit -p for another architecture installs side by side
+        let side_arch = if update_mode {
+            None
+        } else {
+            custom_platform.and(side_by_side_arch(&platform_match.platform_id, native_arch))
+        };
         let variant_key; // Storage for temporary String if needed
         let check_name: &str = if original_name.contains("::") {
             original_name.as_str()
-        } else if let Some(filter) = variant_filter {
-            variant_key = crate::core::manifest::generate_installed_key(&pkg_name, Some(filter));
+        } else if variant_filter.is_some() || side_arch.is_some() {
+            let variant =
+                with_arch_variant(variant_filter.map(str::to_string), side_arch.as_deref());
+            variant_key =
+                crate::core::manifest::generate_installed_key(&pkg_name, variant.as_deref());
             &variant_key
         } else {
             &pkg_name
@@ -1070,15 +1882,63 @@ fn install_packages(
         if installed.is_installed(check_name) {
             // Package already installed
             let inst_pkg = installed.get_package(check_name).unwrap();
-            if inst_pkg.version == version {
-                // Same version installed - ask if user wants to reinstall
+            let missing = inst_pkg.missing_executables();
+            // --compiler for a package installed with another build switches it
+            let switching = compiler.is_some() && inst_pkg.platform != platform_match.platform_id;
+            if inst_pkg.version == version && switching {
                 println!(
                     "  {} {} v{} {}",
-                    "•".cyan(),
+                    "•".yellow(),
                     check_name,
                     version,
-                    "(already installed, same version)".dimmed()
+                    format!(
+                        "(switching {} → {})",
+                        inst_pkg.platform, platform_match.platform_id
+                    )
+                    .yellow()
                 );
+                to_install.push((original_name.clone(), resolved, platform_match, None));
+            } else if inst_pkg.version == version && force {
+                // --force redoes the install, repairing a broken app directory
+                let reason = if missing.is_empty() {
+                    "(forced reinstall)"
+                } else {
+                    "(executables missing, reinstalling)"
+                };
+                println!(
+                    "  {} {} v{} {}",
+                    "•".yellow(),
+                    check_name,
+                    version,
+                    reason.yellow()
+                );
+                to_install.push((original_name.clone(), resolved, platform_match, None));
+            } else if inst_pkg.version == version {
+                // Same version installed - ask if user wants to reinstall
+                if missing.is_empty() {
+                    println!(
+                        "  {} {} v{} {}",
+                        "•".cyan(),
+                        check_name,
+                        version,
+                        "(already installed, same version)".dimmed()
+                    );
+                } else {
+                    println!(
+                        "  {} {} v{} {}",
+                        "⚠".yellow(),
+                        check_name,
+                        version,
+                        "(already installed, but executables are missing)".yellow()
+                    );
+                    for exe in &missing {
+                        println!("    {} {}", "✗".red(), exe);
+                    }
+                    println!(
+                        "    Use 'wenget add {} --force' to re-download and re-extract",
+                        check_name
+                    );
+                }
                 if !yes && crate::utils::prompt::confirm_no_default("  Reinstall?")? {
                     // User wants to reinstall
                     to_install.push((original_name.clone(), resolved, platform_match, None));
@@ -1171,13 +2031,13 @@ fn install_packages(
             "{}",
             "All packages and scripts are already up to date".green()
         );
-        return Ok(());
+        return Ok(Outcome::new(0, resolve_failures));
     }
 
     // Confirm installation
-    if !yes && !crate::utils::confirm("\nProceed with installation?")? {
+    if !yes && !dry_run && !crate::utils::confirm("\nProceed with installation?")? {
         println!("Installation cancelled");
-        return Ok(());
+        return Ok(Outcome::default());
     }
 
     println!();
@@ -1198,6 +2058,17 @@ fn install_packages(
         let pkg_name = &resolved.package.name;
         let repo_url = &resolved.package.repo;
 
+        // Installs of another architecture (explicit -p, or updates of such entries)
+        // live side by side under an arch-suffixed key, app dir and command
+        let side_arch = if update_mode {
+            installed_check_name
+                .as_deref()
+                .and_then(|key| installed.get_package(key))
+                .and_then(|pkg| side_by_side_arch(&pkg.platform, native_arch))
+        } else {
+            custom_platform.and(side_by_side_arch(&platform_match.platform_id, native_arch))
+        };
+
         // Extract variant from input name (e.g., "bun::baseline" -> Some("baseline"))
         // This takes precedence over the global variant_filter parameter
         let input_variant = if original_input_name.contains("::") {
@@ -1231,11 +2102,19 @@ fn install_packages(
                 }
             }
         }
-        let effective_variant_filter = effective_variant_filter.as_deref();
+        // The arch suffix is not part of the asset's own variant
+        let effective_variant_filter =
+            effective_variant_filter
+                .as_deref()
+                .and_then(|filter| match side_arch.as_deref() {
+                    Some(arch) => strip_arch_variant(filter, arch),
+                    None => Some(filter),
+                });
 
         // Try to fetch package info from GitHub API (includes download links)
         // If API rate limit is hit, fallback to cached package info
-        let (pkg_to_install, version, using_fallback) = if let Some(custom_ver) = custom_version {
+        let (mut pkg_to_install, version, using_fallback) = if let Some(custom_ver) = custom_version
+        {
             let normalized_custom = custom_ver.trim_start_matches('v');
             if resolved.package.version.as_deref() == Some(normalized_custom) {
                 // Already resolved in planning phase
@@ -1351,6 +2230,8 @@ fn install_packages(
             (resolved.package.clone(), version, true)
         };
 
+        pkg_to_install.inherit_install_hooks(&resolved.package);
+
         // Get all binaries for this platform
         let binaries = match pkg_to_install.platforms.get(&platform_match.platform_id) {
             Some(bins) => bins,
@@ -1512,6 +2393,7 @@ fn install_packages(
             } else {
                 crate::core::manifest::extract_variant_from_asset(&binary.asset_name, pkg_name)
             };
+            let variant = with_arch_variant(variant, side_arch.as_deref());
             let installed_key =
                 crate::core::manifest::generate_installed_key(pkg_name, variant.as_deref());
 
@@ -1523,6 +2405,22 @@ fn install_packages(
                 parent_key.clone()
             };
 
+            if dry_run {
+                println!("{} {} v{}", "Would install".cyan(), installed_key, version);
+                let command = expected_command_name(
+                    installed,
+                    &installed_key,
+                    pkg_name,
+                    custom_name,
+                    no_suffix,
+                )?;
+                print_dry_run(&binary.url, &command, &paths.app_dir(&installed_key));
+                success_count += 1;
+                successful_packages.push(installed_key);
+                println!();
+                continue;
+            }
+
             println!("{} {} v{}...", "Installing".cyan(), installed_key, version);
             if using_fallback {
                 println!(
@@ -1548,8 +2446,16 @@ fn install_packages(
                 yes,
                 no_suffix,
                 update_mode,
+                keep_download,
+                completions,
+                exe_selection,
+                config.network(),
+                sink,
             ) {
-                Ok(inst_pkg) => {
+                Ok(mut inst_pkg) => {
+                    if let Some(compiler) = compiler {
+                        inst_pkg.compiler = Some(compiler.as_str().to_string());
+                    }
                     installed.upsert_package(installed_key.clone(), inst_pkg);
 
                     // Collect package for cache update if fetched from GitHub API
@@ -1558,12 +2464,18 @@ fn install_packages(
                         packages_to_cache.push((pkg_to_install.clone(), resolved.source.clone()));
                     }
 
-                    println!("  {} Installed successfully", "✓".green());
+                    sink.on_event(InstallEvent::Installed {
+                        package: installed_key.clone(),
+                        version: Some(version.clone()),
+                    });
                     success_count += 1;
                     successful_packages.push(installed_key.clone());
                 }
                 Err(e) => {
-                    println!("  {} {}", "✗".red(), e);
+                    sink.on_event(InstallEvent::Failed {
+                        package: installed_key.clone(),
+                        error: e.to_string(),
+                    });
                     fail_count += 1;
                     failed_packages.push(installed_key.clone());
                 }
@@ -1572,7 +2484,7 @@ fn install_packages(
         }
     }
 
-    if success_count > 0 {
+    if success_count > 0 && !dry_run {
         if let Err(e) = config.save_installed(installed) {
             eprintln!("{} Failed to save installed manifest: {}", "✗".red(), e);
         }
@@ -1598,6 +2510,15 @@ fn install_packages(
     let mut failed_scripts: Vec<String> = Vec::new();
 
     for (name, url, script_type, origin) in scripts_to_process {
+        if dry_run {
+            println!("{} {}", "Would install".cyan(), name);
+            print_dry_run(&url, &name, &paths.app_dir(&name));
+            script_success_count += 1;
+            successful_scripts.push(name);
+            println!();
+            continue;
+        }
+
         println!(
             "{}",
             format!("Installing {} ({})...", name, script_type.display_name()).bold()
@@ -1614,12 +2535,18 @@ fn install_packages(
             custom_name,
         ) {
             Ok(_) => {
-                println!("  {} Installed successfully", "✓".green());
+                sink.on_event(InstallEvent::Installed {
+                    package: name.clone(),
+                    version: None,
+                });
                 script_success_count += 1;
                 successful_scripts.push(name);
             }
             Err(e) => {
-                println!("  {} {}", "✗".red(), e);
+                sink.on_event(InstallEvent::Failed {
+                    package: name.clone(),
+                    error: e.to_string(),
+                });
                 script_fail_count += 1;
                 failed_scripts.push(name);
             }
@@ -1627,19 +2554,25 @@ fn install_packages(
         println!();
     }
 
-    if script_success_count > 0 {
+    if script_success_count > 0 && !dry_run {
         if let Err(e) = config.save_installed(installed) {
             eprintln!("{} Failed to save installed manifest: {}", "✗".red(), e);
         }
     }
 
     // Summary
+    let done = if dry_run {
+        "would be installed"
+    } else {
+        "installed"
+    };
     println!("{}", "Summary:".bold());
     if success_count > 0 {
         println!(
-            "  {} {} package(s) installed: {}",
+            "  {} {} package(s) {}: {}",
             "✓".green(),
             success_count,
+            done,
             successful_packages.join(" ")
         );
     }
@@ -1653,9 +2586,10 @@ fn install_packages(
     }
     if script_success_count > 0 {
         println!(
-            "  {} {} script(s) installed: {}",
+            "  {} {} script(s) {}: {}",
             "✓".green(),
             script_success_count,
+            done,
             successful_scripts.join(" ")
         );
     }
@@ -1668,7 +2602,322 @@ fn install_packages(
         );
     }
 
-    Ok(())
+    Ok(Outcome::new(
+        success_count + script_success_count,
+        fail_count + script_fail_count + resolve_failures,
+    ))
+}
+
+/// Architecture of a platform id when it differs from the native one
+///
+/// Such installs are kept side by side with the native install, e.g.
+/// `ripgrep::x86_64` next to `ripgrep` on Apple Silicon.
+pub(crate) fn side_by_side_arch(platform_id: &str, native_arch: Arch) -> Option<String> {
+    let arch = ParsedAsset::from_filename(platform_id).arch?;
+    (arch != native_arch).then(|| arch.as_str().to_string())
+}
+
+/// Append a side-by-side arch suffix to an asset variant
+fn with_arch_variant(variant: Option<String>, arch: Option<&str>) -> Option<String> {
+    match (variant, arch) {
+        (Some(variant), Some(arch)) => Some(format!("{}-{}", variant, arch)),
+        (None, Some(arch)) => Some(arch.to_string()),
+        (variant, None) => variant,
+    }
+}
+
+/// Remove a side-by-side arch suffix from a variant filter
+///
+/// Returns None when the filter was only the arch suffix.
+fn strip_arch_variant<'a>(filter: &'a str, arch: &str) -> Option<&'a str> {
+    if filter == arch {
+        return None;
+    }
+    Some(
+        filter
+            .strip_suffix(arch)
+            .and_then(|rest| rest.strip_suffix('-'))
+            .unwrap_or(filter),
+    )
+}
+
+/// Platform to resolve one input against
+///
+/// Updates of side-by-side entries keep the platform recorded at install time
+/// unless `-p` is given; everything else uses `-p` or `preferred_platform`.
+fn input_platform_override(
+    installed: &crate::core::InstalledManifest,
+    key: &str,
+    platform_override: Option<&str>,
+    custom_platform: Option<&str>,
+    update_mode: bool,
+    native_arch: Arch,
+) -> Option<String> {
+    if update_mode && custom_platform.is_none() {
+        if let Some(pkg) = installed.get_package(key) {
+            if side_by_side_arch(&pkg.platform, native_arch).is_some() {
+                return Some(pkg.platform.clone());
+            }
+        }
+    }
+    platform_override.map(str::to_string)
+}
+
+/// Compiler to resolve one input against
+///
+/// `--compiler` wins; updates otherwise keep the compiler pinned at install time.
+fn input_compiler(
+    installed: &crate::core::InstalledManifest,
+    key: &str,
+    compiler: Option<Compiler>,
+    update_mode: bool,
+) -> Option<Compiler> {
+    if compiler.is_some() || !update_mode {
+        return compiler;
+    }
+    installed
+        .get_package(key)
+        .and_then(|pkg| pkg.compiler.as_deref())
+        .and_then(Compiler::from_name)
+}
+
+/// Platform matches for a package, best first
+///
+/// Resolves against `platform_override` when set, otherwise against the current
+/// platform. With a `compiler`, only builds for it on the best match's OS and
+/// architecture are kept; none being available is an error listing the builds
+/// that are.
+fn platform_matches(
+    platforms: &HashMap<String, Vec<crate::core::manifest::PlatformBinary>>,
+    platform_override: Option<&str>,
+    current_platform: Platform,
+    compiler: Option<Compiler>,
+) -> Result<Vec<crate::core::platform::PlatformMatch>> {
+    let matches = match platform_override {
+        Some(override_str) => Platform::match_override(override_str, platforms),
+        None => current_platform
+            .prefer_compiler(compiler)
+            .find_best_match(platforms),
+    };
+    let (Some(compiler), Some(best)) = (compiler, matches.first()) else {
+        return Ok(matches);
+    };
+
+    let target = ParsedAsset::from_filename(&best.platform_id);
+    let same_target = |platform_id: &str| {
+        let parsed = ParsedAsset::from_filename(platform_id);
+        parsed.os == target.os && parsed.arch == target.arch
+    };
+    let restricted: Vec<_> = matches
+        .iter()
+        .filter(|m| {
+            same_target(&m.platform_id)
+                && ParsedAsset::from_filename(&m.platform_id).compiler == Some(compiler)
+        })
+        .cloned()
+        .collect();
+    if restricted.is_empty() {
+        let mut available: Vec<&str> = platforms
+            .keys()
+            .map(String::as_str)
+            .filter(|id| same_target(id))
+            .collect();
+        available.sort_unstable();
+        anyhow::bail!(
+            "no {} build available (available variants: {})",
+            compiler.as_str(),
+            available.join(", ")
+        );
+    }
+    Ok(restricted)
+}
+
+/// Like [`platform_matches`], but a platform that only comes from the
+/// `preferred_platform` setting falls back to auto-detection, with a warning,
+/// when the package has no build for it
+fn platform_matches_or_detect(
+    platforms: &HashMap<String, Vec<crate::core::manifest::PlatformBinary>>,
+    platform_override: Option<&str>,
+    preferred_platform: Option<&str>,
+    current_platform: Platform,
+    compiler: Option<Compiler>,
+    pkg_name: &str,
+) -> Result<Vec<crate::core::platform::PlatformMatch>> {
+    let matches = platform_matches(platforms, platform_override, current_platform, compiler)?;
+    match platform_override {
+        Some(preferred) if matches.is_empty() && platform_override == preferred_platform => {
+            println!(
+                "{} {} has no build for preferred_platform '{}', falling back to auto-detection",
+                "⚠".yellow(),
+                pkg_name,
+                preferred
+            );
+            platform_matches(platforms, None, current_platform, compiler)
+        }
+        _ => Ok(matches),
+    }
+}
+
+/// Platform match of a build chosen with `--asset`
+fn pinned_match(platform_id: String) -> crate::core::platform::PlatformMatch {
+    crate::core::platform::PlatformMatch {
+        platform_id,
+        is_exact: true,
+        fallback_type: None,
+        score: 0,
+    }
+}
+
+/// The release asset of `pkg` named by `--asset`, from the newest release or `version`
+fn pinned_asset(
+    github: Option<&GitHubProvider>,
+    pkg: &crate::core::Package,
+    version: Option<&str>,
+    pattern: &str,
+) -> Result<crate::core::manifest::PlatformBinary> {
+    let github = github.context("GitHub provider unavailable")?;
+    let (owner, repo) = GitHubProvider::parse_github_url(&pkg.repo)
+        .with_context(|| format!("--asset needs a GitHub repository, not {}", pkg.repo))?;
+    let release = match version {
+        Some(tag) => github.fetch_release_by_tag(&owner, &repo, tag)?,
+        None => github.fetch_newest_release(&owner, &repo)?,
+    };
+    let asset = pick_asset(&release.assets, pattern)
+        .with_context(|| format!("in release {} of {}/{}", release.tag_name, owner, repo))?;
+    Ok(crate::core::manifest::PlatformBinary {
+        url: asset.browser_download_url.clone(),
+        size: asset.size,
+        checksum: None,
+        asset_name: asset.name.clone(),
+    })
+}
+
+/// The one asset whose name contains `pattern` (case-insensitive)
+fn pick_asset<'a>(assets: &'a [GitHubAsset], pattern: &str) -> Result<&'a GitHubAsset> {
+    let pattern = pattern.to_lowercase();
+    let matching: Vec<&GitHubAsset> = assets
+        .iter()
+        .filter(|a| a.name.to_lowercase().contains(&pattern))
+        .collect();
+    let names = |assets: &[&GitHubAsset]| {
+        assets
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    match matching.as_slice() {
+        [asset] => Ok(asset),
+        [] => anyhow::bail!(
+            "No asset matches '{}' (available: {})",
+            pattern,
+            names(&assets.iter().collect::<Vec<_>>())
+        ),
+        several => anyhow::bail!(
+            "'{}' matches {} assets: {}; use a longer name",
+            pattern,
+            several.len(),
+            names(several)
+        ),
+    }
+}
+
+/// Ask which executable candidates to install, showing their scores and reasons
+///
+/// `defaults` preselects candidates by index; missing entries start unselected.
+fn prompt_executables(
+    candidates: &[crate::installer::extractor::ExecutableCandidate],
+    defaults: &[bool],
+) -> Result<Vec<String>> {
+    use dialoguer::MultiSelect;
+
+    println!("  Found {} possible executables:", candidates.len());
+
+    let items: Vec<String> = candidates
+        .iter()
+        .map(|c| format!("{} (score: {}, {})", c.path, c.score, c.reason))
+        .collect();
+    let defaults: Vec<bool> = (0..items.len())
+        .map(|i| defaults.get(i).copied().unwrap_or(false))
+        .collect();
+
+    let selections = MultiSelect::new()
+        .with_prompt("Select executables to install (Space to select, Enter to confirm)")
+        .items(&items)
+        .defaults(&defaults)
+        .interact()?;
+
+    if selections.is_empty() {
+        anyhow::bail!("No executables selected");
+    }
+
+    Ok(selections
+        .into_iter()
+        .map(|i| candidates[i].path.clone())
+        .collect())
+}
+
+/// Resolve `--exe` values to extracted files
+///
+/// Each value is matched against the archive-relative path first, then against
+/// the file name. A file name shared by several files must be given as a path.
+fn select_executables_by_path(
+    extracted_files: &[String],
+    wanted: &[String],
+) -> Result<Vec<String>> {
+    let normalize = |p: &str| p.replace('\\', "/").trim_start_matches("./").to_string();
+
+    let mut selected: Vec<String> = Vec::new();
+    for want in wanted {
+        let want = normalize(want);
+        let by_path: Vec<&String> = extracted_files
+            .iter()
+            .filter(|f| normalize(f) == want)
+            .collect();
+        let matches = if by_path.is_empty() {
+            extracted_files
+                .iter()
+                .filter(|f| normalize(f).rsplit('/').next() == Some(want.as_str()))
+                .collect()
+        } else {
+            by_path
+        };
+
+        match matches.as_slice() {
+            [file] => {
+                if !selected.contains(file) {
+                    selected.push((*file).clone());
+                }
+            }
+            [] => anyhow::bail!(
+                "--exe {}: no such file in the archive. Extracted files:\n{}",
+                want,
+                extracted_files.join("\n")
+            ),
+            several => anyhow::bail!(
+                "--exe {}: matches several files, give the full path:\n{}",
+                want,
+                several
+                    .iter()
+                    .map(|f| f.as_str())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ),
+        }
+    }
+    Ok(selected)
+}
+
+/// Point to an OS installer when a package has no build for `platform`
+///
+/// Releases shipping only a .msi/.deb/... for a platform otherwise look unsupported.
+fn print_installer_hint(config: &Config, package: &crate::core::Package, platform: Platform) {
+    let hint = GitHubProvider::from_config(config)
+        .ok()
+        .and_then(|github| github.installer_hint(&package.repo, platform));
+    if let Some(hint) = hint {
+        println!("  {} Note: {}", "ℹ".cyan(), hint);
+    }
 }
 
 /// Install a single package
@@ -1692,7 +2941,34 @@ fn install_package(
     yes: bool,
     no_suffix: bool,
     update_mode: bool,
+    keep_download: bool,
+    completions: bool,
+    exe_selection: &ExeSelection,
+    network: &NetworkSettings,
+    sink: &dyn ProgressSink,
 ) -> Result<InstalledPackage> {
+    // Reject unusable custom names before anything is written
+    let custom_name = custom_name.map(sanitize_command_name).transpose()?;
+    let custom_name = custom_name.as_deref();
+
+    let preferences = Preferences::load(&paths.config_toml()).unwrap_or_default();
+
+    // Launchers stay in the directory recorded by a previous install unless
+    // --bin-dir explicitly moves them somewhere else (a bin directory that is
+    // just `custom_bin_path` from config.toml doesn't count)
+    let previous_bin_dir = installed
+        .get_package(installed_key)
+        .and_then(|p| p.bin_dir.as_deref());
+    let old_bin_paths = paths.for_package(previous_bin_dir);
+    let explicit_bin_dir = paths
+        .custom_bin_dir()
+        .filter(|dir| Some(*dir) != preferences.custom_bin_path.as_deref());
+    let paths = &match (previous_bin_dir, explicit_bin_dir) {
+        (Some(dir), None) => paths.for_package(Some(dir)),
+        _ => paths.clone(),
+    };
+    let bin_dir_moved = old_bin_paths.bin_dir() != paths.bin_dir();
+
     // Log if using fallback
     if let Some(fallback_type) = &platform_match.fallback_type {
         log::info!(
@@ -1702,8 +2978,14 @@ fn install_package(
         );
     }
 
+    // Ask about install hooks up front so the prompt doesn't interrupt the install
+    let run_hooks = approve_install_hooks(pkg, source, yes)?;
+
     // Download binary
-    println!("  Downloading from {}...", binary.url);
+    sink.on_event(InstallEvent::Downloading {
+        package: installed_key.to_string(),
+        url: binary.url.clone(),
+    });
 
     let download_dir = paths.downloads_dir();
     fs::create_dir_all(&download_dir)?;
@@ -1715,22 +2997,66 @@ fn install_package(
         .next_back()
         .context("Invalid download URL")?;
 
-    let download_path = download_dir.join(filename);
+    // An archive kept with --keep-download is reused for the same version
+    let archive_path = paths.archive_path(installed_key, version, filename);
+    let download_path = if archive_path.is_file() {
+        archive_path.clone()
+    } else {
+        download_dir.join(filename)
+    };
 
-    downloader::download_file(&binary.url, &download_path)?;
+    // Assets of private repos must be fetched through the authenticated API
+    let reused = match private_asset_url(pkg, version, binary, network) {
+        Some((api_url, token)) => downloader::download_github_asset_cached(
+            &api_url,
+            &download_path,
+            &token,
+            binary.size,
+            binary.checksum.as_deref(),
+            network,
+        )?,
+        None => downloader::download_file_cached(
+            &binary.url,
+            &download_path,
+            binary.size,
+            binary.checksum.as_deref(),
+            network,
+        )?,
+    };
+    if reused && download_path == archive_path {
+        println!("  Using kept archive {}", archive_path.display());
+    } else if reused {
+        println!("  Using cached download {}", download_path.display());
+    }
 
     // Extract to app directory (use installed_key for directory name)
     let app_dir = paths.app_dir(installed_key);
 
-    println!("  Extracting to {}...", app_dir.display());
-
-    // Remove existing installation
-    if app_dir.exists() {
-        fs::remove_dir_all(&app_dir)?;
+    sink.on_event(InstallEvent::Extracting {
+        package: installed_key.to_string(),
+        dest: app_dir.clone(),
+    });
+
+    replace_previous_install(
+        paths,
+        installed.get_package(installed_key),
+        installed_key,
+        version,
+        preferences.keep_versions(),
+    )?;
+
+    // Until the install completes, an error or Ctrl-C removes the half-extracted
+    // app directory and launchers created along the way. Armed only now: before
+    // this point the directory still held the working install.
+    let mut cleanup = CleanupGuard::new();
+    cleanup.track(&app_dir);
+
+    let extracted_files = extract_archive(&download_path, &app_dir, true)?;
+
+    if run_hooks {
+        run_install_hooks("pre-install", &pkg.pre_install, &app_dir)?;
     }
 
-    let extracted_files = extract_archive(&download_path, &app_dir)?;
-
     // Find executable candidates (pass app_dir for Unix permission checks)
     let candidates = find_executable_candidates(&extracted_files, &pkg.name, Some(&app_dir));
 
@@ -1742,7 +3068,25 @@ fn install_package(
     }
 
     // Select executables
-    let selected_executables = if candidates.len() == 1 {
+    let selected_executables = if !exe_selection.paths.is_empty() {
+        let selected = select_executables_by_path(&extracted_files, &exe_selection.paths)?;
+        for path in &selected {
+            println!("  Using executable: {} (--exe)", path);
+        }
+        selected
+    } else if exe_selection.interactive {
+        // --select: let the user decide, with the heuristic's picks preselected
+        let defaults: Vec<bool> = candidates.iter().map(|c| c.score > 0).collect();
+        prompt_executables(&candidates, &defaults)?
+    } else if exe_selection.all {
+        // --all-bins: no prompt however many there are; updates pick up new ones too
+        let selected = all_bins(&candidates);
+        println!("  Found {} executables (--all-bins):", selected.len());
+        for c in &selected {
+            println!("    {} ({})", c.path, c.reason);
+        }
+        selected.into_iter().map(|c| c.path.clone()).collect()
+    } else if candidates.len() == 1 {
         // Single candidate - auto-select
         let selected = &candidates[0];
         println!(
@@ -1904,28 +3248,7 @@ fn install_package(
             auto_select.into_iter().map(|c| c.path.clone()).collect()
         } else {
             // Too many candidates - show interactive selection
-            use dialoguer::MultiSelect;
-
-            println!("  Found {} possible executables:", candidates.len());
-
-            let items: Vec<String> = candidates
-                .iter()
-                .map(|c| format!("{} (score: {}, {})", c.path, c.score, c.reason))
-                .collect();
-
-            let selections = MultiSelect::new()
-                .with_prompt("Select executables to install (Space to select, Enter to confirm)")
-                .items(&items)
-                .interact()?;
-
-            if selections.is_empty() {
-                anyhow::bail!("No executables selected");
-            }
-
-            selections
-                .into_iter()
-                .map(|i| candidates[i].path.clone())
-                .collect()
+            prompt_executables(&candidates, &[])?
         }
     };
 
@@ -1999,18 +3322,12 @@ fn install_package(
                     (custom.to_string(), true)
                 } else {
                     // For additional executables, use auto-detected name
-                    let raw_name = exe_path
-                        .file_name()
-                        .and_then(|s| s.to_str())
-                        .context("Failed to extract command name")?;
+                    let raw_name = command_file_name(&exe_relative, &app_dir);
                     (normalize_command_name(raw_name), false)
                 }
             } else {
                 // Auto-detect and normalize command name
-                let raw_name = exe_path
-                    .file_name()
-                    .and_then(|s| s.to_str())
-                    .context("Failed to extract command name")?;
+                let raw_name = command_file_name(&exe_relative, &app_dir);
 
                 // Apply smart normalization to remove platform suffixes
                 (normalize_command_name(raw_name), false)
@@ -2024,8 +3341,9 @@ fn install_package(
 
         // Create symlink/shim using the resolved name
         let bin_path = paths.bin_shim_path(&resolved_name);
-
-        println!("  Creating launcher at {}...", bin_path.display());
+        if fs::symlink_metadata(&bin_path).is_err() {
+            cleanup.track(&bin_path);
+        }
 
         #[cfg(unix)]
         {
@@ -2037,6 +3355,12 @@ fn install_package(
             create_shim(&exe_path, &bin_path, &resolved_name)?;
         }
 
+        sink.on_event(InstallEvent::Linked {
+            package: installed_key.to_string(),
+            command: resolved_name.clone(),
+            launcher: bin_path,
+        });
+
         // Record the name as taken so subsequent executables in the same package
         // don't resolve to a colliding name.
         taken_names.insert(resolved_name.clone());
@@ -2046,8 +3370,8 @@ fn install_package(
     // Clean up symlinks/shims for old executables that no longer exist in the new version
     if let Some(ref old_exes) = old_executables {
         for old_cmd in old_exes.values() {
-            if !executables.values().any(|n| n == old_cmd) {
-                let old_bin = paths.bin_shim_path(old_cmd);
+            if bin_dir_moved || !executables.values().any(|n| n == old_cmd) {
+                let old_bin = old_bin_paths.bin_shim_path(old_cmd);
                 if old_bin.exists() {
                     fs::remove_file(&old_bin).ok();
                     println!("  Removed obsolete command: {}", old_cmd);
@@ -2056,8 +3380,44 @@ fn install_package(
         }
     }
 
-    // Clean up download
-    fs::remove_file(&download_path)?;
+    if run_hooks {
+        // The package is installed at this point; a failing hook is reported but not fatal
+        if let Err(e) = run_install_hooks("post-install", &pkg.post_install, &app_dir) {
+            println!("  {} {}", "⚠".yellow(), e);
+        }
+    }
+
+    // Clean up download unless asked to keep it around for reuse
+    if download_path == archive_path {
+        println!("  Kept archive at {}", archive_path.display());
+    } else if keep_download {
+        move_file(&download_path, &archive_path)?;
+        println!("  Kept archive at {}", archive_path.display());
+    } else {
+        fs::remove_file(&download_path)?;
+    }
+
+    // Completion scripts are copied out of the archive on request; updates keep them
+    let mut files = extracted_files;
+    let old_pkg = installed.get_package(installed_key);
+    if completions
+        || (update_mode && old_pkg.is_some_and(|p| !completions::recorded(paths, p).is_empty()))
+    {
+        let copied = completions::install(paths, &app_dir, &files)?;
+        if copied.is_empty() {
+            println!("  No shell completions found in the archive");
+        } else {
+            println!(
+                "  Installed {} shell completion file(s) to {}",
+                copied.len(),
+                paths.completions_root().display()
+            );
+        }
+        files.extend(copied);
+    }
+    if let Some(old_pkg) = old_pkg {
+        completions::remove(paths, old_pkg, &files)?;
+    }
 
     // Extract repo_name and variant from installed_key
     // installed_key format: "repo_name" or "repo_name::variant"
@@ -2085,12 +3445,168 @@ fn install_package(
         command_name: None,
         asset_name: binary.asset_name.clone(),
         parent_package: None, // Deprecated field
-        download_url: None,
+        download_url: Some(binary.url.clone()),
+        bin_dir: paths
+            .custom_bin_dir()
+            .map(|p| p.to_string_lossy().to_string()),
+        // Updates keep the recorded range; a fresh `add` resets it (run() re-applies
+        // one given as `name@range`)
+        version_constraint: if update_mode {
+            installed
+                .get_package(installed_key)
+                .and_then(|p| p.version_constraint.clone())
+        } else {
+            None
+        },
+        // Likewise for the compiler pin; install_packages() sets a new one
+        compiler: if update_mode {
+            installed
+                .get_package(installed_key)
+                .and_then(|p| p.compiler.clone())
+        } else {
+            None
+        },
+        checksum: binary.checksum.clone(),
+        files,
     };
 
+    cleanup.disarm();
     Ok(inst_pkg)
 }
 
+/// Clear the app directory for a new install of `installed_key`
+///
+/// Keeps the replaced version for `wenget rollback`: among the kept versions
+/// when `keep_versions` is above 1, otherwise as the package's single rollback
+/// snapshot. Then removes what is left of the old install. On error the old
+/// install is left where it was.
+fn replace_previous_install(
+    paths: &WenPaths,
+    old_pkg: Option<&InstalledPackage>,
+    installed_key: &str,
+    version: &str,
+    keep_versions: usize,
+) -> Result<()> {
+    let app_dir = paths.app_dir(installed_key);
+
+    if let Some(old_pkg) = old_pkg.filter(|old| old.version != version) {
+        if keep_versions > 1 {
+            if versions::keep(paths, installed_key, old_pkg)? {
+                versions::prune(paths, installed_key, keep_versions - 1)?;
+            }
+        } else {
+            versions::snapshot(paths, installed_key, old_pkg)?;
+        }
+    }
+
+    // Remove existing installation
+    if app_dir.exists() {
+        fs::remove_dir_all(&app_dir)?;
+    }
+
+    // A package re-keyed with `rename --package` still lives in its old directory
+    if let Some(old_pkg) = old_pkg {
+        let old_dir = Path::new(&old_pkg.install_path);
+        if old_dir != app_dir && old_dir.starts_with(paths.apps_dir()) && old_dir.exists() {
+            fs::remove_dir_all(old_dir)?;
+        }
+    }
+    Ok(())
+}
+
+/// Move a file, creating the destination's parent directory
+///
+/// Falls back to copying when the file can't be renamed (e.g. across devices).
+fn move_file(from: &Path, to: &Path) -> Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    if fs::rename(from, to).is_err() {
+        fs::copy(from, to)
+            .with_context(|| format!("Failed to move {} to {}", from.display(), to.display()))?;
+        fs::remove_file(from).with_context(|| format!("Failed to remove {}", from.display()))?;
+    }
+    Ok(())
+}
+
+/// Resolve the authenticated download URL for a binary from a private GitHub repo
+///
+/// Returns the asset's API URL and the token to send with it. Offline, without a
+/// GitHub token, when the public URL downloads as it is, or when the lookup fails,
+/// returns None and the binary is downloaded from its public URL as usual.
+fn private_asset_url(
+    pkg: &crate::core::Package,
+    version: &str,
+    binary: &crate::core::manifest::PlatformBinary,
+    network: &NetworkSettings,
+) -> Option<(String, String)> {
+    if network.offline || binary.asset_name.is_empty() {
+        return None;
+    }
+    let token = GitHubProvider::token_from_env()?;
+
+    // A HEAD request isn't counted against the API rate limit
+    let http = crate::utils::http::HttpClient::with_settings(None, network).ok()?;
+    if http.is_reachable(&binary.url) {
+        return None;
+    }
+
+    let github = GitHubProvider::with_options(Some(token.clone()), network).ok()?;
+    match github.private_asset_url(&pkg.repo, version, &binary.asset_name) {
+        Ok(api_url) => api_url.map(|url| (url, token)),
+        Err(e) => {
+            log::debug!("Private asset lookup failed for {}: {}", pkg.repo, e);
+            None
+        }
+    }
+}
+
+/// Decide whether a package's install hooks may run
+///
+/// Hooks are arbitrary commands, so they are always listed first. Bucket hooks run
+/// after confirmation (or with `--yes`); hooks from any other source are only run
+/// with `--yes`, since nobody reviewed them.
+fn approve_install_hooks(
+    pkg: &crate::core::Package,
+    source: &PackageSource,
+    yes: bool,
+) -> Result<bool> {
+    if !pkg.has_install_hooks() {
+        return Ok(false);
+    }
+
+    println!("  {} {} declares install hooks:", "⚠".yellow(), pkg.name);
+    for command in &pkg.pre_install {
+        println!("    pre-install:  {}", command);
+    }
+    for command in &pkg.post_install {
+        println!("    post-install: {}", command);
+    }
+
+    if yes {
+        return Ok(true);
+    }
+
+    if !matches!(source, PackageSource::Bucket { .. }) {
+        println!(
+            "  {} Skipping hooks from a non-bucket source (use --yes to run them)",
+            "ℹ".cyan()
+        );
+        return Ok(false);
+    }
+
+    crate::utils::prompt::confirm_no_default("  Run these commands?")
+}
+
+/// Run one stage of install hooks, skipping empty stages
+fn run_install_hooks(stage: &str, commands: &[String], app_dir: &Path) -> Result<()> {
+    if commands.is_empty() {
+        return Ok(());
+    }
+    crate::installer::hooks::run_hooks(stage, commands, app_dir)
+}
+
 /// Derive a package for a specific version by rewriting the cached download URLs.
 ///
 /// GitHub release assets always live at `.../releases/download/{tag}/{asset_name}`,
@@ -2104,7 +3620,7 @@ fn install_package(
 /// contain the version, or changed its asset naming between versions.
 ///
 /// Returns `None` when the cached version can't be used as a substitution anchor.
-fn derive_versioned_package(
+pub(crate) fn derive_versioned_package(
     cached: &crate::core::Package,
     requested_version: &str,
 ) -> Option<crate::core::Package> {
@@ -2134,12 +3650,15 @@ fn derive_versioned_package(
 
     Some(crate::core::Package {
         name: cached.name.clone(),
+        aliases: cached.aliases.clone(),
         description: cached.description.clone(),
         repo: cached.repo.clone(),
         homepage: cached.homepage.clone(),
         license: cached.license.clone(),
         version: Some(new_ver.to_string()),
         platforms,
+        pre_install: cached.pre_install.clone(),
+        post_install: cached.post_install.clone(),
     })
 }
 
@@ -2172,7 +3691,7 @@ fn update_cache_with_packages(
 /// Install a script from bucket cache
 #[allow(clippy::too_many_arguments)]
 fn install_script_from_bucket(
-    _config: &Config,
+    config: &Config,
     paths: &WenPaths,
     installed: &mut crate::core::InstalledManifest,
     name: &str,
@@ -2184,7 +3703,7 @@ fn install_script_from_bucket(
     println!("  Downloading script from {}...", url);
 
     // Download script content
-    let content = download_script(url)?;
+    let content = download_script(url, config.network())?;
 
     // Determine the final command name
     let command_name = custom_name.unwrap_or(name);
@@ -2230,6 +3749,13 @@ fn install_script_from_bucket(
         asset_name: format!("{}.{}", name, script_type.extension()),
         parent_package: None,
         download_url: Some(url.to_string()),
+        bin_dir: paths
+            .custom_bin_dir()
+            .map(|p| p.to_string_lossy().to_string()),
+        version_constraint: None,
+        compiler: None,
+        checksum: Some(script_checksum(&content)),
+        files: Vec::new(),
     };
     installed.upsert_package(name.to_string(), inst_pkg);
 
@@ -2253,12 +3779,15 @@ mod tests {
         );
         crate::core::Package {
             name: "sshi".to_string(),
+            aliases: Vec::new(),
             description: "desc".to_string(),
             repo: "https://github.com/superyngo/sshi".to_string(),
             homepage: None,
             license: None,
             version: Some(version.to_string()),
             platforms,
+            pre_install: Vec::new(),
+            post_install: Vec::new(),
         }
     }
 
@@ -2383,6 +3912,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_expected_command_name() {
+        let installed = crate::core::InstalledManifest::new();
+        assert_eq!(
+            expected_command_name(&installed, "bun::baseline", "bun", None, false).unwrap(),
+            "bun-baseline"
+        );
+        assert_eq!(
+            expected_command_name(&installed, "bun::baseline", "bun", None, true).unwrap(),
+            "bun"
+        );
+        assert_eq!(
+            expected_command_name(&installed, "ripgrep", "ripgrep", Some("rg"), false).unwrap(),
+            "rg"
+        );
+        assert!(
+            expected_command_name(&installed, "ripgrep", "ripgrep", Some("a/b"), false).is_err()
+        );
+        assert_eq!(
+            file_name("https://example.com/dl/tool-v1.zip"),
+            "tool-v1.zip"
+        );
+    }
+
     #[test]
     fn test_resolve_command_name_variant_already_suffixed() {
         let taken = std::collections::HashSet::new();
@@ -2403,4 +3956,311 @@ mod tests {
             "mytool-1"
         );
     }
+
+    #[test]
+    fn test_side_by_side_arch() {
+        assert_eq!(
+            side_by_side_arch("macos-x86_64", Arch::Aarch64),
+            Some("x86_64".to_string())
+        );
+        assert_eq!(side_by_side_arch("macos-aarch64", Arch::Aarch64), None);
+        assert_eq!(side_by_side_arch("linux-x86_64-musl", Arch::X86_64), None);
+    }
+
+    #[test]
+    fn test_arch_variant_roundtrip() {
+        assert_eq!(with_arch_variant(None, None), None);
+        assert_eq!(
+            with_arch_variant(None, Some("x86_64")),
+            Some("x86_64".to_string())
+        );
+        assert_eq!(
+            with_arch_variant(Some("baseline".to_string()), Some("x86_64")),
+            Some("baseline-x86_64".to_string())
+        );
+
+        assert_eq!(strip_arch_variant("x86_64", "x86_64"), None);
+        assert_eq!(
+            strip_arch_variant("baseline-x86_64", "x86_64"),
+            Some("baseline")
+        );
+        assert_eq!(strip_arch_variant("baseline", "x86_64"), Some("baseline"));
+    }
+
+    #[test]
+    fn test_preferred_platform_falls_back_to_detection() {
+        let mut platforms = HashMap::new();
+        platforms.insert(
+            "linux-x86_64-gnu".to_string(),
+            vec![crate::core::manifest::PlatformBinary {
+                url: "https://example.com/tool.tar.gz".to_string(),
+                size: 1,
+                checksum: None,
+                asset_name: "tool-linux-x86_64-gnu.tar.gz".to_string(),
+            }],
+        );
+        let host = Platform::with_compiler(crate::core::Os::Linux, Arch::X86_64, Compiler::Gnu);
+        let musl = Some("aarch64-unknown-linux-musl");
+
+        // Only a preferred platform falls back; -p stays strict
+        let matches =
+            platform_matches_or_detect(&platforms, musl, musl, host, None, "tool").unwrap();
+        assert_eq!(matches[0].platform_id, "linux-x86_64-gnu");
+        let matches =
+            platform_matches_or_detect(&platforms, musl, None, host, None, "tool").unwrap();
+        assert!(matches.is_empty());
+    }
+
+    #[test]
+    fn test_platform_matches_with_compiler() {
+        let binary = |name: &str| {
+            vec![crate::core::manifest::PlatformBinary {
+                url: format!("https://example.com/{}", name),
+                size: 1,
+                checksum: None,
+                asset_name: name.to_string(),
+            }]
+        };
+        let mut platforms = HashMap::new();
+        for id in ["linux-x86_64-musl", "linux-x86_64-gnu", "linux-i686-musl"] {
+            platforms.insert(id.to_string(), binary(id));
+        }
+        let host = Platform::with_compiler(crate::core::Os::Linux, Arch::X86_64, Compiler::Musl);
+
+        let best = |platforms: &HashMap<_, _>, compiler| {
+            platform_matches(platforms, None, host, compiler).map(|m| m[0].platform_id.clone())
+        };
+        assert_eq!(best(&platforms, None).unwrap(), "linux-x86_64-musl");
+        assert_eq!(
+            best(&platforms, Some(Compiler::Gnu)).unwrap(),
+            "linux-x86_64-gnu"
+        );
+
+        // Only builds for the same OS/arch count
+        platforms.remove("linux-x86_64-gnu");
+        let err = best(&platforms, Some(Compiler::Gnu))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("no gnu build"), "{}", err);
+        assert!(
+            err.contains("available variants: linux-x86_64-musl"),
+            "{}",
+            err
+        );
+        assert!(!err.contains("i686"), "{}", err);
+    }
+
+    #[test]
+    fn test_input_compiler() {
+        let mut installed = crate::core::InstalledManifest::new();
+        let mut pkg = InstalledPackage {
+            repo_name: "rg".to_string(),
+            variant: None,
+            version: "1.0.0".to_string(),
+            platform: "linux-x86_64-gnu".to_string(),
+            installed_at: Utc::now(),
+            install_path: "/tmp/rg".to_string(),
+            executables: HashMap::new(),
+            source: PackageSource::DirectRepo {
+                url: "https://github.com/o/rg".to_string(),
+            },
+            description: String::new(),
+            command_names: vec![],
+            command_name: None,
+            asset_name: String::new(),
+            parent_package: None,
+            download_url: None,
+            bin_dir: None,
+            version_constraint: None,
+            compiler: Some("gnu".to_string()),
+            checksum: None,
+            files: Vec::new(),
+        };
+        installed.upsert_package("rg".to_string(), pkg.clone());
+
+        assert_eq!(
+            input_compiler(&installed, "rg", None, true),
+            Some(Compiler::Gnu)
+        );
+        assert_eq!(
+            input_compiler(&installed, "rg", Some(Compiler::Musl), true),
+            Some(Compiler::Musl)
+        );
+        // A fresh add only uses the flag
+        assert_eq!(input_compiler(&installed, "rg", None, false), None);
+
+        pkg.compiler = None;
+        installed.upsert_package("rg".to_string(), pkg);
+        assert_eq!(input_compiler(&installed, "rg", None, true), None);
+    }
+
+    #[test]
+    fn test_pick_asset() {
+        let assets: Vec<GitHubAsset> = [
+            "tool-x86_64-unknown-linux-gnu.tar.gz",
+            "tool-x86_64-unknown-linux-musl.tar.gz",
+            "tool-x86_64-unknown-linux-musl.tar.gz.sha256",
+        ]
+        .iter()
+        .map(|name| GitHubAsset {
+            name: name.to_string(),
+            browser_download_url: format!("https://example.com/{}", name),
+            url: String::new(),
+            size: 1,
+        })
+        .collect();
+
+        let picked = pick_asset(&assets, "Linux-GNU").unwrap();
+        assert_eq!(picked.name, "tool-x86_64-unknown-linux-gnu.tar.gz");
+        assert_eq!(
+            pick_asset(&assets, "musl.tar.gz.sha").unwrap().name,
+            "tool-x86_64-unknown-linux-musl.tar.gz.sha256"
+        );
+
+        let several = pick_asset(&assets, "musl").unwrap_err().to_string();
+        assert!(several.contains("matches 2 assets"), "{}", several);
+        let none = pick_asset(&assets, "windows").unwrap_err().to_string();
+        assert!(none.starts_with("No asset matches 'windows'"), "{}", none);
+    }
+
+    #[test]
+    fn test_all_bins() {
+        let candidate = |path: &str, score| crate::installer::extractor::ExecutableCandidate {
+            path: path.to_string(),
+            score,
+            reason: String::new(),
+        };
+        let candidates = [
+            candidate("bin/ffmpeg", 235),
+            candidate("bin/ffprobe", 135),
+            candidate("bin/ffplay", 135),
+            candidate("doc/examples/build.sh", 30),
+        ];
+        let selected: Vec<&str> = all_bins(&candidates)
+            .iter()
+            .map(|c| c.path.as_str())
+            .collect();
+        assert_eq!(selected, ["bin/ffmpeg", "bin/ffprobe", "bin/ffplay"]);
+
+        // Nothing scores high enough: keep the best candidate
+        let selected = all_bins(&candidates[3..]);
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].path, "doc/examples/build.sh");
+    }
+
+    #[test]
+    fn test_move_file() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let from = temp.path().join("downloads").join("tool.tar.gz");
+        fs::create_dir_all(from.parent().unwrap()).unwrap();
+        fs::write(&from, "archive").unwrap();
+
+        let to = temp.path().join("archives/tool/1.0.0/tool.tar.gz");
+        move_file(&from, &to).unwrap();
+        assert!(!from.exists());
+        assert_eq!(fs::read_to_string(&to).unwrap(), "archive");
+    }
+
+    #[test]
+    fn test_select_executables_by_path() {
+        let files: Vec<String> = [
+            "tool-1.0/bin/tool",
+            "tool-1.0/bin/helper",
+            "tool-1.0/README.md",
+            "other/helper",
+        ]
+        .iter()
+        .map(|f| f.to_string())
+        .collect();
+        let select = |wanted: &[&str]| {
+            let wanted: Vec<String> = wanted.iter().map(|w| w.to_string()).collect();
+            select_executables_by_path(&files, &wanted)
+        };
+
+        assert_eq!(select(&["tool"]).unwrap(), vec!["tool-1.0/bin/tool"]);
+        assert_eq!(
+            select(&["./tool-1.0/bin/helper", "tool"]).unwrap(),
+            vec!["tool-1.0/bin/helper", "tool-1.0/bin/tool"]
+        );
+        assert_eq!(
+            select(&["tool-1.0\\bin\\tool"]).unwrap(),
+            vec!["tool-1.0/bin/tool"]
+        );
+        // Ambiguous file names and unknown files are errors
+        assert!(select(&["helper"]).is_err());
+        assert!(select(&["missing"]).is_err());
+    }
+
+    #[test]
+    fn test_private_asset_url_offline() {
+        let pkg = cached_pkg(
+            "1.0.0",
+            "https://github.com/superyngo/sshi/releases/download/v1.0.0/sshi.tar.gz",
+            "sshi.tar.gz",
+        );
+        let binary = &pkg.platforms["linux-armv7"][0];
+        let offline = NetworkSettings {
+            offline: true,
+            ..NetworkSettings::default()
+        };
+        // Nothing is looked up, with or without a token in the environment
+        assert_eq!(private_asset_url(&pkg, "1.0.0", binary, &offline), None);
+    }
+
+    #[test]
+    fn test_replace_previous_install_keeps_old_install_on_error() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let paths = WenPaths::with_root(temp.path().to_path_buf(), false);
+        let app_dir = paths.app_dir("tool");
+        fs::create_dir_all(&app_dir).unwrap();
+        fs::write(app_dir.join("tool"), "1.0.0").unwrap();
+        let old = InstalledPackage {
+            repo_name: "tool".to_string(),
+            variant: None,
+            version: "1.0.0".to_string(),
+            platform: "linux-x86_64".to_string(),
+            installed_at: Utc::now(),
+            install_path: app_dir.to_string_lossy().to_string(),
+            executables: HashMap::from([("tool".to_string(), "tool".to_string())]),
+            source: PackageSource::DirectRepo {
+                url: "https://github.com/o/tool".to_string(),
+            },
+            description: String::new(),
+            command_names: vec![],
+            command_name: None,
+            asset_name: String::new(),
+            parent_package: None,
+            download_url: None,
+            bin_dir: None,
+            version_constraint: None,
+            compiler: None,
+            checksum: None,
+            files: Vec::new(),
+        };
+
+        // A file where the versions directory belongs makes keeping fail
+        fs::create_dir_all(paths.app_versions_dir("tool").parent().unwrap()).unwrap();
+        fs::write(paths.app_versions_dir("tool"), "").unwrap();
+        assert!(replace_previous_install(&paths, Some(&old), "tool", "2.0.0", 2).is_err());
+        assert_eq!(fs::read_to_string(app_dir.join("tool")).unwrap(), "1.0.0");
+
+        // Once kept, the app directory is free for the new version
+        fs::remove_file(paths.app_versions_dir("tool")).unwrap();
+        replace_previous_install(&paths, Some(&old), "tool", "2.0.0", 2).unwrap();
+        assert!(!app_dir.exists());
+        assert_eq!(
+            fs::read_to_string(paths.app_versions_dir("tool").join("1.0.0").join("tool")).unwrap(),
+            "1.0.0"
+        );
+
+        // With keep_versions = 1 the replaced version becomes the rollback snapshot
+        fs::create_dir_all(&app_dir).unwrap();
+        fs::write(app_dir.join("tool"), "1.0.0").unwrap();
+        replace_previous_install(&paths, Some(&old), "tool", "2.0.0", 1).unwrap();
+        assert!(!app_dir.exists());
+        let snap = versions::rollback_snapshot(&paths, "tool")
+            .unwrap()
+            .unwrap();
+        assert_eq!(snap.package.version, "1.0.0");
+    }
 }
@@ -0,0 +1,242 @@
+//! Alias command implementation
+//!
+//! An alias is an extra launcher in the bin directory that runs the same
+//! executable as an installed command (`wenget alias k kubectl`). Unlike
+//! `rename`, the command keeps its own launcher. Aliases are stored in the
+//! `[aliases]` table of config.toml and recreated by `wenget repair --shims`.
+
+use crate::core::manifest::PackageSource;
+use crate::core::{Config, InstalledManifest, Preferences, WenPaths};
+use crate::installer::sanitize_command_name;
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Create, remove or list aliases
+pub fn run(name: Option<String>, command: Option<String>, remove: bool) -> Result<()> {
+    let config = Config::new()?;
+    let config_path = config.paths().config_toml();
+    // Read config.toml directly: Config falls back to defaults when it is invalid,
+    // and saving those would drop the existing aliases
+    let mut aliases = Preferences::load(&config_path)?.aliases;
+
+    let Some(name) = name else {
+        if aliases.is_empty() {
+            println!("{}", "No aliases defined".yellow());
+            println!("Create one with: wenget alias <alias> <command>");
+            return Ok(());
+        }
+        for (alias, command) in &aliases {
+            println!("{} -> {}", alias.green(), command);
+        }
+        return Ok(());
+    };
+
+    if remove {
+        if aliases.remove(&name).is_none() {
+            anyhow::bail!("No alias named '{}'", name);
+        }
+        let launcher = config.paths().bin_shim_path(&name);
+        if launcher.exists() || launcher.is_symlink() {
+            fs::remove_file(&launcher)
+                .with_context(|| format!("Failed to remove {}", launcher.display()))?;
+        }
+        Preferences::save_aliases(&config_path, &aliases)?;
+        println!("{} Removed alias {}", "✓".green(), name);
+        return Ok(());
+    }
+
+    let Some(command) = command else {
+        match aliases.get(&name) {
+            Some(command) => println!("{} -> {}", name.green(), command),
+            None => anyhow::bail!(
+                "No alias named '{}'. Create it with: wenget alias {} <command>",
+                name,
+                name
+            ),
+        }
+        return Ok(());
+    };
+
+    let alias = sanitize_command_name(&name)?;
+    let installed = config.get_or_create_installed()?;
+    validate_alias(config.paths(), &installed, &aliases, &alias)?;
+
+    let target = create_alias_launcher(config.paths(), &installed, &alias, &command)?;
+    aliases.insert(alias.clone(), command.clone());
+    Preferences::save_aliases(&config_path, &aliases)?;
+
+    println!(
+        "{} {} -> {} ({})",
+        "✓".green(),
+        alias.green().bold(),
+        command,
+        target.display()
+    );
+    Ok(())
+}
+
+/// Check that an alias doesn't take the place of a command or another file
+fn validate_alias(
+    paths: &WenPaths,
+    installed: &InstalledManifest,
+    aliases: &std::collections::BTreeMap<String, String>,
+    alias: &str,
+) -> Result<()> {
+    if let Some((key, _)) = installed
+        .packages
+        .iter()
+        .find(|(_, pkg)| pkg.get_command_names().contains(&alias))
+    {
+        anyhow::bail!("'{}' is already a command of package '{}'", alias, key);
+    }
+
+    // Redefining an alias replaces its launcher; anything else there is not ours
+    let launcher = paths.bin_shim_path(alias);
+    if !aliases.contains_key(alias) && (launcher.exists() || launcher.is_symlink()) {
+        anyhow::bail!(
+            "{} already exists and is not a wenget alias",
+            launcher.display()
+        );
+    }
+    Ok(())
+}
+
+/// Create the launcher of an alias, replacing an existing one
+///
+/// Returns what the launcher runs: the command's executable, or the command's
+/// launcher for scripts, whose launcher does more than run a file.
+pub fn create_alias_launcher(
+    paths: &WenPaths,
+    installed: &InstalledManifest,
+    alias: &str,
+    command: &str,
+) -> Result<PathBuf> {
+    let target = alias_target(paths, installed, command)?;
+    if !target.exists() {
+        anyhow::bail!("'{}' is missing: {}", command, target.display());
+    }
+
+    fs::create_dir_all(paths.bin_dir())?;
+    let launcher = paths.bin_shim_path(alias);
+    if launcher.exists() || launcher.is_symlink() {
+        fs::remove_file(&launcher)
+            .with_context(|| format!("Failed to remove {}", launcher.display()))?;
+    }
+    link(&target, &launcher, alias)?;
+    Ok(target)
+}
+
+/// File an alias of `command` should run
+fn alias_target(paths: &WenPaths, installed: &InstalledManifest, command: &str) -> Result<PathBuf> {
+    let (_, pkg) = installed
+        .packages
+        .iter()
+        .find(|(_, pkg)| pkg.get_command_names().contains(&command))
+        .with_context(|| format!("'{}' is not an installed command", command))?;
+
+    let pkg_paths = paths.for_package(pkg.bin_dir.as_deref());
+    if matches!(pkg.source, PackageSource::Script { .. }) && command == pkg.repo_name {
+        return Ok(pkg_paths.bin_shim_path(command));
+    }
+
+    let exe_relative = pkg
+        .get_exe_path_for_command(command)
+        .with_context(|| format!("No executable recorded for '{}'", command))?;
+    Ok(Path::new(&pkg.install_path).join(exe_relative))
+}
+
+/// Point a launcher at a file
+fn link(target: &Path, launcher: &Path, _alias: &str) -> Result<()> {
+    #[cfg(unix)]
+    {
+        crate::installer::create_symlink(target, launcher)
+    }
+
+    #[cfg(windows)]
+    {
+        crate::installer::create_shim(target, launcher, _alias)
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use super::*;
+    use crate::core::InstalledPackage;
+    use chrono::Utc;
+    use std::collections::{BTreeMap, HashMap};
+    use tempfile::TempDir;
+
+    fn installed_kubectl(paths: &WenPaths) -> InstalledManifest {
+        let app_dir = paths.app_dir("kubectl");
+        fs::create_dir_all(&app_dir).unwrap();
+        fs::write(app_dir.join("kubectl"), "#!/bin/sh\n").unwrap();
+
+        let mut installed = InstalledManifest::new();
+        installed.upsert_package(
+            "kubectl".to_string(),
+            InstalledPackage {
+                repo_name: "kubectl".to_string(),
+                variant: None,
+                version: "1.30.0".to_string(),
+                platform: "linux-x86_64".to_string(),
+                installed_at: Utc::now(),
+                install_path: app_dir.to_string_lossy().to_string(),
+                executables: HashMap::from([("kubectl".to_string(), "kubectl".to_string())]),
+                source: PackageSource::Bucket {
+                    name: "main".to_string(),
+                },
+                description: String::new(),
+                command_names: vec![],
+                command_name: None,
+                asset_name: "kubectl".to_string(),
+                parent_package: None,
+                download_url: None,
+                bin_dir: None,
+                version_constraint: None,
+                compiler: None,
+                checksum: None,
+                files: Vec::new(),
+            },
+        );
+        installed
+    }
+
+    #[test]
+    fn test_create_alias_launcher() {
+        let temp = TempDir::new().unwrap();
+        let paths = WenPaths::with_root(temp.path().to_path_buf(), false);
+        let installed = installed_kubectl(&paths);
+
+        let target = create_alias_launcher(&paths, &installed, "k", "kubectl").unwrap();
+        assert_eq!(target, paths.app_dir("kubectl").join("kubectl"));
+        assert_eq!(fs::read_link(paths.bin_shim_path("k")).unwrap(), target);
+
+        // Recreating replaces the launcher
+        create_alias_launcher(&paths, &installed, "k", "kubectl").unwrap();
+        assert_eq!(fs::read_link(paths.bin_shim_path("k")).unwrap(), target);
+
+        assert!(create_alias_launcher(&paths, &installed, "h", "helm").is_err());
+    }
+
+    #[test]
+    fn test_validate_alias() {
+        let temp = TempDir::new().unwrap();
+        let paths = WenPaths::with_root(temp.path().to_path_buf(), false);
+        let installed = installed_kubectl(&paths);
+        let mut aliases = BTreeMap::new();
+
+        assert!(validate_alias(&paths, &installed, &aliases, "k").is_ok());
+        // Installed commands can't be shadowed
+        assert!(validate_alias(&paths, &installed, &aliases, "kubectl").is_err());
+
+        // Files not created for an alias are left alone
+        fs::create_dir_all(paths.bin_dir()).unwrap();
+        fs::write(paths.bin_shim_path("k"), "").unwrap();
+        assert!(validate_alias(&paths, &installed, &aliases, "k").is_err());
+        aliases.insert("k".to_string(), "kubectl".to_string());
+        assert!(validate_alias(&paths, &installed, &aliases, "k").is_ok());
+    }
+}
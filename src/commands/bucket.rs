@@ -1,10 +1,17 @@
 //! Bucket command implementation
 
-use crate::bucket::Bucket;
+use crate::bucket::{default_priority, fetch_manifest_text, local_manifest_path, Bucket};
+use crate::cache::{diff_by_bucket, BucketDiff};
 use crate::cli::UpdateMode;
-use crate::core::manifest::{Package, ScriptItem, ScriptPlatform, ScriptType};
-use crate::core::Config;
+use crate::core::manifest::{
+    Package, PackageSource, PlatformBinary, ScriptItem, ScriptPlatform, ScriptType,
+};
+use crate::core::manifest_schema::{
+    manifest_from_value, parse_source_manifest, KNOWN_SCRIPT_TYPES,
+};
+use crate::core::{Config, Platform};
 use crate::providers::{GitHubProvider, GitHubRepo};
+use crate::utils::network::{NetworkOverrides, NetworkSettings};
 use crate::utils::HttpClient;
 use anyhow::{Context, Result};
 use colored::Colorize;
@@ -20,10 +27,19 @@ pub enum BucketCommand {
     Add {
         name: String,
         url: String,
+        priority: Option<u32>,
+        disabled: bool,
+        force: bool,
     },
     Del {
         names: Vec<String>,
     },
+    Enable {
+        name: String,
+    },
+    Disable {
+        name: String,
+    },
     List,
     Refresh,
     Create {
@@ -33,16 +49,29 @@ pub enum BucketCommand {
         output: Option<String>,
         token: Option<String>,
         update_mode: Option<UpdateMode>,
+        platforms: Vec<String>,
+    },
+    Validate {
+        source: String,
+        offline: bool,
     },
 }
 
 /// Run bucket command
-pub fn run(cmd: BucketCommand) -> Result<()> {
+pub fn run(cmd: BucketCommand, network: NetworkOverrides) -> Result<()> {
     match cmd {
-        BucketCommand::Add { name, url } => run_add(name, url),
+        BucketCommand::Add {
+            name,
+            url,
+            priority,
+            disabled,
+            force,
+        } => run_add(name, url, priority, disabled, force, network),
         BucketCommand::Del { names } => run_del(names),
+        BucketCommand::Enable { name } => run_set_enabled(&name, true),
+        BucketCommand::Disable { name } => run_set_enabled(&name, false),
         BucketCommand::List => run_list(),
-        BucketCommand::Refresh => run_refresh(),
+        BucketCommand::Refresh => run_refresh(network),
         BucketCommand::Create {
             repos_src,
             scripts_src,
@@ -50,13 +79,34 @@ pub fn run(cmd: BucketCommand) -> Result<()> {
             output,
             token,
             update_mode,
-        } => run_create(repos_src, scripts_src, direct, output, token, update_mode),
+            platforms,
+        } => run_create(
+            repos_src,
+            scripts_src,
+            direct,
+            output,
+            token,
+            update_mode,
+            platforms,
+            network,
+        ),
+        BucketCommand::Validate { source, offline } => run_validate(source, offline, network),
     }
 }
 
 /// Add a bucket
-fn run_add(name: String, url: String) -> Result<()> {
-    let config = Config::new()?;
+///
+/// The manifest is fetched and parsed first; a bucket whose URL is unreachable
+/// or doesn't serve a valid manifest is only added with `force`.
+fn run_add(
+    name: String,
+    url: String,
+    priority: Option<u32>,
+    disabled: bool,
+    force: bool,
+    network: NetworkOverrides,
+) -> Result<()> {
+    let config = Config::with_network(network)?;
 
     // Ensure WenPM is initialized
     if !config.is_initialized() {
@@ -65,24 +115,66 @@ fn run_add(name: String, url: String) -> Result<()> {
 
     println!("{} bucket '{}'...\n", "Adding".cyan(), name);
 
+    // Later commands run from other directories, so a relative path is made absolute
+    let url = match local_manifest_path(&url) {
+        Some(path) if path.is_relative() => std::env::current_dir()
+            .context("Failed to determine current directory")?
+            .join(path)
+            .to_string_lossy()
+            .to_string(),
+        _ => url,
+    };
+
     // Load bucket config
     let mut bucket_config = config.get_or_create_buckets()?;
+    if bucket_config.find_bucket(&name).is_some() {
+        println!("{} Bucket '{}' already exists", "✗".red(), name);
+        return Ok(());
+    }
+
+    // Check the manifest before saving, so typos show up now rather than on refresh
+    match fetch_manifest_counts(&name, &url, config.network()) {
+        Ok((packages, scripts)) => println!(
+            "{} Manifest OK: {} package(s), {} script(s)",
+            "✓".green(),
+            packages,
+            scripts
+        ),
+        Err(e) if force => println!("{} {:#}\n  Adding anyway (--force)", "⚠".yellow(), e),
+        Err(e) => {
+            println!("{} {:#}", "✗".red(), e);
+            anyhow::bail!(
+                "Bucket '{}' not added. Check the URL, or use --force to add it anyway",
+                name
+            );
+        }
+    }
 
     // Create bucket
     let bucket = Bucket {
         name: name.clone(),
         url: url.clone(),
-        enabled: true,
-        priority: 100,
+        enabled: !disabled,
+        priority: priority.unwrap_or_else(default_priority),
     };
 
     // Try to add bucket
+    let bucket_priority = bucket.priority;
     if bucket_config.add_bucket(bucket) {
         // Save config
         config.save_buckets(&bucket_config)?;
 
         println!("{} Bucket '{}' added", "✓".green(), name);
         println!("  URL: {}", url);
+        println!("  Priority: {}", bucket_priority);
+        if disabled {
+            println!(
+                "  {} Bucket is disabled; enable it with: wenget bucket enable {}",
+                "ℹ".cyan(),
+                name
+            );
+            return Ok(());
+        }
 
         // Invalidate cache so it will be rebuilt on next access
         config.invalidate_cache()?;
@@ -97,6 +189,55 @@ fn run_add(name: String, url: String) -> Result<()> {
     Ok(())
 }
 
+/// Fetch a bucket manifest and count its packages and scripts
+fn fetch_manifest_counts(
+    name: &str,
+    url: &str,
+    network: &NetworkSettings,
+) -> Result<(usize, usize)> {
+    // Bucket manifests are small, so they get the same short default timeout as refresh
+    let settings = NetworkSettings {
+        timeout: Some(network.timeout_or(Duration::from_secs(10))),
+        ..network.clone()
+    };
+    let content = fetch_manifest_text(url, &settings)?;
+    manifest_counts(&content)
+        .with_context(|| format!("Invalid manifest in bucket '{}' ({})", name, url))
+}
+
+/// Parse a bucket manifest and count its packages and scripts
+fn manifest_counts(content: &str) -> Result<(usize, usize)> {
+    let manifest = parse_source_manifest(content)?;
+    Ok((manifest.packages.len(), manifest.scripts.len()))
+}
+
+/// Enable or disable a bucket
+///
+/// The manifest cache is invalidated, so the next operation rebuilds it from
+/// the new set of enabled buckets.
+fn run_set_enabled(name: &str, enabled: bool) -> Result<()> {
+    let config = Config::new()?;
+    let mut bucket_config = config.get_or_create_buckets()?;
+    let state = if enabled { "enabled" } else { "disabled" };
+
+    match bucket_config.find_bucket(name) {
+        None => anyhow::bail!("Bucket '{}' not found (see wenget bucket list)", name),
+        Some(bucket) if bucket.enabled == enabled => {
+            println!("{} Bucket '{}' is already {}", "ℹ".cyan(), name, state);
+            return Ok(());
+        }
+        Some(_) => {}
+    }
+
+    bucket_config.set_enabled(name, enabled);
+    config.save_buckets(&bucket_config)?;
+    config.invalidate_cache()?;
+
+    println!("{} Bucket '{}' {}", "✓".green(), name, state);
+    println!("{}", "Cache will be rebuilt on next command.".cyan());
+    Ok(())
+}
+
 /// Delete buckets
 fn run_del(names: Vec<String>) -> Result<()> {
     let config = Config::new()?;
@@ -174,9 +315,10 @@ fn run_list() -> Result<()> {
     println!("{}", "Configured buckets:".bold());
     println!();
     println!(
-        "{:<20} {:<10} {}",
+        "{:<20} {:<10} {:<10} {}",
         "NAME".bold(),
         "STATUS".bold(),
+        "PRIORITY".bold(),
         "URL".bold()
     );
     println!("{}", "─".repeat(80));
@@ -190,9 +332,10 @@ fn run_list() -> Result<()> {
         };
 
         println!(
-            "{:<20} {:<18} {}",
+            "{:<20} {:<18} {:<10} {}",
             bucket.name.green(),
             status.to_string(),
+            bucket.priority,
             bucket.url
         );
     }
@@ -204,13 +347,25 @@ fn run_list() -> Result<()> {
 }
 
 /// Refresh cache from buckets
-fn run_refresh() -> Result<()> {
-    let config = Config::new()?;
+fn run_refresh(network: NetworkOverrides) -> Result<()> {
+    let config = Config::with_network(network)?;
+
+    if config.network().offline {
+        println!(
+            "{} Offline mode: buckets are not refreshed, the cached manifests stay in use",
+            "ℹ".cyan()
+        );
+        return Ok(());
+    }
 
     println!("{} manifest cache...\n", "Refreshing".cyan());
 
+    // Keep the previous cache to report what the refresh changed
+    let old_cache = config.load_cache().unwrap_or_default();
+
     // Force rebuild cache
     let cache = config.rebuild_cache()?;
+    let diffs = diff_by_bucket(&old_cache, &cache);
 
     println!();
     println!("{}", "Summary:".bold());
@@ -223,6 +378,11 @@ fn run_refresh() -> Result<()> {
             source_name,
             info.package_count
         );
+        if let PackageSource::Bucket { name } = &info.source {
+            if let Some(diff) = diffs.get(name) {
+                print_bucket_diff(diff);
+            }
+        }
     }
 
     println!();
@@ -232,6 +392,191 @@ fn run_refresh() -> Result<()> {
     Ok(())
 }
 
+/// Print what a refresh changed in one bucket
+fn print_bucket_diff(diff: &BucketDiff) {
+    if diff.is_empty() {
+        println!("      {}", "no changes".dimmed());
+        return;
+    }
+    if !diff.added.is_empty() {
+        println!(
+            "      {} {} added: {}",
+            "+".green(),
+            diff.added.len(),
+            summarize_names(diff.added.iter().cloned())
+        );
+    }
+    if !diff.removed.is_empty() {
+        println!(
+            "      {} {} removed: {}",
+            "-".red(),
+            diff.removed.len(),
+            summarize_names(diff.removed.iter().cloned())
+        );
+    }
+    if !diff.updated.is_empty() {
+        let changes = diff.updated.iter().map(|(name, from, to)| {
+            format!(
+                "{} {} -> {}",
+                name,
+                from.as_deref().unwrap_or("latest"),
+                to.as_deref().unwrap_or("latest")
+            )
+        });
+        println!(
+            "      {} {} updated: {}",
+            "↑".cyan(),
+            diff.updated.len(),
+            summarize_names(changes)
+        );
+    }
+}
+
+/// Join up to five names, summarizing the rest as "and N more"
+fn summarize_names(names: impl ExactSizeIterator<Item = String>) -> String {
+    const SHOWN: usize = 5;
+    let total = names.len();
+    let mut shown: Vec<String> = names.take(SHOWN).collect();
+    if total > SHOWN {
+        shown.push(format!("and {} more", total - SHOWN));
+    }
+    shown.join(", ")
+}
+
+/// Validate a bucket manifest before publishing
+fn run_validate(source: String, offline: bool, network: NetworkOverrides) -> Result<()> {
+    println!("{} {}...\n", "Validating".cyan(), source);
+
+    let settings = Config::with_network(network)?.network().clone();
+    let content = fetch_manifest_text(&source, &settings)?;
+
+    let (manifest, mut problems) = lint_manifest(&content)?;
+
+    if !offline {
+        let http = HttpClient::with_settings(GitHubProvider::token_from_env(), &settings)?;
+        let repos = manifest
+            .packages
+            .iter()
+            .map(|p| ("package", &p.name, &p.repo))
+            .chain(
+                manifest
+                    .scripts
+                    .iter()
+                    .map(|s| ("script", &s.name, &s.repo)),
+            );
+        for (kind, name, repo) in repos {
+            if !repo.is_empty() && !http.is_reachable(repo) {
+                problems.push(format!(
+                    "{} '{}': repo URL unreachable: {}",
+                    kind, name, repo
+                ));
+            }
+        }
+    }
+
+    println!(
+        "Checked {} package(s) and {} script(s)",
+        manifest.packages.len(),
+        manifest.scripts.len()
+    );
+
+    if problems.is_empty() {
+        println!("{} No problems found", "✓".green());
+        return Ok(());
+    }
+
+    println!();
+    for problem in &problems {
+        println!("  {} {}", "✗".red(), problem);
+    }
+    println!();
+
+    anyhow::bail!("Found {} problem(s) in {}", problems.len(), source)
+}
+
+/// Parse a bucket manifest and collect everything wrong with it
+///
+/// Scripts with unknown script types are reported and those entries dropped, so the
+/// rest of the manifest can still be checked. Fails only if the manifest can't be
+/// parsed at all.
+fn lint_manifest(content: &str) -> Result<(crate::core::manifest::SourceManifest, Vec<String>)> {
+    use crate::core::platform::ParsedAsset;
+    use std::collections::HashSet;
+
+    let mut problems = Vec::new();
+
+    let mut value: serde_json::Value = serde_json::from_str(content)
+        .map_err(|e| anyhow::anyhow!("Manifest is not valid JSON: {}", e))?;
+
+    if let Some(scripts) = value.get_mut("scripts").and_then(|s| s.as_array_mut()) {
+        for script in scripts {
+            let name = script
+                .get("name")
+                .and_then(|n| n.as_str())
+                .unwrap_or("<unnamed>")
+                .to_string();
+            if let Some(platforms) = script.get_mut("platforms").and_then(|p| p.as_object_mut()) {
+                let unknown: Vec<String> = platforms
+                    .keys()
+                    .filter(|k| !KNOWN_SCRIPT_TYPES.contains(&k.as_str()))
+                    .cloned()
+                    .collect();
+                for script_type in unknown {
+                    problems.push(format!(
+                        "script '{}': unknown script type '{}'",
+                        name, script_type
+                    ));
+                    platforms.remove(&script_type);
+                }
+            }
+        }
+    }
+
+    let manifest = manifest_from_value(value)?;
+
+    let mut seen = HashSet::new();
+    for package in &manifest.packages {
+        if !seen.insert(package.name.as_str()) {
+            problems.push(format!("package '{}': duplicate name", package.name));
+        }
+        if package.description.trim().is_empty() {
+            problems.push(format!("package '{}': missing description", package.name));
+        }
+
+        // A platform resolves when its key names a known OS (and an architecture, or an
+        // OS with a default one) and it lists at least one binary
+        let resolvable = package.platforms.iter().any(|(id, binaries)| {
+            let parsed = ParsedAsset::from_filename(id);
+            let known = match parsed.os {
+                Some(os) => parsed.arch.is_some() || os.default_arch().is_some(),
+                None => false,
+            };
+            known && binaries.iter().any(|b| !b.url.is_empty())
+        });
+        if !resolvable {
+            problems.push(format!(
+                "package '{}': no resolvable platforms",
+                package.name
+            ));
+        }
+    }
+
+    let mut seen = HashSet::new();
+    for script in &manifest.scripts {
+        if !seen.insert(script.name.as_str()) {
+            problems.push(format!("script '{}': duplicate name", script.name));
+        }
+        if script.description.trim().is_empty() {
+            problems.push(format!("script '{}': missing description", script.name));
+        }
+        if script.platforms.is_empty() {
+            problems.push(format!("script '{}': no script platforms", script.name));
+        }
+    }
+
+    Ok((manifest, problems))
+}
+
 /// Bucket manifest structure for output
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct BucketManifest {
@@ -268,25 +613,39 @@ struct GistResponse {
 struct ManifestGenerator {
     http: HttpClient,
     github: GitHubProvider,
+    network: NetworkSettings,
     packages: HashMap<String, Package>,
     scripts: HashMap<String, ScriptItem>,
+    /// Platforms to record (`--platforms`); empty records all
+    platform_filter: Vec<String>,
+    /// Repositories skipped because a requested platform had no asset
+    missing_platforms: Vec<String>,
 }
 
 impl ManifestGenerator {
     #[allow(dead_code)]
     fn new() -> Result<Self> {
-        Self::with_token(None)
+        Self::with_options(None, NetworkSettings::default())
     }
 
-    fn with_token(token: Option<String>) -> Result<Self> {
+    fn with_options(token: Option<String>, network: NetworkSettings) -> Result<Self> {
         Ok(Self {
-            http: HttpClient::with_token(token.clone())?,
-            github: GitHubProvider::with_token(token)?,
+            http: HttpClient::with_settings(token.clone(), &network)?,
+            github: GitHubProvider::with_options(token, &network)?,
+            network,
             packages: HashMap::new(),
             scripts: HashMap::new(),
+            platform_filter: Vec::new(),
+            missing_platforms: Vec::new(),
         })
     }
 
+    /// Only record the given platforms (see [`parse_platform_filter`])
+    fn with_platform_filter(mut self, filter: Vec<String>) -> Self {
+        self.platform_filter = filter;
+        self
+    }
+
     /// Load URLs from a source file (one URL per line, # for comments)
     fn load_sources(&self, file_path: &str) -> Result<Vec<String>> {
         let content = fs::read_to_string(file_path)
@@ -407,18 +766,38 @@ impl ManifestGenerator {
         let platforms = GitHubProvider::extract_platform_binaries(&release.assets);
 
         if platforms.is_empty() {
-            println!(" {} no binaries", "⚠".yellow());
+            if GitHubProvider::is_source_only(&release.assets) {
+                println!(
+                    " {} no release binaries (source-only), skipped",
+                    "⚠".yellow()
+                );
+            } else {
+                println!(" {} no binaries for a supported platform", "⚠".yellow());
+            }
             return Ok(());
         }
 
+        let platforms = match filter_platforms(platforms, &self.platform_filter) {
+            Ok(platforms) => platforms,
+            Err(missing) => {
+                println!(" {} no asset for {}", "✗".red(), missing.join(", "));
+                self.missing_platforms
+                    .push(format!("{}/{} ({})", owner, repo, missing.join(", ")));
+                return Ok(());
+            }
+        };
+
         let package = Package {
             name: repo_info.name.clone(),
+            aliases: Vec::new(),
             description: repo_info.description.clone().unwrap_or_default(),
             repo: repo_info.html_url.clone(),
             homepage: repo_info.homepage.clone().filter(|h| !h.is_empty()),
             license: repo_info.license.as_ref().and_then(|l| l.spdx_id.clone()),
             version: Some(release.tag_name.trim_start_matches('v').to_string()),
             platforms,
+            pre_install: Vec::new(),
+            post_install: Vec::new(),
         };
 
         println!(" {} {} platform(s)", "✓".green(), package.platforms.len());
@@ -439,7 +818,7 @@ impl ManifestGenerator {
 
         // Create HTTP client without token for public gists
         // GitHub Actions GITHUB_TOKEN doesn't have gist read permission
-        let gist_http = HttpClient::new()?;
+        let gist_http = HttpClient::with_settings(None, &self.network)?;
         let gist_url = format!("https://api.github.com/gists/{}", gist_id);
         let gist: GistResponse = gist_http
             .get_json(&gist_url)
@@ -722,6 +1101,7 @@ impl ManifestGenerator {
 }
 
 /// Create a bucket manifest from source files or direct URLs
+#[allow(clippy::too_many_arguments)]
 fn run_create(
     repos_src: Vec<String>,
     scripts_src: Vec<String>,
@@ -729,7 +1109,11 @@ fn run_create(
     output_path: Option<String>,
     token: Option<String>,
     update_mode: Option<UpdateMode>,
+    platforms: Vec<String>,
+    network: NetworkOverrides,
 ) -> Result<()> {
+    let platform_filter = parse_platform_filter(&platforms)?;
+
     // Validate inputs
     if repos_src.is_empty() && scripts_src.is_empty() && direct.is_empty() {
         println!("{}", "No input sources provided.".yellow());
@@ -745,11 +1129,12 @@ fn run_create(
         println!("  -d, --direct       Direct URLs or local paths (comma-separated)");
         println!("  -o, --output       Output file (default: manifest.json)");
         println!("  -t, --token        GitHub token for higher API rate limit");
+        println!("      --platforms    Only record these platforms (e.g. linux,macos,windows)");
         return Ok(());
     }
 
     // Try to get token from environment variable if not provided
-    let auth_token = token.or_else(|| std::env::var("GITHUB_TOKEN").ok());
+    let auth_token = token.or_else(crate::utils::http::github_token_from_env);
 
     if let Some(ref _token) = auth_token {
         println!(
@@ -763,7 +1148,7 @@ fn run_create(
         );
         println!(
             "{}",
-            "  Tip: Use --token or set GITHUB_TOKEN env var for higher rate limit".dimmed()
+            "  Tip: Use --token or set WENGET_GITHUB_TOKEN env var for higher rate limit".dimmed()
         );
     }
 
@@ -786,9 +1171,20 @@ fn run_create(
             .cyan()
     );
 
-    let mut generator = ManifestGenerator::with_token(auth_token)?;
+    let network = Config::with_network(network)?.network().clone();
+    let mut generator =
+        ManifestGenerator::with_options(auth_token, network)?.with_platform_filter(platform_filter);
     let new_manifest = generator.generate(repos_src, scripts_src, direct)?;
 
+    // A manifest silently missing packages isn't reproducible; don't write it
+    if !generator.missing_platforms.is_empty() {
+        anyhow::bail!(
+            "{} package(s) have no asset for a requested platform, no manifest written: {}",
+            generator.missing_platforms.len(),
+            generator.missing_platforms.join("; ")
+        );
+    }
+
     // Determine output path
     let output_file = output_path.unwrap_or_else(|| "manifest.json".to_string());
     let output_path = Path::new(&output_file);
@@ -925,3 +1321,249 @@ fn merge_manifests(mut existing: BucketManifest, new: BucketManifest) -> BucketM
 
     existing
 }
+
+/// OS names accepted by `--platforms`, with their platform id spelling
+const PLATFORM_OS_ALIASES: [(&str, &str); 8] = [
+    ("linux", "linux"),
+    ("macos", "macos"),
+    ("darwin", "macos"),
+    ("mac", "macos"),
+    ("windows", "windows"),
+    ("win", "windows"),
+    ("freebsd", "freebsd"),
+    ("bsd", "freebsd"),
+];
+
+/// Normalize `--platforms` values to platform id prefixes
+///
+/// Each value is an OS (`linux`, `macos`, `windows`, `freebsd`), optionally
+/// followed by an architecture and compiler as in platform ids
+/// (`linux-x86_64`, `linux-x86_64-musl`).
+fn parse_platform_filter(values: &[String]) -> Result<Vec<String>> {
+    let known: Vec<String> = Platform::matrix()
+        .iter()
+        .flat_map(|platform| {
+            let id = platform.to_string();
+            ["gnu", "musl", "msvc"]
+                .iter()
+                .map(|compiler| format!("{}-{}", id, compiler))
+                .chain([id.clone()])
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    let mut filter = Vec::new();
+    for value in values {
+        let value = value.trim().to_lowercase();
+        if value.is_empty() {
+            continue;
+        }
+        let (os, rest) = match value.split_once('-') {
+            Some((os, rest)) => (os, Some(rest)),
+            None => (value.as_str(), None),
+        };
+        let os = PLATFORM_OS_ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == os)
+            .map(|(_, os)| *os);
+        let normalized = match (os, rest) {
+            (Some(os), Some(rest)) => format!("{}-{}", os, rest),
+            (Some(os), None) => os.to_string(),
+            (None, _) => String::new(),
+        };
+        if os.is_none() || !known.iter().any(|id| platform_matches(id, &normalized)) {
+            anyhow::bail!(
+                "Unknown platform '{}' - expected an OS (linux, macos, windows, freebsd) or a platform id like linux-x86_64",
+                value
+            );
+        }
+        if !filter.contains(&normalized) {
+            filter.push(normalized);
+        }
+    }
+    Ok(filter)
+}
+
+/// Whether a platform id falls under a `--platforms` entry
+fn platform_matches(platform_id: &str, filter: &str) -> bool {
+    platform_id == filter
+        || platform_id
+            .strip_prefix(filter)
+            .is_some_and(|rest| rest.starts_with('-'))
+}
+
+/// Keep the platforms selected by `filter` (all when empty)
+///
+/// Returns the filter entries without any matching platform as the error.
+fn filter_platforms(
+    platforms: HashMap<String, Vec<PlatformBinary>>,
+    filter: &[String],
+) -> std::result::Result<HashMap<String, Vec<PlatformBinary>>, Vec<String>> {
+    if filter.is_empty() {
+        return Ok(platforms);
+    }
+    let missing: Vec<String> = filter
+        .iter()
+        .filter(|entry| !platforms.keys().any(|id| platform_matches(id, entry)))
+        .cloned()
+        .collect();
+    if !missing.is_empty() {
+        return Err(missing);
+    }
+    Ok(platforms
+        .into_iter()
+        .filter(|(id, _)| filter.iter().any(|entry| platform_matches(id, entry)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_platform_filter() {
+        let parse = |values: &[&str]| {
+            parse_platform_filter(&values.iter().map(|v| v.to_string()).collect::<Vec<_>>())
+        };
+        assert_eq!(
+            parse(&["linux", "Darwin", "win", "linux"]).unwrap(),
+            ["linux", "macos", "windows"]
+        );
+        assert_eq!(
+            parse(&["linux-x86_64-musl", "macos-aarch64"]).unwrap(),
+            ["linux-x86_64-musl", "macos-aarch64"]
+        );
+        assert!(parse(&[]).unwrap().is_empty());
+        assert!(parse(&["solaris"]).is_err());
+        assert!(parse(&["linux-sparc"]).is_err());
+        assert!(parse(&["linux-x86"]).is_err());
+    }
+
+    #[test]
+    fn test_filter_platforms() {
+        let binary = || {
+            vec![PlatformBinary {
+                url: "https://example.com/a.tar.gz".to_string(),
+                size: 1,
+                checksum: None,
+                asset_name: "a.tar.gz".to_string(),
+            }]
+        };
+        let platforms: HashMap<String, Vec<PlatformBinary>> = [
+            "linux-x86_64-musl",
+            "linux-x86_64-gnu",
+            "linux-aarch64",
+            "macos-aarch64",
+            "windows-x86_64-msvc",
+            "freebsd-x86_64",
+        ]
+        .into_iter()
+        .map(|id| (id.to_string(), binary()))
+        .collect();
+        let keys = |filter: &[&str]| {
+            let filter: Vec<String> = filter.iter().map(|f| f.to_string()).collect();
+            filter_platforms(platforms.clone(), &filter).map(|kept| {
+                let mut ids: Vec<String> = kept.into_keys().collect();
+                ids.sort();
+                ids
+            })
+        };
+
+        assert_eq!(keys(&[]).unwrap().len(), 6);
+        assert_eq!(
+            keys(&["linux", "macos"]).unwrap(),
+            [
+                "linux-aarch64",
+                "linux-x86_64-gnu",
+                "linux-x86_64-musl",
+                "macos-aarch64"
+            ]
+        );
+        assert_eq!(keys(&["linux-x86_64-musl"]).unwrap(), ["linux-x86_64-musl"]);
+        assert_eq!(
+            keys(&["windows", "macos-x86_64", "linux-armv7"]).unwrap_err(),
+            ["macos-x86_64", "linux-armv7"]
+        );
+    }
+
+    #[test]
+    fn test_summarize_names() {
+        let names = |n: usize| (1..=n).map(|i| format!("p{}", i)).collect::<Vec<_>>();
+        assert_eq!(summarize_names(names(2).into_iter()), "p1, p2");
+        assert_eq!(
+            summarize_names(names(7).into_iter()),
+            "p1, p2, p3, p4, p5, and 2 more"
+        );
+    }
+
+    #[test]
+    fn test_manifest_counts() {
+        let content = r#"{
+            "packages": [{
+                "name": "tool",
+                "description": "A tool",
+                "repo": "https://github.com/o/tool",
+                "platforms": {}
+            }],
+            "scripts": []
+        }"#;
+        assert_eq!(manifest_counts(content).unwrap(), (1, 0));
+
+        assert!(manifest_counts("<html>Not Found</html>").is_err());
+        assert!(manifest_counts(r#"{"packages": "nope"}"#).is_err());
+    }
+
+    #[test]
+    fn test_lint_manifest() {
+        let content = r#"{
+            "packages": [
+                {
+                    "name": "tool",
+                    "description": "A tool",
+                    "repo": "https://github.com/o/tool",
+                    "platforms": {
+                        "linux-x86_64": [{"url": "https://example.com/tool.tar.gz", "size": 1, "asset_name": "x"}]
+                    }
+                },
+                {
+                    "name": "tool",
+                    "description": "",
+                    "repo": "https://github.com/o/tool2",
+                    "platforms": {
+                        "amiga-68k": [{"url": "https://example.com/tool2.lha", "size": 1, "asset_name": "x"}]
+                    }
+                }
+            ],
+            "scripts": [
+                {
+                    "name": "hello",
+                    "description": "Say hello",
+                    "repo": "https://gist.github.com/o/1",
+                    "platforms": {
+                        "bash": {"url": "https://example.com/hello.sh"},
+                        "fish": {"url": "https://example.com/hello.fish"}
+                    }
+                }
+            ]
+        }"#;
+
+        let (manifest, problems) = lint_manifest(content).unwrap();
+        assert_eq!(manifest.packages.len(), 2);
+        assert_eq!(manifest.scripts.len(), 1);
+        assert_eq!(
+            problems,
+            vec![
+                "script 'hello': unknown script type 'fish'",
+                "package 'tool': duplicate name",
+                "package 'tool': missing description",
+                "package 'tool': no resolvable platforms",
+            ]
+        );
+
+        let clean = r#"{"packages": [{"name": "a", "description": "A", "repo": "",
+            "platforms": {"windows-x86_64": [{"url": "https://example.com/a.zip", "size": 1, "asset_name": "x"}]}}]}"#;
+        assert!(lint_manifest(clean).unwrap().1.is_empty());
+
+        assert!(lint_manifest("not json").is_err());
+    }
+}
@@ -0,0 +1,217 @@
+//! Cache command implementation
+//!
+//! Shows the footprint and freshness of the manifest cache, and clears it when
+//! it misbehaves. The cache is rebuilt from the buckets on the next operation.
+
+use crate::cache::ManifestCache;
+use crate::core::Config;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use colored::Colorize;
+use std::fs;
+
+/// Cache subcommands
+pub enum CacheCommand {
+    Info,
+    Clear,
+}
+
+/// Run cache command
+pub fn run(cmd: CacheCommand) -> Result<()> {
+    let config = Config::new()?;
+    match cmd {
+        CacheCommand::Info => run_info(&config),
+        CacheCommand::Clear => run_clear(&config),
+    }
+}
+
+/// Print cache location, size, freshness and contents
+fn run_info(config: &Config) -> Result<()> {
+    let path = config.paths().manifest_cache_json();
+
+    println!("{:<14} {}", "Cache file:".bold(), path.display());
+    if !path.exists() {
+        println!("{:<14} {}", "Status:".bold(), "not built".yellow());
+        println!();
+        println!("The cache is built on the next add, search or bucket refresh.");
+        return Ok(());
+    }
+
+    let size = fs::metadata(&path)
+        .with_context(|| format!("Failed to read cache file: {}", path.display()))?
+        .len();
+    let cache = config.load_cache()?;
+
+    let status = if cache.is_valid() {
+        "valid".green()
+    } else {
+        "expired".yellow()
+    };
+    let age = Utc::now() - cache.last_updated;
+
+    println!("{:<14} {}", "Size:".bold(), format_size(size));
+    println!(
+        "{:<14} {} ({} ago)",
+        "Last updated:".bold(),
+        cache.last_updated.format("%Y-%m-%d %H:%M:%S UTC"),
+        format_duration(age.num_seconds())
+    );
+    println!(
+        "{:<14} {}",
+        "TTL:".bold(),
+        format_duration(cache.ttl_seconds)
+    );
+    println!("{:<14} {}", "Status:".bold(), status);
+    println!("{:<14} {}", "Packages:".bold(), cache.packages.len());
+    println!("{:<14} {}", "Scripts:".bold(), cache.scripts.len());
+
+    let rows = source_rows(&cache);
+    if !rows.is_empty() {
+        println!();
+        println!(
+            "{:<20} {:<10} {:<10} {}",
+            "SOURCE".bold(),
+            "PACKAGES".bold(),
+            "SCRIPTS".bold(),
+            "FETCHED".bold()
+        );
+        println!("{}", "─".repeat(70));
+        for row in rows {
+            println!(
+                "{:<20} {:<10} {:<10} {}",
+                row.name.green(),
+                row.packages,
+                row.scripts,
+                row.fetched
+                    .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+                    .unwrap_or_else(|| "-".to_string())
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Remove the cache file so it is rebuilt on next use
+fn run_clear(config: &Config) -> Result<()> {
+    let path = config.paths().manifest_cache_json();
+    if !path.exists() {
+        println!("{} Cache is already empty", "ℹ".cyan());
+        return Ok(());
+    }
+
+    config.invalidate_cache()?;
+    println!("{} Removed {}", "✓".green(), path.display());
+    println!("  The cache will be rebuilt from buckets on the next operation.");
+    Ok(())
+}
+
+/// Package and script counts of one cache source
+#[derive(Debug, PartialEq)]
+struct SourceRow {
+    name: String,
+    packages: usize,
+    scripts: usize,
+    fetched: Option<chrono::DateTime<Utc>>,
+}
+
+/// Per-source counts, sorted by source name
+fn source_rows(cache: &ManifestCache) -> Vec<SourceRow> {
+    let mut rows: Vec<SourceRow> = cache
+        .sources
+        .iter()
+        .map(|(name, info)| SourceRow {
+            name: name.clone(),
+            packages: info.package_count,
+            scripts: cache.scripts_by_source(&info.source).len(),
+            fetched: info.last_fetched,
+        })
+        .collect();
+    rows.sort_by(|a, b| a.name.cmp(&b.name));
+    rows
+}
+
+/// Format a byte count with a binary unit (e.g. "1.5 MiB")
+pub(crate) fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Format a number of seconds as its largest whole unit (e.g. "3h", "2d")
+fn format_duration(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    match seconds {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86400 => format!("{}h", s / 3600),
+        s => format!("{}d", s / 86400),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CachedSourceInfo;
+    use crate::core::manifest::{PackageSource, ScriptItem};
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(-5), "0s");
+        assert_eq!(format_duration(42), "42s");
+        assert_eq!(format_duration(600), "10m");
+        assert_eq!(format_duration(86400), "1d");
+    }
+
+    #[test]
+    fn test_source_rows() {
+        let mut cache = ManifestCache::new();
+        for (name, count) in [("main", 3), ("extra", 1)] {
+            cache.sources.insert(
+                name.to_string(),
+                CachedSourceInfo {
+                    source: PackageSource::Bucket {
+                        name: name.to_string(),
+                    },
+                    package_count: count,
+                    last_fetched: None,
+                    url: None,
+                },
+            );
+        }
+        let script: ScriptItem = serde_json::from_str(
+            r#"{"name": "hello", "description": "", "repo": "",
+                "platforms": {"bash": {"url": "https://x/hello.sh"}}}"#,
+        )
+        .unwrap();
+        cache.add_script(
+            script,
+            PackageSource::Bucket {
+                name: "extra".to_string(),
+            },
+        );
+
+        let rows = source_rows(&cache);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].name, "extra");
+        assert_eq!((rows[0].packages, rows[0].scripts), (1, 1));
+        assert_eq!(rows[1].name, "main");
+        assert_eq!((rows[1].packages, rows[1].scripts), (3, 0));
+    }
+}
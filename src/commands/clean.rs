@@ -0,0 +1,151 @@
+//! Clean command implementation
+//!
+//! Removes what interrupted or `--keep-download` installs leave in the cache
+//! directory (`.part` files in `cache/downloads`, kept archives in
+//! `cache/archives`, self-upgrade binaries in `cache/self-upgrade`) and the
+//! manifest cache. Installed apps and
+//! launchers are never touched.
+
+use crate::commands::cache::format_size;
+use crate::core::{Config, WenPaths};
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Remove leftover downloads and/or the manifest cache
+pub fn run(downloads_only: bool, cache_only: bool) -> Result<()> {
+    let config = Config::new()?;
+    let paths = config.paths();
+    let mut reclaimed = 0;
+
+    if !cache_only {
+        let (count, bytes) = clean_downloads(paths)?;
+        reclaimed += bytes;
+        if count == 0 {
+            println!("{} No leftover downloads", "ℹ".cyan());
+        } else {
+            println!(
+                "{} Removed {} leftover download(s) ({})",
+                "✓".green(),
+                count,
+                format_size(bytes)
+            );
+        }
+    }
+
+    if !downloads_only {
+        let path = paths.manifest_cache_json();
+        if path.exists() {
+            reclaimed += path_size(&path);
+            config.invalidate_cache()?;
+            println!(
+                "{} Removed manifest cache; it is rebuilt from buckets on the next operation",
+                "✓".green()
+            );
+        } else {
+            println!("{} Manifest cache is already empty", "ℹ".cyan());
+        }
+    }
+
+    println!("\nReclaimed {}", format_size(reclaimed).bold());
+    Ok(())
+}
+
+/// Directories holding nothing but leftovers of downloads
+fn download_dirs(paths: &WenPaths) -> [PathBuf; 3] {
+    [
+        paths.downloads_dir(),
+        paths.archives_dir(),
+        paths.cache_dir().join("self-upgrade"),
+    ]
+}
+
+/// Remove everything in the download directories
+///
+/// Returns the number of removed entries and their total size.
+fn clean_downloads(paths: &WenPaths) -> Result<(usize, u64)> {
+    let mut count = 0;
+    let mut bytes = 0;
+    for dir in download_dirs(paths) {
+        if !dir.exists() {
+            continue;
+        }
+        let entries =
+            fs::read_dir(&dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+        for entry in entries {
+            let path = entry
+                .with_context(|| format!("Failed to read {}", dir.display()))?
+                .path();
+            let size = path_size(&path);
+            if path.is_dir() {
+                fs::remove_dir_all(&path)
+            } else {
+                fs::remove_file(&path)
+            }
+            .with_context(|| format!("Failed to remove {}", path.display()))?;
+            count += 1;
+            bytes += size;
+        }
+    }
+    Ok((count, bytes))
+}
+
+/// Size of a file, or of all files below a directory
+fn path_size(path: &Path) -> u64 {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| path_size(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_clean_downloads() {
+        let temp = TempDir::new().unwrap();
+        let paths = WenPaths::with_root(temp.path().to_path_buf(), false);
+
+        // Nothing to do before the directories exist
+        assert_eq!(clean_downloads(&paths).unwrap(), (0, 0));
+
+        let downloads = paths.downloads_dir();
+        fs::create_dir_all(downloads.join("partial")).unwrap();
+        fs::write(downloads.join("rg.tar.gz"), [0u8; 100]).unwrap();
+        fs::write(downloads.join("fd.zip.part"), [0u8; 20]).unwrap();
+        fs::write(downloads.join("partial").join("x"), [0u8; 5]).unwrap();
+        let upgrade = paths.cache_dir().join("self-upgrade");
+        fs::create_dir_all(&upgrade).unwrap();
+        fs::write(upgrade.join("wenget.tar.gz"), [0u8; 1000]).unwrap();
+        let kept = paths.archive_path("ripgrep", "14.1.0", "rg.tar.gz");
+        fs::create_dir_all(kept.parent().unwrap()).unwrap();
+        fs::write(&kept, [0u8; 200]).unwrap();
+
+        let app = paths.app_dir("ripgrep").join("rg");
+        fs::create_dir_all(app.parent().unwrap()).unwrap();
+        fs::write(&app, "binary").unwrap();
+        fs::write(paths.manifest_cache_json(), "{}").unwrap();
+
+        assert_eq!(clean_downloads(&paths).unwrap(), (5, 1325));
+        assert_eq!(fs::read_dir(&downloads).unwrap().count(), 0);
+        assert_eq!(fs::read_dir(&upgrade).unwrap().count(), 0);
+        assert_eq!(fs::read_dir(paths.archives_dir()).unwrap().count(), 0);
+
+        // Installed apps and the manifest cache are left alone
+        assert!(app.exists());
+        assert!(paths.manifest_cache_json().exists());
+    }
+}
@@ -1,14 +1,104 @@
 //! Config command implementation
+//!
+//! `wenget config` opens config.toml in an editor; `wenget config get/set`
+//! read and change single settings without one.
 
 use anyhow::{Context, Result};
 use colored::Colorize;
 use std::env;
+use std::path::PathBuf;
 use std::process::Command;
 
 use crate::core::{Config, Preferences};
 
-/// Run the config command - opens config.toml in default editor
-pub fn run(config: &Config) -> Result<()> {
+/// Settings readable and writable with `wenget config get/set`
+pub const KEYS: &[&str] = &["preferred_platform", "custom_bin_path"];
+
+/// Config subcommands
+pub enum ConfigCommand {
+    /// Open config.toml in an editor
+    Edit,
+    /// Print one setting
+    Get { key: String },
+    /// Change one setting; an empty value removes it
+    Set { key: String, value: String },
+}
+
+/// Run config command
+pub fn run(config: &Config, cmd: ConfigCommand) -> Result<()> {
+    match cmd {
+        ConfigCommand::Edit => run_edit(config),
+        ConfigCommand::Get { key } => run_get(config, &key),
+        ConfigCommand::Set { key, value } => run_set(config, &key, &value),
+    }
+}
+
+/// Print the value of `key`
+fn run_get(config: &Config, key: &str) -> Result<()> {
+    check_key(key)?;
+    let prefs = Preferences::load(&config.paths().config_toml())?;
+    match get_value(&prefs, key) {
+        Some(value) => println!("{}", value),
+        None => println!("{} {} is not set", "ℹ".cyan(), key),
+    }
+    Ok(())
+}
+
+/// Validate and save a new value for `key`
+fn run_set(config: &Config, key: &str, value: &str) -> Result<()> {
+    check_key(key)?;
+    let config_path = config.paths().config_toml();
+    let mut prefs = Preferences::load(&config_path)?;
+    set_value(&mut prefs, key, value);
+    prefs.validate()?;
+
+    let value = get_value(&prefs, key);
+    if !config_path.exists() {
+        Preferences::generate_default_file(&config_path)?;
+    }
+    Preferences::save_value(&config_path, key, value.as_deref())?;
+    match value {
+        Some(value) => println!("{} Set {} = {}", "✓".green(), key, value),
+        None => println!("{} Unset {}", "✓".green(), key),
+    }
+    Ok(())
+}
+
+fn check_key(key: &str) -> Result<()> {
+    if !KEYS.contains(&key) {
+        anyhow::bail!(
+            "Unknown config key: '{}' - Expected one of: {}",
+            key,
+            KEYS.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Current value of a supported key
+fn get_value(prefs: &Preferences, key: &str) -> Option<String> {
+    match key {
+        "preferred_platform" => prefs.preferred_platform.clone(),
+        "custom_bin_path" => prefs
+            .custom_bin_path
+            .as_ref()
+            .map(|path| path.display().to_string()),
+        _ => None,
+    }
+}
+
+/// Change a supported key; an empty value unsets it
+fn set_value(prefs: &mut Preferences, key: &str, value: &str) {
+    let value = Some(value.trim()).filter(|v| !v.is_empty());
+    match key {
+        "preferred_platform" => prefs.preferred_platform = value.map(str::to_string),
+        "custom_bin_path" => prefs.custom_bin_path = value.map(PathBuf::from),
+        _ => {}
+    }
+}
+
+/// Open config.toml in the default editor
+fn run_edit(config: &Config) -> Result<()> {
     let config_path = config.paths().config_toml();
 
     // Generate default config file if it doesn't exist
@@ -135,6 +225,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_get_and_set() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let paths = crate::core::WenPaths::with_root(temp.path().to_path_buf(), false);
+        let config = Config::load(paths, Default::default()).unwrap();
+        let config_path = config.paths().config_toml();
+
+        run_set(&config, "preferred_platform", "x86_64-unknown-linux-musl").unwrap();
+        let bin = temp.path().join("bin2");
+        run_set(&config, "custom_bin_path", &bin.to_string_lossy()).unwrap();
+        let prefs = Preferences::load(&config_path).unwrap();
+        assert_eq!(
+            get_value(&prefs, "preferred_platform").as_deref(),
+            Some("x86_64-unknown-linux-musl")
+        );
+        assert_eq!(prefs.custom_bin_path, Some(bin));
+
+        // Invalid values are rejected with the validation message and not saved
+        let err = run_set(&config, "preferred_platform", "linux").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Invalid platform string: 'linux'"));
+        let err = run_set(&config, "custom_bin_path", "relative/bin").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Custom bin path must be absolute"));
+        assert!(run_get(&config, "retries").is_err());
+        let prefs = Preferences::load(&config_path).unwrap();
+        assert_eq!(
+            prefs.preferred_platform.as_deref(),
+            Some("x86_64-unknown-linux-musl")
+        );
+
+        // An empty value unsets the key
+        run_set(&config, "preferred_platform", "").unwrap();
+        let prefs = Preferences::load(&config_path).unwrap();
+        assert!(prefs.preferred_platform.is_none());
+        assert!(prefs.custom_bin_path.is_some());
+    }
+
     #[test]
     fn test_detect_editor_with_env() {
         env::set_var("EDITOR", "custom-editor");
@@ -1,12 +1,16 @@
 //! Delete command implementation
 
+use crate::commands::Outcome;
+use crate::core::events::{InstallEvent, ProgressSink};
+use crate::core::history::Action;
 use crate::core::{Config, WenPaths};
 use anyhow::{Context, Result};
 use colored::Colorize;
 use glob::Pattern;
 use std::env;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// Delete installed packages
 pub fn run(
@@ -14,10 +18,11 @@ pub fn run(
     yes: bool,
     force: bool,
     variant_filter: Option<String>,
-) -> Result<()> {
+    sink: &dyn ProgressSink,
+) -> Result<Outcome> {
     // Check for self-deletion request
     if names.len() == 1 && names[0].to_lowercase() == "self" {
-        return delete_self(yes);
+        return delete_self(yes).map(|()| Outcome::default());
     }
 
     let config = Config::new()?;
@@ -28,13 +33,13 @@ pub fn run(
 
     if installed.packages.is_empty() {
         println!("{}", "No packages installed".yellow());
-        return Ok(());
+        return Ok(Outcome::new(0, names.len()));
     }
 
     if names.is_empty() {
         println!("{}", "No package names provided".yellow());
         println!("Usage: wenget del <name>...");
-        return Ok(());
+        return Ok(Outcome::default());
     }
 
     // Compile glob patterns
@@ -60,14 +65,14 @@ pub fn run(
             "{}",
             format!("No installed packages found matching: {:?}", names).yellow()
         );
-        return Ok(());
+        return Ok(Outcome::new(0, names.len()));
     }
 
     // Check for wenget self-deletion
     if matching_packages.contains(&"wenget".to_string()) && !force {
         println!("{}", "Cannot delete wenget itself".red());
         println!("Use --force if you really want to delete it");
-        return Ok(());
+        return Ok(Outcome::new(0, 1));
     }
 
     // Group packages by repo: find repos and their variants
@@ -208,13 +213,13 @@ pub fn run(
 
     if final_to_delete.is_empty() {
         println!("No packages selected for deletion");
-        return Ok(());
+        return Ok(Outcome::default());
     }
 
     // Confirm deletion
     if !yes && !crate::utils::prompt::confirm_no_default("\nProceed with deletion?")? {
         println!("Deletion cancelled");
-        return Ok(());
+        return Ok(Outcome::default());
     }
 
     println!();
@@ -224,15 +229,20 @@ pub fn run(
     let mut fail_count = 0;
 
     for name in final_to_delete {
+        // Already removed together with its parent package
+        if installed.get_package(&name).is_none() {
+            continue;
+        }
+
         println!("{} {}...", "Deleting".cyan(), name);
 
-        match delete_package(&config, &paths, &mut installed, &name) {
-            Ok(()) => {
-                println!("  {} Deleted successfully", "✓".green());
-                success_count += 1;
-            }
+        match delete_package(&paths, &mut installed, &name, sink) {
+            Ok(()) => success_count += 1,
             Err(e) => {
-                println!("  {} {}", "✗".red(), e);
+                sink.on_event(InstallEvent::Failed {
+                    package: name.clone(),
+                    error: e.to_string(),
+                });
                 fail_count += 1;
             }
         }
@@ -251,15 +261,15 @@ pub fn run(
         println!("  {} {} package(s) failed", "✗".red(), fail_count);
     }
 
-    Ok(())
+    Ok(Outcome::new(success_count, fail_count))
 }
 
-/// Delete a single package
+/// Delete a single package, along with any entries linked to it via `parent_package`
 fn delete_package(
-    _config: &Config,
     paths: &WenPaths,
     installed: &mut crate::core::InstalledManifest,
     name: &str,
+    sink: &dyn ProgressSink,
 ) -> Result<()> {
     // Get package info to find all command names
     let pkg = installed.get_package(name).context(format!(
@@ -267,41 +277,80 @@ fn delete_package(
         name
     ))?;
 
-    // Remove symlinks/shims for all command names
-    for command_name in pkg.executables.values() {
-        let bin_path = paths.bin_shim_path(command_name);
-        if bin_path.exists() {
+    // Launchers may live in a per-package bin directory (add --bin-dir)
+    let pkg_paths = &paths.for_package(pkg.bin_dir.as_deref());
+
+    // Remove symlinks/shims for every command the package exposes
+    for command_name in package_command_names(pkg, name) {
+        let bin_path = pkg_paths.bin_shim_path(&command_name);
+        // is_symlink() catches dangling links, which exists() reports as missing
+        if bin_path.exists() || bin_path.is_symlink() {
             fs::remove_file(&bin_path)
                 .with_context(|| format!("Failed to remove shim/symlink for '{}'", command_name))?;
         }
     }
 
-    // Also remove symlinks for legacy command_names (pre-migration packages)
-    for command_name in &pkg.command_names {
-        let bin_path = paths.bin_shim_path(command_name);
-        if bin_path.exists() {
-            fs::remove_file(&bin_path).ok();
-        }
-    }
-
-    // Also remove old single-name shim/symlink if it exists (for packages installed with old version)
-    let bin_path = paths.bin_shim_path(name);
-    if bin_path.exists() {
-        fs::remove_file(&bin_path).ok(); // Ignore errors here
-    }
-
-    // Remove app directory
-    let app_dir = paths.app_dir(name);
+    // Remove app directory. Prefer the recorded install path, which survives
+    // `rename --package`, as long as it lives under the apps directory.
+    let recorded = Path::new(&pkg.install_path);
+    let app_dir = if recorded.starts_with(paths.apps_dir()) {
+        recorded.to_path_buf()
+    } else {
+        paths.app_dir(name)
+    };
     if app_dir.exists() {
         fs::remove_dir_all(&app_dir)?;
     }
+    crate::installer::versions::remove_all(paths, name)?;
+    crate::installer::completions::remove(paths, pkg, &[])?;
+
+    crate::core::history::record(paths, Action::Delete, name, pkg);
 
     // Remove from installed manifest
     installed.remove_package(name);
 
+    sink.on_event(InstallEvent::Removed {
+        package: name.to_string(),
+    });
+
+    // Companion entries of a multi-binary install would otherwise leave dangling shims
+    let children: Vec<String> = installed
+        .packages
+        .iter()
+        .filter(|(_, child)| child.parent_package.as_deref() == Some(name))
+        .map(|(key, _)| key.clone())
+        .collect();
+    for child in children {
+        // The parent is already gone from the manifest, so a cyclic link cannot recurse forever
+        if installed.get_package(&child).is_some() {
+            delete_package(paths, installed, &child, sink)?;
+        }
+    }
+
     Ok(())
 }
 
+/// All command names a package may have launchers for
+///
+/// Covers the executables map, the legacy `command_names` / `command_name` fields
+/// and the package key itself (shims created by very old versions).
+fn package_command_names(pkg: &crate::core::InstalledPackage, key: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let candidates = pkg
+        .executables
+        .values()
+        .chain(pkg.command_names.iter())
+        .chain(pkg.command_name.iter())
+        .map(String::as_str)
+        .chain(std::iter::once(key));
+    for candidate in candidates {
+        if !names.iter().any(|n| n == candidate) {
+            names.push(candidate.to_string());
+        }
+    }
+    names
+}
+
 /// Removal options for self-deletion
 #[derive(Debug, Clone, Copy)]
 struct RemovalOptions {
@@ -348,13 +397,26 @@ fn show_removal_menu() -> Result<RemovalOptions> {
 
 /// Delete Wenget itself (complete uninstallation)
 fn delete_self(yes: bool) -> Result<()> {
+    let paths = WenPaths::new()?;
+    let exe_path = env::current_exe().context("Failed to get current executable path")?;
+
+    // A second run while the first one's cleanup script is pending would only
+    // find missing paths and schedule the same deletion again
+    if uninstall_in_progress(&uninstall_script_path()) {
+        println!(
+            "{} Wenget is already being uninstalled; it will be gone in a moment",
+            "ℹ".cyan()
+        );
+        if !paths.root().exists() {
+            println!("  {} already removed", paths.root().display());
+        }
+        return Ok(());
+    }
+
     println!("{}", "Wenget Self-Deletion".bold().red());
     println!("{}", "═".repeat(60));
     println!();
 
-    let paths = WenPaths::new()?;
-    let exe_path = env::current_exe().context("Failed to get current executable path")?;
-
     // Determine removal options
     let options = if yes {
         // When -y flag is used, remove everything (current behavior)
@@ -483,6 +545,31 @@ fn delete_self(yes: bool) -> Result<()> {
     Ok(())
 }
 
+/// How long after `delete self` its cleanup script counts as pending
+///
+/// The script deletes itself about 2 seconds after wenget exits; an older file
+/// was left behind by an interrupted run and must not block a new uninstall.
+const UNINSTALL_PENDING: Duration = Duration::from_secs(60);
+
+/// Temporary script that deletes the executable after wenget exits
+fn uninstall_script_path() -> PathBuf {
+    let name = if cfg!(windows) {
+        "wenget_uninstall.bat"
+    } else {
+        "wenget_uninstall.sh"
+    };
+    env::temp_dir().join(name)
+}
+
+/// Whether an uninstall script was written recently and has not run yet
+fn uninstall_in_progress(script_path: &Path) -> bool {
+    fs::metadata(script_path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age < UNINSTALL_PENDING)
+}
+
 /// Remove Wenget bin directory from PATH
 fn remove_from_path(bin_dir: &Path) -> Result<()> {
     let bin_dir_str = bin_dir.to_string_lossy();
@@ -562,8 +649,8 @@ fn remove_from_shell_config(config_path: &Path, bin_dir: &str) -> Result<()> {
     let content = fs::read_to_string(config_path)
         .with_context(|| format!("Failed to read {}", config_path.display()))?;
 
-    // Remove lines containing the Wenget PATH entry
-    let new_content: String = content
+    // Remove the managed PATH block, then any stray lines from older versions
+    let new_content: String = super::init::strip_path_block(&content)
         .lines()
         .filter(|line| {
             // Skip lines that contain the Wenget bin directory or Wenget comment
@@ -606,8 +693,7 @@ fn delete_executable_windows(
     use std::process::Command;
 
     // Create a temporary batch script to delete the executable after exit
-    let temp_dir = env::temp_dir();
-    let script_path = temp_dir.join("wenget_uninstall.bat");
+    let script_path = uninstall_script_path();
 
     let exe_path_str = exe_path.to_string_lossy();
     let script_content = if exe_in_wenget {
@@ -655,8 +741,7 @@ fn delete_executable_unix(exe_path: &Path, exe_in_wenget: bool, wenget_root: &Pa
     use std::process::Command;
 
     // Create a shell script to delete the executable after exit
-    let temp_dir = env::temp_dir();
-    let script_path = temp_dir.join("wenget_uninstall.sh");
+    let script_path = uninstall_script_path();
 
     let exe_path_str = exe_path.to_string_lossy();
     let script_content = if exe_in_wenget {
@@ -709,6 +794,255 @@ rm -f "$0"
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::core::manifest::PackageSource;
+    use crate::core::{InstalledManifest, InstalledPackage};
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    /// Collects events instead of printing them
+    #[derive(Default)]
+    struct RecordingSink {
+        events: std::cell::RefCell<Vec<InstallEvent>>,
+    }
+
+    impl ProgressSink for RecordingSink {
+        fn on_event(&self, event: InstallEvent) {
+            self.events.borrow_mut().push(event);
+        }
+    }
+
+    #[test]
+    fn test_uninstall_in_progress() {
+        let temp = TempDir::new().unwrap();
+        let script = temp.path().join("wenget_uninstall.sh");
+        assert!(!uninstall_in_progress(&script));
+
+        fs::write(&script, "#!/bin/sh\n").unwrap();
+        assert!(uninstall_in_progress(&script));
+
+        // A leftover from an interrupted run doesn't block a new uninstall
+        let stale = std::time::SystemTime::now() - UNINSTALL_PENDING * 2;
+        fs::File::options()
+            .write(true)
+            .open(&script)
+            .unwrap()
+            .set_modified(stale)
+            .unwrap();
+        assert!(!uninstall_in_progress(&script));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_delete_package_removes_every_command_shim() {
+        let temp = TempDir::new().unwrap();
+        let paths = WenPaths::with_root(temp.path().to_path_buf(), false);
+
+        let app_dir = paths.app_dir("node");
+        fs::create_dir_all(app_dir.join("bin")).unwrap();
+        fs::create_dir_all(paths.bin_dir()).unwrap();
+        for command in ["node", "npm"] {
+            let exe = app_dir.join("bin").join(command);
+            fs::write(&exe, "#!/bin/sh\n").unwrap();
+            std::os::unix::fs::symlink(&exe, paths.bin_shim_path(command)).unwrap();
+        }
+
+        let mut installed = InstalledManifest::new();
+        installed.upsert_package(
+            "node".to_string(),
+            InstalledPackage {
+                repo_name: "node".to_string(),
+                variant: None,
+                version: "1.0.0".to_string(),
+                platform: "linux-x86_64".to_string(),
+                installed_at: Utc::now(),
+                install_path: app_dir.to_string_lossy().to_string(),
+                executables: HashMap::from([
+                    ("bin/node".to_string(), "node".to_string()),
+                    ("bin/npm".to_string(), "npm".to_string()),
+                ]),
+                source: PackageSource::DirectRepo {
+                    url: "https://github.com/o/node".to_string(),
+                },
+                description: "Node".to_string(),
+                command_names: vec![],
+                command_name: None,
+                asset_name: "node.tar.gz".to_string(),
+                parent_package: None,
+                download_url: None,
+                bin_dir: None,
+                version_constraint: None,
+                compiler: None,
+                checksum: None,
+                files: Vec::new(),
+            },
+        );
+
+        let sink = RecordingSink::default();
+        delete_package(&paths, &mut installed, "node", &sink).unwrap();
+        assert_eq!(
+            sink.events.into_inner(),
+            vec![InstallEvent::Removed {
+                package: "node".to_string()
+            }]
+        );
+
+        for command in ["node", "npm"] {
+            let shim = paths.bin_shim_path(command);
+            assert!(
+                !shim.exists() && !shim.is_symlink(),
+                "{} still exists",
+                shim.display()
+            );
+        }
+        assert!(!app_dir.exists());
+        assert!(installed.get_package("node").is_none());
+
+        let history = crate::core::history::read_last(&paths.history_jsonl(), 10).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].action, Action::Delete);
+        assert_eq!(
+            (history[0].package.as_str(), history[0].version.as_str()),
+            ("node", "1.0.0")
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_delete_package_removes_child_entries() {
+        let temp = TempDir::new().unwrap();
+        let paths = WenPaths::with_root(temp.path().to_path_buf(), false);
+        fs::create_dir_all(paths.bin_dir()).unwrap();
+
+        let mut installed = InstalledManifest::new();
+        for (key, command, parent) in [
+            ("bundle", "bundle", None),
+            ("bundle::helper", "bundle-helper", Some("bundle")),
+        ] {
+            let app_dir = paths.app_dir(key);
+            fs::create_dir_all(&app_dir).unwrap();
+            let exe = app_dir.join(command);
+            fs::write(&exe, "#!/bin/sh\n").unwrap();
+            std::os::unix::fs::symlink(&exe, paths.bin_shim_path(command)).unwrap();
+
+            installed.upsert_package(
+                key.to_string(),
+                InstalledPackage {
+                    repo_name: "bundle".to_string(),
+                    variant: parent.map(|_| "helper".to_string()),
+                    version: "1.0.0".to_string(),
+                    platform: "linux-x86_64".to_string(),
+                    installed_at: Utc::now(),
+                    install_path: app_dir.to_string_lossy().to_string(),
+                    executables: HashMap::from([(command.to_string(), command.to_string())]),
+                    source: PackageSource::DirectRepo {
+                        url: "https://github.com/o/bundle".to_string(),
+                    },
+                    description: "Bundle".to_string(),
+                    command_names: vec![],
+                    command_name: None,
+                    asset_name: "bundle.tar.gz".to_string(),
+                    parent_package: parent.map(str::to_string),
+                    download_url: None,
+                    bin_dir: None,
+                    version_constraint: None,
+                    compiler: None,
+                    checksum: None,
+                    files: Vec::new(),
+                },
+            );
+        }
+
+        let sink = RecordingSink::default();
+        delete_package(&paths, &mut installed, "bundle", &sink).unwrap();
+        assert_eq!(
+            sink.events.into_inner(),
+            vec![
+                InstallEvent::Removed {
+                    package: "bundle".to_string()
+                },
+                InstallEvent::Removed {
+                    package: "bundle::helper".to_string()
+                },
+            ]
+        );
+
+        assert!(installed.packages.is_empty());
+        for (key, command) in [("bundle", "bundle"), ("bundle::helper", "bundle-helper")] {
+            let shim = paths.bin_shim_path(command);
+            assert!(
+                !shim.exists() && !shim.is_symlink(),
+                "{command} still linked"
+            );
+            assert!(!paths.app_dir(key).exists(), "{key} app dir remains");
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_delete_side_by_side_arch_keeps_primary() {
+        let temp = TempDir::new().unwrap();
+        let paths = WenPaths::with_root(temp.path().to_path_buf(), false);
+        fs::create_dir_all(paths.bin_dir()).unwrap();
+
+        let mut installed = InstalledManifest::new();
+        for (key, command, variant, platform) in [
+            ("ripgrep", "rg", None, "macos-aarch64"),
+            (
+                "ripgrep::x86_64",
+                "rg-x86_64",
+                Some("x86_64"),
+                "macos-x86_64",
+            ),
+        ] {
+            let app_dir = paths.app_dir(key);
+            fs::create_dir_all(&app_dir).unwrap();
+            let exe = app_dir.join("rg");
+            fs::write(&exe, "#!/bin/sh\n").unwrap();
+            std::os::unix::fs::symlink(&exe, paths.bin_shim_path(command)).unwrap();
+
+            installed.upsert_package(
+                key.to_string(),
+                InstalledPackage {
+                    repo_name: "ripgrep".to_string(),
+                    variant: variant.map(str::to_string),
+                    version: "14.1.0".to_string(),
+                    platform: platform.to_string(),
+                    installed_at: Utc::now(),
+                    install_path: app_dir.to_string_lossy().to_string(),
+                    executables: HashMap::from([("rg".to_string(), command.to_string())]),
+                    source: PackageSource::DirectRepo {
+                        url: "https://github.com/BurntSushi/ripgrep".to_string(),
+                    },
+                    description: "ripgrep".to_string(),
+                    command_names: vec![],
+                    command_name: None,
+                    asset_name: "ripgrep.tar.gz".to_string(),
+                    parent_package: None,
+                    download_url: None,
+                    bin_dir: None,
+                    version_constraint: None,
+                    compiler: None,
+                    checksum: None,
+                    files: Vec::new(),
+                },
+            );
+        }
+        assert_eq!(installed.find_by_repo("ripgrep").len(), 2);
+
+        let sink = RecordingSink::default();
+        delete_package(&paths, &mut installed, "ripgrep::x86_64", &sink).unwrap();
+
+        assert!(installed.get_package("ripgrep::x86_64").is_none());
+        assert!(!paths.app_dir("ripgrep::x86_64").exists());
+        assert!(!paths.bin_shim_path("rg-x86_64").is_symlink());
+
+        assert!(installed.get_package("ripgrep").is_some());
+        assert!(paths.app_dir("ripgrep").exists());
+        assert!(paths.bin_shim_path("rg").is_symlink());
+    }
+
     #[test]
     fn test_specific_variant_not_duplicated_in_final_to_delete() {
         // Simulate the variant resolution logic
@@ -0,0 +1,276 @@
+//! Export command implementation
+//!
+//! Writes the installed packages as a list `wenget import` can reinstall on
+//! another machine. The JSON form keeps each package's source, installed
+//! version, version pin, compiler and side-by-side platform; the plain-text
+//! form is one `wenget add` input per line.
+
+use crate::commands::add::side_by_side_arch;
+use crate::core::manifest::{InstalledManifest, InstalledPackage, PackageSource};
+use crate::core::{Config, Platform};
+use crate::installer::input_detector::{detect_input_type, InputType};
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Packages written by `wenget export`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PackageList {
+    /// Exported packages, sorted by name
+    pub packages: Vec<ExportEntry>,
+}
+
+/// One exported package
+///
+/// Only `name` and `source` are required, so install manifests written by
+/// `wenget add --emit-manifest` can be imported as well.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportEntry {
+    /// Installed package key (e.g. "ripgrep", "bun::baseline")
+    pub name: String,
+    /// Where the package came from
+    pub source: PackageSource,
+    /// Installed version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Semver range updates are restricted to (`wenget add name@range`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_constraint: Option<String>,
+    /// Compiler/libc variant pinned with `--compiler`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compiler: Option<String>,
+    /// Platform of a build installed side by side with the native one (`add -p`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub platform: Option<String>,
+}
+
+impl ExportEntry {
+    fn new(name: &str, pkg: &InstalledPackage, platform: &Platform) -> Self {
+        Self {
+            name: name.to_string(),
+            source: pkg.source.clone(),
+            version: Some(pkg.version.clone()),
+            version_constraint: pkg.version_constraint.clone(),
+            compiler: pkg.compiler.clone(),
+            platform: side_by_side_arch(&pkg.platform, platform.arch).map(|_| pkg.platform.clone()),
+        }
+    }
+
+    /// The `wenget add` input reinstalling this package
+    ///
+    /// Bucket packages are qualified with their bucket when it is in `buckets`.
+    /// Returns None for packages installed from local files that don't exist here.
+    pub fn add_input(&self, buckets: &[String]) -> Option<String> {
+        let pinned = |input: String| match &self.version_constraint {
+            Some(range) => format!("{}@{}", input, range),
+            None => input,
+        };
+        match &self.source {
+            PackageSource::Bucket { name } if buckets.contains(name) => {
+                Some(pinned(format!("{}/{}", name, self.name)))
+            }
+            PackageSource::Bucket { .. } => Some(pinned(self.name.clone())),
+            // Bucket scripts are found by name
+            PackageSource::Script { origin, .. } if origin.starts_with("bucket:") => {
+                Some(self.name.clone())
+            }
+            PackageSource::Script { origin, .. } => portable(origin).map(str::to_string),
+            PackageSource::DirectRepo { url } => {
+                let url = portable(url)?;
+                // GitHub repo URLs take a pin, direct download URLs don't
+                Some(if detect_input_type(url) == InputType::PackageName {
+                    pinned(url.to_string())
+                } else {
+                    url.to_string()
+                })
+            }
+        }
+    }
+}
+
+/// A URL, or a local path that exists on this machine
+fn portable(source: &str) -> Option<&str> {
+    let remote = source.starts_with("http://") || source.starts_with("https://");
+    (remote || Path::new(source).exists()).then_some(source)
+}
+
+/// Export entries for all installed packages, sorted by name
+pub fn entries(installed: &InstalledManifest, platform: &Platform) -> Vec<ExportEntry> {
+    let mut entries: Vec<ExportEntry> = installed
+        .packages
+        .iter()
+        .map(|(name, pkg)| ExportEntry::new(name, pkg, platform))
+        .collect();
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    entries
+}
+
+/// Render entries as a plain-text list of `wenget add` inputs
+fn render_text(entries: &[ExportEntry], buckets: &[String]) -> String {
+    let mut text = String::from("# Installed packages, reinstall with: wenget import <file>\n");
+    for entry in entries {
+        match entry.add_input(buckets) {
+            Some(input) => text.push_str(&format!("{}\n", input)),
+            None => text.push_str(&format!("# {} (local file, not exported)\n", entry.name)),
+        }
+    }
+    text
+}
+
+/// Export the installed packages to `output`, or print them
+pub fn run(output: Option<PathBuf>, text: bool) -> Result<()> {
+    let config = Config::new()?;
+    let installed = config.load_installed()?;
+    let entries = entries(&installed, &Platform::current());
+
+    let content = if text {
+        let buckets: Vec<String> = config
+            .load_buckets()?
+            .buckets
+            .into_iter()
+            .map(|b| b.name)
+            .collect();
+        render_text(&entries, &buckets)
+    } else {
+        let list = PackageList { packages: entries };
+        serde_json::to_string_pretty(&list).context("Failed to serialize package list")? + "\n"
+    };
+
+    match output {
+        Some(path) => {
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            fs::write(&path, content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            println!(
+                "{} Exported {} package(s) to {}",
+                "✓".green(),
+                installed.packages.len(),
+                path.display()
+            );
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::manifest::ScriptType;
+    use chrono::Utc;
+    use std::collections::HashMap;
+
+    fn entry(name: &str, source: PackageSource) -> ExportEntry {
+        ExportEntry {
+            name: name.to_string(),
+            source,
+            version: Some("1.0.0".to_string()),
+            version_constraint: None,
+            compiler: None,
+            platform: None,
+        }
+    }
+
+    #[test]
+    fn test_add_input() {
+        let buckets = vec!["main".to_string()];
+        let bucket = |name: &str| PackageSource::Bucket {
+            name: name.to_string(),
+        };
+
+        let mut rg = entry("ripgrep", bucket("main"));
+        assert_eq!(rg.add_input(&buckets).as_deref(), Some("main/ripgrep"));
+        rg.version_constraint = Some("^14".to_string());
+        assert_eq!(rg.add_input(&buckets).as_deref(), Some("main/ripgrep@^14"));
+        // Unknown buckets fall back to the bare name
+        assert_eq!(rg.add_input(&[]).as_deref(), Some("ripgrep@^14"));
+
+        let repo = entry(
+            "tool",
+            PackageSource::DirectRepo {
+                url: "https://github.com/o/tool".to_string(),
+            },
+        );
+        assert_eq!(
+            repo.add_input(&buckets).as_deref(),
+            Some("https://github.com/o/tool")
+        );
+
+        let script = entry(
+            "hello",
+            PackageSource::Script {
+                origin: "https://example.com/hello.sh".to_string(),
+                script_type: ScriptType::Bash,
+            },
+        );
+        assert_eq!(
+            script.add_input(&buckets).as_deref(),
+            Some("https://example.com/hello.sh")
+        );
+
+        let bucket_script = entry(
+            "hello",
+            PackageSource::Script {
+                origin: "bucket:main".to_string(),
+                script_type: ScriptType::Bash,
+            },
+        );
+        assert_eq!(bucket_script.add_input(&buckets).as_deref(), Some("hello"));
+
+        let local = entry(
+            "tool",
+            PackageSource::DirectRepo {
+                url: "/nonexistent/tool.tar.gz".to_string(),
+            },
+        );
+        assert_eq!(local.add_input(&buckets), None);
+        assert!(render_text(&[local], &buckets).contains("# tool (local file, not exported)"));
+    }
+
+    #[test]
+    fn test_entries_keep_pins() {
+        let mut installed = InstalledManifest::new();
+        installed.upsert_package(
+            "ripgrep".to_string(),
+            InstalledPackage {
+                repo_name: "ripgrep".to_string(),
+                variant: None,
+                version: "14.1.0".to_string(),
+                platform: "linux-x86_64-musl".to_string(),
+                installed_at: Utc::now(),
+                install_path: "/apps/ripgrep".to_string(),
+                executables: HashMap::new(),
+                source: PackageSource::Bucket {
+                    name: "main".to_string(),
+                },
+                description: String::new(),
+                command_names: vec![],
+                command_name: None,
+                asset_name: String::new(),
+                parent_package: None,
+                download_url: None,
+                bin_dir: None,
+                version_constraint: Some("^14".to_string()),
+                compiler: Some("musl".to_string()),
+                checksum: None,
+                files: Vec::new(),
+            },
+        );
+
+        let entries = entries(&installed, &Platform::current());
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].version.as_deref(), Some("14.1.0"));
+        assert_eq!(entries[0].version_constraint.as_deref(), Some("^14"));
+        assert_eq!(entries[0].compiler.as_deref(), Some("musl"));
+
+        // The JSON round-trips
+        let list = PackageList { packages: entries };
+        let json = serde_json::to_string(&list).unwrap();
+        assert_eq!(serde_json::from_str::<PackageList>(&json).unwrap(), list);
+    }
+}
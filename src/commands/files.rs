@@ -0,0 +1,258 @@
+//! Files command implementation
+//!
+//! Lists the files an installed package put on disk: everything under its
+//! install path plus the launchers in the bin directory.
+
+use crate::core::manifest::InstalledPackage;
+use crate::core::{Config, WenPaths};
+use crate::installer::extractor::collect_files_recursively;
+use anyhow::Result;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+/// A file belonging to an installed package
+#[derive(Debug, PartialEq)]
+struct FileEntry {
+    /// Absolute path on disk
+    path: PathBuf,
+    /// Command name, when this file is a linked executable
+    command: Option<String>,
+    /// Whether the file is still present
+    exists: bool,
+}
+
+/// List the files installed by packages
+pub fn run(names: Vec<String>) -> Result<()> {
+    let config = Config::new()?;
+    let installed = config.get_or_create_installed()?;
+
+    if names.is_empty() {
+        println!("{}", "No package names provided".yellow());
+        println!("Usage: wenget files <name>...");
+        return Ok(());
+    }
+
+    let mut first = true;
+    for name in &names {
+        // Exact key first, then every variant of the repo
+        let mut matches: Vec<(&String, &InstalledPackage)> =
+            match installed.packages.get_key_value(name) {
+                Some(entry) => vec![entry],
+                None => installed.find_by_repo(name),
+            };
+        matches.sort_by(|a, b| a.0.cmp(b.0));
+
+        if matches.is_empty() {
+            println!("{} {} is not installed", "✗".red(), name);
+            continue;
+        }
+
+        for (key, pkg) in matches {
+            if !first {
+                println!();
+            }
+            first = false;
+            print_files(config.paths(), key, pkg)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Print the app directory files and launchers of one package
+fn print_files(paths: &WenPaths, key: &str, pkg: &InstalledPackage) -> Result<()> {
+    let install_path = Path::new(&pkg.install_path);
+    println!("{} v{}", key.bold(), pkg.version);
+    println!("  {}", install_path.display().to_string().dimmed());
+
+    let entries = app_files(pkg)?;
+    if pkg.files.is_empty() {
+        println!(
+            "  {} No file list recorded, showing the current contents of the app directory",
+            "ℹ".cyan()
+        );
+    }
+    for entry in &entries {
+        print_entry(entry);
+    }
+
+    let launchers = launchers(paths, pkg);
+    if !launchers.is_empty() {
+        println!("  Launchers:");
+        for entry in &launchers {
+            print_entry(entry);
+        }
+    }
+
+    let missing = entries
+        .iter()
+        .chain(&launchers)
+        .filter(|e| !e.exists)
+        .count();
+    if missing > 0 {
+        println!("  {} {} file(s) missing", "⚠".yellow(), missing);
+    }
+
+    Ok(())
+}
+
+fn print_entry(entry: &FileEntry) {
+    let marker = if entry.exists {
+        "✓".green()
+    } else {
+        "✗".red()
+    };
+    let path = entry.path.display().to_string();
+    match (&entry.command, entry.exists) {
+        (Some(command), true) => println!("  {} {} → {}", marker, path, command.cyan()),
+        (Some(command), false) => {
+            println!(
+                "  {} {} → {} {}",
+                marker,
+                path,
+                command.cyan(),
+                "(missing)".red()
+            )
+        }
+        (None, true) => println!("  {} {}", marker, path),
+        (None, false) => println!("  {} {} {}", marker, path, "(missing)".red()),
+    }
+}
+
+/// Files under the install path, from the recorded list or the directory itself
+fn app_files(pkg: &InstalledPackage) -> Result<Vec<FileEntry>> {
+    let install_path = Path::new(&pkg.install_path);
+
+    let mut relative = pkg.files.clone();
+    if relative.is_empty() && install_path.is_dir() {
+        collect_files_recursively(install_path, install_path, &mut relative)?;
+    }
+    relative.sort();
+
+    Ok(relative
+        .into_iter()
+        .map(|file| {
+            let path = install_path.join(&file);
+            FileEntry {
+                command: pkg.executables.get(&file).cloned(),
+                exists: path.exists(),
+                path,
+            }
+        })
+        .collect())
+}
+
+/// Launchers the package has in its bin directory
+fn launchers(paths: &WenPaths, pkg: &InstalledPackage) -> Vec<FileEntry> {
+    let paths = paths.for_package(pkg.bin_dir.as_deref());
+
+    let mut commands: Vec<&String> = pkg.executables.values().collect();
+    commands.sort();
+    commands.dedup();
+
+    commands
+        .into_iter()
+        .map(|command| {
+            let path = paths.bin_shim_path(command);
+            FileEntry {
+                // Dangling symlinks count as present: del still removes them
+                exists: path.exists() || path.is_symlink(),
+                path,
+                command: Some(command.clone()),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::manifest::PackageSource;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn package(install_path: &Path, files: Vec<String>) -> InstalledPackage {
+        InstalledPackage {
+            repo_name: "rg".to_string(),
+            variant: None,
+            version: "14.0.0".to_string(),
+            platform: "linux-x86_64".to_string(),
+            installed_at: Utc::now(),
+            install_path: install_path.to_string_lossy().to_string(),
+            executables: HashMap::from([("bin/rg".to_string(), "rg".to_string())]),
+            source: PackageSource::DirectRepo {
+                url: "https://github.com/BurntSushi/ripgrep".to_string(),
+            },
+            description: "ripgrep".to_string(),
+            command_names: vec![],
+            command_name: None,
+            asset_name: "rg.tar.gz".to_string(),
+            parent_package: None,
+            download_url: None,
+            bin_dir: None,
+            version_constraint: None,
+            compiler: None,
+            checksum: None,
+            files,
+        }
+    }
+
+    #[test]
+    fn test_app_files_marks_executables_and_missing() {
+        let temp = TempDir::new().unwrap();
+        let app_dir = temp.path().join("rg");
+        fs::create_dir_all(app_dir.join("bin")).unwrap();
+        fs::write(app_dir.join("bin/rg"), "").unwrap();
+
+        let pkg = package(
+            &app_dir,
+            vec!["bin/rg".to_string(), "README.md".to_string()],
+        );
+        let entries = app_files(&pkg).unwrap();
+
+        assert_eq!(
+            entries,
+            vec![
+                FileEntry {
+                    path: app_dir.join("README.md"),
+                    command: None,
+                    exists: false,
+                },
+                FileEntry {
+                    path: app_dir.join("bin/rg"),
+                    command: Some("rg".to_string()),
+                    exists: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_app_files_falls_back_to_directory() {
+        let temp = TempDir::new().unwrap();
+        let app_dir = temp.path().join("rg");
+        fs::create_dir_all(&app_dir).unwrap();
+        fs::write(app_dir.join("LICENSE"), "").unwrap();
+
+        let entries = app_files(&package(&app_dir, Vec::new())).unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, app_dir.join("LICENSE"));
+        assert!(entries[0].exists);
+    }
+
+    #[test]
+    fn test_launchers() {
+        let temp = TempDir::new().unwrap();
+        let paths = WenPaths::with_root(temp.path().to_path_buf(), false);
+
+        let entries = launchers(&paths, &package(&paths.app_dir("rg"), Vec::new()));
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, paths.bin_shim_path("rg"));
+        assert_eq!(entries[0].command.as_deref(), Some("rg"));
+        assert!(!entries[0].exists);
+    }
+}
@@ -0,0 +1,210 @@
+//! Import command implementation
+//!
+//! Reinstalls the packages of a list written by `wenget export`, read from a
+//! file or URL, through the regular add pipeline. JSON lists (including install
+//! manifests from `wenget add --emit-manifest`) keep compiler and side-by-side
+//! platform choices; plain-text lists are passed to `add` line by line. With
+//! `--dry-run` the entries are only resolved and reported as new, upgraded or
+//! already installed; nothing is downloaded or recorded.
+
+use crate::commands::add::{self, AddOptions};
+use crate::commands::export::PackageList;
+use crate::commands::Outcome;
+use crate::core::events::ProgressSink;
+use crate::core::Config;
+use crate::utils::network::NetworkOverrides;
+use crate::utils::HttpClient;
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fs;
+
+/// Packages installed with the same platform and compiler choice
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Batch {
+    platform: Option<String>,
+    compiler: Option<String>,
+    inputs: Vec<String>,
+}
+
+/// Install the packages listed in a file or URL
+pub fn run(
+    source: String,
+    yes: bool,
+    dry_run: bool,
+    network: NetworkOverrides,
+    sink: &dyn ProgressSink,
+) -> Result<Outcome> {
+    let config = Config::with_network(network)?;
+    let content = if source.starts_with("http://") || source.starts_with("https://") {
+        HttpClient::with_settings(None, config.network())?
+            .get_text(&source)
+            .with_context(|| format!("Failed to fetch package list from {}", source))?
+    } else {
+        fs::read_to_string(&source).with_context(|| format!("Failed to read {}", source))?
+    };
+
+    let buckets: Vec<String> = config
+        .get_or_create_buckets()?
+        .buckets
+        .into_iter()
+        .map(|b| b.name)
+        .collect();
+    let batches =
+        parse(&content, &buckets).with_context(|| format!("Invalid package list: {}", source))?;
+
+    let total: usize = batches.iter().map(|b| b.inputs.len()).sum();
+    if total == 0 {
+        println!("{}", "No packages to import".yellow());
+        return Ok(Outcome::default());
+    }
+    println!(
+        "{} {} package(s) from {}\n",
+        if dry_run { "Checking" } else { "Importing" }.cyan(),
+        total,
+        source
+    );
+
+    let mut outcome = Outcome::default();
+    for mut batch in batches {
+        let inputs = std::mem::take(&mut batch.inputs);
+        outcome = outcome.merge(add::run(
+            inputs,
+            add_options(batch, yes, dry_run),
+            network,
+            sink,
+        )?);
+    }
+    Ok(outcome)
+}
+
+/// Options for installing one batch through `add`
+///
+/// A dry run never prompts: nothing would be reinstalled anyway, so entries
+/// already installed at the same version are reported as skipped.
+fn add_options(batch: Batch, yes: bool, dry_run: bool) -> AddOptions {
+    AddOptions {
+        yes: yes || dry_run,
+        platform: batch.platform,
+        compiler: batch.compiler,
+        dry_run,
+        ..AddOptions::default()
+    }
+}
+
+/// Split a package list into `add` batches
+///
+/// Lists starting with `{` are JSON; anything else is one `add` input per line,
+/// with blank lines and `#` comments ignored.
+fn parse(content: &str, buckets: &[String]) -> Result<Vec<Batch>> {
+    if !content.trim_start().starts_with('{') {
+        let inputs: Vec<String> = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect();
+        return Ok(vec![Batch {
+            platform: None,
+            compiler: None,
+            inputs,
+        }]);
+    }
+
+    let list: PackageList = serde_json::from_str(content).context("Failed to parse JSON")?;
+    let mut batches: Vec<Batch> = Vec::new();
+    for entry in list.packages {
+        let Some(input) = entry.add_input(buckets) else {
+            println!(
+                "{} Skipping {}: installed from a local file that doesn't exist here",
+                "⚠".yellow(),
+                entry.name
+            );
+            continue;
+        };
+        match batches
+            .iter_mut()
+            .find(|b| b.platform == entry.platform && b.compiler == entry.compiler)
+        {
+            Some(batch) => batch.inputs.push(input),
+            None => batches.push(Batch {
+                platform: entry.platform,
+                compiler: entry.compiler,
+                inputs: vec![input],
+            }),
+        }
+    }
+    Ok(batches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_text() {
+        let batches = parse(
+            "# exported\nmain/ripgrep@^14\n\n  https://github.com/o/tool  \n",
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            batches,
+            [Batch {
+                platform: None,
+                compiler: None,
+                inputs: vec![
+                    "main/ripgrep@^14".to_string(),
+                    "https://github.com/o/tool".to_string()
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_add_options_dry_run() {
+        let batch = Batch {
+            platform: Some("macos-x86_64".to_string()),
+            compiler: Some("musl".to_string()),
+            inputs: Vec::new(),
+        };
+        let options = add_options(batch.clone(), false, true);
+        assert!(options.dry_run);
+        assert!(options.yes);
+        assert_eq!(options.platform.as_deref(), Some("macos-x86_64"));
+        assert_eq!(options.compiler.as_deref(), Some("musl"));
+
+        let options = add_options(batch, false, false);
+        assert!(!options.dry_run);
+        assert!(!options.yes);
+    }
+
+    #[test]
+    fn test_parse_json_batches() {
+        let json = r#"{"packages": [
+            {"name": "ripgrep", "source": {"type": "bucket", "name": "main"}, "version": "14.1.0", "version_constraint": "^14"},
+            {"name": "fd", "source": {"type": "bucket", "name": "extra"}, "compiler": "musl"},
+            {"name": "ripgrep::x86_64", "source": {"type": "bucket", "name": "main"}, "platform": "macos-x86_64"},
+            {"name": "bat", "source": {"type": "bucket", "name": "main"}},
+            {"name": "local", "source": {"type": "directrepo", "url": "/nonexistent/local.zip"}}
+        ]}"#;
+        let batches = parse(json, &["main".to_string()]).unwrap();
+        let summary: Vec<(Option<&str>, Option<&str>, Vec<&str>)> = batches
+            .iter()
+            .map(|b| {
+                (
+                    b.platform.as_deref(),
+                    b.compiler.as_deref(),
+                    b.inputs.iter().map(String::as_str).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (None, None, vec!["main/ripgrep@^14", "main/bat"]),
+                (None, Some("musl"), vec!["fd"]),
+                (Some("macos-x86_64"), None, vec!["main/ripgrep::x86_64"]),
+            ]
+        );
+    }
+}
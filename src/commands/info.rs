@@ -3,15 +3,28 @@
 //! Shows detailed package information from cache (with glob support), GitHub URL,
 //! or installed packages (for manually installed or non-bucket sources)
 
-use crate::core::manifest::InstalledPackage;
-use crate::core::Config;
-use crate::package_resolver::{PackageInput, PackageResolver, ResolvedPackage};
+use crate::core::manifest::{InstalledPackage, PlatformBinary};
+use crate::core::platform::{BinarySelector, PlatformMatch, ScoringWeights};
+use crate::core::{Compiler, Config, Package, Platform};
+use crate::package_resolver::{from_bucket, PackageInput, PackageResolver, ResolvedPackage};
+use crate::providers::github::GitHubAsset;
+use crate::providers::GitHubProvider;
+use crate::utils::network::NetworkOverrides;
 use anyhow::Result;
 use colored::Colorize;
 
 /// Show package and script information
-pub fn run(names: Vec<String>) -> Result<()> {
-    let config = Config::new()?;
+///
+/// With `all_platforms`, packages also list the asset wenget would pick on each
+/// platform of [`Platform::matrix`]. With `assets`, they list every asset of the
+/// latest GitHub release with the score it gets on this platform.
+pub fn run(
+    names: Vec<String>,
+    all_platforms: bool,
+    assets: bool,
+    network: NetworkOverrides,
+) -> Result<()> {
+    let config = Config::with_network(network)?;
 
     if names.is_empty() {
         println!("{}", "No package names or URLs provided".yellow());
@@ -48,12 +61,21 @@ pub fn run(names: Vec<String>) -> Result<()> {
                         println!();
                     }
                     display_package_info(&resolved, &installed, &resolver)?;
+                    if all_platforms {
+                        display_platform_selection(&resolved.package);
+                    }
+                    if assets {
+                        display_asset_scores(&config, &resolved.package);
+                    }
                     total_found += 1;
                 }
             }
-            Err(_) => {
+            Err(e) => {
                 // If not found as package, try as script
-                if let Some(cached_script) = cache.find_script(name) {
+                if let Some(cached_script) = cache
+                    .find_script(input.name())
+                    .filter(|cs| from_bucket(&cs.source, input.bucket()))
+                {
                     if total_found > 0 {
                         println!();
                         println!("{}", "─".repeat(80));
@@ -71,7 +93,7 @@ pub fn run(names: Vec<String>) -> Result<()> {
                     display_installed_only_info(name, inst_pkg)?;
                     total_found += 1;
                 } else {
-                    eprintln!("{} {}: Not found", "Error".red().bold(), name);
+                    eprintln!("{} {}: {:#}", "Error".red().bold(), name, e);
                 }
             }
         }
@@ -233,6 +255,8 @@ fn display_package_info(
                 b.size as f64 / 1_048_576.0,
                 install_status
             );
+            println!("      {} {}", "File:".dimmed(), b.asset_name);
+            println!("      {} {}", "SHA256:".dimmed(), checksum_label(b));
         } else {
             println!(
                 "    {} {} [{} packages]",
@@ -269,6 +293,7 @@ fn display_package_info(
                     variant_label,
                     install_status
                 );
+                println!("        {} {}", "SHA256:".dimmed(), checksum_label(b));
             }
         }
     }
@@ -276,6 +301,159 @@ fn display_package_info(
     Ok(())
 }
 
+/// The match and binary `add` would pick for each platform of the matrix
+///
+/// Uses the same matching as installation (exact builds first, then compatible
+/// fallbacks) and the first binary of the matched platform; `None` when
+/// nothing is installable there.
+fn platform_selection(pkg: &Package) -> Vec<(Platform, Option<(PlatformMatch, &PlatformBinary)>)> {
+    Platform::matrix()
+        .into_iter()
+        .map(|platform| {
+            let selected = platform
+                .find_best_match(&pkg.platforms)
+                .into_iter()
+                .find_map(|m| {
+                    let binary = pkg.platforms.get(&m.platform_id)?.first()?;
+                    Some((m, binary))
+                });
+            (platform, selected)
+        })
+        .collect()
+}
+
+/// Print which asset would be installed on each platform (`info --all-platforms`)
+fn display_platform_selection(pkg: &Package) {
+    println!();
+    println!("  {}", "Selection by platform:".bold());
+
+    for (platform, selected) in platform_selection(pkg) {
+        let label = format!("{:<18}", platform.to_string());
+        match selected {
+            Some((platform_match, binary)) => {
+                let others = pkg.platforms[&platform_match.platform_id].len() - 1;
+                let mut line = binary.asset_name.clone();
+                if others > 0 {
+                    line.push_str(&format!(" (+{} variant(s))", others));
+                }
+                println!("    {} {} {}", "•".cyan(), label, line);
+                if let Some(fallback) = &platform_match.fallback_type {
+                    println!(
+                        "      {} {} ({})",
+                        "Fallback:".dimmed(),
+                        platform_match.platform_id,
+                        fallback.description()
+                    );
+                }
+            }
+            None => println!("    {} {} {}", "•".dimmed(), label, "none".yellow()),
+        }
+    }
+}
+
+/// How an asset of the latest release fares on this platform
+#[derive(Debug, PartialEq)]
+struct AssetScore {
+    name: String,
+    size: u64,
+    /// The selector's score, or why the asset can't be picked
+    score: Result<usize, &'static str>,
+}
+
+/// Score release assets like the installer, best first
+///
+/// Assets that can be picked come first, in the installer's order (score, then
+/// smaller size, then name), followed by the rest by name.
+fn score_assets(
+    assets: &[GitHubAsset],
+    platform: Platform,
+    weights: ScoringWeights,
+) -> Vec<AssetScore> {
+    let mut scores: Vec<AssetScore> = assets
+        .iter()
+        .map(|asset| AssetScore {
+            name: asset.name.clone(),
+            size: asset.size,
+            score: if BinarySelector::should_exclude(&asset.name.to_lowercase()) {
+                Err("excluded")
+            } else {
+                BinarySelector::score_asset(&asset.name, platform, weights).ok_or("no match")
+            },
+        })
+        .collect();
+    scores.sort_by(|a, b| match (&a.score, &b.score) {
+        (Ok(x), Ok(y)) => y
+            .cmp(x)
+            .then(a.size.cmp(&b.size))
+            .then_with(|| a.name.cmp(&b.name)),
+        (Ok(_), Err(_)) => std::cmp::Ordering::Less,
+        (Err(_), Ok(_)) => std::cmp::Ordering::Greater,
+        (Err(_), Err(_)) => a.name.cmp(&b.name),
+    });
+    scores
+}
+
+/// List the assets of the package's latest release with their scores
+///
+/// Failures are printed in place, so the rest of the info stays visible.
+fn display_asset_scores(config: &Config, pkg: &Package) {
+    println!();
+    let platform = Platform::current().prefer_compiler(
+        config
+            .preferences()
+            .preferred_compiler
+            .as_deref()
+            .and_then(Compiler::from_name),
+    );
+    let release = GitHubProvider::parse_github_url(&pkg.repo)
+        .ok_or_else(|| anyhow::anyhow!("Not a GitHub repository: {}", pkg.repo))
+        .and_then(|(owner, repo)| {
+            GitHubProvider::from_config(config)?.fetch_latest_release(&owner, &repo)
+        });
+    let release = match release {
+        Ok(release) => release,
+        Err(e) => {
+            println!("  {} {:#}", "Release assets unavailable:".yellow(), e);
+            return;
+        }
+    };
+
+    println!(
+        "  {} {} on {}",
+        "Release assets:".bold(),
+        release.tag_name,
+        platform
+    );
+    let weights = config.preferences().scoring.unwrap_or_default();
+    let scores = score_assets(&release.assets, platform, weights);
+    if scores.is_empty() {
+        println!("    {}", "none".yellow());
+    }
+    for (i, asset) in scores.iter().enumerate() {
+        let score = match asset.score {
+            Ok(score) if i == 0 => format!("{:>9}", score).green().bold().to_string(),
+            Ok(score) => format!("{:>9}", score),
+            Err(reason) => format!("{:>9}", reason).dimmed().to_string(),
+        };
+        let selected = if i == 0 && asset.score.is_ok() {
+            format!(" {}", "<- selected".green())
+        } else {
+            String::new()
+        };
+        println!("    {} {}{}", score, asset.name, selected);
+    }
+}
+
+/// Checksum of a binary as shown by `info`, without the "sha256:" prefix
+///
+/// Printed on its own so it can be copied and compared with upstream release notes.
+fn checksum_label(binary: &crate::core::manifest::PlatformBinary) -> String {
+    match binary.checksum.as_deref() {
+        Some(sum) => sum.strip_prefix("sha256:").unwrap_or(sum).to_string(),
+        None => "not published".dimmed().to_string(),
+    }
+}
+
 /// Display detailed information for a single script
 fn display_script_info(
     cached_script: &crate::cache::CachedScript,
@@ -469,3 +647,129 @@ fn display_installed_only_info(name: &str, inst_pkg: &InstalledPackage) -> Resul
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::platform::{Arch, Os};
+
+    #[test]
+    fn test_score_assets() {
+        let asset = |name: &str, size| GitHubAsset {
+            name: name.to_string(),
+            browser_download_url: format!("https://example.com/{}", name),
+            url: String::new(),
+            size,
+        };
+        let assets = [
+            asset("rg-14.1.0-x86_64-unknown-linux-gnu.tar.gz", 300),
+            asset("rg-14.1.0-x86_64-pc-windows-msvc.zip", 300),
+            asset("rg-14.1.0-x86_64-unknown-linux-musl.tar.gz", 200),
+            asset("rg-14.1.0-x86_64-unknown-linux-musl.tar.gz.sha256", 1),
+            asset("rg-14.1.0-aarch64-unknown-linux-gnu.tar.gz", 300),
+        ];
+        let scores = score_assets(
+            &assets,
+            Platform::new(Os::Linux, Arch::X86_64),
+            ScoringWeights::default(),
+        );
+
+        let order: Vec<(&str, Result<usize, &str>)> =
+            scores.iter().map(|s| (s.name.as_str(), s.score)).collect();
+        let musl = order[0].1.unwrap();
+        let gnu = order[1].1.unwrap();
+        assert!(musl > gnu);
+        assert_eq!(
+            order,
+            [
+                ("rg-14.1.0-x86_64-unknown-linux-musl.tar.gz", Ok(musl)),
+                ("rg-14.1.0-x86_64-unknown-linux-gnu.tar.gz", Ok(gnu)),
+                (
+                    "rg-14.1.0-aarch64-unknown-linux-gnu.tar.gz",
+                    Err("no match")
+                ),
+                ("rg-14.1.0-x86_64-pc-windows-msvc.zip", Err("no match")),
+                (
+                    "rg-14.1.0-x86_64-unknown-linux-musl.tar.gz.sha256",
+                    Err("excluded")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_checksum_label() {
+        let mut binary = PlatformBinary {
+            url: "https://example.com/tool.tar.gz".to_string(),
+            size: 1,
+            checksum: Some("sha256:ABCDEF".to_string()),
+            asset_name: "tool.tar.gz".to_string(),
+        };
+        assert_eq!(checksum_label(&binary), "ABCDEF");
+
+        binary.checksum = Some("0123abcd".to_string());
+        assert_eq!(checksum_label(&binary), "0123abcd");
+
+        binary.checksum = None;
+        assert!(checksum_label(&binary).contains("not published"));
+    }
+
+    #[test]
+    fn test_platform_selection() {
+        let binary = |name: &str| PlatformBinary {
+            url: format!("https://example.com/{}", name),
+            size: 1,
+            checksum: None,
+            asset_name: name.to_string(),
+        };
+        let pkg = Package {
+            name: "tool".to_string(),
+            aliases: Vec::new(),
+            description: String::new(),
+            repo: "https://github.com/o/tool".to_string(),
+            homepage: None,
+            license: None,
+            version: None,
+            platforms: [
+                (
+                    "linux-x86_64-musl".to_string(),
+                    vec![
+                        binary("tool-x86_64-linux-musl.tar.gz"),
+                        binary("tool-baseline-x86_64-linux-musl.tar.gz"),
+                    ],
+                ),
+                (
+                    "windows-x86_64".to_string(),
+                    vec![binary("tool-x86_64-windows.zip")],
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            pre_install: Vec::new(),
+            post_install: Vec::new(),
+        };
+
+        let selection = platform_selection(&pkg);
+        assert_eq!(selection.len(), Platform::matrix().len());
+
+        let pick = |os, arch| {
+            selection
+                .iter()
+                .find(|(p, _)| *p == Platform::new(os, arch))
+                .unwrap()
+                .1
+                .as_ref()
+                .map(|(_, b)| b.asset_name.as_str())
+        };
+        assert_eq!(
+            pick(Os::Linux, Arch::X86_64),
+            Some("tool-x86_64-linux-musl.tar.gz")
+        );
+        assert_eq!(
+            pick(Os::Windows, Arch::X86_64),
+            Some("tool-x86_64-windows.zip")
+        );
+        assert_eq!(pick(Os::MacOS, Arch::Aarch64), None);
+        assert_eq!(pick(Os::FreeBSD, Arch::X86_64), None);
+    }
+}
@@ -3,6 +3,7 @@
 use crate::bucket::Bucket;
 use crate::core::is_elevated;
 use crate::core::Config;
+use crate::utils::network::NetworkOverrides;
 use anyhow::{Context, Result};
 use colored::Colorize;
 use std::env;
@@ -13,13 +14,18 @@ const WENGET_BUCKET_NAME: &str = "wenget";
 const WENGET_BUCKET_URL: &str =
     "https://raw.githubusercontent.com/superyngo/Wenget/refs/heads/main/bucket/manifest.json";
 
+/// Markers delimiting the PATH block wenget manages in shell rc files
+const PATH_BLOCK_START: &str = "# >>> wenget >>>";
+const PATH_BLOCK_END: &str = "# <<< wenget <<<";
+
+/// Marker written by older versions above their (unterminated) PATH export line
+const LEGACY_PATH_MARKER: &str = "# Wenget";
+
 #[cfg(windows)]
 use std::path::Path;
 
 #[cfg(not(windows))]
-use std::fs::{self, OpenOptions};
-#[cfg(not(windows))]
-use std::io::Write;
+use std::fs;
 
 /// Planned changes to show user before confirmation
 struct PlannedChanges {
@@ -72,7 +78,7 @@ impl PlannedChanges {
 }
 
 /// Collect planned changes for a fresh initialization
-fn collect_fresh_init_changes(config: &Config) -> PlannedChanges {
+fn collect_fresh_init_changes(config: &Config, no_path: bool) -> PlannedChanges {
     let mut changes = PlannedChanges {
         create_dirs: Vec::new(),
         create_files: Vec::new(),
@@ -125,7 +131,7 @@ fn collect_fresh_init_changes(config: &Config) -> PlannedChanges {
     }
 
     // PATH modification
-    if !is_in_path(paths.bin_dir()).unwrap_or(false) {
+    if !no_path && !is_in_path(paths.bin_dir()).unwrap_or(false) {
         #[cfg(windows)]
         {
             let bin_dir = if paths.is_system_install() {
@@ -148,7 +154,7 @@ fn collect_fresh_init_changes(config: &Config) -> PlannedChanges {
 }
 
 /// Collect planned changes for an already-initialized state
-fn collect_existing_init_changes(config: &Config) -> Result<PlannedChanges> {
+fn collect_existing_init_changes(config: &Config, no_path: bool) -> Result<PlannedChanges> {
     let mut changes = PlannedChanges {
         create_dirs: Vec::new(),
         create_files: Vec::new(),
@@ -176,7 +182,7 @@ fn collect_existing_init_changes(config: &Config) -> Result<PlannedChanges> {
     }
 
     // Check PATH
-    if !is_in_path(paths.bin_dir())? {
+    if !no_path && !is_in_path(paths.bin_dir())? {
         #[cfg(windows)]
         {
             let bin_dir = if paths.is_system_install() {
@@ -210,7 +216,10 @@ fn prompt_confirm_changes(changes: &PlannedChanges) -> Result<bool> {
 }
 
 /// Initialize Wenget (create directories and manifests)
-pub fn run(yes: bool) -> Result<()> {
+///
+/// With `no_path`, shell rc files and the Windows PATH are left untouched and
+/// the line to add manually is printed instead.
+pub fn run(yes: bool, no_path: bool, network: NetworkOverrides) -> Result<()> {
     // Show installation mode
     if is_elevated() {
         println!(
@@ -242,7 +251,7 @@ pub fn run(yes: bool) -> Result<()> {
     }
     println!();
 
-    let config = Config::new()?;
+    let config = Config::with_network(network)?;
 
     if config.is_initialized() {
         println!("{}", "✓ Wenget is already initialized".green());
@@ -250,7 +259,11 @@ pub fn run(yes: bool) -> Result<()> {
         println!();
 
         // Collect changes needed for existing installation
-        let changes = collect_existing_init_changes(&config)?;
+        let changes = collect_existing_init_changes(&config, no_path)?;
+
+        if no_path && !is_in_path(config.paths().bin_dir())? {
+            print_manual_path_instructions(&config);
+        }
 
         if changes.is_empty() {
             // Everything is already set up
@@ -291,7 +304,7 @@ pub fn run(yes: bool) -> Result<()> {
     }
 
     // Fresh initialization - collect all changes
-    let changes = collect_fresh_init_changes(&config);
+    let changes = collect_fresh_init_changes(&config, no_path);
 
     // Show what will be done and confirm
     if !yes {
@@ -323,7 +336,11 @@ pub fn run(yes: bool) -> Result<()> {
     setup_wenget_executable(&config)?;
 
     // Set up PATH
-    setup_path(&config)?;
+    if no_path {
+        print_manual_path_instructions(&config);
+    } else {
+        setup_path(&config)?;
+    }
 
     // Add wenget bucket (already confirmed above)
     if changes.add_bucket {
@@ -471,6 +488,35 @@ fn setup_path(config: &Config) -> Result<()> {
     Ok(())
 }
 
+/// Tell the user how to add the bin directory to PATH themselves (--no-path)
+fn print_manual_path_instructions(config: &Config) {
+    let paths = config.paths();
+
+    println!("{} Skipping PATH setup (--no-path)", "ℹ".cyan());
+
+    #[cfg(windows)]
+    {
+        let bin_dir = if paths.is_system_install() {
+            paths.internal_bin_dir()
+        } else {
+            paths.bin_dir()
+        };
+        println!("  Add the following directory to your PATH:");
+        println!("  {}", bin_dir.display().to_string().cyan());
+    }
+
+    #[cfg(not(windows))]
+    {
+        println!("  Add the following line to your shell configuration:");
+        println!(
+            "  {}",
+            format!("export PATH=\"{}:$PATH\"", paths.bin_dir().display()).cyan()
+        );
+    }
+
+    println!();
+}
+
 /// Set up PATH on Windows (modify user or system environment variable)
 #[cfg(windows)]
 fn setup_path_windows(bin_dir: &str, is_system_install: bool) -> Result<()> {
@@ -560,13 +606,11 @@ fn setup_path_unix(bin_dir: &str) -> Result<()> {
         return Ok(());
     }
 
-    let export_line = format!("\n# Wenget\nexport PATH=\"{}:$PATH\"\n", bin_dir);
-
     let mut updated_files = Vec::new();
     let mut skipped_files = Vec::new();
 
     for config_path in shell_configs {
-        match update_shell_config(&config_path, &export_line, bin_dir) {
+        match update_shell_config(&config_path, bin_dir) {
             Ok(true) => updated_files.push(config_path),
             Ok(false) => skipped_files.push(config_path),
             Err(e) => {
@@ -635,31 +679,129 @@ fn detect_shell_configs(home: &std::path::Path) -> Vec<PathBuf> {
 }
 
 /// Update a shell configuration file
+///
+/// Rewrites the managed PATH block in place, so re-running init (e.g. after the
+/// root moved) replaces the old entry instead of appending another one.
+/// Returns false when the file already had the right content.
 #[cfg(not(windows))]
-fn update_shell_config(config_path: &PathBuf, export_line: &str, bin_dir: &str) -> Result<bool> {
-    // Check if already configured
-    if config_path.exists() {
-        let content = fs::read_to_string(config_path)
-            .with_context(|| format!("Failed to read {}", config_path.display()))?;
+fn update_shell_config(config_path: &PathBuf, bin_dir: &str) -> Result<bool> {
+    let content = if config_path.exists() {
+        fs::read_to_string(config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?
+    } else {
+        String::new()
+    };
 
-        if content.contains(bin_dir) {
-            return Ok(false); // Already configured
-        }
+    let updated = upsert_path_block(&content, bin_dir);
+    if updated == content {
+        return Ok(false); // Already configured
     }
 
-    // Append to file
-    let mut file = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(config_path)
-        .with_context(|| format!("Failed to open {}", config_path.display()))?;
-
-    file.write_all(export_line.as_bytes())
+    fs::write(config_path, updated)
         .with_context(|| format!("Failed to write to {}", config_path.display()))?;
 
     Ok(true)
 }
 
+/// Build the managed PATH block for a bin directory
+#[cfg_attr(windows, allow(dead_code))]
+fn path_block(bin_dir: &str) -> String {
+    format!(
+        "{}\nexport PATH=\"{}:$PATH\"\n{}\n",
+        PATH_BLOCK_START, bin_dir, PATH_BLOCK_END
+    )
+}
+
+/// Replace any wenget-managed PATH entries in `content` with a single block
+///
+/// If the user already puts `bin_dir` on PATH themselves, no block is added.
+#[cfg_attr(windows, allow(dead_code))]
+fn upsert_path_block(content: &str, bin_dir: &str) -> String {
+    let mut updated = strip_path_block(content);
+
+    if puts_on_path(&updated, bin_dir) {
+        return updated;
+    }
+
+    if !updated.is_empty() {
+        if !updated.ends_with('\n') {
+            updated.push('\n');
+        }
+        updated.push('\n');
+    }
+    updated.push_str(&path_block(bin_dir));
+    updated
+}
+
+/// Whether a non-comment line of `content` that sets PATH has `bin_dir` as an entry
+#[cfg_attr(windows, allow(dead_code))]
+fn puts_on_path(content: &str, bin_dir: &str) -> bool {
+    let bin_dir = bin_dir.trim_end_matches('/');
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#') && line.contains("PATH"))
+        .flat_map(|line| {
+            line.split(|c: char| c == ':' || c == '=' || c == '"' || c == '\'' || c.is_whitespace())
+        })
+        .any(|entry| !entry.is_empty() && entry.trim_end_matches('/') == bin_dir)
+}
+
+/// Remove wenget-managed PATH entries from shell config content
+///
+/// Handles both the marked `# >>> wenget >>>` block and the old-style
+/// `# Wenget` comment followed by an `export PATH=` line. A start marker
+/// without its end marker is removed on its own, leaving the lines after it.
+#[cfg_attr(windows, allow(dead_code))]
+pub(crate) fn strip_path_block(content: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut kept: Vec<&str> = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let trimmed = lines[i].trim();
+        // Lines removed starting at `i`, if it starts a wenget entry
+        let removed = if trimmed == PATH_BLOCK_START {
+            // Without its end marker only the marker goes: it must not pair
+            // with the end marker of a block added later and swallow the lines
+            // in between
+            Some(
+                lines[i + 1..]
+                    .iter()
+                    .position(|inner| inner.trim() == PATH_BLOCK_END)
+                    .map_or(1, |end| end + 2),
+            )
+        } else if trimmed == LEGACY_PATH_MARKER {
+            let has_export = lines
+                .get(i + 1)
+                .is_some_and(|next| next.trim_start().starts_with("export PATH="));
+            Some(if has_export { 2 } else { 1 })
+        } else {
+            None
+        };
+
+        match removed {
+            Some(count) => {
+                // Drop the blank separator line that preceded the block, only that one
+                if kept.last().is_some_and(|l| l.trim().is_empty()) {
+                    kept.pop();
+                }
+                i += count;
+            }
+            None => {
+                kept.push(lines[i]);
+                i += 1;
+            }
+        }
+    }
+
+    if kept.is_empty() {
+        String::new()
+    } else {
+        format!("{}\n", kept.join("\n"))
+    }
+}
+
 /// Check if a directory is in PATH
 fn is_in_path(dir: PathBuf) -> Result<bool> {
     let path_var = env::var("PATH").unwrap_or_default();
@@ -734,3 +876,87 @@ fn add_wenget_bucket(config: &Config) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upsert_path_block_is_idempotent() {
+        let original = "alias ll='ls -l'\n";
+        let once = upsert_path_block(original, "/home/u/.local/bin");
+        assert!(once.starts_with(original));
+        assert!(once.contains(PATH_BLOCK_START));
+        assert!(once.contains("export PATH=\"/home/u/.local/bin:$PATH\""));
+
+        let twice = upsert_path_block(&once, "/home/u/.local/bin");
+        assert_eq!(once, twice);
+    }
+
+    #[test]
+    fn test_upsert_path_block_replaces_stale_and_legacy_entries() {
+        let content = "alias ll='ls -l'\n\n# Wenget\nexport PATH=\"/old/bin:$PATH\"\n\n# >>> wenget >>>\nexport PATH=\"/older/bin:$PATH\"\n# <<< wenget <<<\n";
+        let updated = upsert_path_block(content, "/new/bin");
+
+        assert!(!updated.contains("/old/bin"));
+        assert!(!updated.contains("/older/bin"));
+        assert_eq!(updated.matches(PATH_BLOCK_START).count(), 1);
+        assert!(updated.contains("export PATH=\"/new/bin:$PATH\""));
+        assert!(updated.starts_with("alias ll='ls -l'\n"));
+    }
+
+    #[test]
+    fn test_upsert_path_block_respects_user_entry() {
+        let content = "export PATH=\"/home/u/.local/bin:$PATH\"\n";
+        assert_eq!(upsert_path_block(content, "/home/u/.local/bin"), content);
+    }
+
+    #[test]
+    fn test_strip_path_block() {
+        let content =
+            "export EDITOR=vim\n\n# >>> wenget >>>\nexport PATH=\"/x:$PATH\"\n# <<< wenget <<<\n";
+        assert_eq!(strip_path_block(content), "export EDITOR=vim\n");
+        assert_eq!(strip_path_block(""), "");
+
+        // Only the one separator line goes; the user's own blank lines stay
+        let content = "a\n\n\n# >>> wenget >>>\nexport PATH=\"/x:$PATH\"\n# <<< wenget <<<\nb\n\n";
+        assert_eq!(strip_path_block(content), "a\n\nb\n\n");
+    }
+
+    #[test]
+    fn test_strip_path_block_unterminated() {
+        // The end marker was deleted: nothing after the start marker may be lost
+        let content =
+            "export EDITOR=vim\n\n# >>> wenget >>>\nexport PATH=\"/x:$PATH\"\nalias ll='ls -l'\n";
+        assert_eq!(
+            strip_path_block(content),
+            "export EDITOR=vim\nexport PATH=\"/x:$PATH\"\nalias ll='ls -l'\n"
+        );
+    }
+
+    #[test]
+    fn test_upsert_path_block_unterminated_twice() {
+        let content = "# >>> wenget >>>\nexport EDITOR=vim\nalias ll='ls -l'\n";
+        let once = upsert_path_block(content, "/new/bin");
+        let twice = upsert_path_block(&once, "/new/bin");
+        assert_eq!(once, twice);
+        assert!(twice.starts_with("export EDITOR=vim\nalias ll='ls -l'\n"));
+        assert_eq!(twice.matches(PATH_BLOCK_START).count(), 1);
+        assert!(twice.contains("export PATH=\"/new/bin:$PATH\""));
+    }
+
+    #[test]
+    fn test_upsert_path_block_matches_exact_entries() {
+        // A longer directory or a comment mentioning bin_dir doesn't count
+        let content = "export PATH=\"/home/u/.wenget/bin2:$PATH\"\n# see /home/u/.wenget/bin\n";
+        let updated = upsert_path_block(content, "/home/u/.wenget/bin");
+        assert!(updated.contains("export PATH=\"/home/u/.wenget/bin:$PATH\""));
+
+        for user in [
+            "export PATH=$PATH:/home/u/.wenget/bin/\n",
+            "set -gx PATH /home/u/.wenget/bin $PATH\n",
+        ] {
+            assert_eq!(upsert_path_block(user, "/home/u/.wenget/bin"), user);
+        }
+    }
+}
@@ -0,0 +1,252 @@
+//! Install manifest written by `wenget add --emit-manifest`
+//!
+//! Records exactly what one `add` invocation installed (name, version, source,
+//! asset and checksum) as a lockfile-style JSON file, e.g. to attach to a CI
+//! build. Packages that were already up to date are not part of it.
+
+use crate::core::manifest::{InstalledManifest, InstalledPackage, PackageSource};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where and how to write the install manifest
+#[derive(Debug, Clone)]
+pub struct EmitManifest {
+    /// Output file
+    pub path: PathBuf,
+    /// Merge into an existing file instead of replacing it
+    pub append: bool,
+}
+
+/// Lockfile-style record of installed packages
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InstallManifest {
+    /// Installed packages, sorted by name
+    pub packages: Vec<InstallRecord>,
+}
+
+/// One installed package
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstallRecord {
+    /// Installed package key (e.g. "ripgrep", "bun::baseline")
+    pub name: String,
+    /// Resolved version
+    pub version: String,
+    /// Platform identifier of the installed build
+    pub platform: String,
+    /// Where the package came from
+    pub source: PackageSource,
+    /// URL the package was downloaded from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Release asset or script file name
+    pub asset_name: String,
+    /// Checksum published for the asset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+    /// When it was installed
+    pub installed_at: DateTime<Utc>,
+}
+
+impl InstallRecord {
+    fn new(name: &str, pkg: &InstalledPackage) -> Self {
+        let url = pkg.download_url.clone().or_else(|| match &pkg.source {
+            PackageSource::DirectRepo { url } => Some(url.clone()),
+            PackageSource::Script { origin, .. } => Some(origin.clone()),
+            PackageSource::Bucket { .. } => None,
+        });
+        Self {
+            name: name.to_string(),
+            version: pkg.version.clone(),
+            platform: pkg.platform.clone(),
+            source: pkg.source.clone(),
+            url,
+            asset_name: pkg.asset_name.clone(),
+            checksum: pkg.checksum.clone(),
+            installed_at: pkg.installed_at,
+        }
+    }
+}
+
+/// Install times of the packages installed before a command runs
+pub fn snapshot(installed: &InstalledManifest) -> HashMap<String, DateTime<Utc>> {
+    installed
+        .packages
+        .iter()
+        .map(|(name, pkg)| (name.clone(), pkg.installed_at))
+        .collect()
+}
+
+/// Packages installed or reinstalled since `before` was taken, sorted by name
+pub fn installed_since(
+    installed: &InstalledManifest,
+    before: &HashMap<String, DateTime<Utc>>,
+) -> Vec<InstallRecord> {
+    let mut records: Vec<InstallRecord> = installed
+        .packages
+        .iter()
+        .filter(|(name, pkg)| before.get(*name) != Some(&pkg.installed_at))
+        .map(|(name, pkg)| InstallRecord::new(name, pkg))
+        .collect();
+    records.sort_by(|a, b| a.name.cmp(&b.name));
+    records
+}
+
+/// Write the records to `emit.path`
+///
+/// In append mode the records are merged into the existing file, replacing
+/// entries with the same name; otherwise the file is overwritten.
+pub fn write(emit: &EmitManifest, records: Vec<InstallRecord>) -> Result<()> {
+    let mut manifest = if emit.append && emit.path.exists() {
+        load(&emit.path)?
+    } else {
+        InstallManifest::default()
+    };
+    manifest.merge(records);
+
+    if let Some(parent) = emit.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    let json = serde_json::to_string_pretty(&manifest)?;
+    fs::write(&emit.path, json + "\n")
+        .with_context(|| format!("Failed to write {}", emit.path.display()))
+}
+
+fn load(path: &Path) -> Result<InstallManifest> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("{} is not an install manifest", path.display()))
+}
+
+impl InstallManifest {
+    /// Add records, replacing existing ones with the same name
+    fn merge(&mut self, records: Vec<InstallRecord>) {
+        for record in records {
+            self.packages
+                .retain(|existing| existing.name != record.name);
+            self.packages.push(record);
+        }
+        self.packages.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn package(version: &str, installed_at: DateTime<Utc>) -> InstalledPackage {
+        InstalledPackage {
+            repo_name: "rg".to_string(),
+            variant: None,
+            version: version.to_string(),
+            platform: "linux-x86_64-musl".to_string(),
+            installed_at,
+            install_path: "/apps/rg".to_string(),
+            executables: HashMap::new(),
+            source: PackageSource::Bucket {
+                name: "main".to_string(),
+            },
+            description: String::new(),
+            command_names: vec![],
+            command_name: None,
+            asset_name: format!("rg-{}-x86_64-unknown-linux-musl.tar.gz", version),
+            parent_package: None,
+            download_url: Some(format!("https://example.com/rg-{}.tar.gz", version)),
+            bin_dir: None,
+            version_constraint: None,
+            compiler: None,
+            checksum: Some("sha256:abc".to_string()),
+            files: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_installed_since() {
+        let then = Utc::now() - Duration::days(1);
+        let mut installed = InstalledManifest::new();
+        installed.upsert_package("fd".to_string(), package("9.0.0", then));
+        installed.upsert_package("rg".to_string(), package("13.0.0", then));
+        let before = snapshot(&installed);
+
+        // Unchanged packages are not recorded; new and reinstalled ones are
+        installed.upsert_package("rg".to_string(), package("14.0.0", Utc::now()));
+        installed.upsert_package("bat".to_string(), package("0.24.0", Utc::now()));
+
+        let records = installed_since(&installed, &before);
+        let names: Vec<_> = records.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["bat", "rg"]);
+        assert_eq!(records[1].version, "14.0.0");
+        assert_eq!(
+            records[1].url.as_deref(),
+            Some("https://example.com/rg-14.0.0.tar.gz")
+        );
+        assert_eq!(records[1].checksum.as_deref(), Some("sha256:abc"));
+    }
+
+    #[test]
+    fn test_write_overwrite_and_append() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out").join("installed.lock.json");
+        let record =
+            |name: &str, version: &str| InstallRecord::new(name, &package(version, Utc::now()));
+        let names = |path: &Path| -> Vec<(String, String)> {
+            load(path)
+                .unwrap()
+                .packages
+                .into_iter()
+                .map(|r| (r.name, r.version))
+                .collect()
+        };
+
+        let overwrite = EmitManifest {
+            path: path.clone(),
+            append: false,
+        };
+        write(
+            &overwrite,
+            vec![record("rg", "13.0.0"), record("fd", "9.0.0")],
+        )
+        .unwrap();
+        assert_eq!(
+            names(&path),
+            [
+                ("fd".to_string(), "9.0.0".to_string()),
+                ("rg".to_string(), "13.0.0".to_string())
+            ]
+        );
+
+        // Append replaces same-named entries and keeps the rest
+        let append = EmitManifest {
+            path: path.clone(),
+            append: true,
+        };
+        write(
+            &append,
+            vec![record("rg", "14.0.0"), record("bat", "0.24.0")],
+        )
+        .unwrap();
+        assert_eq!(
+            names(&path),
+            [
+                ("bat".to_string(), "0.24.0".to_string()),
+                ("fd".to_string(), "9.0.0".to_string()),
+                ("rg".to_string(), "14.0.0".to_string())
+            ]
+        );
+
+        // Overwrite drops earlier entries
+        write(&overwrite, vec![record("bat", "0.24.0")]).unwrap();
+        assert_eq!(names(&path), [("bat".to_string(), "0.24.0".to_string())]);
+
+        // Appending to something that isn't a manifest fails instead of clobbering it
+        fs::write(&path, "not json").unwrap();
+        assert!(write(&append, vec![record("rg", "14.0.0")]).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "not json");
+    }
+}
@@ -1,17 +1,52 @@
 //! List command implementation
 
+use crate::core::manifest::{InstalledManifest, InstalledPackage, PackageSource};
 use crate::core::{Config, Platform};
-use anyhow::Result;
+use crate::utils::network::NetworkOverrides;
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 use colored::Colorize;
 use console::Term;
+use serde::Serialize;
 
 /// List installed packages or all available packages
-pub fn run(all: bool) -> Result<()> {
-    let config = Config::new()?;
+pub fn run(
+    all: bool,
+    by_source: bool,
+    since: Option<String>,
+    before: Option<String>,
+    json: bool,
+    network: NetworkOverrides,
+) -> Result<()> {
+    let config = Config::with_network(network)?;
+
+    let range = InstallDateRange {
+        since: since
+            .as_deref()
+            .map(parse_date)
+            .transpose()
+            .map_err(|e| anyhow!("--since: {}", e))?,
+        before: before
+            .as_deref()
+            .map(parse_date)
+            .transpose()
+            .map_err(|e| anyhow!("--before: {}", e))?,
+    };
+
+    if json {
+        // Installed packages only; --all adds the installed scripts
+        return print_installed_json(&config, range, all);
+    }
 
-    if all {
+    if range.since.is_some() || range.before.is_some() {
+        // Show installed packages from a date range, oldest first
+        list_installed_by_date(&config, range)?;
+    } else if all {
         // Show all available packages from cache
         list_all_packages(&config)?;
+    } else if by_source {
+        // Show installed packages grouped by where they came from
+        list_installed_by_source(&config)?;
     } else {
         // Show only installed packages
         list_installed_packages(&config)?;
@@ -174,6 +209,280 @@ fn list_installed_packages(config: &Config) -> Result<()> {
     Ok(())
 }
 
+/// Where an installed package came from, as grouped by `list --by-source`
+///
+/// The derived ordering lists buckets (by name) first, then direct URLs, then scripts.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum SourceGroup {
+    Bucket(String),
+    DirectRepo,
+    Script,
+}
+
+impl SourceGroup {
+    fn of(source: &PackageSource) -> Self {
+        match source {
+            PackageSource::Bucket { name } => SourceGroup::Bucket(name.clone()),
+            PackageSource::DirectRepo { .. } => SourceGroup::DirectRepo,
+            PackageSource::Script { .. } => SourceGroup::Script,
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            SourceGroup::Bucket(name) => format!("Bucket: {}", name),
+            SourceGroup::DirectRepo => "Direct URL".to_string(),
+            SourceGroup::Script => "Script".to_string(),
+        }
+    }
+}
+
+/// Group installed packages by source, each group sorted by package key
+fn group_by_source(
+    manifest: &InstalledManifest,
+) -> Vec<(SourceGroup, Vec<(&String, &InstalledPackage)>)> {
+    let mut groups: Vec<(SourceGroup, Vec<(&String, &InstalledPackage)>)> = Vec::new();
+    for (key, package) in &manifest.packages {
+        let group = SourceGroup::of(&package.source);
+        match groups.iter_mut().find(|(g, _)| *g == group) {
+            Some((_, packages)) => packages.push((key, package)),
+            None => groups.push((group, vec![(key, package)])),
+        }
+    }
+
+    groups.sort_by(|a, b| a.0.cmp(&b.0));
+    for (_, packages) in &mut groups {
+        packages.sort_by(|a, b| a.0.cmp(b.0));
+    }
+    groups
+}
+
+/// List installed packages grouped by bucket, direct URL or script origin
+fn list_installed_by_source(config: &Config) -> Result<()> {
+    let manifest = config.get_or_create_installed()?;
+
+    if manifest.packages.is_empty() {
+        println!("{}", "No packages installed".yellow());
+        println!("Install packages with: wenget add <name>");
+        return Ok(());
+    }
+
+    // Column widths: indent(2) + NAME(28) + sp + VERSION(10) + sp = 42
+    let width = term_width();
+    let fixed_cols = 2 + 28 + 1 + 10 + 1;
+    let detail_width = width.saturating_sub(fixed_cols);
+
+    println!("{}", "Installed packages by source".bold());
+
+    let groups = group_by_source(&manifest);
+    for (group, packages) in &groups {
+        println!();
+        println!(
+            "{} {}",
+            group.label().cyan().bold(),
+            format!("({})", packages.len()).dimmed()
+        );
+
+        for (key, package) in packages {
+            // Buckets are named by the header; otherwise show the exact origin
+            let detail = match &package.source {
+                PackageSource::Bucket { .. } => package.description.as_str(),
+                PackageSource::DirectRepo { url } => url.as_str(),
+                PackageSource::Script { origin, .. } => origin.as_str(),
+            };
+            println!(
+                "  {:<28} {:<10} {}",
+                key.green(),
+                package.version,
+                truncate_desc(detail, detail_width)
+            );
+        }
+    }
+
+    println!();
+    println!(
+        "Total: {} package(s) installed from {} source(s)",
+        manifest.packages.len(),
+        groups.len()
+    );
+
+    Ok(())
+}
+
+/// One installed package, as emitted by `list --json`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct ListEntry<'a> {
+    /// Installed package key (e.g. "ripgrep", "bun::baseline")
+    name: &'a str,
+    version: &'a str,
+    platform: &'a str,
+    /// Main command, when the package has any
+    command_name: Option<&'a str>,
+    install_path: &'a str,
+    source: &'a PackageSource,
+}
+
+impl<'a> ListEntry<'a> {
+    fn new(name: &'a str, pkg: &'a InstalledPackage) -> Self {
+        // The legacy field names it outright; otherwise prefer the command
+        // named after the package, then the first one alphabetically
+        let command_name = pkg.command_name.as_deref().or_else(|| {
+            let mut commands = pkg.get_command_names();
+            commands.sort();
+            commands
+                .iter()
+                .find(|command| **command == pkg.repo_name)
+                .or(commands.first())
+                .copied()
+        });
+        Self {
+            name,
+            version: &pkg.version,
+            platform: &pkg.platform,
+            command_name,
+            install_path: &pkg.install_path,
+            source: &pkg.source,
+        }
+    }
+}
+
+/// Installed packages within `range` as `list --json` entries, sorted by key
+///
+/// Scripts are only included with `include_scripts` (`--all`).
+fn json_entries(
+    manifest: &InstalledManifest,
+    range: InstallDateRange,
+    include_scripts: bool,
+) -> Vec<ListEntry<'_>> {
+    let mut entries: Vec<ListEntry> = manifest
+        .packages
+        .iter()
+        .filter(|(_, pkg)| range.contains(pkg.installed_at))
+        .filter(|(_, pkg)| include_scripts || !matches!(pkg.source, PackageSource::Script { .. }))
+        .map(|(key, pkg)| ListEntry::new(key, pkg))
+        .collect();
+    entries.sort_by(|a, b| a.name.cmp(b.name));
+    entries
+}
+
+/// Print installed packages as a JSON array, and nothing else
+fn print_installed_json(config: &Config, range: InstallDateRange, all: bool) -> Result<()> {
+    let manifest = config.get_or_create_installed()?;
+    let output = serde_json::to_string_pretty(&json_entries(&manifest, range, all))
+        .context("Failed to serialize installed packages")?;
+    println!("{}", output);
+    Ok(())
+}
+
+/// Install dates selected by `list --since/--before`
+#[derive(Debug, Clone, Copy, Default)]
+struct InstallDateRange {
+    /// Inclusive lower bound
+    since: Option<DateTime<Utc>>,
+    /// Exclusive upper bound
+    before: Option<DateTime<Utc>>,
+}
+
+impl InstallDateRange {
+    fn contains(&self, date: DateTime<Utc>) -> bool {
+        self.since.is_none_or(|since| date >= since)
+            && self.before.is_none_or(|before| date < before)
+    }
+}
+
+/// Parse a date given on the command line
+///
+/// Accepts RFC 3339 (`2024-01-01T12:00:00Z`), `YYYY-MM-DD HH:MM[:SS]` and
+/// `YYYY-MM-DD`; dates without a time mean midnight, and times without an
+/// offset are taken as UTC, like the recorded install times.
+fn parse_date(value: &str) -> Result<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Ok(date.with_timezone(&Utc));
+    }
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(date) = NaiveDateTime::parse_from_str(value, format) {
+            return Ok(date.and_utc());
+        }
+    }
+    for format in ["%Y-%m-%d", "%Y/%m/%d"] {
+        if let Ok(date) = NaiveDate::parse_from_str(value, format) {
+            return Ok(date.and_time(chrono::NaiveTime::MIN).and_utc());
+        }
+    }
+    bail!(
+        "'{}' is not a date (expected YYYY-MM-DD or an RFC 3339 timestamp)",
+        value
+    )
+}
+
+/// Installed packages within `range`, oldest first
+fn installed_between(
+    manifest: &InstalledManifest,
+    range: InstallDateRange,
+) -> Vec<(&String, &InstalledPackage)> {
+    let mut packages: Vec<_> = manifest
+        .packages
+        .iter()
+        .filter(|(_, package)| range.contains(package.installed_at))
+        .collect();
+    packages.sort_by(|a, b| a.1.installed_at.cmp(&b.1.installed_at).then(a.0.cmp(b.0)));
+    packages
+}
+
+/// List installed packages from a date range as a timeline
+fn list_installed_by_date(config: &Config, range: InstallDateRange) -> Result<()> {
+    let manifest = config.get_or_create_installed()?;
+    let packages = installed_between(&manifest, range);
+
+    if packages.is_empty() {
+        println!("{}", "No packages installed in that period".yellow());
+        return Ok(());
+    }
+
+    // Column widths: INSTALLED(16) + sp + NAME(20) + sp + VERSION(10) + sp + SOURCE(12) + sp = 62
+    let width = term_width();
+    let fixed_cols = 16 + 1 + 20 + 1 + 10 + 1 + 12 + 1;
+    let desc_width = width.saturating_sub(fixed_cols);
+
+    println!("{}", "Installed packages by date".bold());
+    println!();
+    println!(
+        "{:<16} {:<20} {:<10} {:<12} {}",
+        "INSTALLED (UTC)".bold(),
+        "NAME".bold(),
+        "VERSION".bold(),
+        "SOURCE".bold(),
+        "DESCRIPTION".bold()
+    );
+    println!("{}", "─".repeat(width.min(120)));
+
+    for (key, package) in &packages {
+        let source_display = match &package.source {
+            PackageSource::Bucket { name } => name.clone(),
+            PackageSource::DirectRepo { .. } => "url".to_string(),
+            PackageSource::Script { script_type, .. } => script_type.display_name().to_lowercase(),
+        };
+        println!(
+            "{:<16} {:<20} {:<10} {:<12} {}",
+            package.installed_at.format("%Y-%m-%d %H:%M"),
+            key.green(),
+            package.version,
+            source_display.cyan(),
+            truncate_desc(&package.description, desc_width)
+        );
+    }
+
+    println!();
+    println!(
+        "Total: {} of {} package(s)",
+        packages.len(),
+        manifest.packages.len()
+    );
+
+    Ok(())
+}
+
 /// List all available packages from cache
 fn list_all_packages(config: &Config) -> Result<()> {
     // Get packages from cache
@@ -296,3 +605,186 @@ fn list_all_packages(config: &Config) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::manifest::ScriptType;
+    use chrono::Utc;
+    use std::collections::HashMap;
+
+    fn installed(source: PackageSource) -> InstalledPackage {
+        InstalledPackage {
+            repo_name: String::new(),
+            variant: None,
+            version: "1.0.0".to_string(),
+            platform: "linux-x86_64".to_string(),
+            installed_at: Utc::now(),
+            install_path: String::new(),
+            executables: HashMap::new(),
+            source,
+            description: String::new(),
+            command_names: vec![],
+            command_name: None,
+            asset_name: String::new(),
+            parent_package: None,
+            download_url: None,
+            bin_dir: None,
+            version_constraint: None,
+            compiler: None,
+            checksum: None,
+            files: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_date() {
+        let midnight = |y, m, d| {
+            NaiveDate::from_ymd_opt(y, m, d)
+                .unwrap()
+                .and_time(chrono::NaiveTime::MIN)
+                .and_utc()
+        };
+        assert_eq!(parse_date("2024-01-01").unwrap(), midnight(2024, 1, 1));
+        assert_eq!(parse_date(" 2024/03/05 ").unwrap(), midnight(2024, 3, 5));
+        assert_eq!(
+            parse_date("2024-01-01T02:00:00+02:00").unwrap(),
+            midnight(2024, 1, 1)
+        );
+        assert_eq!(
+            parse_date("2024-01-01 12:30").unwrap(),
+            midnight(2024, 1, 1) + chrono::Duration::minutes(750)
+        );
+        assert!(parse_date("last week").is_err());
+        assert!(parse_date("2024-13-01").is_err());
+    }
+
+    #[test]
+    fn test_installed_between() {
+        let mut manifest = InstalledManifest::new();
+        for (key, date) in [
+            ("new", "2024-06-01"),
+            ("old", "2023-12-31T23:59:59Z"),
+            ("mid", "2024-03-01"),
+            ("edge", "2024-01-01"),
+        ] {
+            let mut package = installed(PackageSource::DirectRepo {
+                url: format!("https://github.com/o/{}", key),
+            });
+            package.installed_at = parse_date(date).unwrap();
+            manifest.packages.insert(key.to_string(), package);
+        }
+        let keys = |range| -> Vec<&str> {
+            installed_between(&manifest, range)
+                .iter()
+                .map(|(key, _)| key.as_str())
+                .collect()
+        };
+
+        // Since is inclusive, before exclusive; oldest first
+        let range = InstallDateRange {
+            since: Some(parse_date("2024-01-01").unwrap()),
+            before: None,
+        };
+        assert_eq!(keys(range), ["edge", "mid", "new"]);
+
+        let range = InstallDateRange {
+            since: None,
+            before: Some(parse_date("2024-06-01").unwrap()),
+        };
+        assert_eq!(keys(range), ["old", "edge", "mid"]);
+
+        assert_eq!(keys(InstallDateRange::default()).len(), 4);
+    }
+
+    #[test]
+    fn test_json_entries() {
+        let mut manifest = InstalledManifest::new();
+        let mut rg = installed(PackageSource::Bucket {
+            name: "main".to_string(),
+        });
+        rg.repo_name = "ripgrep".to_string();
+        rg.install_path = "/home/u/.wenget/apps/ripgrep".to_string();
+        rg.executables = HashMap::from([("rg".to_string(), "rg".to_string())]);
+        manifest.packages.insert("ripgrep".to_string(), rg);
+        manifest.packages.insert(
+            "hello".to_string(),
+            installed(PackageSource::Script {
+                origin: "./hello.sh".to_string(),
+                script_type: ScriptType::Bash,
+            }),
+        );
+
+        // Scripts only with --all
+        let entries = json_entries(&manifest, InstallDateRange::default(), false);
+        assert_eq!(entries.len(), 1);
+        let names: Vec<&str> = json_entries(&manifest, InstallDateRange::default(), true)
+            .iter()
+            .map(|e| e.name)
+            .collect();
+        assert_eq!(names, ["hello", "ripgrep"]);
+
+        assert_eq!(
+            serde_json::to_value(&entries).unwrap(),
+            serde_json::json!([{
+                "name": "ripgrep",
+                "version": "1.0.0",
+                "platform": "linux-x86_64",
+                "command_name": "rg",
+                "install_path": "/home/u/.wenget/apps/ripgrep",
+                "source": {"type": "bucket", "name": "main"}
+            }])
+        );
+    }
+
+    #[test]
+    fn test_group_by_source() {
+        let bucket = |name: &str| PackageSource::Bucket {
+            name: name.to_string(),
+        };
+        let mut manifest = InstalledManifest::new();
+        for (key, source) in [
+            ("ripgrep", bucket("main")),
+            ("fd", bucket("main")),
+            ("zoxide", bucket("extra")),
+            (
+                "tool",
+                PackageSource::DirectRepo {
+                    url: "https://github.com/o/tool".to_string(),
+                },
+            ),
+            (
+                "hello",
+                PackageSource::Script {
+                    origin: "./hello.sh".to_string(),
+                    script_type: ScriptType::Bash,
+                },
+            ),
+        ] {
+            manifest.packages.insert(key.to_string(), installed(source));
+        }
+
+        let groups = group_by_source(&manifest);
+        let summary: Vec<(SourceGroup, Vec<&str>)> = groups
+            .iter()
+            .map(|(group, packages)| {
+                let keys = packages.iter().map(|(key, _)| key.as_str()).collect();
+                (group.clone(), keys)
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (SourceGroup::Bucket("extra".to_string()), vec!["zoxide"]),
+                (
+                    SourceGroup::Bucket("main".to_string()),
+                    vec!["fd", "ripgrep"]
+                ),
+                (SourceGroup::DirectRepo, vec!["tool"]),
+                (SourceGroup::Script, vec!["hello"]),
+            ]
+        );
+        assert_eq!(SourceGroup::DirectRepo.label(), "Direct URL");
+    }
+}
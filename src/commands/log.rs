@@ -0,0 +1,44 @@
+//! Log command implementation
+//!
+//! Prints the install history recorded by add, update and delete (see
+//! [`crate::core::history`]).
+
+use crate::core::history::{self, Action};
+use crate::core::manifest::PackageSource;
+use crate::core::Config;
+use anyhow::Result;
+use colored::Colorize;
+
+/// Print the last `limit` history entries, oldest first
+pub fn run(limit: usize) -> Result<()> {
+    let config = Config::new()?;
+    let entries = history::read_last(&config.paths().history_jsonl(), limit)?;
+
+    if entries.is_empty() {
+        println!("{}", "No install history yet".yellow());
+        return Ok(());
+    }
+
+    for entry in entries {
+        let action = format!("{:<8}", entry.action.as_str());
+        let action = match entry.action {
+            Action::Install => action.green(),
+            Action::Update => action.cyan(),
+            Action::Delete => action.red(),
+        };
+        let source = match &entry.source {
+            PackageSource::Bucket { name } => name.as_str(),
+            PackageSource::DirectRepo { url } => url.as_str(),
+            PackageSource::Script { origin, .. } => origin.as_str(),
+        };
+        println!(
+            "{}  {} {:<20} {:<12} {}",
+            entry.timestamp.format("%Y-%m-%d %H:%M"),
+            action,
+            entry.package,
+            entry.version,
+            source.dimmed()
+        );
+    }
+    Ok(())
+}
@@ -1,29 +1,58 @@
 //! Command implementations for WenPM
 
 pub mod add;
+pub mod alias;
 pub mod bucket;
+pub mod cache;
+pub mod clean;
 pub mod config;
 pub mod delete;
+pub mod export;
+pub mod files;
+pub mod import;
 pub mod info;
 pub mod init;
+pub mod install_manifest;
 pub mod list;
+pub mod log;
+pub mod outcome;
+pub mod outdated;
+pub mod prefetch;
 pub mod rename;
 pub mod repair;
+pub mod rollback;
 pub mod search;
+pub mod try_run;
 pub mod update;
+pub mod verify;
 
 // Re-export command functions
 pub use add::run as run_add;
+pub use alias::run as run_alias;
 pub use bucket::run as run_bucket;
+pub use cache::run as run_cache;
+pub use clean::run as run_clean;
 pub use config::run as run_config;
 pub use delete::run as run_delete;
+pub use export::run as run_export;
+pub use files::run as run_files;
+pub use import::run as run_import;
 pub use info::run as run_info;
 pub use init::run as run_init;
 pub use list::run as run_list;
+pub use log::run as run_log;
+pub use outdated::run as run_outdated;
 pub use rename::run as run_rename;
 pub use repair::run as run_repair;
+pub use rollback::run as run_rollback;
 pub use search::run as run_search;
+pub use try_run::run as run_try;
 pub use update::run as run_update;
+pub use update::run_check as run_update_check;
+pub use update::run_metadata as run_update_metadata;
+pub use verify::run as run_verify;
+
+pub use outcome::Outcome;
 
 // Placeholders for future commands
 // pub mod setup_path;
@@ -0,0 +1,75 @@
+//! Outcome of batch commands and the exit codes derived from it
+//!
+//! `add`, `update` and `del` work through several items and keep going when one
+//! fails. They report how many items succeeded and failed, and `main` turns that
+//! into the process exit code:
+//!
+//! - `0`: every item succeeded, or there was nothing to do
+//! - `1`: hard error, or every item failed
+//! - `2`: partial failure, some items failed while others succeeded
+
+/// Exit code when everything succeeded or there was nothing to do
+pub const EXIT_SUCCESS: i32 = 0;
+
+/// Exit code for hard errors and batches where every item failed
+pub const EXIT_FAILURE: i32 = 1;
+
+/// Exit code when some items of a batch failed
+pub const EXIT_PARTIAL: i32 = 2;
+
+/// Success and failure counts of a batch command
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Outcome {
+    /// Items that completed
+    pub succeeded: usize,
+    /// Items that failed
+    pub failed: usize,
+}
+
+impl Outcome {
+    /// Create an outcome from success and failure counts
+    pub fn new(succeeded: usize, failed: usize) -> Self {
+        Self { succeeded, failed }
+    }
+
+    /// Combine the counts of two parts of the same command
+    pub fn merge(self, other: Outcome) -> Self {
+        Self {
+            succeeded: self.succeeded + other.succeeded,
+            failed: self.failed + other.failed,
+        }
+    }
+
+    /// Process exit code for this outcome
+    pub fn exit_code(&self) -> i32 {
+        match (self.succeeded, self.failed) {
+            (_, 0) => EXIT_SUCCESS,
+            (0, _) => EXIT_FAILURE,
+            _ => EXIT_PARTIAL,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_code() {
+        assert_eq!(Outcome::default().exit_code(), EXIT_SUCCESS);
+        assert_eq!(Outcome::new(3, 0).exit_code(), EXIT_SUCCESS);
+        assert_eq!(Outcome::new(2, 1).exit_code(), EXIT_PARTIAL);
+        assert_eq!(Outcome::new(0, 2).exit_code(), EXIT_FAILURE);
+    }
+
+    #[test]
+    fn test_merge() {
+        let merged = Outcome::new(1, 0).merge(Outcome::new(0, 1));
+        assert_eq!(merged, Outcome::new(1, 1));
+        assert_eq!(merged.exit_code(), EXIT_PARTIAL);
+
+        // Failures in separate parts stay a total failure
+        let merged = Outcome::new(0, 1).merge(Outcome::new(0, 2));
+        assert_eq!(merged.exit_code(), EXIT_FAILURE);
+    }
+}
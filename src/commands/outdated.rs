@@ -0,0 +1,280 @@
+//! Outdated command implementation
+//!
+//! Lists installed packages next to the newest release available for them,
+//! without installing anything. Range-pinned packages are compared with the
+//! newest release in their range, like `update` does.
+
+use crate::commands::update::{check_package, UpdateStatus, MAX_CONCURRENT_FETCHES};
+use crate::core::manifest::PackageSource;
+use crate::core::{Config, InstalledPackage};
+use crate::providers::GitHubProvider;
+use crate::utils::network::NetworkOverrides;
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Whether an installed package can be upgraded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum Status {
+    UpToDate,
+    UpgradeAvailable,
+    /// Scripts have no release versions
+    #[serde(rename = "n/a")]
+    NotApplicable,
+    /// The latest version couldn't be determined
+    Unknown,
+}
+
+/// One row of `wenget outdated`, as emitted by `--json`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct OutdatedEntry {
+    name: String,
+    installed: String,
+    latest: Option<String>,
+    status: Status,
+    /// Why the check failed (unknown status only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+}
+
+impl OutdatedEntry {
+    fn new(name: &str, pkg: &InstalledPackage, status: Option<UpdateStatus>) -> Self {
+        let (latest, status, reason) = match status {
+            None => (None, Status::NotApplicable, None),
+            Some(UpdateStatus::UpToDate { version }) => (Some(version), Status::UpToDate, None),
+            Some(UpdateStatus::Update { to, .. }) => (Some(to), Status::UpgradeAvailable, None),
+            Some(UpdateStatus::NotInstalled) => {
+                (None, Status::Unknown, Some("not installed".to_string()))
+            }
+            Some(UpdateStatus::Unknown { reason }) => (None, Status::Unknown, Some(reason)),
+        };
+        Self {
+            name: name.to_string(),
+            installed: pkg.version.clone(),
+            latest,
+            status,
+            reason,
+        }
+    }
+}
+
+/// Scripts are installed with the literal version "script"
+fn is_script(pkg: &InstalledPackage) -> bool {
+    matches!(pkg.source, PackageSource::Script { .. }) || pkg.version == "script"
+}
+
+/// List installed packages with their latest versions
+pub fn run(json: bool, network: NetworkOverrides) -> Result<()> {
+    let config = Config::with_network(network)?;
+    let installed = config.get_or_create_installed()?;
+
+    if installed.packages.is_empty() {
+        if json {
+            println!("[]");
+        } else {
+            println!("{}", "No packages installed".yellow());
+        }
+        return Ok(());
+    }
+
+    let cache = config.get_or_rebuild_cache()?;
+    let github = GitHubProvider::from_config(&config)?;
+
+    let mut names: Vec<&String> = installed.packages.keys().collect();
+    names.sort();
+
+    let pb = if json {
+        indicatif::ProgressBar::hidden()
+    } else {
+        let pb = indicatif::ProgressBar::new(names.len() as u64);
+        pb.set_style(
+            indicatif::ProgressStyle::with_template(
+                "{spinner:.cyan} [{bar:30.cyan/blue}] {pos}/{len} checking for updates...",
+            )
+            .unwrap()
+            .progress_chars("=>-"),
+        );
+        pb
+    };
+
+    // Check in parallel; every slot is filled in input order
+    let next = AtomicUsize::new(0);
+    let entries: Mutex<Vec<Option<OutdatedEntry>>> =
+        Mutex::new((0..names.len()).map(|_| None).collect());
+    std::thread::scope(|scope| {
+        for _ in 0..names.len().min(MAX_CONCURRENT_FETCHES) {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(name) = names.get(i) else {
+                    break;
+                };
+                let pkg = &installed.packages[*name];
+                let status = (!is_script(pkg)).then(|| {
+                    check_package(&installed, &cache, name, |repo_url, req| match req {
+                        Some(req) => github.fetch_matching_version(repo_url, req),
+                        None => github.fetch_latest_version(repo_url).map(Some),
+                    })
+                });
+                entries.lock().unwrap()[i] = Some(OutdatedEntry::new(name, pkg, status));
+                pb.inc(1);
+            });
+        }
+    });
+    pb.finish_and_clear();
+
+    let entries: Vec<OutdatedEntry> = Mutex::into_inner(entries)
+        .unwrap()
+        .into_iter()
+        .map(|slot| slot.expect("every package is checked"))
+        .collect();
+
+    if json {
+        let output = serde_json::to_string_pretty(&entries)
+            .context("Failed to serialize outdated packages")?;
+        println!("{}", output);
+        return Ok(());
+    }
+
+    print_table(&entries);
+    Ok(())
+}
+
+fn print_table(entries: &[OutdatedEntry]) {
+    println!(
+        "{:<24} {:<14} {:<14} {}",
+        "NAME".bold(),
+        "INSTALLED".bold(),
+        "LATEST".bold(),
+        "STATUS".bold()
+    );
+    println!("{}", "─".repeat(72));
+
+    for entry in entries {
+        let status = match entry.status {
+            Status::UpToDate => "up to date".green(),
+            Status::UpgradeAvailable => "upgrade available".yellow(),
+            Status::NotApplicable => "n/a".dimmed(),
+            Status::Unknown => format!(
+                "unknown: {}",
+                entry.reason.as_deref().unwrap_or("check failed")
+            )
+            .red(),
+        };
+        let latest = match entry.status {
+            Status::NotApplicable => "n/a",
+            _ => entry.latest.as_deref().unwrap_or("?"),
+        };
+        println!(
+            "{:<24} {:<14} {:<14} {}",
+            entry.name, entry.installed, latest, status
+        );
+    }
+
+    let upgradable = entries
+        .iter()
+        .filter(|e| e.status == Status::UpgradeAvailable)
+        .count();
+    let unknown = entries
+        .iter()
+        .filter(|e| e.status == Status::Unknown)
+        .count();
+    println!();
+    if unknown > 0 {
+        println!(
+            "{} {} package(s) could not be checked",
+            "⚠".yellow(),
+            unknown
+        );
+    }
+    if upgradable == 0 && unknown == 0 {
+        println!("{} All packages are up to date", "✓".green());
+    } else if upgradable > 0 {
+        println!(
+            "{} {} package(s) can be upgraded. Run: wenget update",
+            "ℹ".cyan(),
+            upgradable
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::collections::HashMap;
+
+    fn installed_pkg(version: &str, source: PackageSource) -> InstalledPackage {
+        InstalledPackage {
+            repo_name: "tool".to_string(),
+            variant: None,
+            version: version.to_string(),
+            platform: "linux-x86_64".to_string(),
+            installed_at: Utc::now(),
+            install_path: "/apps/tool".to_string(),
+            executables: HashMap::new(),
+            source,
+            description: String::new(),
+            command_names: vec![],
+            command_name: None,
+            asset_name: String::new(),
+            parent_package: None,
+            download_url: None,
+            bin_dir: None,
+            version_constraint: None,
+            compiler: None,
+            checksum: None,
+            files: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_entries_and_json() {
+        let repo = PackageSource::DirectRepo {
+            url: "https://github.com/o/tool".to_string(),
+        };
+        let pkg = installed_pkg("1.2.0", repo);
+
+        let upgrade = OutdatedEntry::new(
+            "tool",
+            &pkg,
+            Some(UpdateStatus::Update {
+                from: "1.2.0".to_string(),
+                to: "1.3.0".to_string(),
+            }),
+        );
+        assert_eq!(upgrade.status, Status::UpgradeAvailable);
+        assert_eq!(upgrade.latest.as_deref(), Some("1.3.0"));
+
+        let failed = OutdatedEntry::new(
+            "tool",
+            &pkg,
+            Some(UpdateStatus::Unknown {
+                reason: "rate limited".to_string(),
+            }),
+        );
+        assert_eq!(failed.status, Status::Unknown);
+        assert_eq!(failed.reason.as_deref(), Some("rate limited"));
+
+        let script = installed_pkg(
+            "script",
+            PackageSource::Script {
+                origin: "https://example.com/hello.sh".to_string(),
+                script_type: crate::core::manifest::ScriptType::Bash,
+            },
+        );
+        assert!(is_script(&script));
+        let script_entry = OutdatedEntry::new("hello", &script, None);
+
+        let json = serde_json::to_value([&upgrade, &script_entry]).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {"name": "tool", "installed": "1.2.0", "latest": "1.3.0", "status": "upgrade-available"},
+                {"name": "hello", "installed": "script", "latest": null, "status": "n/a"}
+            ])
+        );
+    }
+}
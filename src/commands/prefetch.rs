@@ -0,0 +1,318 @@
+//! Pre-fetching the binaries of every platform (`wenget add -p all`)
+//!
+//! Instead of installing, every `PlatformBinary` of the resolved packages is
+//! downloaded into a content-addressed store, `cache/prefetch/<sha256>/<asset>`,
+//! e.g. to seed an offline mirror. Published checksums are verified; nothing is
+//! extracted, linked or recorded in installed.json.
+
+use crate::commands::cache::format_size;
+use crate::commands::Outcome;
+use crate::core::manifest::PlatformBinary;
+use crate::core::{Config, Package, WenPaths};
+use crate::downloader;
+use crate::package_resolver::{PackageInput, PackageResolver};
+use crate::providers::{GitHubProvider, SourceProvider};
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// `-p` value selecting every platform
+pub const ALL_PLATFORMS: &str = "all";
+
+/// Whether a `-p` value asks for every platform
+pub fn is_all_platforms(platform: Option<&str>) -> bool {
+    platform.is_some_and(|p| p.eq_ignore_ascii_case(ALL_PLATFORMS))
+}
+
+/// Download every platform's binaries of the named packages
+pub(crate) fn run(
+    config: &Config,
+    paths: &WenPaths,
+    names: Vec<&String>,
+    version: Option<&str>,
+    dry_run: bool,
+) -> Result<Outcome> {
+    let cache = config.get_or_rebuild_cache()?;
+    let resolver = PackageResolver::new(config, &cache)?;
+    let github = GitHubProvider::from_config(config)?;
+
+    let mut outcome = Outcome::default();
+    // Bytes fetched and files stored per platform, over all packages
+    let mut per_platform: BTreeMap<String, (usize, u64)> = BTreeMap::new();
+
+    for name in names {
+        let resolved = match resolver.resolve(&PackageInput::parse(name)) {
+            Ok(resolved) => resolved,
+            Err(e) => {
+                eprintln!("{} {}: {:#}", "✗".red(), name, e);
+                outcome = outcome.merge(Outcome::new(0, 1));
+                continue;
+            }
+        };
+
+        for resolved in resolved {
+            let pkg = target_package(&github, resolved.package, version);
+            let label = format!(
+                "{} v{}",
+                pkg.name,
+                pkg.version.as_deref().unwrap_or("unknown")
+            );
+            if pkg.platforms.is_empty() {
+                println!("{} {}: no platform binaries", "✗".red(), label);
+                outcome = outcome.merge(Outcome::new(0, 1));
+                continue;
+            }
+
+            println!("{} {}", "Fetching every platform of".cyan(), label.bold());
+            let mut failed = false;
+            for (platform_id, binary) in binaries_by_platform(&pkg) {
+                if dry_run {
+                    println!(
+                        "  {} {:<24} {}",
+                        "Would fetch".cyan(),
+                        platform_id,
+                        binary.url
+                    );
+                    continue;
+                }
+                match fetch(paths, config, binary) {
+                    Ok((path, fetched)) => {
+                        let entry = per_platform.entry(platform_id.to_string()).or_default();
+                        entry.0 += 1;
+                        entry.1 += fetched;
+                        let note = if fetched == 0 {
+                            "already stored".dimmed().to_string()
+                        } else {
+                            format_size(fetched)
+                        };
+                        println!(
+                            "  {} {:<24} {} ({})",
+                            "✓".green(),
+                            platform_id,
+                            path.display(),
+                            note
+                        );
+                    }
+                    Err(e) => {
+                        println!(
+                            "  {} {:<24} {}: {:#}",
+                            "✗".red(),
+                            platform_id,
+                            binary.asset_name,
+                            e
+                        );
+                        failed = true;
+                    }
+                }
+            }
+            outcome = outcome.merge(if failed {
+                Outcome::new(0, 1)
+            } else {
+                Outcome::new(1, 0)
+            });
+        }
+    }
+
+    if !dry_run && !per_platform.is_empty() {
+        println!();
+        println!("{}", "Fetched per platform:".bold());
+        let mut total = 0;
+        for (platform_id, (files, bytes)) in &per_platform {
+            println!(
+                "  {:<24} {} file(s)  {}",
+                platform_id,
+                files,
+                format_size(*bytes)
+            );
+            total += bytes;
+        }
+        println!("  {:<24} {}", "total".bold(), format_size(total).bold());
+        println!("Stored in {}", paths.prefetch_dir().display());
+    }
+
+    Ok(outcome)
+}
+
+/// The package at `version`, or at its latest release, falling back to the cached one
+fn target_package(github: &GitHubProvider, cached: Package, version: Option<&str>) -> Package {
+    let fetched = match version {
+        Some(version) => github
+            .fetch_package_by_version(&cached.repo, version)
+            .ok()
+            .or_else(|| super::add::derive_versioned_package(&cached, version)),
+        None => github.fetch_package(&cached.repo).ok(),
+    };
+    fetched.unwrap_or(cached)
+}
+
+/// Every binary of a package with its platform, sorted by platform
+fn binaries_by_platform(pkg: &Package) -> Vec<(&str, &PlatformBinary)> {
+    let mut binaries: Vec<(&str, &PlatformBinary)> = pkg
+        .platforms
+        .iter()
+        .flat_map(|(platform_id, binaries)| binaries.iter().map(|b| (platform_id.as_str(), b)))
+        .collect();
+    binaries.sort_by(|a, b| (a.0, &a.1.asset_name).cmp(&(b.0, &b.1.asset_name)));
+    binaries
+}
+
+/// Download one binary into the store
+///
+/// Returns where it is stored and the bytes downloaded; 0 when a copy with the
+/// published checksum is already stored.
+fn fetch(paths: &WenPaths, config: &Config, binary: &PlatformBinary) -> Result<(PathBuf, u64)> {
+    // Asset names are plain file names; never let one point outside the store
+    let file_name = binary
+        .asset_name
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default();
+    if file_name.is_empty() {
+        anyhow::bail!("no asset name");
+    }
+    if let Some(expected) = binary.checksum.as_deref() {
+        let stored = paths.prefetch_path(&normalize_checksum(expected), file_name);
+        if stored.is_file() {
+            return Ok((stored, 0));
+        }
+    }
+
+    let download_dir = paths.downloads_dir();
+    fs::create_dir_all(&download_dir)
+        .with_context(|| format!("Failed to create {}", download_dir.display()))?;
+    let download = download_dir.join(file_name);
+    downloader::download_file(&binary.url, &download, config.network())?;
+
+    let result = store(paths, &download, file_name, binary.checksum.as_deref());
+    fs::remove_file(&download).ok();
+    result
+}
+
+/// Move a download into the store under its SHA256, verifying the published checksum
+fn store(
+    paths: &WenPaths,
+    download: &std::path::Path,
+    file_name: &str,
+    checksum: Option<&str>,
+) -> Result<(PathBuf, u64)> {
+    let digest = downloader::sha256_file(download)?;
+    if let Some(expected) = checksum {
+        if normalize_checksum(expected) != digest {
+            anyhow::bail!(
+                "checksum mismatch (expected {}, got {})",
+                normalize_checksum(expected),
+                digest
+            );
+        }
+    }
+
+    let bytes = fs::metadata(download)?.len();
+    let dest = paths.prefetch_path(&digest, file_name);
+    let dir = dest.parent().expect("store paths have a parent");
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    fs::rename(download, &dest)
+        .or_else(|_| fs::copy(download, &dest).map(|_| ()))
+        .with_context(|| format!("Failed to store {}", dest.display()))?;
+    Ok((dest, bytes))
+}
+
+/// Lowercase hex digest of a published checksum ("sha256:" prefix optional)
+fn normalize_checksum(checksum: &str) -> String {
+    let checksum = checksum.trim();
+    checksum
+        .strip_prefix("sha256:")
+        .unwrap_or(checksum)
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn binary(asset_name: &str) -> PlatformBinary {
+        PlatformBinary {
+            url: format!("https://example.com/{}", asset_name),
+            size: 0,
+            checksum: None,
+            asset_name: asset_name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_is_all_platforms() {
+        assert!(is_all_platforms(Some("all")));
+        assert!(is_all_platforms(Some("ALL")));
+        assert!(!is_all_platforms(Some("linux-x64")));
+        assert!(!is_all_platforms(None));
+    }
+
+    #[test]
+    fn test_binaries_by_platform() {
+        let pkg = Package {
+            name: "tool".to_string(),
+            aliases: Vec::new(),
+            description: String::new(),
+            repo: "https://github.com/o/tool".to_string(),
+            homepage: None,
+            license: None,
+            version: Some("1.0.0".to_string()),
+            platforms: HashMap::from([
+                (
+                    "windows-x86_64".to_string(),
+                    vec![binary("tool-windows.zip")],
+                ),
+                (
+                    "linux-x86_64".to_string(),
+                    vec![
+                        binary("tool-linux-musl.tar.gz"),
+                        binary("tool-linux.tar.gz"),
+                    ],
+                ),
+            ]),
+            pre_install: Vec::new(),
+            post_install: Vec::new(),
+        };
+        let order: Vec<(&str, &str)> = binaries_by_platform(&pkg)
+            .into_iter()
+            .map(|(platform, b)| (platform, b.asset_name.as_str()))
+            .collect();
+        assert_eq!(
+            order,
+            [
+                ("linux-x86_64", "tool-linux-musl.tar.gz"),
+                ("linux-x86_64", "tool-linux.tar.gz"),
+                ("windows-x86_64", "tool-windows.zip"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_store() {
+        let temp = TempDir::new().unwrap();
+        let paths = WenPaths::with_root(temp.path().to_path_buf(), false);
+        fs::create_dir_all(paths.downloads_dir()).unwrap();
+        let download = paths.downloads_dir().join("tool.tar.gz");
+        // SHA256 of "hello"
+        let digest = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+        fs::write(&download, "hello").unwrap();
+        let mismatch = store(&paths, &download, "tool.tar.gz", Some("sha256:00")).unwrap_err();
+        assert!(mismatch.to_string().contains("checksum mismatch"));
+
+        let (path, bytes) = store(
+            &paths,
+            &download,
+            "tool.tar.gz",
+            Some(&format!("sha256:{}", digest.to_uppercase())),
+        )
+        .unwrap();
+        assert_eq!(path, paths.prefetch_path(digest, "tool.tar.gz"));
+        assert_eq!(bytes, 5);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "hello");
+        assert!(!download.exists());
+    }
+}
@@ -6,6 +6,7 @@ use dialoguer::{Input, Select};
 use std::fs;
 use std::path::Path;
 
+use crate::core::manifest::PackageSource;
 use crate::core::{Config, InstalledManifest, InstalledPackage};
 use crate::installer;
 
@@ -14,10 +15,48 @@ use crate::installer;
 /// Supports two modes:
 /// 1. Direct: `wenget rename <old_cmd> <new_cmd>` - rename specified command
 /// 2. Interactive: `wenget rename <package_name>` - select from multiple commands
-pub fn run(old_name: String, new_name: Option<String>, config: &Config) -> Result<()> {
+/// 3. Package key: `wenget rename --package <old_key> <new_key>` - re-key installed.json
+pub fn run(
+    old_name: String,
+    new_name: Option<String>,
+    package: bool,
+    config: &Config,
+) -> Result<()> {
     let paths = config.paths();
     let mut installed = config.load_installed()?;
 
+    if package {
+        let new_key = new_name.context("A new package key is required with --package")?;
+
+        rename_package_key(&mut installed, &old_name, &new_key)?;
+        config.save_installed(&installed)?;
+
+        // Versions kept for rollback are stored by package key
+        for (kept, moved) in [
+            (
+                paths.app_versions_dir(&old_name),
+                paths.app_versions_dir(&new_key),
+            ),
+            (
+                paths.rollback_snapshot_dir(&old_name),
+                paths.rollback_snapshot_dir(&new_key),
+            ),
+        ] {
+            if kept.exists() {
+                fs::rename(&kept, moved)
+                    .with_context(|| format!("Failed to move {}", kept.display()))?;
+            }
+        }
+
+        println!(
+            "{} Renamed package: {} → {}",
+            "✓".green().bold(),
+            old_name.yellow(),
+            new_key.green().bold()
+        );
+        return Ok(());
+    }
+
     // Find the package by command name or package key
     let (pkg_key, old_cmd_name, package) = find_package_and_command(&installed, &old_name)?;
 
@@ -198,6 +237,9 @@ fn rename_command(
         anyhow::bail!("Install path does not exist: {}", install_path.display());
     }
 
+    // Launchers may live in a per-package bin directory (add --bin-dir)
+    let paths = &paths.for_package(package.bin_dir.as_deref());
+
     // Read the target of the old symlink/shim before removing it
     let old_shim = paths.bin_shim_path(old_cmd);
 
@@ -266,6 +308,60 @@ fn rename_command(
     Ok(())
 }
 
+/// Re-key an installed package in the manifest
+///
+/// Command shims and the install directory are left untouched; the package keeps
+/// pointing at its existing `install_path`. For packages that did not come from a
+/// bucket, `repo_name` and `variant` are derived from the new key so that the
+/// `repo::variant` convention still holds. Bucket packages keep their `repo_name`
+/// because it is used to look the package up again on update.
+fn rename_package_key(
+    installed: &mut InstalledManifest,
+    old_key: &str,
+    new_key: &str,
+) -> Result<()> {
+    let new_key = new_key.trim();
+    if new_key.is_empty() {
+        anyhow::bail!("New package key cannot be empty");
+    }
+    if new_key == old_key {
+        anyhow::bail!("Package is already named '{}'", old_key);
+    }
+    if installed.packages.contains_key(new_key) {
+        anyhow::bail!("Package key '{}' is already in use", new_key);
+    }
+    // The key names the app directory on the next update; each part of a
+    // `repo::variant` key has to be as safe as a command name
+    for part in new_key.split("::") {
+        installer::sanitize_command_name(part)
+            .with_context(|| format!("Invalid package key '{}'", new_key))?;
+    }
+
+    let mut package = installed.remove_package(old_key).with_context(|| {
+        format!(
+            "Package '{}' not found. Use 'wenget ls' to see installed packages.",
+            old_key
+        )
+    })?;
+
+    if !matches!(package.source, PackageSource::Bucket { .. }) {
+        match new_key.split_once("::") {
+            Some((repo, variant)) => {
+                package.repo_name = repo.to_string();
+                package.variant = Some(variant.to_string());
+            }
+            None => {
+                package.repo_name = new_key.to_string();
+                package.variant = None;
+            }
+        }
+    }
+
+    installed.upsert_package(new_key.to_string(), package);
+
+    Ok(())
+}
+
 /// Read the target binary path from a Windows shim (.cmd file)
 #[cfg(windows)]
 fn read_shim_target(shim_path: &Path) -> Result<std::path::PathBuf> {
@@ -326,6 +422,11 @@ mod tests {
             asset_name: "pkg1.tar.gz".to_string(),
             parent_package: None,
             download_url: None,
+            bin_dir: None,
+            version_constraint: None,
+            compiler: None,
+            checksum: None,
+            files: Vec::new(),
         };
         manifest.packages.insert("pkg1".to_string(), package);
 
@@ -357,6 +458,11 @@ mod tests {
             asset_name: "pkg1.tar.gz".to_string(),
             parent_package: None,
             download_url: None,
+            bin_dir: None,
+            version_constraint: None,
+            compiler: None,
+            checksum: None,
+            files: Vec::new(),
         };
         manifest.packages.insert("pkg1".to_string(), package1);
 
@@ -381,10 +487,80 @@ mod tests {
             asset_name: "pkg2.tar.gz".to_string(),
             parent_package: None,
             download_url: None,
+            bin_dir: None,
+            version_constraint: None,
+            compiler: None,
+            checksum: None,
+            files: Vec::new(),
         };
         manifest.packages.insert("pkg2".to_string(), package2);
 
         // Try to rename pkg1's cmd to "cmd2" which is already used
         assert!(validate_new_name(&manifest, "pkg1", "cmd2").is_err());
     }
+
+    #[test]
+    fn test_rename_package_key() {
+        let mut manifest = InstalledManifest::new();
+        let mut exe = HashMap::new();
+        exe.insert("tool".to_string(), "tool".to_string());
+
+        let package = InstalledPackage {
+            repo_name: "ugly-tool-name".to_string(),
+            variant: None,
+            version: "1.0.0".to_string(),
+            platform: "linux-x86_64".to_string(),
+            installed_at: chrono::Utc::now(),
+            install_path: "/path/to/ugly-tool-name".to_string(),
+            executables: exe,
+            source: crate::core::manifest::PackageSource::DirectRepo {
+                url: "https://github.com/owner/ugly-tool-name".to_string(),
+            },
+            description: String::new(),
+            command_names: vec![],
+            command_name: None,
+            asset_name: "tool.tar.gz".to_string(),
+            parent_package: None,
+            download_url: None,
+            bin_dir: None,
+            version_constraint: None,
+            compiler: None,
+            checksum: None,
+            files: Vec::new(),
+        };
+        manifest
+            .packages
+            .insert("ugly-tool-name".to_string(), package.clone());
+        manifest.packages.insert("taken".to_string(), package);
+
+        // Collisions and unknown keys are rejected without touching the manifest
+        assert!(rename_package_key(&mut manifest, "ugly-tool-name", "taken").is_err());
+        assert!(rename_package_key(&mut manifest, "missing", "other").is_err());
+        // Keys end up in paths; nothing that could leave the apps directory
+        for bad in [
+            "../../Documents",
+            "a/b",
+            "a\\b",
+            "..",
+            "tool::..",
+            "tool::",
+            "a b",
+        ] {
+            assert!(
+                rename_package_key(&mut manifest, "ugly-tool-name", bad).is_err(),
+                "{}",
+                bad
+            );
+        }
+        assert!(manifest.packages.contains_key("ugly-tool-name"));
+
+        rename_package_key(&mut manifest, "ugly-tool-name", "tool::musl").unwrap();
+        assert!(!manifest.packages.contains_key("ugly-tool-name"));
+        let renamed = manifest.get_package("tool::musl").unwrap();
+        assert_eq!(renamed.repo_name, "tool");
+        assert_eq!(renamed.variant.as_deref(), Some("musl"));
+        // Commands and install location are left alone
+        assert_eq!(renamed.install_path, "/path/to/ugly-tool-name");
+        assert_eq!(renamed.get_command_names(), vec!["tool"]);
+    }
 }
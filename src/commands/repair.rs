@@ -1,21 +1,31 @@
 //! Repair command for Wenget
 //!
-//! Checks and repairs corrupted configuration files.
+//! Checks and repairs corrupted configuration files, and rebuilds launchers
+//! (symlinks/shims) from installed.json with `--shims`.
 
 use crate::bucket::BucketConfig;
 use crate::cache::ManifestCache;
-use crate::core::manifest::InstalledManifest;
+use crate::commands::alias::create_alias_launcher;
+use crate::core::manifest::{InstalledManifest, PackageSource};
 use crate::core::repair::{check_json_file, create_backup, FileStatus};
-use crate::core::Config;
+use crate::core::{Config, WenPaths};
+use crate::utils::network::NetworkOverrides;
 use anyhow::Result;
 use colored::Colorize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 /// Run the repair command
-pub fn run(force: bool) -> Result<()> {
+pub fn run(force: bool, shims: bool, network: NetworkOverrides) -> Result<()> {
+    if shims {
+        return repair_shims();
+    }
+
     println!("{}", "Checking Wenget configuration files...".cyan());
     println!();
 
-    let config = Config::new()?;
+    let config = Config::with_network(network)?;
     let paths = config.paths();
 
     // Check all config files
@@ -196,3 +206,300 @@ fn repair_cache(
 
     Ok(())
 }
+
+/// Outcome of rebuilding launchers
+#[derive(Debug, Default, PartialEq)]
+struct ShimReport {
+    /// Launchers that were missing or pointed somewhere else
+    recreated: usize,
+    /// Launchers that were already correct
+    unchanged: usize,
+    /// Launchers that couldn't be rebuilt
+    failed: usize,
+    /// Packages whose recorded install path was updated
+    relocated: usize,
+}
+
+/// Recreate every launcher recorded in installed.json
+fn repair_shims() -> Result<()> {
+    println!("{}", "Rebuilding launchers from installed.json...".cyan());
+    println!();
+
+    let config = Config::new()?;
+    let mut installed = config.get_or_create_installed()?;
+
+    if installed.packages.is_empty() {
+        println!("{}", "No packages installed".yellow());
+        return Ok(());
+    }
+
+    let mut report = rebuild_shims(config.paths(), &mut installed);
+    rebuild_aliases(
+        config.paths(),
+        &installed,
+        &config.preferences().aliases,
+        &mut report,
+    );
+
+    if report.relocated > 0 {
+        config.save_installed(&installed)?;
+    }
+
+    println!();
+    println!("{}", "Summary:".bold());
+    println!(
+        "  {} {} launcher(s) recreated",
+        "✓".green(),
+        report.recreated
+    );
+    println!(
+        "  {} {} launcher(s) already correct",
+        "✓".green(),
+        report.unchanged
+    );
+    if report.relocated > 0 {
+        println!(
+            "  {} {} package path(s) updated to this machine",
+            "ℹ".cyan(),
+            report.relocated
+        );
+    }
+    if report.failed > 0 {
+        println!("  {} {} launcher(s) failed", "✗".red(), report.failed);
+    }
+
+    Ok(())
+}
+
+/// Rebuild the launchers of all installed packages
+///
+/// Install paths recorded on another machine (e.g. after copying the whole wenget
+/// directory) are moved to this machine's app directory when the files are there.
+fn rebuild_shims(paths: &WenPaths, installed: &mut InstalledManifest) -> ShimReport {
+    let mut report = ShimReport::default();
+
+    let mut keys: Vec<String> = installed.packages.keys().cloned().collect();
+    keys.sort();
+
+    for key in keys {
+        let Some(pkg) = installed.packages.get_mut(&key) else {
+            continue;
+        };
+
+        let app_dir = PathBuf::from(&pkg.install_path);
+        let app_dir = if app_dir.exists() {
+            app_dir
+        } else {
+            let local = paths.app_dir(&key);
+            if local.exists() {
+                pkg.install_path = local.to_string_lossy().to_string();
+                report.relocated += 1;
+            }
+            local
+        };
+
+        let pkg_paths = paths.for_package(pkg.bin_dir.as_deref());
+        if let Err(e) = fs::create_dir_all(pkg_paths.bin_dir()) {
+            println!("  {} {}: {}", "✗".red(), key, e);
+            report.failed += pkg.executables.len();
+            continue;
+        }
+
+        let mut executables: Vec<(&String, &String)> = pkg.executables.iter().collect();
+        executables.sort();
+
+        for (exe_relative, command) in executables {
+            let exe_path = app_dir.join(exe_relative);
+            if !exe_path.exists() {
+                println!(
+                    "  {} {}: executable missing: {}",
+                    "✗".red(),
+                    command,
+                    exe_path.display()
+                );
+                report.failed += 1;
+                continue;
+            }
+
+            let shim_path = pkg_paths.bin_shim_path(command);
+            let before = launcher_state(&shim_path);
+
+            let result = match &pkg.source {
+                PackageSource::Script { script_type, .. } if command == &pkg.repo_name => {
+                    remove_launcher(&shim_path).and_then(|_| {
+                        crate::installer::create_script_shim(&pkg_paths, command, script_type)
+                    })
+                }
+                _ => remove_launcher(&shim_path)
+                    .and_then(|_| link_executable(&exe_path, &shim_path, command)),
+            };
+
+            match result {
+                Ok(()) if before.is_some() && before == launcher_state(&shim_path) => {
+                    report.unchanged += 1;
+                }
+                Ok(()) => {
+                    println!("  {} {} -> {}", "✓".green(), command, exe_path.display());
+                    report.recreated += 1;
+                }
+                Err(e) => {
+                    println!("  {} {}: {}", "✗".red(), command, e);
+                    report.failed += 1;
+                }
+            }
+        }
+    }
+
+    report
+}
+
+/// Recreate the launchers of the aliases in config.toml
+fn rebuild_aliases(
+    paths: &WenPaths,
+    installed: &InstalledManifest,
+    aliases: &BTreeMap<String, String>,
+    report: &mut ShimReport,
+) {
+    for (alias, command) in aliases {
+        let before = launcher_state(&paths.bin_shim_path(alias));
+        match create_alias_launcher(paths, installed, alias, command) {
+            Ok(_) if before.is_some() && before == launcher_state(&paths.bin_shim_path(alias)) => {
+                report.unchanged += 1;
+            }
+            Ok(target) => {
+                println!(
+                    "  {} {} -> {} (alias)",
+                    "✓".green(),
+                    alias,
+                    target.display()
+                );
+                report.recreated += 1;
+            }
+            Err(e) => {
+                println!("  {} alias {}: {}", "✗".red(), alias, e);
+                report.failed += 1;
+            }
+        }
+    }
+}
+
+/// Snapshot of a launcher (symlink target or shim content), for change detection
+fn launcher_state(path: &Path) -> Option<String> {
+    if let Ok(target) = fs::read_link(path) {
+        return Some(target.to_string_lossy().to_string());
+    }
+    fs::read_to_string(path).ok()
+}
+
+/// Remove a launcher, including a dangling symlink
+pub(crate) fn remove_launcher(path: &Path) -> Result<()> {
+    if path.exists() || path.is_symlink() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Point a launcher at an executable
+pub(crate) fn link_executable(exe_path: &Path, shim_path: &Path, _command: &str) -> Result<()> {
+    #[cfg(unix)]
+    {
+        crate::installer::create_symlink(exe_path, shim_path)
+    }
+
+    #[cfg(windows)]
+    {
+        crate::installer::create_shim(exe_path, shim_path, _command)
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use super::*;
+    use crate::core::InstalledPackage;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_rebuild_shims_relocates_copied_install() {
+        let temp = TempDir::new().unwrap();
+        let paths = WenPaths::with_root(temp.path().to_path_buf(), false);
+
+        // The app was copied here, but installed.json still points at the old machine
+        let app_dir = paths.app_dir("tool");
+        fs::create_dir_all(&app_dir).unwrap();
+        fs::write(app_dir.join("tool"), "#!/bin/sh\n").unwrap();
+        fs::create_dir_all(paths.bin_dir()).unwrap();
+        std::os::unix::fs::symlink("/old/machine/apps/tool/tool", paths.bin_shim_path("tool"))
+            .unwrap();
+
+        let mut installed = InstalledManifest::new();
+        installed.upsert_package(
+            "tool".to_string(),
+            InstalledPackage {
+                repo_name: "tool".to_string(),
+                variant: None,
+                version: "1.0.0".to_string(),
+                platform: "linux-x86_64".to_string(),
+                installed_at: Utc::now(),
+                install_path: "/old/machine/apps/tool".to_string(),
+                executables: HashMap::from([("tool".to_string(), "tool".to_string())]),
+                source: PackageSource::DirectRepo {
+                    url: "https://github.com/o/tool".to_string(),
+                },
+                description: "Tool".to_string(),
+                command_names: vec![],
+                command_name: None,
+                asset_name: "tool.tar.gz".to_string(),
+                parent_package: None,
+                download_url: None,
+                bin_dir: None,
+                version_constraint: None,
+                compiler: None,
+                checksum: None,
+                files: Vec::new(),
+            },
+        );
+
+        let report = rebuild_shims(&paths, &mut installed);
+        assert_eq!(
+            report,
+            ShimReport {
+                recreated: 1,
+                unchanged: 0,
+                failed: 0,
+                relocated: 1,
+            }
+        );
+        assert_eq!(
+            installed.get_package("tool").unwrap().install_path,
+            app_dir.to_string_lossy()
+        );
+        assert_eq!(
+            fs::read_link(paths.bin_shim_path("tool")).unwrap(),
+            app_dir.join("tool")
+        );
+
+        // A second pass finds everything in place
+        let report = rebuild_shims(&paths, &mut installed);
+        assert_eq!(report.unchanged, 1);
+        assert_eq!(report.recreated, 0);
+
+        // Aliases get their launchers back; ones for missing commands fail
+        let aliases = BTreeMap::from([
+            ("t".to_string(), "tool".to_string()),
+            ("x".to_string(), "missing".to_string()),
+        ]);
+        let mut report = ShimReport::default();
+        rebuild_aliases(&paths, &installed, &aliases, &mut report);
+        assert_eq!((report.recreated, report.failed), (1, 1));
+        assert_eq!(
+            fs::read_link(paths.bin_shim_path("t")).unwrap(),
+            app_dir.join("tool")
+        );
+        let mut report = ShimReport::default();
+        rebuild_aliases(&paths, &installed, &aliases, &mut report);
+        assert_eq!(report.unchanged, 1);
+    }
+}
@@ -0,0 +1,313 @@
+//! Rollback command implementation
+//!
+//! Switches a package back to a previous version kept because of the
+//! `keep_versions` setting (see [`crate::installer::versions`]). The version
+//! being replaced is kept in turn, so a rollback can be undone with
+//! `wenget rollback <name> --to <version>`.
+//!
+//! Without kept versions, the package's rollback snapshot (the version it
+//! replaced last) is restored instead, and the snapshot is deleted.
+
+use crate::commands::repair::{link_executable, remove_launcher};
+use crate::core::manifest::PackageSource;
+use crate::core::{Config, InstalledPackage, WenPaths};
+use crate::installer::versions::{self, KeptVersion};
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::Path;
+
+/// Switch an installed package to a previous version
+pub fn run(name: String, to: Option<String>, config: &Config) -> Result<()> {
+    let paths = config.paths();
+    let mut installed = config.load_installed()?;
+
+    let current = installed
+        .get_package(&name)
+        .cloned()
+        .with_context(|| format!("Package '{}' is not installed", name))?;
+    if matches!(current.source, PackageSource::Script { .. }) {
+        anyhow::bail!(
+            "'{}' is a script; scripts have no versions to roll back to",
+            name
+        );
+    }
+
+    let kept = versions::kept_versions(paths, &name)?;
+    let snapshot = versions::rollback_snapshot(paths, &name)?.filter(|snap| {
+        kept.is_empty()
+            && snap.package.version != current.version
+            && to.as_deref().is_none_or(|v| v == snap.package.version)
+    });
+    let restored = match snapshot {
+        Some(snap) => restore_snapshot(paths, &name, &current, snap)?,
+        None => {
+            let target = pick_version(&name, &current, kept, to.as_deref())?;
+            rollback(paths, &name, &current, target)?
+        }
+    };
+
+    println!(
+        "{} Rolled back {} from {} to {}",
+        "✓".green(),
+        name.green().bold(),
+        current.version.yellow(),
+        restored.version.green()
+    );
+    installed.upsert_package(name, restored);
+    config.save_installed(&installed)
+}
+
+/// Kept version to roll back to: `to`, or the last one installed before the current one
+fn pick_version(
+    name: &str,
+    current: &InstalledPackage,
+    kept: Vec<KeptVersion>,
+    to: Option<&str>,
+) -> Result<KeptVersion> {
+    if kept.is_empty() {
+        anyhow::bail!(
+            "No previous version of '{}' is kept. It is kept when the package is updated; set keep_versions in config.toml (wenget config) to keep more than one",
+            name
+        );
+    }
+    let available = kept
+        .iter()
+        .map(|k| k.package.version.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let found = match to {
+        Some(version) if version == current.version => {
+            anyhow::bail!("{} {} is already installed", name, version)
+        }
+        Some(version) => kept.into_iter().find(|k| k.package.version == version),
+        None => kept
+            .into_iter()
+            .find(|k| k.package.installed_at < current.installed_at),
+    };
+    found.with_context(|| match to {
+        Some(version) => format!(
+            "Version {} of '{}' is not kept (kept: {})",
+            version, name, available
+        ),
+        None => format!(
+            "No version of '{}' older than {} is kept; pick one with --to (kept: {})",
+            name, current.version, available
+        ),
+    })
+}
+
+/// Swap the kept version into the app directory and relink its commands
+///
+/// Returns the installed.json record of the restored version.
+fn rollback(
+    paths: &WenPaths,
+    name: &str,
+    current: &InstalledPackage,
+    target: KeptVersion,
+) -> Result<InstalledPackage> {
+    let app_dir = versions::installed_dir(paths, name, current);
+    versions::keep(paths, name, current)?;
+    fs::rename(&target.dir, &app_dir).with_context(|| {
+        format!(
+            "Failed to move {} to {}",
+            target.dir.display(),
+            app_dir.display()
+        )
+    })?;
+    versions::forget(&target)?;
+    relink(paths, current, target.package, &app_dir)
+}
+
+/// Restore the rollback snapshot into the app directory and delete the snapshot
+///
+/// The current version is discarded. Returns the installed.json record of the
+/// restored version.
+fn restore_snapshot(
+    paths: &WenPaths,
+    name: &str,
+    current: &InstalledPackage,
+    snapshot: KeptVersion,
+) -> Result<InstalledPackage> {
+    let app_dir = versions::installed_dir(paths, name, current);
+
+    // Park the current version next to the snapshot until the swap succeeded
+    let parked = paths.rollback_snapshot_dir(name).join("replaced");
+    if app_dir.exists() {
+        fs::rename(&app_dir, &parked)
+            .with_context(|| format!("Failed to move {}", app_dir.display()))?;
+    }
+    if let Err(e) = fs::rename(&snapshot.dir, &app_dir) {
+        if parked.exists() {
+            fs::rename(&parked, &app_dir).ok();
+        }
+        return Err(e).with_context(|| {
+            format!(
+                "Failed to move {} to {}",
+                snapshot.dir.display(),
+                app_dir.display()
+            )
+        });
+    }
+    versions::clear_snapshot(paths, name)?;
+    relink(paths, current, snapshot.package, &app_dir)
+}
+
+/// Point the launchers at the restored version in `app_dir`
+///
+/// Returns its installed.json record, keeping the user's settings from `current`.
+fn relink(
+    paths: &WenPaths,
+    current: &InstalledPackage,
+    mut restored: InstalledPackage,
+    app_dir: &Path,
+) -> Result<InstalledPackage> {
+    // Launcher location and version pin are the user's, not the version's
+    restored.install_path = app_dir.to_string_lossy().to_string();
+    restored.bin_dir = current.bin_dir.clone();
+    restored.version_constraint = current.version_constraint.clone();
+
+    let pkg_paths = paths.for_package(restored.bin_dir.as_deref());
+    let restored_commands = restored.get_command_names();
+    for command in current.get_command_names() {
+        if !restored_commands.contains(&command) {
+            remove_launcher(&pkg_paths.bin_shim_path(command))?;
+        }
+    }
+    fs::create_dir_all(pkg_paths.bin_dir())?;
+    for (exe_relative, command) in &restored.executables {
+        let shim_path = pkg_paths.bin_shim_path(command);
+        remove_launcher(&shim_path)?;
+        link_executable(&app_dir.join(exe_relative), &shim_path, command)
+            .with_context(|| format!("Failed to link '{}'", command))?;
+    }
+
+    Ok(restored)
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn install(paths: &WenPaths, version: &str, age_days: i64) -> InstalledPackage {
+        let app_dir = paths.app_dir("tool");
+        fs::create_dir_all(&app_dir).unwrap();
+        fs::write(app_dir.join("tool"), version).unwrap();
+        InstalledPackage {
+            repo_name: "tool".to_string(),
+            variant: None,
+            version: version.to_string(),
+            platform: "linux-x86_64".to_string(),
+            installed_at: Utc::now() - Duration::days(age_days),
+            install_path: app_dir.to_string_lossy().to_string(),
+            executables: HashMap::from([("tool".to_string(), "tool".to_string())]),
+            source: PackageSource::Bucket {
+                name: "main".to_string(),
+            },
+            description: String::new(),
+            command_names: vec![],
+            command_name: None,
+            asset_name: format!("tool-{}.tar.gz", version),
+            parent_package: None,
+            download_url: None,
+            bin_dir: None,
+            version_constraint: None,
+            compiler: None,
+            checksum: None,
+            files: Vec::new(),
+        }
+    }
+
+    /// Install 1.0.0, 1.1.0 and 1.2.0, keeping the first two
+    fn installed_with_history(paths: &WenPaths) -> InstalledPackage {
+        for (version, age) in [("1.0.0", 3), ("1.1.0", 2)] {
+            let pkg = install(paths, version, age);
+            versions::keep(paths, "tool", &pkg).unwrap();
+        }
+        install(paths, "1.2.0", 1)
+    }
+
+    #[test]
+    fn test_pick_version() {
+        let temp = TempDir::new().unwrap();
+        let paths = WenPaths::with_root(temp.path().to_path_buf(), false);
+        let current = installed_with_history(&paths);
+        let kept = || versions::kept_versions(&paths, "tool").unwrap();
+
+        let prior = pick_version("tool", &current, kept(), None).unwrap();
+        assert_eq!(prior.package.version, "1.1.0");
+        let chosen = pick_version("tool", &current, kept(), Some("1.0.0")).unwrap();
+        assert_eq!(chosen.package.version, "1.0.0");
+
+        assert!(pick_version("tool", &current, kept(), Some("0.9.0")).is_err());
+        assert!(pick_version("tool", &current, kept(), Some("1.2.0")).is_err());
+        assert!(pick_version("tool", &current, Vec::new(), None).is_err());
+    }
+
+    #[test]
+    fn test_rollback_swaps_versions() {
+        let temp = TempDir::new().unwrap();
+        let paths = WenPaths::with_root(temp.path().to_path_buf(), false);
+        let current = installed_with_history(&paths);
+        fs::create_dir_all(paths.bin_dir()).unwrap();
+
+        let target = pick_version(
+            "tool",
+            &current,
+            versions::kept_versions(&paths, "tool").unwrap(),
+            None,
+        )
+        .unwrap();
+        let restored = rollback(&paths, "tool", &current, target).unwrap();
+
+        let app_dir = paths.app_dir("tool");
+        assert_eq!(restored.version, "1.1.0");
+        assert_eq!(restored.install_path, app_dir.to_string_lossy());
+        assert_eq!(fs::read_to_string(app_dir.join("tool")).unwrap(), "1.1.0");
+        assert_eq!(
+            fs::read_link(paths.bin_shim_path("tool")).unwrap(),
+            app_dir.join("tool")
+        );
+
+        // The replaced version is kept, the restored one no longer is
+        let mut kept: Vec<_> = versions::kept_versions(&paths, "tool")
+            .unwrap()
+            .into_iter()
+            .map(|k| k.package.version)
+            .collect();
+        kept.sort();
+        assert_eq!(kept, ["1.0.0", "1.2.0"]);
+    }
+
+    #[test]
+    fn test_restore_snapshot() {
+        let temp = TempDir::new().unwrap();
+        let paths = WenPaths::with_root(temp.path().to_path_buf(), false);
+        let old = install(&paths, "1.0.0", 2);
+        assert!(versions::snapshot(&paths, "tool", &old).unwrap());
+        let current = install(&paths, "1.1.0", 1);
+        fs::create_dir_all(paths.bin_dir()).unwrap();
+
+        let snapshot = versions::rollback_snapshot(&paths, "tool")
+            .unwrap()
+            .unwrap();
+        let restored = restore_snapshot(&paths, "tool", &current, snapshot).unwrap();
+
+        let app_dir = paths.app_dir("tool");
+        assert_eq!(restored.version, "1.0.0");
+        assert_eq!(fs::read_to_string(app_dir.join("tool")).unwrap(), "1.0.0");
+        assert_eq!(
+            fs::read_link(paths.bin_shim_path("tool")).unwrap(),
+            app_dir.join("tool")
+        );
+        assert!(versions::rollback_snapshot(&paths, "tool")
+            .unwrap()
+            .is_none());
+        assert!(!paths.rollback_snapshot_dir("tool").exists());
+    }
+}
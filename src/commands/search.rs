@@ -1,17 +1,66 @@
 //! Search command implementation
+//!
+//! Plain searches match glob patterns against package names and aliases.
+//! `--interactive` ranks every cached package and script against a free-text
+//! query and lets the user narrow them down in a fuzzy picker, then shows info
+//! for the pick or installs it.
 
-use crate::core::{Config, Platform};
-use anyhow::Result;
+use crate::cache::{CachedPackage, CachedScript, ManifestCache};
+use crate::core::events::TerminalSink;
+use crate::core::lock::StateLock;
+use crate::core::manifest::PackageSource;
+use crate::core::{Config, InstalledManifest, Platform};
+use crate::package_resolver::{from_bucket, split_bucket_qualifier};
+use crate::utils::network::NetworkOverrides;
+use anyhow::{Context, Result};
 use colored::Colorize;
 use glob::Pattern;
+use serde::Serialize;
+use std::io::IsTerminal;
+
+/// A single search hit, as emitted by `wenget search --json`
+#[derive(Debug, Serialize)]
+struct SearchResult<'a> {
+    /// "package" or "script"
+    kind: &'static str,
+    name: &'a str,
+    description: &'a str,
+    repo: &'a str,
+    source: &'a PackageSource,
+    installed: bool,
+}
 
 /// Search for packages and scripts
-pub fn run(patterns: Vec<String>) -> Result<()> {
-    let config = Config::new()?;
+pub fn run(
+    patterns: Vec<String>,
+    json: bool,
+    limit: Option<usize>,
+    interactive: bool,
+    network: NetworkOverrides,
+) -> Result<()> {
+    let config = Config::with_network(network)?;
 
     // Load cache
     let cache = config.get_or_rebuild_cache()?;
 
+    // The picker needs a terminal; otherwise print the plain results
+    let mut patterns = patterns;
+    if interactive {
+        if std::io::stdin().is_terminal() && std::io::stdout().is_terminal() {
+            return run_interactive(&config, &cache, &patterns, network);
+        }
+        log::info!("Not a terminal, printing plain search results");
+        if patterns.is_empty() {
+            patterns.push("*".to_string());
+        }
+    }
+
+    if json {
+        // Machine-readable output never prints hints, only the (possibly empty) result list
+        let installed = config.get_or_create_installed()?;
+        return print_json(&cache, &patterns, &installed, limit);
+    }
+
     if cache.packages.is_empty() && cache.scripts.is_empty() {
         println!("{}", "No packages or scripts in sources".yellow());
         println!("Add buckets with: wenget bucket add <name> <url>");
@@ -24,51 +73,9 @@ pub fn run(patterns: Vec<String>) -> Result<()> {
         return Ok(());
     }
 
-    // Get current platform
-    let platform = Platform::current();
-    let platform_ids = platform.possible_identifiers();
-
-    // Compile glob patterns
-    let glob_patterns: Vec<Pattern> = patterns
-        .iter()
-        .map(|p| Pattern::new(p))
-        .collect::<Result<_, _>>()?;
-
-    // Filter packages
-    let matching_packages: Vec<_> = cache
-        .packages
-        .values()
-        .filter(|cached_pkg| {
-            let pkg = &cached_pkg.package;
-            // Check if name matches any pattern
-            let name_matches = glob_patterns
-                .iter()
-                .any(|pattern| pattern.matches(&pkg.name));
-
-            // Check if supports current platform
-            let platform_matches = platform_ids.iter().any(|id| pkg.platforms.contains_key(id));
-
-            name_matches && platform_matches
-        })
-        .collect();
-
-    // Filter scripts
-    let matching_scripts: Vec<_> = cache
-        .scripts
-        .values()
-        .filter(|cached_script| {
-            let script = &cached_script.script;
-            // Check if name matches any pattern
-            let name_matches = glob_patterns
-                .iter()
-                .any(|pattern| pattern.matches(&script.name));
-
-            // Check if supports current platform
-            let platform_matches = script.is_compatible_with_current_platform();
-
-            name_matches && platform_matches
-        })
-        .collect();
+    let platform_ids = Platform::current().possible_identifiers();
+    let (matching_packages, matching_scripts) =
+        find_matches(&cache, &patterns, &platform_ids, limit)?;
 
     if matching_packages.is_empty() && matching_scripts.is_empty() {
         println!(
@@ -152,6 +159,283 @@ pub fn run(patterns: Vec<String>) -> Result<()> {
     Ok(())
 }
 
+/// Collect packages and scripts matching any of the glob patterns.
+///
+/// A `bucket/pattern` only matches entries of that bucket. Package aliases are matched like names. Results are ranked so that exact
+/// name (or alias) matches come first, followed by the
+/// remaining matches in alphabetical order. `limit` caps the combined number
+/// of results, packages taking precedence over scripts.
+fn find_matches<'a>(
+    cache: &'a ManifestCache,
+    patterns: &[String],
+    platform_ids: &[String],
+    limit: Option<usize>,
+) -> Result<(Vec<&'a CachedPackage>, Vec<&'a CachedScript>)> {
+    // Compile glob patterns; "bucket/pattern" only matches entries of that bucket
+    let glob_patterns: Vec<(Option<&str>, Pattern)> = patterns
+        .iter()
+        .map(|p| {
+            let (bucket, name) = match split_bucket_qualifier(p) {
+                Some((bucket, name)) => (Some(bucket), name),
+                None => (None, p.as_str()),
+            };
+            Pattern::new(name).map(|pattern| (bucket, pattern))
+        })
+        .collect::<Result<_, _>>()?;
+
+    let is_exact = |name: &str| glob_patterns.iter().any(|(_, p)| p.as_str() == name);
+
+    // Filter packages
+    let mut matching_packages: Vec<_> = cache
+        .packages
+        .values()
+        .filter(|cached_pkg| {
+            let pkg = &cached_pkg.package;
+            // Check if name matches any pattern
+            let name_matches = glob_patterns.iter().any(|(bucket, pattern)| {
+                from_bucket(&cached_pkg.source, *bucket)
+                    && pkg.names().any(|name| pattern.matches(name))
+            });
+
+            // Check if supports current platform
+            let platform_matches = platform_ids.iter().any(|id| pkg.platforms.contains_key(id));
+
+            name_matches && platform_matches
+        })
+        .collect();
+    matching_packages.sort_by_key(|p| {
+        (
+            !p.package.names().any(is_exact),
+            p.package.name.to_lowercase(),
+        )
+    });
+
+    // Filter scripts
+    let mut matching_scripts: Vec<_> = cache
+        .scripts
+        .values()
+        .filter(|cached_script| {
+            let script = &cached_script.script;
+            // Check if name matches any pattern
+            let name_matches = glob_patterns.iter().any(|(bucket, pattern)| {
+                from_bucket(&cached_script.source, *bucket) && pattern.matches(&script.name)
+            });
+
+            // Check if supports current platform
+            let platform_matches = script.is_compatible_with_current_platform();
+
+            name_matches && platform_matches
+        })
+        .collect();
+    matching_scripts.sort_by_key(|s| (!is_exact(&s.script.name), s.script.name.to_lowercase()));
+
+    if let Some(limit) = limit {
+        matching_packages.truncate(limit);
+        matching_scripts.truncate(limit - matching_packages.len());
+    }
+
+    Ok((matching_packages, matching_scripts))
+}
+
+/// Print search results as a JSON array
+fn print_json(
+    cache: &ManifestCache,
+    patterns: &[String],
+    installed: &InstalledManifest,
+    limit: Option<usize>,
+) -> Result<()> {
+    let platform_ids = Platform::current().possible_identifiers();
+    let (packages, scripts) = find_matches(cache, patterns, &platform_ids, limit)?;
+
+    let is_installed =
+        |name: &str| installed.is_installed(name) || !installed.find_by_repo(name).is_empty();
+
+    let results: Vec<SearchResult> = packages
+        .iter()
+        .map(|cached| SearchResult {
+            kind: "package",
+            name: &cached.package.name,
+            description: &cached.package.description,
+            repo: &cached.package.repo,
+            source: &cached.source,
+            installed: is_installed(&cached.package.name),
+        })
+        .chain(scripts.iter().map(|cached| SearchResult {
+            kind: "script",
+            name: &cached.script.name,
+            description: &cached.script.description,
+            repo: &cached.script.repo,
+            source: &cached.source,
+            installed: is_installed(&cached.script.name),
+        }))
+        .collect();
+
+    let output =
+        serde_json::to_string_pretty(&results).context("Failed to serialize search results")?;
+    println!("{}", output);
+
+    Ok(())
+}
+
+/// A package or script offered by the interactive picker
+#[derive(Debug, PartialEq)]
+struct Candidate {
+    /// Input for `wenget add` / `wenget info`, bucket-qualified for bucket entries
+    input: String,
+    /// Line shown in the picker
+    label: String,
+    score: u32,
+}
+
+/// Score an entry against a free-text query; 0 means no match
+///
+/// Each query word scores for the best matching name or alias: equal (400),
+/// prefix (300), substring (200) or letters in order (50, `rg` in `ripgrep`).
+/// A description containing the word adds 100. Every word has to match.
+fn rank(query: &str, names: &[&str], description: &str) -> u32 {
+    let description = description.to_lowercase();
+    let mut total = 0;
+    for word in query.split_whitespace() {
+        let word = word.trim_matches('*').to_lowercase();
+        if word.is_empty() {
+            continue;
+        }
+        let name_score = names
+            .iter()
+            .map(|name| {
+                let name = name.to_lowercase();
+                if name == word {
+                    400
+                } else if name.starts_with(&word) {
+                    300
+                } else if name.contains(&word) {
+                    200
+                } else if is_subsequence(&word, &name) {
+                    50
+                } else {
+                    0
+                }
+            })
+            .max()
+            .unwrap_or(0);
+        let description_score = if description.contains(&word) { 100 } else { 0 };
+        if name_score + description_score == 0 {
+            return 0;
+        }
+        total += name_score + description_score;
+    }
+    total.max(1)
+}
+
+/// Whether the characters of `needle` appear in `haystack` in order
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut chars = haystack.chars();
+    needle.chars().all(|c| chars.any(|h| h == c))
+}
+
+/// Cached packages and scripts for this platform matching `query`, best first
+fn candidates(cache: &ManifestCache, query: &str, platform_ids: &[String]) -> Vec<Candidate> {
+    let input = |name: &str, source: &PackageSource| match source {
+        PackageSource::Bucket { name: bucket } => format!("{}/{}", bucket, name),
+        _ => name.to_string(),
+    };
+
+    let packages = cache
+        .packages
+        .values()
+        .filter(|cached| {
+            platform_ids
+                .iter()
+                .any(|id| cached.package.platforms.contains_key(id))
+        })
+        .map(|cached| {
+            let pkg = &cached.package;
+            let names: Vec<&str> = pkg.names().collect();
+            Candidate {
+                input: input(&pkg.name, &cached.source),
+                label: format!("{:<24} {}", pkg.name, truncate(&pkg.description, 60)),
+                score: rank(query, &names, &pkg.description),
+            }
+        });
+    let scripts = cache
+        .scripts
+        .values()
+        .filter(|cached| cached.script.is_compatible_with_current_platform())
+        .map(|cached| {
+            let script = &cached.script;
+            Candidate {
+                input: input(&script.name, &cached.source),
+                label: format!(
+                    "{:<24} [script] {}",
+                    script.name,
+                    truncate(&script.description, 51)
+                ),
+                score: rank(query, &[&script.name], &script.description),
+            }
+        });
+
+    let mut candidates: Vec<Candidate> = packages.chain(scripts).filter(|c| c.score > 0).collect();
+    candidates.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.label.cmp(&b.label)));
+    candidates
+}
+
+/// Pick a package in a fuzzy picker, then show its info or install it
+fn run_interactive(
+    config: &Config,
+    cache: &ManifestCache,
+    patterns: &[String],
+    network: NetworkOverrides,
+) -> Result<()> {
+    let query = patterns.join(" ");
+    let platform_ids = Platform::current().possible_identifiers();
+    let candidates = candidates(cache, &query, &platform_ids);
+    if candidates.is_empty() {
+        println!(
+            "{}",
+            format!("No packages or scripts found matching: {:?}", query).yellow()
+        );
+        return Ok(());
+    }
+
+    let labels: Vec<&str> = candidates.iter().map(|c| c.label.as_str()).collect();
+    let Some(selection) = dialoguer::FuzzySelect::new()
+        .with_prompt("Search (type to filter, Esc to quit)")
+        .items(&labels)
+        .default(0)
+        .max_length(15)
+        .interact_opt()
+        .context("Failed to get user selection")?
+    else {
+        return Ok(());
+    };
+    let input = candidates[selection].input.clone();
+
+    let action = dialoguer::Select::new()
+        .with_prompt(input.as_str())
+        .items(&["Show info", "Install", "Cancel"])
+        .default(0)
+        .interact()
+        .context("Failed to get user selection")?;
+    match action {
+        0 => super::info::run(vec![input], false, false, network),
+        1 => {
+            // Installing changes state, so it takes the lock other mutating commands hold
+            let _lock = StateLock::acquire(&config.paths().lock_file(), false)?;
+            let outcome = super::add::run(
+                vec![input.clone()],
+                super::add::AddOptions::default(),
+                network,
+                &TerminalSink,
+            )?;
+            if outcome.failed > 0 {
+                anyhow::bail!("Failed to install {}", input);
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
 /// Truncate string to max length
 fn truncate(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
@@ -160,3 +444,156 @@ fn truncate(s: &str, max_len: usize) -> String {
         format!("{}...", &s[..max_len - 3])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::manifest::{Package, PlatformBinary};
+    use std::collections::HashMap;
+
+    fn cached(name: &str, platform_id: &str) -> CachedPackage {
+        let mut platforms = HashMap::new();
+        platforms.insert(
+            platform_id.to_string(),
+            vec![PlatformBinary {
+                url: format!("https://example.com/{}.tar.gz", name),
+                size: 1000,
+                checksum: None,
+                asset_name: format!("{}.tar.gz", name),
+            }],
+        );
+        CachedPackage {
+            package: Package {
+                name: name.to_string(),
+                aliases: Vec::new(),
+                description: String::new(),
+                repo: format!("https://github.com/test/{}", name),
+                homepage: None,
+                license: None,
+                version: None,
+                platforms,
+                pre_install: Vec::new(),
+                post_install: Vec::new(),
+            },
+            source: PackageSource::Bucket {
+                name: "test".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_find_matches_ranking_and_limit() {
+        let platform_id = "linux-x86_64".to_string();
+        let mut cache = ManifestCache::new();
+        for name in ["ripgrep-all", "rg", "ripgrep", "bat"] {
+            cache
+                .packages
+                .insert(name.to_string(), cached(name, &platform_id));
+        }
+
+        let patterns = vec!["ripgrep".to_string(), "r*".to_string()];
+        let ids = vec![platform_id];
+
+        let (packages, scripts) = find_matches(&cache, &patterns, &ids, None).unwrap();
+        let names: Vec<_> = packages.iter().map(|p| p.package.name.as_str()).collect();
+        // Exact match first, then the rest alphabetically
+        assert_eq!(names, vec!["ripgrep", "rg", "ripgrep-all"]);
+        assert!(scripts.is_empty());
+
+        let (packages, _) = find_matches(&cache, &patterns, &ids, Some(2)).unwrap();
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[0].package.name, "ripgrep");
+    }
+
+    #[test]
+    fn test_interactive_ranking() {
+        let platform_id = "linux-x86_64".to_string();
+        let mut cache = ManifestCache::new();
+        for (name, description) in [
+            (
+                "ripgrep",
+                "Recursively search directories for a regex pattern",
+            ),
+            ("rga", "ripgrep, but also search in PDFs and archives"),
+            ("bat", "A cat clone with wings"),
+            ("fd", "A simple, fast and user-friendly alternative to find"),
+        ] {
+            let mut pkg = cached(name, &platform_id);
+            pkg.package.description = description.to_string();
+            cache.packages.insert(name.to_string(), pkg);
+        }
+        cache.packages.get_mut("ripgrep").unwrap().package.aliases = vec!["rg".to_string()];
+        let ids = vec![platform_id];
+
+        // The alias brings ripgrep to the top, rga's name starts with "rg"
+        let found = candidates(&cache, "rg", &ids);
+        let inputs: Vec<&str> = found.iter().map(|c| c.input.as_str()).collect();
+        assert_eq!(inputs, ["test/ripgrep", "test/rga"]);
+
+        // Descriptions count too
+        let found = candidates(&cache, "search", &ids);
+        let inputs: Vec<&str> = found.iter().map(|c| c.input.as_str()).collect();
+        assert_eq!(inputs, ["test/rga", "test/ripgrep"]);
+
+        // An empty query lists everything alphabetically
+        assert_eq!(candidates(&cache, "", &ids).len(), 4);
+        assert_eq!(candidates(&cache, "", &ids)[0].input, "test/bat");
+
+        assert_eq!(rank("rg", &["ripgrep"], ""), 50);
+        assert_eq!(rank("rip grep", &["ripgrep"], ""), 500);
+        assert_eq!(rank("rg zzz", &["ripgrep"], ""), 0);
+    }
+
+    #[test]
+    fn test_find_matches_aliases() {
+        let platform_id = "linux-x86_64".to_string();
+        let mut cache = ManifestCache::new();
+        let mut fd = cached("fd", &platform_id);
+        fd.package.aliases = vec!["fd-find".to_string()];
+        cache.packages.insert("fd".to_string(), fd);
+        cache
+            .packages
+            .insert("fdupes".to_string(), cached("fdupes", &platform_id));
+
+        let ids = vec![platform_id];
+        let (packages, _) = find_matches(&cache, &["fd-find".to_string()], &ids, None).unwrap();
+        assert_eq!(packages.len(), 1);
+        assert_eq!(packages[0].package.name, "fd");
+
+        // Glob patterns match aliases too
+        let patterns = vec!["fd-*".to_string(), "fdupes".to_string()];
+        let (packages, _) = find_matches(&cache, &patterns, &ids, None).unwrap();
+        let names: Vec<_> = packages.iter().map(|p| p.package.name.as_str()).collect();
+        assert_eq!(names, vec!["fdupes", "fd"]);
+    }
+
+    #[test]
+    fn test_find_matches_bucket_qualified() {
+        let platform_id = "linux-x86_64".to_string();
+        let mut cache = ManifestCache::new();
+        let mut extra_fd = cached("fd", &platform_id);
+        extra_fd.source = PackageSource::Bucket {
+            name: "extra".to_string(),
+        };
+        cache.packages.insert("extra-fd".to_string(), extra_fd);
+        cache
+            .packages
+            .insert("fd".to_string(), cached("fd", &platform_id));
+
+        let ids = vec![platform_id];
+        let (packages, _) = find_matches(&cache, &["fd".to_string()], &ids, None).unwrap();
+        assert_eq!(packages.len(), 2);
+
+        let (packages, _) = find_matches(&cache, &["extra/f*".to_string()], &ids, None).unwrap();
+        assert_eq!(packages.len(), 1);
+        assert_eq!(
+            packages[0].source,
+            PackageSource::Bucket {
+                name: "extra".to_string()
+            }
+        );
+
+        let (packages, _) = find_matches(&cache, &["nope/fd".to_string()], &ids, None).unwrap();
+        assert!(packages.is_empty());
+    }
+}
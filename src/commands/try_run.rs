@@ -0,0 +1,181 @@
+//! Try command implementation
+//!
+//! Runs a package once without installing it: the release asset is downloaded
+//! and extracted into a temporary directory, the executable is run with the
+//! given arguments and the directory is removed afterwards. No launcher is
+//! created and `installed.json` is not touched.
+
+use crate::commands::outcome::EXIT_FAILURE;
+use crate::core::{Config, Platform};
+use crate::downloader;
+use crate::installer::{extract_archive, find_executable};
+use crate::package_resolver::{PackageInput, PackageResolver};
+use crate::providers::GitHubProvider;
+use crate::utils::network::NetworkOverrides;
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Run a package from a temporary directory and return its exit code
+pub fn run(name: String, args: Vec<String>, network: NetworkOverrides) -> Result<i32> {
+    let config = Config::with_network(network)?;
+    let cache = config.get_or_rebuild_cache()?;
+    let resolver = PackageResolver::new(&config, &cache)?;
+
+    if name.contains('*') {
+        bail!("'{}': wildcards are not supported by try", name);
+    }
+    let mut resolved = resolver.resolve(&PackageInput::parse(&name))?;
+    if resolved.len() > 1 {
+        let names: Vec<_> = resolved.iter().map(|r| r.package.repo.as_str()).collect();
+        bail!(
+            "'{}' matches several packages ({}); qualify it as <bucket>/{}",
+            name,
+            names.join(", "),
+            name
+        );
+    }
+    let package = resolved.remove(0).package;
+
+    let platform = Platform::current();
+    let matches = match config.preferences().preferred_platform.as_deref() {
+        Some(preferred) => Platform::match_override(preferred, &package.platforms),
+        None => platform.find_best_match(&package.platforms),
+    };
+    let Some(platform_match) = matches.into_iter().next() else {
+        let hint = GitHubProvider::from_config(&config)
+            .ok()
+            .and_then(|github| github.installer_hint(&package.repo, platform));
+        match hint {
+            Some(hint) => bail!("{} has no build to run: {}", package.name, hint),
+            None => bail!("{} does not support platform {}", package.name, platform),
+        }
+    };
+    let binary = package.platforms[&platform_match.platform_id]
+        .first()
+        .with_context(|| {
+            format!(
+                "{} has no {} build",
+                package.name, platform_match.platform_id
+            )
+        })?;
+
+    let temp = TempDir::create(&package.name)?;
+
+    // Status goes to stderr so the tool's stdout stays clean for pipes
+    eprintln!(
+        "{} {} ({})...",
+        "Downloading".cyan(),
+        package.name,
+        binary.asset_name
+    );
+    let filename = binary
+        .url
+        .split('/')
+        .next_back()
+        .context("Invalid download URL")?;
+    let download_path = temp.path().join(filename);
+    downloader::download_file_cached(
+        &binary.url,
+        &download_path,
+        binary.size,
+        binary.checksum.as_deref(),
+        config.network(),
+    )?;
+
+    let extract_dir = temp.path().join("app");
+    let files = extract_archive(&download_path, &extract_dir, false)?;
+    let executable = find_executable(&files, &package.name)
+        .with_context(|| format!("No executable found in {}", binary.asset_name))?;
+    let executable = extract_dir.join(executable);
+
+    eprintln!("{} {}", "Running".cyan(), executable.display());
+
+    // The child inherits stdin/stdout/stderr, so its output streams through
+    let status = Command::new(&executable)
+        .args(&args)
+        .status()
+        .with_context(|| format!("Failed to run {}", executable.display()))?;
+
+    Ok(exit_code(status))
+}
+
+/// Exit code to propagate for a finished child process
+///
+/// A child killed by a signal has no code; it is reported like a shell would,
+/// as 128 + the signal number on Unix.
+fn exit_code(status: std::process::ExitStatus) -> i32 {
+    if let Some(code) = status.code() {
+        return code;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return 128 + signal;
+        }
+    }
+    EXIT_FAILURE
+}
+
+/// Temporary directory removed when dropped
+struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// Create a fresh directory for one try run of `name`
+    fn create(name: &str) -> Result<Self> {
+        let path = std::env::temp_dir().join(format!("wenget-try-{}-{}", name, std::process::id()));
+        if path.exists() {
+            fs::remove_dir_all(&path)
+                .with_context(|| format!("Failed to clean up {}", path.display()))?;
+        }
+        fs::create_dir_all(&path)
+            .with_context(|| format!("Failed to create directory: {}", path.display()))?;
+        Ok(Self { path })
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_dir_all(&self.path) {
+            log::warn!("Failed to remove {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_temp_dir_removed_on_drop() {
+        let temp = TempDir::create("wenget-test-tool").unwrap();
+        let path = temp.path().to_path_buf();
+        fs::write(path.join("file"), "x").unwrap();
+        assert!(path.is_dir());
+
+        drop(temp);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_exit_code_propagates() {
+        let status = Command::new("sh").args(["-c", "exit 7"]).status().unwrap();
+        assert_eq!(exit_code(status), 7);
+
+        let status = Command::new("sh")
+            .args(["-c", "kill -TERM $$"])
+            .status()
+            .unwrap();
+        assert_eq!(exit_code(status), 128 + 15);
+    }
+}
@@ -1,19 +1,26 @@
 //! Update (Upgrade) command implementation
 
-use crate::commands::add;
-use crate::core::manifest::PackageSource;
-use crate::core::{Config, Package};
+use crate::commands::{add, Outcome};
+use crate::core::events::ProgressSink;
+use crate::core::history::{self, Action};
+use crate::core::manifest::{PackageSource, ScriptType};
+use crate::core::version::parse_constraint;
+use crate::core::{Config, InstalledPackage, Package};
+use crate::installer::{download_script, read_local_script, script_checksum};
 use crate::providers::base::SourceProvider;
 use crate::providers::GitHubProvider;
-use anyhow::Result;
+use crate::utils::network::{NetworkOverrides, NetworkSettings};
+use anyhow::{bail, Context, Result};
 use colored::Colorize;
 use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Mutex;
 
 /// Maximum number of concurrent GitHub API requests when checking for updates.
 /// Capped to avoid hitting the unauthenticated rate limit (60 req/hour) too quickly.
-const MAX_CONCURRENT_FETCHES: usize = 8;
+pub(crate) const MAX_CONCURRENT_FETCHES: usize = 8;
 
 /// A parallel fetch outcome: the repo name paired with its fetched package (or error).
 type FetchResult = (String, Result<Package>);
@@ -111,20 +118,296 @@ fn is_newer_version(old: &str, new: &str) -> bool {
     false
 }
 
+/// Result of checking a single package for updates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateStatus {
+    /// The installed version is the newest available
+    UpToDate { version: String },
+    /// A newer release is available
+    Update { from: String, to: String },
+    /// No installed package or variant matches the name
+    NotInstalled,
+    /// The latest version could not be determined (rate limit, network, unversioned script)
+    Unknown { reason: String },
+}
+
+impl UpdateStatus {
+    /// Process exit code reported by `wenget update --check`
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            UpdateStatus::UpToDate { .. } => 0,
+            UpdateStatus::Update { .. } => 10,
+            UpdateStatus::NotInstalled => 2,
+            UpdateStatus::Unknown { .. } => 3,
+        }
+    }
+}
+
+/// Check whether an update is available for one installed package
+///
+/// Resolves the repo from installed.json and the existing cache (no bucket refresh)
+/// and makes a single GitHub call for the latest version. Range-pinned packages are
+/// checked against their range.
+pub fn check_one(name: &str, pre: bool, network: NetworkOverrides) -> Result<UpdateStatus> {
+    let config = Config::with_network(network)?.with_prereleases(pre);
+    let installed = config.get_or_create_installed()?;
+    let cache = config.get_or_rebuild_cache()?;
+    let github = GitHubProvider::from_config(&config)?;
+
+    Ok(check_package(
+        &installed,
+        &cache,
+        name,
+        |repo_url, req| match req {
+            Some(req) => github.fetch_matching_version(repo_url, req),
+            None => github.fetch_latest_version(repo_url).map(Some),
+        },
+    ))
+}
+
+/// Core of [`check_one`], with the GitHub lookup injected
+///
+/// `latest` returns the newest release of a repo, restricted to the version range
+/// when one is given.
+pub(crate) fn check_package<F>(
+    installed: &crate::core::InstalledManifest,
+    cache: &crate::cache::ManifestCache,
+    name: &str,
+    latest: F,
+) -> UpdateStatus
+where
+    F: Fn(&str, Option<&semver::VersionReq>) -> Result<Option<String>>,
+{
+    // Accept an installed key ("bun::baseline") or a repo name covering its variants
+    let inst_pkg = match installed.get_package(name) {
+        Some(pkg) => pkg,
+        None => match installed
+            .find_by_repo(name)
+            .into_iter()
+            .min_by_key(|(k, _)| *k)
+        {
+            Some((_, pkg)) => pkg,
+            None => return UpdateStatus::NotInstalled,
+        },
+    };
+
+    let repo_url = match &inst_pkg.source {
+        PackageSource::Bucket { .. } => {
+            match cache.packages_by_name().get(inst_pkg.repo_name.as_str()) {
+                Some(cached) => cached.package.repo.clone(),
+                None => {
+                    return UpdateStatus::Unknown {
+                        reason: format!("{} not found in the bucket cache", inst_pkg.repo_name),
+                    }
+                }
+            }
+        }
+        PackageSource::DirectRepo { url } => url.clone(),
+        PackageSource::Script { .. } => {
+            return UpdateStatus::Unknown {
+                reason: "scripts have no release versions".to_string(),
+            }
+        }
+    };
+
+    let req = match inst_pkg.version_constraint.as_deref().map(parse_constraint) {
+        Some(Ok(req)) => Some(req),
+        Some(Err(e)) => {
+            return UpdateStatus::Unknown {
+                reason: e.to_string(),
+            }
+        }
+        None => None,
+    };
+
+    match latest(&repo_url, req.as_ref()) {
+        Ok(Some(tag)) => {
+            let to = tag.trim_start_matches('v').to_string();
+            if is_newer_version(&inst_pkg.version, &to) {
+                UpdateStatus::Update {
+                    from: inst_pkg.version.clone(),
+                    to,
+                }
+            } else {
+                UpdateStatus::UpToDate {
+                    version: inst_pkg.version.clone(),
+                }
+            }
+        }
+        Ok(None) => UpdateStatus::Unknown {
+            reason: format!(
+                "no release satisfies {}",
+                inst_pkg.version_constraint.as_deref().unwrap_or("*")
+            ),
+        },
+        Err(e) => UpdateStatus::Unknown {
+            reason: e.to_string(),
+        },
+    }
+}
+
+/// Print the update status of one package (`wenget update --check <name>`)
+///
+/// Returns the process exit code: 0 up to date, 10 update available,
+/// 2 not installed, 3 unknown.
+pub fn run_check(names: Vec<String>, pre: bool, network: NetworkOverrides) -> Result<i32> {
+    let [name] = names.as_slice() else {
+        anyhow::bail!("--check takes exactly one package name");
+    };
+
+    let status = check_one(name, pre, network)?;
+    match &status {
+        UpdateStatus::UpToDate { version } => {
+            println!("{} {} v{} is up to date", "✓".green(), name, version)
+        }
+        UpdateStatus::Update { from, to } => println!(
+            "{} {} {} -> {}",
+            "ℹ".cyan(),
+            name,
+            from.yellow(),
+            to.green()
+        ),
+        UpdateStatus::NotInstalled => println!("{} {} is not installed", "✗".red(), name),
+        UpdateStatus::Unknown { reason } => println!(
+            "{} Could not check {} for updates: {}",
+            "⚠".yellow(),
+            name,
+            reason
+        ),
+    }
+
+    Ok(status.exit_code())
+}
+
+/// Refresh the stored metadata of installed packages (`wenget update --metadata-only`)
+///
+/// Only package info is fetched; nothing is downloaded and the installed
+/// binaries and versions stay as they are.
+pub fn run_metadata(names: Vec<String>, network: NetworkOverrides) -> Result<Outcome> {
+    if names.is_empty() {
+        bail!("--metadata-only needs the names of the packages to refresh");
+    }
+
+    let config = Config::with_network(network)?;
+    let mut installed = config.get_or_create_installed()?;
+    let mut cache = config.get_or_rebuild_cache()?;
+    let github = GitHubProvider::from_config(&config)?;
+
+    let mut outcome = Outcome::default();
+    for name in &names {
+        match refresh_metadata(&mut installed, &mut cache, name, |url| {
+            github.fetch_package(url)
+        }) {
+            Ok(description) => {
+                println!("{} {} metadata refreshed", "✓".green(), name);
+                if !description.is_empty() {
+                    println!("  {}", description.dimmed());
+                }
+                outcome = outcome.merge(Outcome::new(1, 0));
+            }
+            Err(e) => {
+                eprintln!("{} {}: {:#}", "✗".red(), name, e);
+                outcome = outcome.merge(Outcome::new(0, 1));
+            }
+        }
+    }
+
+    if outcome.succeeded > 0 {
+        config.save_installed(&installed)?;
+        config.save_cache(&cache)?;
+    }
+
+    Ok(outcome)
+}
+
+/// Core of [`run_metadata`] for one name, with the GitHub lookup injected
+///
+/// Resolves the repo like [`check_package`], stores the fetched package info in
+/// the cache (bucket packages) and copies its description to every installed
+/// variant of the repo. Returns the new description.
+fn refresh_metadata<F>(
+    installed: &mut crate::core::InstalledManifest,
+    cache: &mut crate::cache::ManifestCache,
+    name: &str,
+    fetch: F,
+) -> Result<String>
+where
+    F: Fn(&str) -> Result<Package>,
+{
+    // Accept an installed key ("bun::baseline") or a repo name; all variants are refreshed
+    let (repo_name, source) = match installed.get_package(name) {
+        Some(pkg) if !pkg.repo_name.is_empty() => (pkg.repo_name.clone(), pkg.source.clone()),
+        Some(pkg) => (name.to_string(), pkg.source.clone()),
+        None => match installed
+            .find_by_repo(name)
+            .into_iter()
+            .min_by_key(|(k, _)| *k)
+        {
+            Some((_, pkg)) => (name.to_string(), pkg.source.clone()),
+            None => bail!("not installed"),
+        },
+    };
+
+    let repo_url = match &source {
+        PackageSource::Bucket { .. } => match cache.packages_by_name().get(repo_name.as_str()) {
+            Some(cached) => cached.package.repo.clone(),
+            None => bail!(
+                "{} not found in the bucket cache; run 'wenget bucket refresh' first",
+                repo_name
+            ),
+        },
+        PackageSource::DirectRepo { url } => url.clone(),
+        PackageSource::Script { .. } => bail!("scripts have no release metadata to refresh"),
+    };
+
+    let package = fetch(&repo_url)
+        .with_context(|| format!("Failed to fetch package info from {}", repo_url))?;
+    let description = package.description.clone();
+
+    // Direct-repo packages are not cached; they are always resolved live
+    if matches!(source, PackageSource::Bucket { .. }) {
+        cache.add_package(package, source);
+    }
+
+    let mut keys: Vec<String> = installed
+        .find_by_repo(&repo_name)
+        .into_iter()
+        .map(|(key, _)| key.clone())
+        .collect();
+    if keys.is_empty() {
+        keys.push(name.to_string());
+    }
+    for key in keys {
+        if let Some(pkg) = installed.packages.get_mut(&key) {
+            pkg.description = description.clone();
+        }
+    }
+
+    Ok(description)
+}
+
 /// Upgrade installed packages
-pub fn run(names: Vec<String>, yes: bool, platform: Option<String>) -> Result<()> {
+pub fn run(
+    names: Vec<String>,
+    yes: bool,
+    platform: Option<String>,
+    pre: bool,
+    network: NetworkOverrides,
+    sink: &dyn ProgressSink,
+) -> Result<Outcome> {
+    let config = Config::with_network(network)?.with_prereleases(pre);
+
     // Check for wenget updates first
-    if check_and_upgrade_self(yes)? {
+    if check_and_upgrade_self(yes, config.network())? {
         // On Windows, exit after self-update to avoid shell instability
-        return Ok(());
+        return Ok(Outcome::default());
     }
 
-    let config = Config::new()?;
     let installed = config.get_or_create_installed()?;
 
     if installed.packages.is_empty() {
         println!("{}", "No packages installed".yellow());
-        return Ok(());
+        return Ok(Outcome::default());
     }
 
     // Force refresh bucket cache to ensure we have latest versions
@@ -132,23 +415,81 @@ pub fn run(names: Vec<String>, yes: bool, platform: Option<String>) -> Result<()
     let mut cache = config.rebuild_cache()?;
 
     // Create GitHub provider to fetch latest versions
-    let github = GitHubProvider::new()?;
+    let github = GitHubProvider::from_config(&config)?;
 
     // Determine which packages to upgrade
     let update_all = names.is_empty() || (names.len() == 1 && names[0] == "all");
+    let mut constrained_upgrades = Vec::new();
+    let mut scripts = ScriptCheck::default();
+    let mut failed_checks: HashSet<String> = HashSet::new();
     let to_upgrade: Vec<String> = if update_all {
         // List upgradeable packages (also syncs latest package info into the cache)
-        let upgradeable = find_upgradeable(&installed, &github, &mut cache, yes)?;
-
-        if upgradeable.is_empty() {
-            println!("{}", "All packages are up to date".green());
-            return Ok(());
+        let check = find_upgradeable(&installed, &github, &mut cache, yes)?;
+        let upgradeable = check.upgradeable;
+        failed_checks.extend(check.failed);
+
+        // Packages pinned to a semver range are checked against their range instead
+        let constrained_keys: Vec<String> = installed
+            .packages
+            .iter()
+            .filter(|(_, pkg)| pkg.version_constraint.is_some())
+            .map(|(key, _)| key.clone())
+            .collect();
+        constrained_upgrades =
+            find_constrained_upgrades(&installed, &constrained_keys, &cache, &github);
+
+        // Scripts from a path or URL are re-fetched and compared by checksum
+        let script_keys: Vec<String> = installed
+            .packages
+            .iter()
+            .filter(|(_, pkg)| script_origin(pkg).is_some())
+            .map(|(key, _)| key.clone())
+            .collect();
+        scripts = check_scripts(&installed, &script_keys, config.network());
+        failed_checks.extend(scripts.failed.iter().cloned());
+
+        if upgradeable.is_empty() && constrained_upgrades.is_empty() && scripts.changed.is_empty() {
+            if failed_checks.is_empty() {
+                println!("{}", "All packages are up to date".green());
+                return Ok(Outcome::default());
+            }
+            UpdateSummary::new(
+                &installed,
+                &installed,
+                &HashSet::new(),
+                &HashSet::new(),
+                &failed_checks,
+            )
+            .print();
+            return Ok(Outcome::new(0, failed_checks.len()));
         }
 
         println!("{}", "Packages to upgrade:".bold());
         for (name, current, latest) in &upgradeable {
             println!("  • {} {} -> {}", name, current.yellow(), latest.green());
         }
+        for upgrade in &constrained_upgrades {
+            println!(
+                "  • {} {} -> {} ({})",
+                upgrade.repo_name,
+                upgrade.current.yellow(),
+                upgrade.tag.green(),
+                upgrade.constraint.dimmed()
+            );
+        }
+        for change in &scripts.changed {
+            println!(
+                "  • {} (script) {} -> {}",
+                change.key,
+                change
+                    .old
+                    .as_deref()
+                    .map(short_checksum)
+                    .unwrap_or_else(|| "unknown".to_string())
+                    .yellow(),
+                short_checksum(&change.new).green()
+            );
+        }
         println!();
 
         upgradeable.into_iter().map(|(name, _, _)| name).collect()
@@ -158,6 +499,7 @@ pub fn run(names: Vec<String>, yes: bool, platform: Option<String>) -> Result<()
 
     // Expand: include all installed variants when upgrading a repo
     let mut expanded = Vec::new();
+    let mut not_installed = 0;
     for name in &to_upgrade {
         // Check if this is a repo name or a specific variant
         if name.contains("::") {
@@ -170,6 +512,7 @@ pub fn run(names: Vec<String>, yes: bool, platform: Option<String>) -> Result<()
                     "Warning:".yellow(),
                     name
                 );
+                not_installed += 1;
             }
             continue;
         }
@@ -186,6 +529,7 @@ pub fn run(names: Vec<String>, yes: bool, platform: Option<String>) -> Result<()
                     "Warning:".yellow(),
                     name
                 );
+                not_installed += 1;
             }
         } else {
             for (key, _pkg) in variants {
@@ -194,9 +538,22 @@ pub fn run(names: Vec<String>, yes: bool, platform: Option<String>) -> Result<()
         }
     }
 
-    if expanded.is_empty() {
+    if expanded.is_empty() && constrained_upgrades.is_empty() && scripts.changed.is_empty() {
         println!("{}", "No installed packages to update".yellow());
-        return Ok(());
+        return Ok(Outcome::new(0, not_installed));
+    }
+
+    // Named updates: resolve range-pinned packages against their range
+    if !update_all {
+        let (constrained_keys, rest): (Vec<String>, Vec<String>) =
+            expanded.into_iter().partition(|key| {
+                installed
+                    .get_package(key)
+                    .is_some_and(|pkg| pkg.version_constraint.is_some())
+            });
+        expanded = rest;
+        constrained_upgrades =
+            find_constrained_upgrades(&installed, &constrained_keys, &cache, &github);
     }
 
     // For named updates, find_upgradeable was skipped, so sync the latest package info
@@ -207,6 +564,7 @@ pub fn run(names: Vec<String>, yes: bool, platform: Option<String>) -> Result<()
 
         // Filter out packages that are already up to date
         let mut filtered = Vec::new();
+        let mut script_keys = Vec::new();
         let cache_by_name = cache.packages_by_name();
         for key in expanded {
             if let Some(inst_pkg) = installed.get_package(&key) {
@@ -257,7 +615,8 @@ pub fn run(names: Vec<String>, yes: bool, platform: Option<String>) -> Result<()
                                 filtered.push(key);
                             }
                         } else {
-                            filtered.push(key);
+                            // Re-fetched from its path or URL below
+                            script_keys.push(key);
                         }
                     }
                     _ => {
@@ -270,12 +629,30 @@ pub fn run(names: Vec<String>, yes: bool, platform: Option<String>) -> Result<()
             }
         }
         to_run = filtered;
+
+        scripts = check_scripts(&installed, &script_keys, config.network());
+        failed_checks.extend(scripts.failed.iter().cloned());
+        for key in &scripts.unchanged {
+            println!(
+                "  • {} (script) is already up to date",
+                key.as_str().bright_white()
+            );
+        }
     }
 
-    if to_run.is_empty() {
-        return Ok(());
+    let mut outcome = Outcome::new(0, not_installed + failed_checks.len());
+    if to_run.is_empty() && constrained_upgrades.is_empty() && scripts.changed.is_empty() {
+        return Ok(outcome);
     }
 
+    // Snapshot the keys being reinstalled for the summary
+    let attempted: HashSet<String> = to_run
+        .iter()
+        .chain(constrained_upgrades.iter().flat_map(|u| u.keys.iter()))
+        .chain(scripts.changed.iter().map(|c| &c.key))
+        .cloned()
+        .collect();
+
     // Persist the API-synced package info so the add step (running in update_mode) reads
     // the latest version and download links from the cache, even if the GitHub API
     // becomes unavailable during installation.
@@ -283,10 +660,174 @@ pub fn run(names: Vec<String>, yes: bool, platform: Option<String>) -> Result<()
         log::warn!("Failed to save synced cache: {}", e);
     }
 
+    // Keys whose reinstall was declined at a confirmation prompt
+    let mut skipped: HashSet<String> = HashSet::new();
+
     // Use add command to upgrade (reinstall). The platform override (if any)
     // is threaded through so updates honor an explicit `-p` target; when None,
     // the add path falls back to the `preferred_platform` config setting.
-    add::run(to_run, yes, None, platform, None, None, false, true)
+    if !to_run.is_empty() {
+        let keys = to_run.clone();
+        let added = add::run(
+            to_run,
+            add::AddOptions {
+                yes,
+                platform: platform.clone(),
+                pre,
+                update_mode: true,
+                ..add::AddOptions::default()
+            },
+            network,
+            sink,
+        )?;
+        // Nothing installed and nothing failed: the installation was cancelled
+        if added == Outcome::default() {
+            skipped.extend(keys);
+        }
+        outcome = outcome.merge(added);
+    }
+
+    // Range-pinned packages are reinstalled at the exact release picked for them
+    for upgrade in constrained_upgrades {
+        let keys = upgrade.keys.clone();
+        let added = add::run(
+            upgrade.keys,
+            add::AddOptions {
+                yes,
+                platform: platform.clone(),
+                version: Some(upgrade.tag),
+                pre,
+                update_mode: true,
+                ..add::AddOptions::default()
+            },
+            network,
+            sink,
+        )?;
+        if added == Outcome::default() {
+            skipped.extend(keys);
+        }
+        outcome = outcome.merge(added);
+    }
+
+    let script_keys: Vec<String> = scripts.changed.iter().map(|c| c.key.clone()).collect();
+    let replaced = replace_scripts(&config, scripts.changed, yes)?;
+    if replaced == Outcome::default() {
+        skipped.extend(script_keys);
+    }
+    outcome = outcome.merge(replaced);
+
+    if update_all {
+        let after = config.get_or_create_installed()?;
+        UpdateSummary::new(&installed, &after, &attempted, &skipped, &failed_checks).print();
+    }
+
+    Ok(outcome)
+}
+
+/// A newer release within a package's semver range
+struct ConstrainedUpgrade {
+    repo_name: String,
+    /// Installed keys (all variants) of the repo
+    keys: Vec<String>,
+    current: String,
+    tag: String,
+    constraint: String,
+}
+
+/// Find range-pinned packages with a newer release that still satisfies their range
+///
+/// Repos with no release satisfying their range are reported and skipped.
+fn find_constrained_upgrades(
+    installed: &crate::core::InstalledManifest,
+    keys: &[String],
+    cache: &crate::cache::ManifestCache,
+    github: &GitHubProvider,
+) -> Vec<ConstrainedUpgrade> {
+    let cache_by_name = cache.packages_by_name();
+    let mut upgrades: Vec<ConstrainedUpgrade> = Vec::new();
+    let mut checked = HashSet::new();
+
+    for key in keys {
+        let inst_pkg = match installed.get_package(key) {
+            Some(p) => p,
+            None => continue,
+        };
+        let constraint = match &inst_pkg.version_constraint {
+            Some(c) => c,
+            None => continue,
+        };
+
+        // Variants of a repo share one check
+        if !checked.insert(inst_pkg.repo_name.clone()) {
+            if let Some(upgrade) = upgrades
+                .iter_mut()
+                .find(|u| u.repo_name == inst_pkg.repo_name)
+            {
+                upgrade.keys.push(key.clone());
+            }
+            continue;
+        }
+
+        let repo_url = match &inst_pkg.source {
+            PackageSource::Bucket { .. } => match cache_by_name.get(inst_pkg.repo_name.as_str()) {
+                Some(cached) => cached.package.repo.clone(),
+                None => {
+                    eprintln!(
+                        "{} Package {} not found in cache, skipping update check",
+                        "Warning:".yellow(),
+                        inst_pkg.repo_name
+                    );
+                    continue;
+                }
+            },
+            PackageSource::DirectRepo { url } => url.clone(),
+            PackageSource::Script { .. } => continue,
+        };
+
+        let req = match parse_constraint(constraint) {
+            Ok(req) => req,
+            Err(e) => {
+                eprintln!("{} {}: {}", "Warning:".yellow(), inst_pkg.repo_name, e);
+                continue;
+            }
+        };
+
+        match github.fetch_matching_version(&repo_url, &req) {
+            Ok(Some(tag)) => {
+                if is_newer_version(&inst_pkg.version, &tag) {
+                    upgrades.push(ConstrainedUpgrade {
+                        repo_name: inst_pkg.repo_name.clone(),
+                        keys: vec![key.clone()],
+                        current: inst_pkg.version.clone(),
+                        tag,
+                        constraint: constraint.clone(),
+                    });
+                }
+            }
+            Ok(None) => println!(
+                "  {} No release of {} satisfies {}",
+                "⚠".yellow(),
+                inst_pkg.repo_name,
+                constraint
+            ),
+            Err(e) => eprintln!(
+                "{} Failed to check updates for {}: {}",
+                "Warning:".yellow(),
+                inst_pkg.repo_name,
+                e
+            ),
+        }
+    }
+
+    upgrades
+}
+
+/// Upgradeable packages found by `find_upgradeable`
+struct UpgradeCheck {
+    /// (repo name, installed version, latest version)
+    upgradeable: Vec<(String, String, String)>,
+    /// Repos whose latest version couldn't be determined
+    failed: Vec<String>,
 }
 
 /// Find upgradeable packages by checking their sources
@@ -295,8 +836,9 @@ fn find_upgradeable(
     github: &GitHubProvider,
     cache: &mut crate::cache::ManifestCache,
     yes: bool,
-) -> Result<Vec<(String, String, String)>> {
+) -> Result<UpgradeCheck> {
     let mut upgradeable = Vec::new();
+    let mut failed = Vec::new();
 
     let grouped = installed.group_by_repo();
     let total = grouped.len();
@@ -325,6 +867,12 @@ fn find_upgradeable(
         // Use the first variant to get version and source info
         let (_key, inst_pkg) = variants[0];
 
+        // Range-pinned packages are handled by find_constrained_upgrades
+        if inst_pkg.version_constraint.is_some() {
+            pb.inc(1);
+            continue;
+        }
+
         let repo_url = match &inst_pkg.source {
             PackageSource::Bucket { name: bucket_name } => {
                 // Get package info from cache for bucket packages
@@ -339,6 +887,7 @@ fn find_upgradeable(
                         repo_name,
                         bucket_name
                     );
+                    failed.push(repo_name);
                     pb.inc(1);
                     continue;
                 }
@@ -348,10 +897,10 @@ fn find_upgradeable(
                 url.clone()
             }
             PackageSource::Script { origin, .. } => {
-                // Check if this is a bucket-sourced script
+                // Scripts from a path or URL are compared by checksum instead
                 if !origin.starts_with("bucket:") {
                     log::debug!(
-                        "Skipping non-bucket script '{}' - no update source",
+                        "Skipping non-bucket script '{}' - checked against its origin",
                         repo_name
                     );
                     pb.inc(1);
@@ -482,6 +1031,7 @@ fn find_upgradeable(
                             "Warning:".yellow(),
                             repo_name
                         );
+                        failed.push(repo_name);
                     }
                 } else {
                     eprintln!(
@@ -489,6 +1039,7 @@ fn find_upgradeable(
                         "Warning:".yellow(),
                         repo_name
                     );
+                    failed.push(repo_name);
                 }
             }
         }
@@ -497,7 +1048,10 @@ fn find_upgradeable(
     pb.finish();
     println!();
 
-    Ok(upgradeable)
+    Ok(UpgradeCheck {
+        upgradeable,
+        failed,
+    })
 }
 
 /// Sync the latest package info for the given installed keys into the cache.
@@ -561,15 +1115,258 @@ fn sync_bucket_packages_to_cache(
     }
 }
 
+/// A script from a path or URL whose content changed at its origin
+struct ScriptChange {
+    key: String,
+    origin: String,
+    script_type: ScriptType,
+    content: String,
+    /// Checksum of the installed content, if known
+    old: Option<String>,
+    new: String,
+}
+
+/// Scripts from a path or URL checked against their origin
+#[derive(Default)]
+struct ScriptCheck {
+    changed: Vec<ScriptChange>,
+    unchanged: Vec<String>,
+    /// Keys whose origin couldn't be read
+    failed: Vec<String>,
+}
+
+/// Origin and type of a script installed from a local path or URL
+///
+/// Bucket scripts (origin `bucket:<name>`) are updated through the bucket.
+fn script_origin(pkg: &InstalledPackage) -> Option<(&str, &ScriptType)> {
+    match &pkg.source {
+        PackageSource::Script {
+            origin,
+            script_type,
+        } if !origin.starts_with("bucket:") => Some((origin, script_type)),
+        _ => None,
+    }
+}
+
+/// Checksum of an installed script's content
+///
+/// Scripts installed before checksums were recorded are hashed from disk.
+fn installed_script_checksum(pkg: &InstalledPackage) -> Option<String> {
+    if let Some(checksum) = &pkg.checksum {
+        return Some(checksum.clone());
+    }
+    let script_file = pkg.executables.keys().next()?;
+    let content = fs::read_to_string(Path::new(&pkg.install_path).join(script_file)).ok()?;
+    Some(script_checksum(&content))
+}
+
+/// Re-fetch the given scripts from their origin and compare checksums
+fn check_scripts(
+    installed: &crate::core::InstalledManifest,
+    keys: &[String],
+    network: &NetworkSettings,
+) -> ScriptCheck {
+    let mut check = ScriptCheck::default();
+    for key in keys {
+        let Some(pkg) = installed.get_package(key) else {
+            continue;
+        };
+        let Some((origin, script_type)) = script_origin(pkg) else {
+            continue;
+        };
+
+        let fetched = if origin.starts_with("http://") || origin.starts_with("https://") {
+            download_script(origin, network)
+        } else {
+            read_local_script(Path::new(origin))
+        };
+        let content = match fetched {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!(
+                    "{} Failed to re-fetch {}: {:#}",
+                    "Warning:".yellow(),
+                    key,
+                    e
+                );
+                check.failed.push(key.clone());
+                continue;
+            }
+        };
+
+        let old = installed_script_checksum(pkg);
+        let new = script_checksum(&content);
+        if old.as_deref() == Some(new.as_str()) {
+            check.unchanged.push(key.clone());
+        } else {
+            check.changed.push(ScriptChange {
+                key: key.clone(),
+                origin: origin.to_string(),
+                script_type: script_type.clone(),
+                content,
+                old,
+                new,
+            });
+        }
+    }
+    check
+}
+
+/// Replace changed scripts with their new content
+fn replace_scripts(config: &Config, changes: Vec<ScriptChange>, yes: bool) -> Result<Outcome> {
+    if changes.is_empty() {
+        return Ok(Outcome::default());
+    }
+    if !yes
+        && !crate::utils::confirm(&format!(
+            "\nReplace {} changed script(s)? Review them before running them",
+            changes.len()
+        ))?
+    {
+        println!("Script update cancelled");
+        return Ok(Outcome::default());
+    }
+
+    let paths = config.paths();
+    let mut installed = config.get_or_create_installed()?;
+    let mut outcome = Outcome::default();
+    for change in changes {
+        println!("{} {} (script)...", "Updating".cyan(), change.key);
+        let bin_dir = installed
+            .get_package(&change.key)
+            .and_then(|pkg| pkg.bin_dir.clone());
+        let pkg_paths = paths.for_package(bin_dir.as_deref());
+        match add::install_single_script(
+            &pkg_paths,
+            &change.key,
+            &change.content,
+            &change.script_type,
+            &change.origin,
+        ) {
+            Ok(pkg) => {
+                history::record(paths, Action::Update, &change.key, &pkg);
+                installed.upsert_package(change.key, pkg);
+                outcome = outcome.merge(Outcome::new(1, 0));
+            }
+            Err(e) => {
+                println!("  {} {:#}", "✗".red(), e);
+                outcome = outcome.merge(Outcome::new(0, 1));
+            }
+        }
+    }
+    if outcome.succeeded > 0 {
+        config.save_installed(&installed)?;
+    }
+    Ok(outcome)
+}
+
+/// First 8 hex digits of a checksum, to tell script contents apart
+fn short_checksum(checksum: &str) -> String {
+    let digest = checksum.strip_prefix("sha256:").unwrap_or(checksum);
+    digest.chars().take(8).collect()
+}
+
+/// Version shown in the summary; scripts are told apart by checksum
+fn summary_version(pkg: &InstalledPackage) -> String {
+    match (&pkg.source, &pkg.checksum) {
+        (PackageSource::Script { .. }, Some(checksum)) => short_checksum(checksum),
+        _ => pkg.version.clone(),
+    }
+}
+
+/// What `update` without names did to each installed package
+#[derive(Debug, Default, PartialEq)]
+struct UpdateSummary {
+    /// (key, old version, new version)
+    upgraded: Vec<(String, String, String)>,
+    latest: Vec<String>,
+    /// Declined at a confirmation prompt
+    skipped: Vec<String>,
+    failed: Vec<String>,
+}
+
+impl UpdateSummary {
+    /// Compare installed.json before and after the update
+    ///
+    /// A package counts as upgraded when it was reinstalled, as skipped when
+    /// its reinstall was declined, and as failed when its check failed or it
+    /// was attempted but not reinstalled.
+    fn new(
+        before: &crate::core::InstalledManifest,
+        after: &crate::core::InstalledManifest,
+        attempted: &HashSet<String>,
+        skipped: &HashSet<String>,
+        failed_checks: &HashSet<String>,
+    ) -> Self {
+        let mut keys: Vec<&String> = before.packages.keys().collect();
+        keys.sort();
+
+        let mut summary = Self::default();
+        for key in keys {
+            let old = &before.packages[key];
+            match after.get_package(key) {
+                Some(new) if new.installed_at != old.installed_at => {
+                    summary
+                        .upgraded
+                        .push((key.clone(), summary_version(old), summary_version(new)))
+                }
+                _ if skipped.contains(key) => summary.skipped.push(key.clone()),
+                _ if attempted.contains(key)
+                    || failed_checks.contains(key)
+                    || failed_checks.contains(&old.repo_name) =>
+                {
+                    summary.failed.push(key.clone())
+                }
+                _ => summary.latest.push(key.clone()),
+            }
+        }
+        summary
+    }
+
+    fn print(&self) {
+        println!();
+        println!("{}", "Summary:".bold());
+        if !self.upgraded.is_empty() {
+            println!("  {}", "Upgraded (old → new):".green());
+            for (key, old, new) in &self.upgraded {
+                println!(
+                    "    {} {} {} → {}",
+                    "✓".green(),
+                    key,
+                    old.yellow(),
+                    new.green()
+                );
+            }
+        }
+        if !self.latest.is_empty() {
+            println!(
+                "  {} {}",
+                "Already latest:".cyan(),
+                self.latest.join(", ").dimmed()
+            );
+        }
+        if !self.skipped.is_empty() {
+            println!(
+                "  {} {}",
+                "Skipped:".yellow(),
+                self.skipped.join(", ").dimmed()
+            );
+        }
+        if !self.failed.is_empty() {
+            println!("  {} {}", "Failed:".red(), self.failed.join(", "));
+        }
+    }
+}
+
 /// Check for wenget updates and prompt user
 /// Returns true if wenget was updated on Windows (caller should exit)
-fn check_and_upgrade_self(yes: bool) -> Result<bool> {
+fn check_and_upgrade_self(yes: bool, network: &NetworkSettings) -> Result<bool> {
     let current_version = env!("CARGO_PKG_VERSION");
 
     println!("{}", "Checking for wenget updates...".dimmed());
 
     // Try to check latest version - don't fail the whole update if this fails
-    let provider = match GitHubProvider::new() {
+    let provider = match GitHubProvider::with_settings(network) {
         Ok(p) => p,
         Err(e) => {
             log::debug!("Failed to create GitHub provider for self-check: {}", e);
@@ -609,7 +1406,7 @@ fn check_and_upgrade_self(yes: bool) -> Result<bool> {
     }
 
     // Perform self-update, passing provider and known version to avoid redundant API calls
-    upgrade_self_with_provider(provider, &latest_version)?;
+    upgrade_self_with_provider(provider, &latest_version, network)?;
 
     // On Windows, recommend restarting shell
     #[cfg(windows)]
@@ -649,7 +1446,11 @@ fn override_matches_host(override_str: &str, host: crate::core::Platform) -> boo
 }
 
 /// Upgrade wenget itself
-fn upgrade_self_with_provider(provider: GitHubProvider, latest_version: &str) -> Result<()> {
+fn upgrade_self_with_provider(
+    provider: GitHubProvider,
+    latest_version: &str,
+    network: &NetworkSettings,
+) -> Result<()> {
     use crate::core::{Platform, WenPaths};
     use crate::downloader::download_file;
     use crate::installer::{extract_archive, find_executable};
@@ -665,15 +1466,19 @@ fn upgrade_self_with_provider(provider: GitHubProvider, latest_version: &str) ->
     // Select binary for current platform
     // Note: Uses same platform matching logic as add command (see add.rs).
     // This handles libc detection (musl vs glibc), compiler variants, and fallbacks.
-    let current_platform = Platform::current();
+    let prefs = Config::new().ok().map(|c| c.preferences().clone());
+    let current_platform = Platform::current().prefer_compiler(
+        prefs
+            .as_ref()
+            .and_then(|p| p.preferred_compiler.as_deref())
+            .and_then(crate::core::Compiler::from_name),
+    );
 
     // Honor `preferred_platform` from config for self-update, but ONLY when it
     // targets the same OS+arch as the host. The self-update binary must run on
     // this machine, so a cross-OS/arch override would brick wenget; a pure
     // libc/compiler choice (e.g. musl on glibc) keeps OS+arch and is safe.
-    let preferred = Config::new()
-        .ok()
-        .and_then(|c| c.preferences().preferred_platform.clone());
+    let preferred = prefs.and_then(|p| p.preferred_platform);
 
     let matches = match preferred.as_deref() {
         Some(pref) if override_matches_host(pref, current_platform) => {
@@ -741,14 +1546,14 @@ fn upgrade_self_with_provider(provider: GitHubProvider, latest_version: &str) ->
     fs::create_dir_all(&temp_dir)?;
 
     let download_path = temp_dir.join(filename);
-    download_file(&binary.url, &download_path)?;
+    download_file(&binary.url, &download_path, network)?;
 
     // Extract archive
     let extract_dir = temp_dir.join("extracted");
     fs::create_dir_all(&extract_dir)?;
 
     println!("{}", "Extracting...".cyan());
-    let extracted_files = extract_archive(&download_path, &extract_dir)?;
+    let extracted_files = extract_archive(&download_path, &extract_dir, false)?;
 
     // Find the wenget executable
     let exe_relative_path = find_executable(&extracted_files, "wenget")
@@ -962,4 +1767,246 @@ mod tests {
         assert!(is_newer_version("1.0.0", "v2.0.0"));
         assert!(!is_newer_version("v2.0.0", "1.0.0"));
     }
+
+    fn installed_pkg(version: &str, source: PackageSource) -> crate::core::InstalledPackage {
+        crate::core::InstalledPackage {
+            repo_name: "tool".to_string(),
+            variant: None,
+            version: version.to_string(),
+            platform: "linux-x86_64".to_string(),
+            installed_at: chrono::Utc::now(),
+            install_path: "/apps/tool".to_string(),
+            executables: HashMap::new(),
+            source,
+            description: String::new(),
+            command_names: vec![],
+            command_name: None,
+            asset_name: "tool.tar.gz".to_string(),
+            parent_package: None,
+            download_url: None,
+            bin_dir: None,
+            version_constraint: None,
+            compiler: None,
+            checksum: None,
+            files: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_check_package() {
+        let cache = crate::cache::ManifestCache::new();
+        let mut installed = crate::core::InstalledManifest::new();
+        installed.upsert_package(
+            "tool".to_string(),
+            installed_pkg(
+                "1.2.0",
+                PackageSource::DirectRepo {
+                    url: "https://github.com/o/tool".to_string(),
+                },
+            ),
+        );
+
+        let latest = |tag: &'static str| {
+            move |url: &str, req: Option<&semver::VersionReq>| -> Result<Option<String>> {
+                assert_eq!(url, "https://github.com/o/tool");
+                assert!(req.is_none());
+                Ok(Some(tag.to_string()))
+            }
+        };
+
+        assert_eq!(
+            check_package(&installed, &cache, "tool", latest("v1.3.0")),
+            UpdateStatus::Update {
+                from: "1.2.0".to_string(),
+                to: "1.3.0".to_string()
+            }
+        );
+        assert_eq!(
+            check_package(&installed, &cache, "tool", latest("1.2.0")),
+            UpdateStatus::UpToDate {
+                version: "1.2.0".to_string()
+            }
+        );
+        assert_eq!(
+            check_package(&installed, &cache, "other", latest("1.3.0")),
+            UpdateStatus::NotInstalled
+        );
+
+        let rate_limited = check_package(&installed, &cache, "tool", |_, _| {
+            Err(anyhow::anyhow!("GitHub API rate limit exceeded"))
+        });
+        assert!(matches!(rate_limited, UpdateStatus::Unknown { .. }));
+        assert_eq!(rate_limited.exit_code(), 3);
+
+        // Bucket packages missing from the cache can't be resolved to a repo
+        installed.upsert_package(
+            "tool".to_string(),
+            installed_pkg(
+                "1.2.0",
+                PackageSource::Bucket {
+                    name: "main".to_string(),
+                },
+            ),
+        );
+        assert!(matches!(
+            check_package(&installed, &cache, "tool", latest("1.3.0")),
+            UpdateStatus::Unknown { .. }
+        ));
+    }
+
+    fn fetched(description: &str) -> Package {
+        Package {
+            name: "tool".to_string(),
+            aliases: Vec::new(),
+            description: description.to_string(),
+            repo: "https://github.com/o/tool".to_string(),
+            homepage: Some("https://tool.dev".to_string()),
+            license: Some("Apache-2.0".to_string()),
+            version: Some("9.9.9".to_string()),
+            platforms: Default::default(),
+            pre_install: Vec::new(),
+            post_install: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_check_scripts() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let origin = temp.path().join("hello.sh");
+        std::fs::write(&origin, "echo hello\n").unwrap();
+        let script = |checksum: Option<String>, origin: &str| {
+            let mut pkg = installed_pkg(
+                "script",
+                PackageSource::Script {
+                    origin: origin.to_string(),
+                    script_type: ScriptType::Bash,
+                },
+            );
+            pkg.checksum = checksum;
+            pkg
+        };
+        let origin_str = origin.to_string_lossy().to_string();
+
+        let mut installed = crate::core::InstalledManifest::new();
+        installed.upsert_package(
+            "same".to_string(),
+            script(Some(script_checksum("echo hello\n")), &origin_str),
+        );
+        installed.upsert_package(
+            "edited".to_string(),
+            script(Some(script_checksum("echo hi\n")), &origin_str),
+        );
+        installed.upsert_package(
+            "gone".to_string(),
+            script(None, &temp.path().join("gone.sh").to_string_lossy()),
+        );
+        // Bucket scripts are updated through their bucket
+        installed.upsert_package("bucket".to_string(), script(None, "bucket:main"));
+
+        let keys: Vec<String> = ["bucket", "edited", "gone", "same"]
+            .iter()
+            .map(|k| k.to_string())
+            .collect();
+        let check = check_scripts(&installed, &keys, &NetworkSettings::default());
+        assert_eq!(check.unchanged, ["same"]);
+        assert_eq!(check.failed, ["gone"]);
+        assert_eq!(check.changed.len(), 1);
+        let change = &check.changed[0];
+        assert_eq!(change.key, "edited");
+        assert_eq!(change.content, "echo hello\n");
+        assert_eq!(change.new, script_checksum("echo hello\n"));
+    }
+
+    #[test]
+    fn test_update_summary() {
+        let mut before = crate::core::InstalledManifest::new();
+        for key in ["fd", "rg", "tool", "broken", "declined"] {
+            let mut pkg = installed_pkg(
+                "1.0.0",
+                PackageSource::Bucket {
+                    name: "main".to_string(),
+                },
+            );
+            pkg.repo_name = key.to_string();
+            before.upsert_package(key.to_string(), pkg);
+        }
+        let mut after = before.clone();
+        let rg = after.packages.get_mut("rg").unwrap();
+        rg.version = "2.0.0".to_string();
+        rg.installed_at += chrono::Duration::seconds(1);
+
+        // rg and tool were reinstalled, but only rg made it; broken couldn't be
+        // checked, and the reinstall of declined was cancelled at the prompt
+        let attempted: HashSet<String> = ["rg", "tool", "declined"].map(String::from).into();
+        let skipped: HashSet<String> = ["declined".to_string()].into();
+        let failed: HashSet<String> = ["broken".to_string()].into();
+        assert_eq!(
+            UpdateSummary::new(&before, &after, &attempted, &skipped, &failed),
+            UpdateSummary {
+                upgraded: vec![("rg".to_string(), "1.0.0".to_string(), "2.0.0".to_string())],
+                latest: vec!["fd".to_string()],
+                skipped: vec!["declined".to_string()],
+                failed: vec!["broken".to_string(), "tool".to_string()],
+            }
+        );
+
+        // Scripts are told apart by checksum
+        let mut script = installed_pkg(
+            "script",
+            PackageSource::Script {
+                origin: "./hello.sh".to_string(),
+                script_type: ScriptType::Bash,
+            },
+        );
+        assert_eq!(summary_version(&script), "script");
+        script.checksum = Some(script_checksum("hello"));
+        assert_eq!(summary_version(&script), "2cf24dba");
+    }
+
+    #[test]
+    fn test_refresh_metadata() {
+        let bucket = PackageSource::Bucket {
+            name: "main".to_string(),
+        };
+        let mut cache = crate::cache::ManifestCache::new();
+        cache.add_package(fetched("Old description"), bucket.clone());
+
+        let mut installed = crate::core::InstalledManifest::new();
+        installed.upsert_package("tool".to_string(), installed_pkg("1.2.0", bucket.clone()));
+        let mut variant = installed_pkg("1.2.0", bucket.clone());
+        variant.variant = Some("musl".to_string());
+        installed.upsert_package("tool::musl".to_string(), variant);
+
+        let description = refresh_metadata(&mut installed, &mut cache, "tool", |url| {
+            assert_eq!(url, "https://github.com/o/tool");
+            Ok(fetched("New description"))
+        })
+        .unwrap();
+        assert_eq!(description, "New description");
+
+        // Every variant gets the new description; binaries and versions stay put
+        for key in ["tool", "tool::musl"] {
+            let pkg = installed.get_package(key).unwrap();
+            assert_eq!(pkg.description, "New description");
+            assert_eq!(pkg.version, "1.2.0");
+            assert_eq!(pkg.install_path, "/apps/tool");
+        }
+        let cached = cache.find_package("tool").unwrap();
+        assert_eq!(cached.package.description, "New description");
+        assert_eq!(cached.package.license.as_deref(), Some("Apache-2.0"));
+
+        // A failed fetch changes nothing
+        assert!(refresh_metadata(&mut installed, &mut cache, "tool", |_| {
+            Err(anyhow::anyhow!("GitHub API rate limit exceeded"))
+        })
+        .is_err());
+        assert_eq!(
+            installed.get_package("tool").unwrap().description,
+            "New description"
+        );
+
+        assert!(
+            refresh_metadata(&mut installed, &mut cache, "other", |_| Ok(fetched("x"))).is_err()
+        );
+    }
 }
@@ -0,0 +1,322 @@
+//! Verify command implementation
+//!
+//! Checks every package in installed.json against the disk: the install
+//! directory, the recorded files and executables, and the launchers in the bin
+//! directory. `--repair` recreates missing or broken launchers; anything else
+//! needs a reinstall.
+
+use crate::commands::outcome::{EXIT_FAILURE, EXIT_SUCCESS};
+use crate::commands::repair::{link_executable, remove_launcher};
+use crate::core::manifest::PackageSource;
+use crate::core::{Config, InstalledPackage, WenPaths};
+use anyhow::Result;
+use colored::Colorize;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Something missing from an installed package
+#[derive(Debug, Clone, PartialEq)]
+enum Problem {
+    /// The package's install directory is gone
+    MissingInstallDir(PathBuf),
+    /// A recorded file is gone (path as recorded)
+    MissingFile(String),
+    /// An executable is gone (path relative to the install directory)
+    MissingExecutable(String),
+    /// The launcher of a command doesn't exist
+    MissingLauncher { command: String, path: PathBuf },
+    /// The launcher is a symlink whose target doesn't resolve, or that points elsewhere
+    BrokenLauncher { command: String, target: PathBuf },
+}
+
+impl Problem {
+    /// Command whose launcher `--repair` can recreate
+    fn launcher_command(&self) -> Option<&str> {
+        match self {
+            Problem::MissingLauncher { command, .. } | Problem::BrokenLauncher { command, .. } => {
+                Some(command)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::MissingInstallDir(path) => {
+                write!(f, "install directory missing: {}", path.display())
+            }
+            Problem::MissingFile(file) => write!(f, "file missing: {}", file),
+            Problem::MissingExecutable(exe) => write!(f, "executable missing: {}", exe),
+            Problem::MissingLauncher { command, path } => {
+                write!(f, "launcher missing: {} ({})", command, path.display())
+            }
+            Problem::BrokenLauncher { command, target } => {
+                write!(f, "launcher broken: {} -> {}", command, target.display())
+            }
+        }
+    }
+}
+
+/// Verify all installed packages, optionally recreating broken launchers
+///
+/// Returns a failing exit code when any package is still broken.
+pub fn run(repair: bool) -> Result<i32> {
+    let config = Config::new()?;
+    let paths = config.paths();
+    let installed = config.get_or_create_installed()?;
+
+    if installed.packages.is_empty() {
+        println!("{}", "No packages installed".yellow());
+        return Ok(EXIT_SUCCESS);
+    }
+
+    let mut keys: Vec<&String> = installed.packages.keys().collect();
+    keys.sort();
+
+    let mut broken = 0;
+    for key in keys {
+        let pkg = &installed.packages[key];
+        let mut problems = check_package(paths, pkg);
+
+        if repair && problems.iter().any(|p| p.launcher_command().is_some()) {
+            for problem in &problems {
+                let Some(command) = problem.launcher_command() else {
+                    continue;
+                };
+                match recreate_launcher(paths, pkg, command) {
+                    Ok(()) => println!("{} {}: recreated launcher {}", "✓".green(), key, command),
+                    Err(e) => println!("{} {}: {}: {:#}", "✗".red(), key, command, e),
+                }
+            }
+            problems = check_package(paths, pkg);
+        }
+
+        if problems.is_empty() {
+            println!("{} {} {}", "✓".green(), key, "OK".green());
+        } else {
+            broken += 1;
+            println!("{} {} {}", "✗".red(), key, "Broken".red());
+            for problem in &problems {
+                println!("    {}", problem);
+            }
+        }
+    }
+
+    println!();
+    if broken == 0 {
+        println!(
+            "{} All {} package(s) are intact",
+            "✓".green(),
+            installed.packages.len()
+        );
+        return Ok(EXIT_SUCCESS);
+    }
+
+    println!(
+        "{} {} of {} package(s) are broken",
+        "✗".red(),
+        broken,
+        installed.packages.len()
+    );
+    if repair {
+        println!("Reinstall them with: wenget add <name> --force");
+    } else {
+        println!("Recreate missing launchers with: wenget verify --repair");
+    }
+    Ok(EXIT_FAILURE)
+}
+
+/// Everything missing from an installed package, in a stable order
+fn check_package(paths: &WenPaths, pkg: &InstalledPackage) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let install_dir = Path::new(&pkg.install_path);
+    let install_dir_exists = install_dir.is_dir();
+
+    // Without the directory every file inside is missing too; say it once
+    if !install_dir_exists {
+        problems.push(Problem::MissingInstallDir(install_dir.to_path_buf()));
+    } else {
+        for exe in pkg.missing_executables() {
+            problems.push(Problem::MissingExecutable(exe.to_string()));
+        }
+    }
+
+    for file in &pkg.files {
+        let path = Path::new(file);
+        let relative = !path.is_absolute();
+        if relative && (!install_dir_exists || pkg.executables.contains_key(file)) {
+            continue;
+        }
+        let path = if relative {
+            install_dir.join(path)
+        } else {
+            path.to_path_buf()
+        };
+        if fs::symlink_metadata(&path).is_err() {
+            problems.push(Problem::MissingFile(file.clone()));
+        }
+    }
+
+    let pkg_paths = paths.for_package(pkg.bin_dir.as_deref());
+    let mut executables: Vec<(&String, &String)> = pkg.executables.iter().collect();
+    executables.sort();
+    for (exe, command) in executables {
+        let launcher = pkg_paths.bin_shim_path(command);
+        if let Some(problem) = check_launcher(&launcher, command, &install_dir.join(exe)) {
+            problems.push(problem);
+        }
+    }
+
+    problems
+}
+
+/// Check a command's launcher
+///
+/// Symlinks have to resolve, and a symlink to a binary has to point at the
+/// package's executable. Shims and script wrappers only have to exist.
+fn check_launcher(launcher: &Path, command: &str, exe_path: &Path) -> Option<Problem> {
+    match fs::read_link(launcher) {
+        Ok(target) => {
+            let resolved = launcher
+                .parent()
+                .map(|dir| dir.join(&target))
+                .unwrap_or_else(|| target.clone());
+            let points_elsewhere = target != exe_path
+                && fs::canonicalize(&resolved).ok() != fs::canonicalize(exe_path).ok();
+            (!resolved.exists() || points_elsewhere).then(|| Problem::BrokenLauncher {
+                command: command.to_string(),
+                target,
+            })
+        }
+        Err(_) if launcher.exists() => None,
+        Err(_) => Some(Problem::MissingLauncher {
+            command: command.to_string(),
+            path: launcher.to_path_buf(),
+        }),
+    }
+}
+
+/// Recreate the launcher of one command, like `wenget repair --shims`
+fn recreate_launcher(paths: &WenPaths, pkg: &InstalledPackage, command: &str) -> Result<()> {
+    let Some(exe) = pkg.get_exe_path_for_command(command) else {
+        anyhow::bail!("no executable recorded for {}", command);
+    };
+    let exe_path = Path::new(&pkg.install_path).join(exe);
+    if !exe_path.exists() {
+        anyhow::bail!("executable missing: {}", exe_path.display());
+    }
+
+    let pkg_paths = paths.for_package(pkg.bin_dir.as_deref());
+    fs::create_dir_all(pkg_paths.bin_dir())?;
+    let launcher = pkg_paths.bin_shim_path(command);
+    remove_launcher(&launcher)?;
+    match &pkg.source {
+        PackageSource::Script { script_type, .. } if command == pkg.repo_name => {
+            crate::installer::create_script_shim(&pkg_paths, command, script_type)
+        }
+        _ => link_executable(&exe_path, &launcher, command),
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn package(paths: &WenPaths) -> InstalledPackage {
+        let app_dir = paths.app_dir("ffmpeg");
+        InstalledPackage {
+            repo_name: "ffmpeg".to_string(),
+            variant: None,
+            version: "7.0".to_string(),
+            platform: "linux-x86_64".to_string(),
+            installed_at: Utc::now(),
+            install_path: app_dir.to_string_lossy().to_string(),
+            executables: HashMap::from([
+                ("bin/ffmpeg".to_string(), "ffmpeg".to_string()),
+                ("bin/ffprobe".to_string(), "ffprobe".to_string()),
+            ]),
+            source: PackageSource::DirectRepo {
+                url: "https://github.com/o/ffmpeg".to_string(),
+            },
+            description: String::new(),
+            command_names: vec![],
+            command_name: None,
+            asset_name: "ffmpeg.tar.xz".to_string(),
+            parent_package: None,
+            download_url: None,
+            bin_dir: None,
+            version_constraint: None,
+            compiler: None,
+            checksum: None,
+            files: vec![
+                "bin/ffmpeg".to_string(),
+                "bin/ffprobe".to_string(),
+                "LICENSE".to_string(),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_check_and_repair() {
+        let temp = TempDir::new().unwrap();
+        let paths = WenPaths::with_root(temp.path().to_path_buf(), false);
+        let pkg = package(&paths);
+        let app_dir = paths.app_dir("ffmpeg");
+        for file in &pkg.files {
+            fs::create_dir_all(app_dir.join(file).parent().unwrap()).unwrap();
+            fs::write(app_dir.join(file), "").unwrap();
+        }
+        fs::create_dir_all(paths.bin_dir()).unwrap();
+        for (exe, command) in &pkg.executables {
+            link_executable(&app_dir.join(exe), &paths.bin_shim_path(command), command).unwrap();
+        }
+        assert!(check_package(&paths, &pkg).is_empty());
+
+        // Tampering: a deleted launcher, a launcher pointing elsewhere, a deleted file
+        fs::remove_file(paths.bin_shim_path("ffmpeg")).unwrap();
+        fs::remove_file(paths.bin_shim_path("ffprobe")).unwrap();
+        std::os::unix::fs::symlink("/nowhere/ffprobe", paths.bin_shim_path("ffprobe")).unwrap();
+        fs::remove_file(app_dir.join("LICENSE")).unwrap();
+        assert_eq!(
+            check_package(&paths, &pkg),
+            [
+                Problem::MissingFile("LICENSE".to_string()),
+                Problem::MissingLauncher {
+                    command: "ffmpeg".to_string(),
+                    path: paths.bin_shim_path("ffmpeg"),
+                },
+                Problem::BrokenLauncher {
+                    command: "ffprobe".to_string(),
+                    target: PathBuf::from("/nowhere/ffprobe"),
+                },
+            ]
+        );
+
+        recreate_launcher(&paths, &pkg, "ffmpeg").unwrap();
+        recreate_launcher(&paths, &pkg, "ffprobe").unwrap();
+        assert_eq!(
+            check_package(&paths, &pkg),
+            [Problem::MissingFile("LICENSE".to_string())]
+        );
+        assert_eq!(
+            fs::read_link(paths.bin_shim_path("ffprobe")).unwrap(),
+            app_dir.join("bin/ffprobe")
+        );
+
+        // Without its directory, an executable can't be linked again
+        fs::remove_dir_all(&app_dir).unwrap();
+        let problems = check_package(&paths, &pkg);
+        assert_eq!(problems[0], Problem::MissingInstallDir(app_dir.clone()));
+        assert!(problems
+            .iter()
+            .all(|p| !matches!(p, Problem::MissingFile(_))));
+        assert!(recreate_launcher(&paths, &pkg, "ffmpeg").is_err());
+    }
+}
@@ -7,10 +7,12 @@
 //! - Directory initialization
 
 use super::manifest::{InstalledManifest, SourceManifest};
+use super::manifest_schema::parse_source_manifest;
 use super::paths::WenPaths;
 use super::preferences::Preferences;
 use crate::bucket::BucketConfig;
 use crate::cache::ManifestCache;
+use crate::utils::network::{NetworkOverrides, NetworkSettings};
 use anyhow::{Context, Result};
 use std::fs;
 use std::path::Path;
@@ -20,17 +22,25 @@ pub struct Config {
     paths: WenPaths,
     #[allow(dead_code)]
     preferences: Preferences,
+    network: NetworkSettings,
+    prereleases: bool,
 }
 
 impl Config {
     /// Create a new Config instance
     pub fn new() -> Result<Self> {
-        // First, create a temporary WenPaths to get the config file path
-        let temp_paths = WenPaths::new()?;
-        let config_path = temp_paths.config_toml();
+        Self::with_network(NetworkOverrides::default())
+    }
+
+    /// Create a new Config instance, applying `--timeout` / `--retries` from the CLI
+    pub fn with_network(overrides: NetworkOverrides) -> Result<Self> {
+        Self::load(WenPaths::new()?, overrides)
+    }
 
+    /// Create a Config for the wenget root of `paths`, applying its config.toml
+    pub(crate) fn load(paths: WenPaths, overrides: NetworkOverrides) -> Result<Self> {
         // Load preferences
-        let preferences = Preferences::load(&config_path)?;
+        let preferences = Preferences::load(&paths.config_toml())?;
 
         // Validate preferences
         if let Err(e) = preferences.validate() {
@@ -38,10 +48,21 @@ impl Config {
             log::warn!("Using default preferences instead");
         }
 
-        // Create WenPaths with custom bin directory if specified
-        let paths = WenPaths::new_with_custom_bin(preferences.custom_bin_path.clone())?;
+        // Launchers go to `custom_bin_path` when it is set; relative paths are rejected
+        // by validation above and ignored here
+        let paths = match preferences.custom_bin_path.clone() {
+            Some(dir) if dir.is_absolute() => paths.with_bin_dir(dir),
+            _ => paths,
+        };
+
+        let network = NetworkSettings::from_env(overrides, &preferences);
 
-        Ok(Self { paths, preferences })
+        Ok(Self {
+            paths,
+            preferences,
+            network,
+            prereleases: false,
+        })
     }
 
     /// Get the paths manager
@@ -55,6 +76,22 @@ impl Config {
         &self.preferences
     }
 
+    /// Consider prereleases when looking up the latest release (`--pre`)
+    pub fn with_prereleases(mut self, prereleases: bool) -> Self {
+        self.prereleases = prereleases;
+        self
+    }
+
+    /// Whether prereleases count as the latest release
+    pub fn prereleases(&self) -> bool {
+        self.prereleases
+    }
+
+    /// Get the resolved network timeout and retry settings
+    pub fn network(&self) -> &NetworkSettings {
+        &self.network
+    }
+
     /// Initialize WenPM (create directories if needed)
     pub fn init(&self) -> Result<()> {
         self.paths.init_dirs()?;
@@ -208,9 +245,23 @@ impl Config {
 
     /// Get or rebuild manifest cache
     /// Returns the cache if valid, otherwise rebuilds it
+    ///
+    /// In offline mode an existing cache is used even when it has expired.
     pub fn get_or_rebuild_cache(&self) -> Result<ManifestCache> {
         let cache = self.load_cache()?;
 
+        if self.network.offline {
+            if !self.paths.manifest_cache_json().exists() {
+                anyhow::bail!(
+                    "Offline mode: no manifest cache yet. Run 'wenget bucket refresh' while online"
+                );
+            }
+            if !cache.is_valid() {
+                log::info!("Offline mode: using the expired manifest cache");
+            }
+            return Ok(cache);
+        }
+
         // Check if cache is valid
         if cache.is_valid() && !cache.packages.is_empty() {
             return Ok(cache);
@@ -222,11 +273,14 @@ impl Config {
 
     /// Force rebuild manifest cache from buckets only
     pub fn rebuild_cache(&self) -> Result<ManifestCache> {
-        use crate::bucket::Bucket;
+        use crate::bucket::{fetch_manifest_text, Bucket};
         use crate::cache::build_cache_from_results;
-        use crate::utils::HttpClient;
         use std::time::Duration;
 
+        if self.network.offline {
+            anyhow::bail!("Offline mode: cannot fetch buckets to rebuild the manifest cache");
+        }
+
         let bucket_config = self.get_or_create_buckets()?;
         let enabled_buckets: Vec<Bucket> = bucket_config
             .enabled_buckets()
@@ -240,6 +294,13 @@ impl Config {
             return Ok(cache);
         }
 
+        // Bucket manifests are small, so they get a shorter default timeout
+        let settings = NetworkSettings {
+            timeout: Some(self.network.timeout_or(Duration::from_secs(10))),
+            ..self.network.clone()
+        };
+        let settings = &settings;
+
         let results: Vec<(Bucket, Result<SourceManifest>)> = std::thread::scope(|scope| {
             let handles: Vec<_> = enabled_buckets
                 .into_iter()
@@ -250,12 +311,9 @@ impl Config {
                         log::debug!("Fetching bucket '{}' from {}", name, url);
 
                         let fetch_result = (|| -> Result<SourceManifest> {
-                            let http = HttpClient::with_timeout(Duration::from_secs(10))?;
-                            let content = http
-                                .get_text(&url)
-                                .with_context(|| format!("Failed to fetch bucket from {}", url))?;
-                            serde_json::from_str(&content).with_context(|| {
-                                format!("Failed to parse bucket manifest from {}", url)
+                            let content = fetch_manifest_text(&url, settings)?;
+                            parse_source_manifest(&content).with_context(|| {
+                                format!("Invalid manifest in bucket '{}' ({})", name, url)
                             })
                         })();
 
@@ -304,6 +362,83 @@ mod tests {
         (config, temp_dir)
     }
 
+    #[test]
+    fn test_custom_bin_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        let bin = root.join("my-bin");
+        fs::write(
+            root.join("config.toml"),
+            format!("custom_bin_path = {:?}\n", bin.to_string_lossy()),
+        )
+        .unwrap();
+
+        let config = Config::load(
+            WenPaths::with_root(root.clone(), false),
+            NetworkOverrides::default(),
+        )
+        .unwrap();
+        assert_eq!(config.paths().bin_dir(), bin);
+        assert_eq!(config.paths().custom_bin_dir(), Some(bin.as_path()));
+        assert!(config.paths().bin_shim_path("rg").starts_with(&bin));
+
+        // Relative paths are ignored
+        fs::write(root.join("config.toml"), "custom_bin_path = \"bin2\"\n").unwrap();
+        let config = Config::load(
+            WenPaths::with_root(root.clone(), false),
+            NetworkOverrides::default(),
+        )
+        .unwrap();
+        assert_eq!(config.paths().bin_dir(), root.join("bin"));
+    }
+
+    #[test]
+    fn test_rebuild_cache_from_local_buckets() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("wenget");
+        let manifest = |name: &str| {
+            format!(
+                r#"{{"packages": [{{"name": "{}", "description": "A tool",
+                    "repo": "https://github.com/o/{}", "platforms": {{}}}}], "scripts": []}}"#,
+                name, name
+            )
+        };
+        let share = temp_dir.path().join("share");
+        fs::create_dir_all(&share).unwrap();
+        fs::write(share.join("plain.json"), manifest("plain-tool")).unwrap();
+        fs::write(share.join("url.json"), manifest("url-tool")).unwrap();
+
+        let config = Config::load(
+            WenPaths::with_root(root, false),
+            NetworkOverrides::default(),
+        )
+        .unwrap();
+        config.init().unwrap();
+        let mut buckets = BucketConfig::new();
+        for (name, url) in [
+            (
+                "plain",
+                share.join("plain.json").to_string_lossy().to_string(),
+            ),
+            (
+                "url",
+                format!("file://{}", share.join("url.json").to_string_lossy()),
+            ),
+        ] {
+            buckets.add_bucket(crate::bucket::Bucket {
+                name: name.to_string(),
+                url,
+                enabled: true,
+                priority: crate::bucket::default_priority(),
+            });
+        }
+        config.save_buckets(&buckets).unwrap();
+
+        let cache = config.rebuild_cache().unwrap();
+        assert!(cache.find_package("plain-tool").is_some());
+        assert!(cache.find_package("url-tool").is_some());
+    }
+
     #[test]
     fn test_config_creation() {
         let config = Config::new();
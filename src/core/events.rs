@@ -0,0 +1,59 @@
+//! Structured progress events for install, update and delete
+//!
+//! The install and delete paths report their progress through a [`ProgressSink`]
+//! instead of printing directly, so callers driving wenget programmatically can
+//! consume structured events. The CLI uses [`TerminalSink`].
+
+use colored::Colorize;
+use std::path::PathBuf;
+
+/// A step in installing or removing a package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstallEvent {
+    /// An asset download is starting
+    Downloading { package: String, url: String },
+    /// The downloaded archive is being unpacked into the app directory
+    Extracting { package: String, dest: PathBuf },
+    /// A launcher was created for one of the package's commands
+    Linked {
+        package: String,
+        command: String,
+        launcher: PathBuf,
+    },
+    /// The package was installed (or updated) successfully; scripts carry no version
+    Installed {
+        package: String,
+        version: Option<String>,
+    },
+    /// The package was removed
+    Removed { package: String },
+    /// Installing or removing the package failed
+    Failed { package: String, error: String },
+}
+
+/// Receiver for [`InstallEvent`]s
+pub trait ProgressSink {
+    fn on_event(&self, event: InstallEvent);
+}
+
+/// Prints events as the CLI's indented status lines
+pub struct TerminalSink;
+
+impl ProgressSink for TerminalSink {
+    fn on_event(&self, event: InstallEvent) {
+        match event {
+            InstallEvent::Downloading { url, .. } => println!("  Downloading from {}...", url),
+            InstallEvent::Extracting { dest, .. } => {
+                println!("  Extracting to {}...", dest.display())
+            }
+            InstallEvent::Linked { launcher, .. } => {
+                println!("  Created launcher at {}", launcher.display())
+            }
+            InstallEvent::Installed { .. } => {
+                println!("  {} Installed successfully", "✓".green())
+            }
+            InstallEvent::Removed { .. } => println!("  {} Deleted successfully", "✓".green()),
+            InstallEvent::Failed { error, .. } => println!("  {} {}", "✗".red(), error),
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Install history
+//!
+//! Every successful install, update and delete appends one JSON line to
+//! `history.jsonl` in the wenget root; `wenget log` prints it. installed.json
+//! only knows when the current version was installed, the history keeps
+//! everything before it. Recording is best-effort: a history that can't be
+//! written is logged and never fails the operation itself.
+
+use crate::core::manifest::PackageSource;
+use crate::core::{InstalledPackage, WenPaths};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+/// What happened to a package
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    Install,
+    Update,
+    Delete,
+}
+
+impl Action {
+    /// Name shown by `wenget log`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Action::Install => "install",
+            Action::Update => "update",
+            Action::Delete => "delete",
+        }
+    }
+}
+
+/// One line of the history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: DateTime<Utc>,
+    pub action: Action,
+    /// Installed package key (e.g. "ripgrep", "bun::baseline")
+    pub package: String,
+    pub version: String,
+    pub source: PackageSource,
+}
+
+impl HistoryEntry {
+    /// Entry for `pkg`, installed under `package`, stamped now
+    pub fn new(action: Action, package: &str, pkg: &InstalledPackage) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            action,
+            package: package.to_string(),
+            version: pkg.version.clone(),
+            source: pkg.source.clone(),
+        }
+    }
+}
+
+/// Append an entry to the history file, creating it if needed
+pub fn append(path: &Path, entry: &HistoryEntry) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    let line = serde_json::to_string(entry)?;
+    writeln!(file, "{}", line).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Record an operation in the history of `paths`, logging instead of failing
+pub fn record(paths: &WenPaths, action: Action, package: &str, pkg: &InstalledPackage) {
+    let entry = HistoryEntry::new(action, package, pkg);
+    if let Err(e) = append(&paths.history_jsonl(), &entry) {
+        log::warn!(
+            "Failed to record {} of {}: {:#}",
+            action.as_str(),
+            package,
+            e
+        );
+    }
+}
+
+/// The last `limit` entries, oldest first
+///
+/// A missing file is an empty history. Lines that don't parse (e.g. cut off
+/// by a crash) are skipped.
+pub fn read_last(path: &Path, limit: usize) -> Result<Vec<HistoryEntry>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+
+    let entries: Vec<HistoryEntry> = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                log::warn!("Skipping unreadable history line: {}", e);
+                None
+            }
+        })
+        .collect();
+    let skip = entries.len().saturating_sub(limit);
+    Ok(entries.into_iter().skip(skip).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(action: Action, version: &str) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: Utc::now(),
+            action,
+            package: "ripgrep".to_string(),
+            version: version.to_string(),
+            source: PackageSource::Bucket {
+                name: "main".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_append_and_read_last() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("history.jsonl");
+        assert!(read_last(&path, 10).unwrap().is_empty());
+
+        let entries = [
+            entry(Action::Install, "13.0.0"),
+            entry(Action::Update, "14.1.0"),
+            entry(Action::Delete, "14.1.0"),
+        ];
+        for e in &entries {
+            append(&path, e).unwrap();
+        }
+        // A line cut off by a crash doesn't hide the others
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(file, "{{\"timestamp\":").unwrap();
+
+        assert_eq!(read_last(&path, 10).unwrap(), entries);
+        assert_eq!(read_last(&path, 2).unwrap(), entries[1..]);
+        assert!(read_last(&path, 0).unwrap().is_empty());
+
+        let line = fs::read_to_string(&path).unwrap();
+        assert!(line.starts_with("{\"timestamp\":"));
+        assert!(line.contains("\"action\":\"install\""));
+    }
+}
@@ -0,0 +1,78 @@
+//! Process lock for mutating operations
+//!
+//! Commands that modify installed.json, buckets or the cache hold an advisory lock
+//! on `{root}/lock` so concurrent wenget processes can't overwrite each other's state.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use fs2::FileExt;
+use std::fs::{self, File, OpenOptions};
+use std::path::Path;
+
+/// Exclusive lock on the wenget state, released when dropped
+pub struct StateLock {
+    file: File,
+}
+
+impl StateLock {
+    /// Acquire the lock at `path`
+    ///
+    /// Fails immediately if another process holds it, unless `wait` is set, in which
+    /// case this blocks until the lock is released.
+    pub fn acquire(path: &Path, wait: bool) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)
+            .with_context(|| format!("Failed to open lock file: {}", path.display()))?;
+
+        if file.try_lock_exclusive().is_err() {
+            if !wait {
+                anyhow::bail!(
+                    "Another wenget operation is in progress (lock: {}). Re-run with --wait to wait for it to finish",
+                    path.display()
+                );
+            }
+
+            println!(
+                "{}",
+                "Waiting for another wenget operation to finish...".dimmed()
+            );
+            file.lock_exclusive()
+                .with_context(|| format!("Failed to lock {}", path.display()))?;
+        }
+
+        Ok(Self { file })
+    }
+}
+
+impl Drop for StateLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_lock_is_exclusive() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("lock");
+
+        let lock = StateLock::acquire(&path, false).unwrap();
+        let err = StateLock::acquire(&path, false).err().unwrap();
+        assert!(err.to_string().contains("in progress"));
+
+        drop(lock);
+        assert!(StateLock::acquire(&path, false).is_ok());
+    }
+}
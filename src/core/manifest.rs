@@ -199,6 +199,10 @@ pub struct Package {
     /// Package name (used as identifier)
     pub name: String,
 
+    /// Alternative names the package can be found by (e.g. "rg" for ripgrep)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+
     /// Short description
     pub description: String,
 
@@ -223,6 +227,48 @@ pub struct Package {
     /// Examples: "windows-x86_64", "linux-x86_64-musl", "macos-aarch64"
     /// Each platform can have multiple package variants (e.g., baseline, desktop, etc.)
     pub platforms: HashMap<String, Vec<PlatformBinary>>,
+
+    /// Shell commands run in the app directory after extraction, before launchers
+    /// are created (bucket manifests only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pre_install: Vec<String>,
+
+    /// Shell commands run in the app directory after launchers are created
+    /// (bucket manifests only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_install: Vec<String>,
+}
+
+impl Package {
+    /// The package name followed by its aliases
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.name.as_str()).chain(self.aliases.iter().map(String::as_str))
+    }
+
+    /// Whether the package declares any install hooks
+    pub fn has_install_hooks(&self) -> bool {
+        !self.pre_install.is_empty() || !self.post_install.is_empty()
+    }
+
+    /// Copy install hooks from `other` when this package has none
+    ///
+    /// Release info fetched from the GitHub API never carries hooks, so they are
+    /// inherited from the bucket entry the package was resolved from.
+    pub fn inherit_install_hooks(&mut self, other: &Package) {
+        if !self.has_install_hooks() {
+            self.pre_install = other.pre_install.clone();
+            self.post_install = other.post_install.clone();
+        }
+    }
+
+    /// Copy aliases from `other` when this package has none
+    ///
+    /// Like install hooks, aliases only exist in bucket manifests.
+    pub fn inherit_aliases(&mut self, other: &Package) {
+        if self.aliases.is_empty() {
+            self.aliases = other.aliases.clone();
+        }
+    }
 }
 
 /// Script item metadata (for bucket scripts)
@@ -448,6 +494,33 @@ pub struct InstalledPackage {
     /// Used for scripts from buckets to detect updates via URL change
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub download_url: Option<String>,
+
+    /// Directory the launchers were created in, when it differs from the default
+    /// (e.g. set by `wenget add --bin-dir`). None means the configured bin directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bin_dir: Option<String>,
+
+    /// Semver range updates are restricted to (e.g. "^13", set by `wenget add ripgrep@^13`).
+    /// None means updates follow the latest release.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_constraint: Option<String>,
+
+    /// Compiler/libc variant pinned with `wenget add <name> --compiler` (e.g. "gnu").
+    /// Updates keep installing this variant. None follows automatic selection.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compiler: Option<String>,
+
+    /// Checksum of the downloaded asset as published by the source (e.g. "sha256:...").
+    /// None when the source published none. For scripts, the SHA256 of the
+    /// installed content, which `update` compares with the script's origin.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+
+    /// Files extracted into install_path, relative to it (e.g. ["bin/rg", "README.md"]),
+    /// followed by the absolute paths of completions copied with `add --completions`.
+    /// Empty for scripts and for packages installed by older versions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
 }
 
 impl InstalledPackage {
@@ -468,6 +541,22 @@ impl InstalledPackage {
             .find(|(_, name)| name.as_str() == command_name)
             .map(|(path, _)| path.as_str())
     }
+
+    /// Executables (relative to install_path) that are no longer on disk
+    ///
+    /// A package whose install directory is gone reports all of them, so an
+    /// interrupted extraction shows up even though the manifest entry exists.
+    pub fn missing_executables(&self) -> Vec<&str> {
+        let install_path = std::path::Path::new(&self.install_path);
+        let mut missing: Vec<&str> = self
+            .executables
+            .keys()
+            .filter(|exe| !install_path.join(exe).is_file())
+            .map(|exe| exe.as_str())
+            .collect();
+        missing.sort();
+        missing
+    }
 }
 
 /// Installed manifest (installed.json)
@@ -788,7 +877,9 @@ pub fn extract_variant_from_asset(asset_name: &str, repo_name: &str) -> Option<S
         .trim_end_matches(".tar.xz")
         .trim_end_matches(".exe")
         .trim_end_matches(".7z")
-        .trim_end_matches(".tgz");
+        .trim_end_matches(".tgz")
+        .trim_end_matches(".tar.lz4")
+        .trim_end_matches(".tar");
 
     // Remove repo name prefix (case-insensitive)
     let repo_lower = repo_name.to_lowercase();
@@ -944,6 +1035,32 @@ pub fn generate_installed_key(repo_name: &str, variant: Option<&str>) -> String
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_package_install_hooks() {
+        let json = r#"{
+            "name": "tool",
+            "description": "A tool",
+            "repo": "https://github.com/owner/tool",
+            "platforms": {},
+            "post_install": ["./tool --init"]
+        }"#;
+        let bucket_pkg: Package = serde_json::from_str(json).unwrap();
+        assert!(bucket_pkg.pre_install.is_empty());
+        assert_eq!(bucket_pkg.post_install, vec!["./tool --init"]);
+
+        // Package info from the API has no hooks and inherits the bucket's
+        let mut api_pkg = bucket_pkg.clone();
+        api_pkg.post_install.clear();
+        assert!(!api_pkg.has_install_hooks());
+        api_pkg.inherit_install_hooks(&bucket_pkg);
+        assert_eq!(api_pkg.post_install, bucket_pkg.post_install);
+
+        // Packages without hooks don't serialize the fields
+        api_pkg.post_install.clear();
+        let out = serde_json::to_string(&api_pkg).unwrap();
+        assert!(!out.contains("post_install"));
+    }
+
     #[test]
     fn test_source_manifest_new() {
         let manifest = SourceManifest::new();
@@ -974,6 +1091,11 @@ mod tests {
             asset_name: "test-windows-x64.zip".to_string(),
             parent_package: None,
             download_url: None,
+            bin_dir: None,
+            version_constraint: None,
+            compiler: None,
+            checksum: None,
+            files: Vec::new(),
         };
 
         manifest.upsert_package("test".to_string(), package);
@@ -1007,6 +1129,11 @@ mod tests {
             asset_name: "ripgrep-linux-x64.tar.gz".to_string(),
             parent_package: None,
             download_url: None,
+            bin_dir: None,
+            version_constraint: None,
+            compiler: None,
+            checksum: None,
+            files: Vec::new(),
         };
 
         let names = pkg.get_command_names();
@@ -1019,6 +1146,46 @@ mod tests {
         assert_eq!(pkg.get_exe_path_for_command("nonexistent"), None);
     }
 
+    #[test]
+    fn test_missing_executables() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let app_dir = temp.path().join("ripgrep");
+        std::fs::create_dir_all(app_dir.join("bin")).unwrap();
+        std::fs::write(app_dir.join("bin/rg"), "").unwrap();
+
+        let mut executables = HashMap::new();
+        executables.insert("bin/rg".to_string(), "rg".to_string());
+        executables.insert("bin/rg-doc".to_string(), "rg-doc".to_string());
+
+        let mut pkg = InstalledPackage {
+            repo_name: "ripgrep".to_string(),
+            variant: None,
+            version: "14.0.0".to_string(),
+            platform: "linux-x86_64".to_string(),
+            installed_at: Utc::now(),
+            install_path: app_dir.to_string_lossy().to_string(),
+            executables,
+            source: PackageSource::Bucket {
+                name: "main".to_string(),
+            },
+            description: "Search tool".to_string(),
+            command_names: vec![],
+            command_name: None,
+            asset_name: "ripgrep-linux-x64.tar.gz".to_string(),
+            parent_package: None,
+            download_url: None,
+            bin_dir: None,
+            version_constraint: None,
+            compiler: None,
+            checksum: None,
+            files: Vec::new(),
+        };
+        assert_eq!(pkg.missing_executables(), vec!["bin/rg-doc"]);
+
+        pkg.install_path = temp.path().join("gone").to_string_lossy().to_string();
+        assert_eq!(pkg.missing_executables(), vec!["bin/rg", "bin/rg-doc"]);
+    }
+
     #[test]
     fn test_is_command_taken_with_executables() {
         let mut manifest = InstalledManifest::new();
@@ -1043,6 +1210,11 @@ mod tests {
             asset_name: "rg.tar.gz".to_string(),
             parent_package: None,
             download_url: None,
+            bin_dir: None,
+            version_constraint: None,
+            compiler: None,
+            checksum: None,
+            files: Vec::new(),
         };
 
         manifest.upsert_package("ripgrep".to_string(), pkg);
@@ -1081,6 +1253,11 @@ mod tests {
                 asset_name: "rg.tar.gz".to_string(),
                 parent_package: None,
                 download_url: None,
+                bin_dir: None,
+                version_constraint: None,
+                compiler: None,
+                checksum: None,
+                files: Vec::new(),
             },
         );
 
@@ -1104,6 +1281,11 @@ mod tests {
                 asset_name: "fzf.tar.gz".to_string(),
                 parent_package: None,
                 download_url: None,
+                bin_dir: None,
+                version_constraint: None,
+                compiler: None,
+                checksum: None,
+                files: Vec::new(),
             },
         );
 
@@ -1168,6 +1350,11 @@ mod tests {
             asset_name: "test.tar.gz".to_string(),
             parent_package: None,
             download_url: None,
+            bin_dir: None,
+            version_constraint: None,
+            compiler: None,
+            checksum: None,
+            files: Vec::new(),
         };
 
         let json = serde_json::to_string(&pkg).unwrap();
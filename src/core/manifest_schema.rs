@@ -0,0 +1,413 @@
+//! Structural checks of bucket manifests
+//!
+//! A plain typed parse stops at the first mismatch with messages like
+//! "missing field `repo` at line 12", which don't say which package is wrong.
+//! The manifest is checked as a JSON value first, naming the entry, the field
+//! and what was expected, and only then parsed into a [`SourceManifest`].
+
+use super::manifest::SourceManifest;
+use anyhow::{anyhow, Result};
+use serde_json::{Map, Value};
+
+/// Script types a manifest may declare (the serialized `ScriptType` names)
+pub const KNOWN_SCRIPT_TYPES: &[&str] = &["powershell", "batch", "bash", "python"];
+
+/// Problems listed in one error; the rest are counted
+const MAX_LISTED: usize = 10;
+
+/// What a field has to hold
+struct Expect {
+    /// Description used in messages, e.g. "a string"
+    what: &'static str,
+    fits: fn(&Value) -> bool,
+}
+
+const STRING: Expect = Expect {
+    what: "a string",
+    fits: Value::is_string,
+};
+
+const STRINGS: Expect = Expect {
+    what: "an array of strings",
+    fits: is_string_array,
+};
+
+const SIZE: Expect = Expect {
+    what: "a whole number of bytes",
+    fits: Value::is_u64,
+};
+
+const PLATFORMS: Expect = Expect {
+    what: "an object keyed by os-arch (e.g. \"linux-x86_64\")",
+    fits: Value::is_object,
+};
+
+const SCRIPT_PLATFORMS: Expect = Expect {
+    what: "an object keyed by script type (powershell, batch, bash or python)",
+    fits: Value::is_object,
+};
+
+/// Whether a field may be left out, or also be null (`Option` fields)
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Presence {
+    Required,
+    Optional,
+    Nullable,
+}
+
+fn is_string_array(value: &Value) -> bool {
+    value
+        .as_array()
+        .is_some_and(|items| items.iter().all(Value::is_string))
+}
+
+/// "a string", "an array", ... for messages about what was found instead
+fn kind_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+/// Parse a bucket manifest, with errors naming the offending entries
+pub fn parse_source_manifest(content: &str) -> Result<SourceManifest> {
+    let value: Value =
+        serde_json::from_str(content).map_err(|e| anyhow!("Manifest is not valid JSON: {}", e))?;
+    manifest_from_value(value)
+}
+
+/// Check a manifest parsed as JSON and convert it
+pub fn manifest_from_value(value: Value) -> Result<SourceManifest> {
+    let errors = schema_errors(&value);
+    if !errors.is_empty() {
+        return Err(anyhow!(format_errors(&errors)));
+    }
+    // Anything the checks above miss still gets serde's message
+    serde_json::from_value(value)
+        .map_err(|e| anyhow!("Manifest does not match the bucket format: {}", e))
+}
+
+/// Everything structurally wrong with a manifest
+pub fn schema_errors(value: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    let Some(root) = value.as_object() else {
+        errors.push(format!(
+            "manifest must be an object with a `packages` array, got {}",
+            kind_name(value)
+        ));
+        return errors;
+    };
+
+    match root.get("packages") {
+        None => errors.push("missing `packages` (an array of packages)".to_string()),
+        Some(Value::Array(packages)) => {
+            for (index, package) in packages.iter().enumerate() {
+                check_package(
+                    &mut errors,
+                    &entry_label("packages", index, package),
+                    package,
+                );
+            }
+        }
+        Some(other) => errors.push(format!(
+            "`packages` must be an array of packages, got {}",
+            kind_name(other)
+        )),
+    }
+
+    match root.get("scripts") {
+        None => {}
+        Some(Value::Array(scripts)) => {
+            for (index, script) in scripts.iter().enumerate() {
+                check_script(&mut errors, &entry_label("scripts", index, script), script);
+            }
+        }
+        Some(other) => errors.push(format!(
+            "`scripts` must be an array of scripts, got {}",
+            kind_name(other)
+        )),
+    }
+
+    errors
+}
+
+/// "packages[3] (ripgrep)", or just "packages[3]" without a usable name
+fn entry_label(list: &str, index: usize, entry: &Value) -> String {
+    match entry.get("name").and_then(Value::as_str) {
+        Some(name) => format!("{}[{}] ({})", list, index, name),
+        None => format!("{}[{}]", list, index),
+    }
+}
+
+/// Check one field of an object, returning its value when it has the right type
+fn check_field<'a>(
+    errors: &mut Vec<String>,
+    at: &str,
+    object: &'a Map<String, Value>,
+    key: &str,
+    presence: Presence,
+    expect: &Expect,
+) -> Option<&'a Value> {
+    match object.get(key) {
+        None if presence == Presence::Required => {
+            errors.push(format!("{}: missing `{}` ({})", at, key, expect.what));
+            None
+        }
+        None => None,
+        Some(Value::Null) if presence == Presence::Nullable => None,
+        Some(value) if (expect.fits)(value) => Some(value),
+        Some(value) => {
+            errors.push(format!(
+                "{}: `{}` must be {}, got {}",
+                at,
+                key,
+                expect.what,
+                kind_name(value)
+            ));
+            None
+        }
+    }
+}
+
+fn check_package(errors: &mut Vec<String>, at: &str, package: &Value) {
+    let Some(object) = package.as_object() else {
+        errors.push(format!(
+            "{}: must be an object, got {}",
+            at,
+            kind_name(package)
+        ));
+        return;
+    };
+
+    for key in ["name", "description", "repo"] {
+        check_field(errors, at, object, key, Presence::Required, &STRING);
+    }
+    for key in ["homepage", "license", "version"] {
+        check_field(errors, at, object, key, Presence::Nullable, &STRING);
+    }
+    for key in ["aliases", "pre_install", "post_install"] {
+        check_field(errors, at, object, key, Presence::Optional, &STRINGS);
+    }
+
+    let Some(platforms) = check_field(
+        errors,
+        at,
+        object,
+        "platforms",
+        Presence::Required,
+        &PLATFORMS,
+    )
+    .and_then(Value::as_object) else {
+        return;
+    };
+    for (id, binaries) in platforms {
+        let Some(binaries) = binaries.as_array() else {
+            errors.push(format!(
+                "{}: platforms.{} must be an array of binaries ({{url, size, asset_name}}), got {}",
+                at,
+                id,
+                kind_name(binaries)
+            ));
+            continue;
+        };
+        for (index, binary) in binaries.iter().enumerate() {
+            let at = format!("{}: platforms.{}[{}]", at, id, index);
+            let Some(binary) = binary.as_object() else {
+                errors.push(format!(
+                    "{} must be an object with url, size and asset_name, got {}",
+                    at,
+                    kind_name(binary)
+                ));
+                continue;
+            };
+            check_field(errors, &at, binary, "url", Presence::Required, &STRING);
+            check_field(errors, &at, binary, "size", Presence::Required, &SIZE);
+            check_field(
+                errors,
+                &at,
+                binary,
+                "asset_name",
+                Presence::Required,
+                &STRING,
+            );
+            check_field(errors, &at, binary, "checksum", Presence::Nullable, &STRING);
+        }
+    }
+}
+
+fn check_script(errors: &mut Vec<String>, at: &str, script: &Value) {
+    let Some(object) = script.as_object() else {
+        errors.push(format!(
+            "{}: must be an object, got {}",
+            at,
+            kind_name(script)
+        ));
+        return;
+    };
+
+    for key in ["name", "description", "repo"] {
+        check_field(errors, at, object, key, Presence::Required, &STRING);
+    }
+    for key in ["homepage", "license"] {
+        check_field(errors, at, object, key, Presence::Nullable, &STRING);
+    }
+
+    let Some(platforms) = check_field(
+        errors,
+        at,
+        object,
+        "platforms",
+        Presence::Required,
+        &SCRIPT_PLATFORMS,
+    )
+    .and_then(Value::as_object) else {
+        return;
+    };
+    for (script_type, platform) in platforms {
+        if !KNOWN_SCRIPT_TYPES.contains(&script_type.as_str()) {
+            errors.push(format!(
+                "{}: unknown script type '{}' (expected powershell, batch, bash or python)",
+                at, script_type
+            ));
+            continue;
+        }
+        let at = format!("{}: platforms.{}", at, script_type);
+        let Some(platform) = platform.as_object() else {
+            errors.push(format!(
+                "{} must be an object with a url, got {}",
+                at,
+                kind_name(platform)
+            ));
+            continue;
+        };
+        check_field(errors, &at, platform, "url", Presence::Required, &STRING);
+        check_field(
+            errors,
+            &at,
+            platform,
+            "checksum",
+            Presence::Nullable,
+            &STRING,
+        );
+    }
+}
+
+/// One problem as is, several as a list
+fn format_errors(errors: &[String]) -> String {
+    if let [only] = errors {
+        return only.clone();
+    }
+    let mut message = format!("{} problems in the manifest:", errors.len());
+    for error in errors.iter().take(MAX_LISTED) {
+        message.push_str("\n  - ");
+        message.push_str(error);
+    }
+    if errors.len() > MAX_LISTED {
+        message.push_str(&format!("\n  - and {} more", errors.len() - MAX_LISTED));
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn valid_package() -> Value {
+        json!({
+            "name": "ripgrep",
+            "description": "Fast grep",
+            "repo": "https://github.com/BurntSushi/ripgrep",
+            "homepage": null,
+            "platforms": {
+                "linux-x86_64": [{
+                    "url": "https://example.com/rg.tar.gz",
+                    "size": 1024,
+                    "asset_name": "rg.tar.gz"
+                }]
+            }
+        })
+    }
+
+    #[test]
+    fn test_valid_manifest() {
+        let manifest = json!({
+            "packages": [valid_package()],
+            "scripts": [{
+                "name": "hello",
+                "description": "Says hello",
+                "repo": "https://github.com/o/hello",
+                "platforms": {"bash": {"url": "https://example.com/hello.sh"}}
+            }]
+        });
+        assert!(schema_errors(&manifest).is_empty());
+        let parsed = parse_source_manifest(&manifest.to_string()).unwrap();
+        assert_eq!(parsed.packages.len(), 1);
+        assert_eq!(parsed.scripts.len(), 1);
+    }
+
+    #[test]
+    fn test_errors_name_entry_and_field() {
+        let mut broken = valid_package();
+        broken.as_object_mut().unwrap().remove("repo");
+        broken["platforms"] = json!([]);
+        let mut bad_binary = valid_package();
+        bad_binary["name"] = json!("fd");
+        bad_binary["platforms"]["linux-x86_64"][0]["size"] = json!("1 MB");
+
+        let errors = schema_errors(&json!({ "packages": [valid_package(), broken, bad_binary] }));
+        assert_eq!(
+            errors,
+            [
+                "packages[1] (ripgrep): missing `repo` (a string)",
+                "packages[1] (ripgrep): `platforms` must be an object keyed by os-arch (e.g. \"linux-x86_64\"), got an array",
+                "packages[2] (fd): platforms.linux-x86_64[0]: `size` must be a whole number of bytes, got a string",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_top_level_and_scripts() {
+        assert_eq!(
+            schema_errors(&json!([])),
+            ["manifest must be an object with a `packages` array, got an array"]
+        );
+        assert_eq!(
+            schema_errors(&json!({})),
+            ["missing `packages` (an array of packages)"]
+        );
+
+        let errors = schema_errors(&json!({
+            "packages": [],
+            "scripts": [{
+                "name": "hello",
+                "description": "",
+                "repo": "",
+                "platforms": {"fish": {"url": "x"}, "bash": "https://example.com/hello.sh"}
+            }]
+        }));
+        assert_eq!(errors.len(), 2);
+        assert!(errors
+            .iter()
+            .any(|e| e.contains("unknown script type 'fish'")));
+        assert!(errors
+            .iter()
+            .any(|e| e.starts_with("scripts[0] (hello): platforms.bash must be an object")));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse_source_manifest("<html>Not Found</html>").unwrap_err();
+        assert!(err.to_string().starts_with("Manifest is not valid JSON"));
+
+        let many: Vec<Value> = (0..12).map(|_| json!({})).collect();
+        let err = parse_source_manifest(&json!({ "packages": many }).to_string()).unwrap_err();
+        let message = err.to_string();
+        assert!(message.starts_with("48 problems in the manifest:"));
+        assert!(message.ends_with("and 38 more"));
+    }
+}
@@ -1,13 +1,18 @@
 //! Core modules for WenPM
 
 pub mod config;
+pub mod events;
+pub mod history;
+pub mod lock;
 pub mod manifest;
+pub mod manifest_schema;
 pub mod paths;
 pub mod platform;
 pub mod preferences;
 pub mod privilege;
 pub mod registry;
 pub mod repair;
+pub mod version;
 
 // Re-export commonly used items
 pub use config::Config;
@@ -18,7 +23,9 @@ pub use manifest::{
 };
 pub use paths::WenPaths;
 #[allow(unused_imports)]
-pub use platform::{Arch, BinaryAsset, BinarySelector, Compiler, FileExtension, Os, Platform};
+pub use platform::{
+    Arch, BinaryAsset, BinarySelector, Compiler, FileExtension, Os, Platform, ScoringWeights,
+};
 pub use preferences::Preferences;
 pub use privilege::is_elevated;
 #[allow(unused_imports)]
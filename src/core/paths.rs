@@ -14,6 +14,9 @@
 //! ## System-level installation (when running as root/Administrator):
 //! - Linux: /opt/wenget/ with symlinks in /usr/local/bin
 //! - Windows: %ProgramW6432%\wenget\ with bin in PATH
+//!
+//! ## Custom root (`WENGET_HOME` set):
+//! - Everything lives under $WENGET_HOME, including the bin directory ($WENGET_HOME/bin)
 
 use crate::core::privilege::is_elevated;
 use anyhow::{Context, Result};
@@ -21,16 +24,24 @@ use std::path::{Path, PathBuf};
 
 /// Sanitize a path component by replacing invalid filesystem characters
 ///
-/// Specifically converts `::` (used in variant keys) to `-` for filesystem compatibility.
-/// This allows internal keys like "bun::baseline" to become safe paths like "bun-baseline".
+/// Converts `::` (used in variant keys) to `-` for filesystem compatibility, so
+/// internal keys like "bun::baseline" become safe paths like "bun-baseline".
+/// Path separators become `-` as well (versions come from tags such as
+/// "cli/v1.2.3"), and a bare `.` or `..` can't point outside the parent.
 ///
 /// # Examples
 /// ```
 /// assert_eq!(sanitize_path_component("bun::baseline"), "bun-baseline");
+/// assert_eq!(sanitize_path_component("cli/v1.2.3"), "cli-v1.2.3");
 /// assert_eq!(sanitize_path_component("ripgrep"), "ripgrep");
 /// ```
 pub fn sanitize_path_component(name: &str) -> String {
-    name.replace("::", "-")
+    let name = name.replace("::", "-").replace(['/', '\\'], "-");
+    if name.is_empty() || name.chars().all(|c| c == '.') {
+        name.replace('.', "_")
+    } else {
+        name
+    }
 }
 
 /// Wenget paths manager
@@ -42,8 +53,13 @@ pub struct WenPaths {
     is_system_install: bool,
     /// Custom bin directory (overrides default)
     custom_bin_dir: Option<PathBuf>,
+    /// Whether the root was overridden with `WENGET_HOME`
+    custom_root: bool,
 }
 
+/// Environment variable that overrides the root directory
+pub const HOME_ENV: &str = "WENGET_HOME";
+
 impl WenPaths {
     /// Create a new WenPaths instance
     ///
@@ -53,6 +69,8 @@ impl WenPaths {
     /// - System (Linux): /opt/wenget/
     /// - System (Windows): %ProgramW6432%\wenget\
     ///
+    /// `WENGET_HOME` overrides the root in either case.
+    ///
     /// # Errors
     /// Returns an error if the home directory cannot be determined (for user installs)
     pub fn new() -> Result<Self> {
@@ -63,6 +81,13 @@ impl WenPaths {
     pub fn new_with_custom_bin(custom_bin_dir: Option<PathBuf>) -> Result<Self> {
         let is_system = is_elevated();
 
+        if let Some(root) = Self::root_override()? {
+            return Ok(Self {
+                custom_bin_dir,
+                ..Self::with_root(root, is_system)
+            });
+        }
+
         let root = if is_system {
             Self::system_root_path()
         } else {
@@ -73,9 +98,36 @@ impl WenPaths {
             root,
             is_system_install: is_system,
             custom_bin_dir,
+            custom_root: false,
         })
     }
 
+    /// Create a WenPaths instance rooted at a custom directory
+    ///
+    /// All paths, including the bin directory, are placed under `root`.
+    pub fn with_root(root: PathBuf, is_system_install: bool) -> Self {
+        Self {
+            root,
+            is_system_install,
+            custom_bin_dir: None,
+            custom_root: true,
+        }
+    }
+
+    /// Read the root override from `WENGET_HOME` (relative paths resolve against the cwd)
+    fn root_override() -> Result<Option<PathBuf>> {
+        let Some(value) = std::env::var_os(HOME_ENV).filter(|v| !v.is_empty()) else {
+            return Ok(None);
+        };
+
+        let root = PathBuf::from(value);
+        if root.is_relative() {
+            let cwd = std::env::current_dir().context("Failed to determine current directory")?;
+            return Ok(Some(cwd.join(root)));
+        }
+        Ok(Some(root))
+    }
+
     /// Create a WenPaths instance explicitly for user-level installation
     ///
     /// This bypasses the privilege detection and always uses ~/.wenget/
@@ -85,6 +137,7 @@ impl WenPaths {
             root: Self::user_root_path()?,
             is_system_install: false,
             custom_bin_dir: None,
+            custom_root: false,
         })
     }
 
@@ -97,6 +150,7 @@ impl WenPaths {
             root: Self::system_root_path(),
             is_system_install: true,
             custom_bin_dir: None,
+            custom_root: false,
         }
     }
 
@@ -152,6 +206,11 @@ impl WenPaths {
         self.root.join("manifest-cache.json")
     }
 
+    /// Get the install history (`wenget log`)
+    pub fn history_jsonl(&self) -> PathBuf {
+        self.root.join("history.jsonl")
+    }
+
     /// Get the apps directory
     pub fn apps_dir(&self) -> PathBuf {
         self.root.join("apps")
@@ -162,6 +221,26 @@ impl WenPaths {
         self.apps_dir().join(sanitize_path_component(name))
     }
 
+    /// Get the directory of previous versions kept for rollback
+    pub fn versions_dir(&self) -> PathBuf {
+        self.root.join("versions")
+    }
+
+    /// Get the previous versions kept of a specific app
+    pub fn app_versions_dir(&self, name: &str) -> PathBuf {
+        self.versions_dir().join(sanitize_path_component(name))
+    }
+
+    /// Get the directory shell completions are copied to (`add --completions`)
+    pub fn completions_root(&self) -> PathBuf {
+        self.root.join("completions")
+    }
+
+    /// Get the completions directory of one shell (e.g. "zsh")
+    pub fn completions_dir(&self, shell: &str) -> PathBuf {
+        self.completions_root().join(shell)
+    }
+
     /// Get a specific app's bin directory
     #[allow(dead_code)]
     pub fn app_bin_dir(&self, name: &str) -> PathBuf {
@@ -178,6 +257,10 @@ impl WenPaths {
             return custom.clone();
         }
 
+        if self.custom_root {
+            return self.root.join("bin");
+        }
+
         if self.is_system_install {
             #[cfg(unix)]
             {
@@ -195,6 +278,31 @@ impl WenPaths {
         }
     }
 
+    /// Get the custom bin directory override, if one is set
+    pub fn custom_bin_dir(&self) -> Option<&Path> {
+        self.custom_bin_dir.as_deref()
+    }
+
+    /// Return a copy of these paths with launchers placed in `bin_dir`
+    pub fn with_bin_dir(&self, bin_dir: PathBuf) -> Self {
+        Self {
+            custom_bin_dir: Some(bin_dir),
+            ..self.clone()
+        }
+    }
+
+    /// Return a copy of these paths for the launchers of an installed package
+    ///
+    /// `bin_dir` is the directory recorded in installed.json (`add --bin-dir` or
+    /// `custom_bin_path` at install time). None means the default bin directory,
+    /// even when `custom_bin_path` is set now.
+    pub fn for_package(&self, bin_dir: Option<&str>) -> Self {
+        Self {
+            custom_bin_dir: bin_dir.map(PathBuf::from),
+            ..self.clone()
+        }
+    }
+
     /// Get the internal bin directory (always {root}/bin)
     ///
     /// This is used for Windows system installs where we need to add
@@ -213,6 +321,50 @@ impl WenPaths {
         self.cache_dir().join("downloads")
     }
 
+    /// Get the directory of archives kept with `add --keep-download`
+    pub fn archives_dir(&self) -> PathBuf {
+        self.cache_dir().join("archives")
+    }
+
+    /// Get the directory of rollback snapshots, one per package
+    pub fn rollback_dir(&self) -> PathBuf {
+        self.cache_dir().join("rollback")
+    }
+
+    /// Get the rollback snapshot of a package
+    ///
+    /// e.g. `cache/rollback/ripgrep/` holding `app/` and `package.json`
+    pub fn rollback_snapshot_dir(&self, name: &str) -> PathBuf {
+        self.rollback_dir().join(sanitize_path_component(name))
+    }
+
+    /// Get the content-addressed store of `add -p all` downloads
+    pub fn prefetch_dir(&self) -> PathBuf {
+        self.cache_dir().join("prefetch")
+    }
+
+    /// Get the path a pre-fetched file with this SHA256 digest is stored at
+    ///
+    /// e.g. `cache/prefetch/<sha256>/ripgrep-14.1.0-x86_64-unknown-linux-musl.tar.gz`
+    pub fn prefetch_path(&self, sha256: &str, filename: &str) -> PathBuf {
+        self.prefetch_dir().join(sha256).join(filename)
+    }
+
+    /// Get the path an archive of a package version is kept at
+    ///
+    /// e.g. `cache/archives/bun-baseline/1.1.0/bun-linux-x64-baseline.zip`
+    pub fn archive_path(&self, name: &str, version: &str, filename: &str) -> PathBuf {
+        self.archives_dir()
+            .join(sanitize_path_component(name))
+            .join(sanitize_path_component(version))
+            .join(filename)
+    }
+
+    /// Get the lock file guarding mutating operations
+    pub fn lock_file(&self) -> PathBuf {
+        self.root.join("lock")
+    }
+
     /// Get the config file path (config.toml)
     pub fn config_toml(&self) -> PathBuf {
         self.root.join("config.toml")
@@ -335,6 +487,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_with_bin_dir() {
+        let paths = WenPaths::new_user().unwrap();
+        assert!(paths.custom_bin_dir().is_none());
+
+        let custom = PathBuf::from("/tmp/project/bin");
+        let overridden = paths.with_bin_dir(custom.clone());
+        assert_eq!(overridden.bin_dir(), custom);
+        assert_eq!(overridden.custom_bin_dir(), Some(custom.as_path()));
+        // Everything else is unchanged
+        assert_eq!(overridden.root(), paths.root());
+        assert!(overridden.bin_shim_path("rg").starts_with(&custom));
+    }
+
+    #[test]
+    fn test_with_root() {
+        let root = PathBuf::from("/tmp/wenget-home");
+        let paths = WenPaths::with_root(root.clone(), false);
+        assert_eq!(paths.root(), root.as_path());
+        assert_eq!(paths.installed_json(), root.join("installed.json"));
+        assert_eq!(
+            paths.manifest_cache_json(),
+            root.join("manifest-cache.json")
+        );
+        assert_eq!(paths.apps_dir(), root.join("apps"));
+        assert_eq!(paths.downloads_dir(), root.join("cache").join("downloads"));
+        assert_eq!(paths.bin_dir(), root.join("bin"));
+
+        // An explicit bin directory still wins
+        let custom = PathBuf::from("/tmp/project/bin");
+        assert_eq!(paths.with_bin_dir(custom.clone()).bin_dir(), custom);
+    }
+
     #[test]
     fn test_app_paths() {
         let paths = WenPaths::new_user().unwrap();
@@ -345,6 +530,30 @@ mod tests {
         assert!(bin_dir.ends_with("apps/test/bin") || bin_dir.ends_with("apps\\test\\bin"));
     }
 
+    #[test]
+    fn test_sanitize_path_component() {
+        assert_eq!(sanitize_path_component("bun::baseline"), "bun-baseline");
+        assert_eq!(sanitize_path_component("cli/v1.2.3"), "cli-v1.2.3");
+        assert_eq!(sanitize_path_component("a\\b"), "a-b");
+        assert_eq!(sanitize_path_component("../../etc"), "..-..-etc");
+        assert_eq!(sanitize_path_component(".."), "__");
+        assert_eq!(sanitize_path_component("1.2.0"), "1.2.0");
+    }
+
+    #[test]
+    fn test_archive_path() {
+        let paths = WenPaths::with_root(PathBuf::from("/w"), false);
+        assert_eq!(
+            paths.archive_path("bun::baseline", "1.1.0", "bun.zip"),
+            Path::new("/w")
+                .join("cache")
+                .join("archives")
+                .join("bun-baseline")
+                .join("1.1.0")
+                .join("bun.zip")
+        );
+    }
+
     #[test]
     fn test_executable_name() {
         #[cfg(windows)]
@@ -5,6 +5,7 @@
 //! - Binary selection from release assets based on platform
 //! - Platform string normalization
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Types of fallback compatibility
@@ -120,6 +121,28 @@ impl Os {
     }
 }
 
+/// Native architecture of the Windows machine
+///
+/// Emulated processes see their own architecture in `PROCESSOR_ARCHITECTURE`,
+/// so the machine-wide value is read from the registry instead.
+#[cfg(windows)]
+fn windows_native_arch() -> Option<Arch> {
+    use winreg::enums::HKEY_LOCAL_MACHINE;
+    use winreg::RegKey;
+
+    let value: String = RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey(r"SYSTEM\CurrentControlSet\Control\Session Manager\Environment")
+        .ok()?
+        .get_value("PROCESSOR_ARCHITECTURE")
+        .ok()?;
+    Arch::from_processor_architecture(&value)
+}
+
+#[cfg(not(windows))]
+fn windows_native_arch() -> Option<Arch> {
+    None
+}
+
 /// Supported architectures
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Arch {
@@ -131,9 +154,15 @@ pub enum Arch {
 
 impl Arch {
     /// Get the current architecture
+    ///
+    /// An x86_64 build running under emulation on Windows ARM64 reports aarch64,
+    /// so native arm64 assets are still preferred there.
     pub fn current() -> Self {
         if cfg!(target_arch = "x86_64") {
-            Arch::X86_64
+            match windows_native_arch() {
+                Some(Arch::Aarch64) => Arch::Aarch64,
+                _ => Arch::X86_64,
+            }
         } else if cfg!(target_arch = "x86") {
             Arch::I686
         } else if cfg!(target_arch = "aarch64") {
@@ -145,6 +174,18 @@ impl Arch {
         }
     }
 
+    /// Map a Windows `PROCESSOR_ARCHITECTURE` value (AMD64, x86, ARM64, ARM)
+    #[cfg_attr(not(windows), allow(dead_code))]
+    pub fn from_processor_architecture(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_uppercase().as_str() {
+            "AMD64" => Some(Arch::X86_64),
+            "X86" => Some(Arch::I686),
+            "ARM64" => Some(Arch::Aarch64),
+            "ARM" => Some(Arch::Armv7),
+            _ => None,
+        }
+    }
+
     /// Get architecture keywords for matching
     pub fn keywords(&self) -> &[&str] {
         match self {
@@ -204,33 +245,31 @@ impl LibcType {
     ///
     /// Detection methods (in order):
     /// 1. Check for /lib/ld-musl-* (musl dynamic linker)
-    /// 2. Check for musl in ldd output
+    /// 2. Parse `ldd --version` output
     /// 3. Default to Glibc on Linux, Unknown on other platforms
     #[cfg(target_os = "linux")]
     pub fn detect() -> Self {
         // Method 1: Check for musl dynamic linker
         // Alpine and other musl distros have /lib/ld-musl-<arch>.so.1
-        let musl_linker_patterns = [
-            "/lib/ld-musl-x86_64.so.1",
-            "/lib/ld-musl-aarch64.so.1",
-            "/lib/ld-musl-armhf.so.1",
-            "/lib/ld-musl-i386.so.1",
-        ];
-
-        for pattern in &musl_linker_patterns {
-            if std::path::Path::new(pattern).exists() {
-                return LibcType::Musl;
-            }
+        let lib_entries: Vec<String> = std::fs::read_dir("/lib")
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.file_name().to_string_lossy().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        if lib_entries.iter().any(|name| Self::is_musl_loader(name)) {
+            return LibcType::Musl;
         }
 
         // Method 2: Check ldd output (fallback)
         if let Ok(output) = std::process::Command::new("ldd").arg("--version").output() {
             let stdout = String::from_utf8_lossy(&output.stdout);
             let stderr = String::from_utf8_lossy(&output.stderr);
-            let combined = format!("{}{}", stdout, stderr).to_lowercase();
-
-            if combined.contains("musl") {
-                return LibcType::Musl;
+            // musl's ldd prints its banner to stderr
+            if let Some(libc) = Self::from_ldd_output(&format!("{}{}", stdout, stderr)) {
+                return libc;
             }
         }
 
@@ -242,6 +281,34 @@ impl LibcType {
     pub fn detect() -> Self {
         LibcType::Unknown
     }
+
+    /// Whether a file name in /lib is the musl dynamic linker (`ld-musl-<arch>.so.1`)
+    fn is_musl_loader(file_name: &str) -> bool {
+        file_name.starts_with("ld-musl-") && file_name.contains(".so")
+    }
+
+    /// Identify the libc from `ldd --version` output
+    ///
+    /// Returns None when the output names neither musl nor glibc.
+    fn from_ldd_output(output: &str) -> Option<Self> {
+        let lower = output.to_lowercase();
+        if lower.contains("musl") {
+            Some(LibcType::Musl)
+        } else if lower.contains("glibc") || lower.contains("gnu libc") {
+            Some(LibcType::Glibc)
+        } else {
+            None
+        }
+    }
+
+    /// The binary variant to prefer on a system with this libc
+    pub fn compiler(&self) -> Option<Compiler> {
+        match self {
+            LibcType::Musl => Some(Compiler::Musl),
+            LibcType::Glibc => Some(Compiler::Gnu),
+            LibcType::Unknown => None,
+        }
+    }
 }
 
 impl Compiler {
@@ -254,10 +321,26 @@ impl Compiler {
         }
     }
 
+    /// Parse a compiler/libc name (`gnu`, `glibc`, `musl` or `msvc`)
+    pub fn from_name(name: &str) -> Option<Self> {
+        let lower = name.trim().to_lowercase();
+        [Compiler::Gnu, Compiler::Musl, Compiler::Msvc]
+            .into_iter()
+            .find(|c| c.keywords().contains(&lower.as_str()))
+    }
+
     /// Get priority for this compiler on a given OS
     /// Higher values = preferred
-    pub fn priority(&self, os: Os) -> u8 {
+    ///
+    /// On Linux, musl wins unless `preferred` asks for gnu (a detected glibc host
+    /// or the `preferred_compiler` setting).
+    pub fn priority(&self, os: Os, preferred: Option<Compiler>) -> u8 {
         match os {
+            Os::Linux if preferred == Some(Compiler::Gnu) => match self {
+                Compiler::Gnu => 3,
+                Compiler::Musl => 2,
+                Compiler::Msvc => 1,
+            },
             Os::Linux => match self {
                 Compiler::Musl => 3,
                 Compiler::Gnu => 2,
@@ -290,6 +373,9 @@ pub enum FileExtension {
     TarGz,
     TarXz,
     TarBz2,
+    /// Uncompressed tarball
+    Tar,
+    TarLz4,
     SevenZ,
     /// Uncompressed binary (no extension or unrecognized extension)
     UncompressedBinary,
@@ -310,6 +396,10 @@ impl FileExtension {
             FileExtension::TarXz
         } else if lower.ends_with(".tar.bz2") {
             FileExtension::TarBz2
+        } else if lower.ends_with(".tar.lz4") {
+            FileExtension::TarLz4
+        } else if lower.ends_with(".tar") {
+            FileExtension::Tar
         } else if lower.ends_with(".7z") {
             FileExtension::SevenZ
         } else if Self::is_likely_binary_without_extension(filename) {
@@ -402,6 +492,8 @@ impl FileExtension {
             FileExtension::TarXz => 4,
             FileExtension::Zip => 3,
             FileExtension::TarBz2 => 3,
+            FileExtension::TarLz4 => 3,
+            FileExtension::Tar => 2, // Same contents as a compressed tarball, just larger
             FileExtension::SevenZ => 2,
             FileExtension::Exe => 2,
             FileExtension::UncompressedBinary => 1, // Lower preference than archives
@@ -556,13 +648,15 @@ impl ParsedAsset {
             return (Some(Os::Windows), true);
         }
 
-        // .tar.gz / .tar.xz / .tar.bz2 / bare binaries without any OS keyword implies Linux
+        // .tar(.gz/.xz/.bz2) / bare binaries without any OS keyword implies Linux
         // (e.g. "nnn-static-5.2.x86_64.tar.gz" or "tool-x86_64" are Linux-only conventions)
         if matches!(
             ext,
             FileExtension::TarGz
                 | FileExtension::TarXz
                 | FileExtension::TarBz2
+                | FileExtension::Tar
+                | FileExtension::TarLz4
                 | FileExtension::UncompressedBinary
         ) {
             let arch_keywords = [
@@ -631,14 +725,30 @@ pub struct Platform {
 
 impl Platform {
     /// Get the current platform
+    ///
+    /// On Linux the compiler is the variant matching the host libc: musl on
+    /// musl systems (Alpine), gnu where glibc is detected.
     pub fn current() -> Self {
+        let os = Os::current();
+        let compiler = match os {
+            Os::Linux => LibcType::detect().compiler(),
+            _ => None,
+        };
         Self {
-            os: Os::current(),
+            os,
             arch: Arch::current(),
-            compiler: None,
+            compiler,
         }
     }
 
+    /// Override the preferred compiler/libc variant (the `preferred_compiler` setting)
+    pub fn prefer_compiler(mut self, compiler: Option<Compiler>) -> Self {
+        if compiler.is_some() {
+            self.compiler = compiler;
+        }
+        self
+    }
+
     /// Create a platform from components
     pub fn new(os: Os, arch: Arch) -> Self {
         Self {
@@ -648,6 +758,27 @@ impl Platform {
         }
     }
 
+    /// All common OS/architecture combinations that release assets are sorted into
+    pub fn matrix() -> [Platform; 11] {
+        [
+            // Windows
+            Platform::new(Os::Windows, Arch::X86_64),
+            Platform::new(Os::Windows, Arch::I686),
+            Platform::new(Os::Windows, Arch::Aarch64),
+            // Linux
+            Platform::new(Os::Linux, Arch::X86_64),
+            Platform::new(Os::Linux, Arch::I686),
+            Platform::new(Os::Linux, Arch::Aarch64),
+            Platform::new(Os::Linux, Arch::Armv7),
+            // macOS
+            Platform::new(Os::MacOS, Arch::X86_64),
+            Platform::new(Os::MacOS, Arch::Aarch64),
+            // FreeBSD
+            Platform::new(Os::FreeBSD, Arch::X86_64),
+            Platform::new(Os::FreeBSD, Arch::Aarch64),
+        ]
+    }
+
     /// Create a platform with compiler specification
     #[allow(dead_code)]
     pub fn with_compiler(os: Os, arch: Arch, compiler: Compiler) -> Self {
@@ -660,9 +791,10 @@ impl Platform {
 
     /// Get all possible platform identifiers for this platform
     ///
-    /// Returns variants in priority order based on detected libc:
-    /// - On musl systems (Alpine): musl > base > gnu
-    /// - On glibc systems: gnu > base > musl
+    /// Returns variants in priority order based on the platform's compiler, or the
+    /// detected libc when none is set:
+    /// - Preferring musl (Alpine): musl > base > gnu
+    /// - Preferring gnu (glibc systems): gnu > base > musl
     /// - On Windows: msvc > base > gnu
     ///
     /// Examples:
@@ -672,21 +804,21 @@ impl Platform {
     /// - "windows-x86_64-msvc"
     /// - "windows-x86_64-gnu"
     pub fn possible_identifiers(&self) -> Vec<String> {
-        let base = format!("{}", self);
+        let base = format!("{}-{}", self.os.as_str(), self.arch.as_str());
         let mut identifiers = Vec::new();
 
-        // Add compiler variants in priority order based on detected libc
+        // Add compiler variants in priority order based on the preferred libc
         match self.os {
             Os::Linux => {
-                let libc = LibcType::detect();
-                match libc {
-                    LibcType::Musl => {
+                let preferred = self.compiler.or_else(|| LibcType::detect().compiler());
+                match preferred {
+                    Some(Compiler::Musl) => {
                         // On musl systems: prefer musl > base > gnu
                         identifiers.push(format!("{}-musl", base));
                         identifiers.push(base.clone());
                         identifiers.push(format!("{}-gnu", base));
                     }
-                    LibcType::Glibc | LibcType::Unknown => {
+                    _ => {
                         // On glibc systems: prefer gnu > base > musl
                         // (musl binaries are statically linked and work on glibc too)
                         identifiers.push(format!("{}-gnu", base));
@@ -741,7 +873,7 @@ impl Platform {
         // Phase 2: If no exact matches, try fallbacks
         if matches.is_empty() {
             let fallback_ids = self.fallback_identifiers();
-            for (id, fallback_type) in fallback_ids {
+            for (priority, (id, fallback_type)) in fallback_ids.into_iter().enumerate() {
                 if available_platforms.contains_key(&id) {
                     let score = match fallback_type {
                         FallbackType::MuslOnGnu => 500,
@@ -749,7 +881,7 @@ impl Platform {
                         FallbackType::WindowsCompilerVariant => 450,
                         FallbackType::Arch32On64 => 300,
                         FallbackType::X64OnArm => 200,
-                    };
+                    } - priority; // Earlier fallbacks of the same type win
                     matches.push(PlatformMatch {
                         platform_id: id,
                         is_exact: false,
@@ -761,7 +893,7 @@ impl Platform {
         }
 
         // Sort by score descending
-        matches.sort_by(|a, b| b.score.cmp(&a.score));
+        matches.sort_by_key(|m| std::cmp::Reverse(m.score));
         matches
     }
 
@@ -840,11 +972,15 @@ impl Platform {
                 fallbacks.push(("windows-i686-msvc".to_string(), FallbackType::Arch32On64));
                 fallbacks.push(("windows-i686-gnu".to_string(), FallbackType::Arch32On64));
             }
-            // Windows ARM: can run x86_64 via emulation (Windows 11)
+            // Windows ARM: can run x86_64 (Windows 11) and i686 via emulation,
+            // keeping the usual msvc > base > gnu order within each arch
             (Os::Windows, Arch::Aarch64) => {
-                fallbacks.push(("windows-x86_64".to_string(), FallbackType::X64OnArm));
-                fallbacks.push(("windows-x86_64-msvc".to_string(), FallbackType::X64OnArm));
-                fallbacks.push(("windows-i686".to_string(), FallbackType::X64OnArm));
+                for arch in [Arch::X86_64, Arch::I686] {
+                    let emulated = Platform::new(Os::Windows, arch);
+                    for id in emulated.possible_identifiers() {
+                        fallbacks.push((id, FallbackType::X64OnArm));
+                    }
+                }
             }
             _ => {}
         }
@@ -876,6 +1012,39 @@ pub struct BinaryAsset {
     pub size: u64,
 }
 
+/// Weights of the asset scoring components
+///
+/// An asset scores `os + arch (or default_arch) + compiler * priority +
+/// format * format_score`; the highest score wins. The defaults favour an
+/// explicit architecture over an implied one and the host's compiler variant
+/// over archive format. Overridable through `[scoring]` in config.toml.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoringWeights {
+    /// Bonus for an OS match (a mismatch always excludes the asset)
+    pub os: usize,
+    /// Bonus for an explicit architecture match
+    pub arch: usize,
+    /// Bonus when no architecture is named and the OS default arch applies
+    pub default_arch: usize,
+    /// Multiplier for the compiler priority (0 to 3)
+    pub compiler: usize,
+    /// Multiplier for the file format score (1 to 5)
+    pub format: usize,
+}
+
+impl Default for ScoringWeights {
+    fn default() -> Self {
+        Self {
+            os: 100,
+            arch: 50,
+            default_arch: 25,
+            compiler: 10,
+            format: 1,
+        }
+    }
+}
+
 /// Binary selector for choosing the right asset from releases
 pub struct BinarySelector;
 
@@ -893,13 +1062,12 @@ impl BinarySelector {
         let mut scored_assets: Vec<(usize, &BinaryAsset)> = assets
             .iter()
             .filter_map(|asset| {
-                let score = Self::score_asset(&asset.name, platform)?;
+                let score = Self::score_asset(&asset.name, platform, ScoringWeights::default())?;
                 Some((score, asset))
             })
             .collect();
 
-        // Sort by score (highest first)
-        scored_assets.sort_by(|a, b| b.0.cmp(&a.0));
+        scored_assets.sort_by(|a, b| Self::rank_scored(a.0, a.1, b.0, b.1));
 
         scored_assets.first().map(|(_, asset)| (*asset).clone())
     }
@@ -923,17 +1091,32 @@ impl BinarySelector {
         let mut scored_assets: Vec<(usize, BinaryAsset, Option<Compiler>)> = assets
             .iter()
             .filter_map(|asset| {
-                let score = Self::score_asset(&asset.name, platform)?;
+                let score = Self::score_asset(&asset.name, platform, ScoringWeights::default())?;
                 let compiler = Self::detect_compiler_from_filename(&asset.name);
                 Some((score, asset.clone(), compiler))
             })
             .collect();
 
-        // Sort by score (highest first)
-        scored_assets.sort_by(|a, b| b.0.cmp(&a.0));
+        scored_assets.sort_by(|a, b| Self::rank_scored(a.0, &a.1, b.0, &b.1));
         scored_assets
     }
 
+    /// Order scored assets best first
+    ///
+    /// Highest score wins; ties go to the smaller asset (better compression,
+    /// no bundled extras), then to the name so the choice is deterministic.
+    fn rank_scored(
+        score_a: usize,
+        asset_a: &BinaryAsset,
+        score_b: usize,
+        asset_b: &BinaryAsset,
+    ) -> std::cmp::Ordering {
+        score_b
+            .cmp(&score_a)
+            .then(asset_a.size.cmp(&asset_b.size))
+            .then_with(|| asset_a.name.cmp(&asset_b.name))
+    }
+
     /// Extract compiler from filename (helper method)
     ///
     /// # Arguments
@@ -957,7 +1140,7 @@ impl BinarySelector {
 
     /// Score an asset filename based on how well it matches the platform
     ///
-    /// New 4-component scoring algorithm:
+    /// New 4-component scoring algorithm, with the default weights:
     /// - OS match: +100 (mandatory)
     /// - Explicit arch match: +50
     /// - Default arch match: +25
@@ -965,7 +1148,11 @@ impl BinarySelector {
     /// - File format: +2 to +5
     ///
     /// Returns None if the asset should be excluded
-    fn score_asset(filename: &str, platform: Platform) -> Option<usize> {
+    pub(crate) fn score_asset(
+        filename: &str,
+        platform: Platform,
+        weights: ScoringWeights,
+    ) -> Option<usize> {
         let filename_lower = filename.to_lowercase();
 
         // Exclude certain files
@@ -997,13 +1184,13 @@ impl BinarySelector {
         if !os_matches {
             return None;
         }
-        score += 100;
+        score += weights.os;
 
         // Architecture matching
         match parsed.arch {
             Some(arch) if arch == platform.arch => {
                 // Explicit architecture match
-                score += 50;
+                score += weights.arch;
             }
             Some(_) => {
                 // Explicit architecture mismatch - exclude
@@ -1022,7 +1209,7 @@ impl BinarySelector {
                 if let Some(default_arch) = platform.os.default_arch() {
                     if platform.arch == default_arch {
                         // Use default architecture (lower score than explicit)
-                        score += 25;
+                        score += weights.default_arch;
                     }
                     // If platform arch doesn't match default, still allow but no arch bonus
                 } else {
@@ -1034,18 +1221,20 @@ impl BinarySelector {
 
         // Compiler scoring based on OS-specific priority
         if let Some(compiler) = parsed.compiler {
-            let priority = compiler.priority(platform.os);
-            score += (priority as usize) * 10;
+            let priority = compiler.priority(platform.os, platform.compiler);
+            score += (priority as usize) * weights.compiler;
         }
 
         // File format preference
-        score += parsed.extension.format_score();
+        score += parsed.extension.format_score() * weights.format;
 
         Some(score)
     }
 
     /// Check if a filename should be excluded from selection
-    fn should_exclude(filename: &str) -> bool {
+    ///
+    /// Expects a lowercase filename.
+    pub(crate) fn should_exclude(filename: &str) -> bool {
         let excludes = [
             "source",
             ".deb",
@@ -1064,7 +1253,72 @@ impl BinarySelector {
             ".md",
         ];
 
-        excludes.iter().any(|&e| filename.contains(e))
+        excludes.iter().any(|&e| filename.contains(e)) || Self::is_split_part(filename)
+    }
+
+    /// OS installer packages and the OS they install on
+    ///
+    /// They are excluded from selection, since wenget only extracts archives and
+    /// binaries, but can be pointed out when nothing else is available.
+    const INSTALLERS: [(&'static str, Os); 5] = [
+        (".msi", Os::Windows),
+        (".deb", Os::Linux),
+        (".rpm", Os::Linux),
+        (".dmg", Os::MacOS),
+        (".pkg", Os::MacOS),
+    ];
+
+    /// Whether a file is an OS installer (.msi, .deb, ...) for any platform
+    pub fn is_installer(filename: &str) -> bool {
+        let lower = filename.to_lowercase();
+        Self::INSTALLERS.iter().any(|(ext, _)| lower.ends_with(ext))
+    }
+
+    /// Find an OS installer for `platform` among the assets
+    ///
+    /// Returns the installer's extension and asset. Installers naming another
+    /// architecture are skipped, and one naming the platform's architecture is
+    /// preferred over one naming none.
+    pub fn installer_for_platform(
+        assets: &[BinaryAsset],
+        platform: Platform,
+    ) -> Option<(&'static str, &BinaryAsset)> {
+        assets
+            .iter()
+            .filter_map(|asset| {
+                let lower = asset.name.to_lowercase();
+                let (ext, _) = Self::INSTALLERS
+                    .iter()
+                    .find(|(ext, os)| *os == platform.os && lower.ends_with(ext))?;
+                let arch_matches = match ParsedAsset::from_filename(&asset.name).arch {
+                    Some(arch) if arch != platform.arch => return None,
+                    Some(_) => true,
+                    None => false,
+                };
+                Some((arch_matches, *ext, asset))
+            })
+            .max_by_key(|(arch_matches, _, _)| *arch_matches)
+            .map(|(_, ext, asset)| (ext, asset))
+    }
+
+    /// Check if a filename is one part of an archive split into numbered pieces
+    /// (e.g. `tool.tar.gz.001`, `tool.zip.002`)
+    ///
+    /// Parts are never selected: a single part is an incomplete fragment, and split
+    /// assets are not reassembled, so such releases need another asset to install.
+    fn is_split_part(filename: &str) -> bool {
+        let Some((stem, part)) = filename.rsplit_once('.') else {
+            return false;
+        };
+        if part.len() != 3 || !part.bytes().all(|b| b.is_ascii_digit()) {
+            return false;
+        }
+
+        let split_extensions = [
+            ".zip", ".tar.gz", ".tgz", ".tar.xz", ".tar.bz2", ".tbz", ".tar.lz4", ".tar", ".7z",
+            ".exe",
+        ];
+        split_extensions.iter().any(|ext| stem.ends_with(ext))
     }
 
     /// Extract platform information from available assets
@@ -1074,7 +1328,16 @@ impl BinarySelector {
     /// For example, if both musl and gnu variants exist for linux-x86_64,
     /// both will be included in the result. Also captures multiple package
     /// variants like baseline, desktop, etc.
+    #[allow(dead_code)] // callers go through extract_platforms_with; kept for the default weights
     pub fn extract_platforms(assets: &[BinaryAsset]) -> HashMap<String, Vec<BinaryAsset>> {
+        Self::extract_platforms_with(assets, ScoringWeights::default())
+    }
+
+    /// [`Self::extract_platforms`] with custom scoring weights
+    pub fn extract_platforms_with(
+        assets: &[BinaryAsset],
+        weights: ScoringWeights,
+    ) -> HashMap<String, Vec<BinaryAsset>> {
         let mut platforms: HashMap<String, Vec<BinaryAsset>> = HashMap::new();
 
         // Parse each asset once and cache the data that scoring needs.
@@ -1106,25 +1369,7 @@ impl BinarySelector {
             .collect();
 
         // Try all common platform combinations
-        let test_platforms = vec![
-            // Windows
-            Platform::new(Os::Windows, Arch::X86_64),
-            Platform::new(Os::Windows, Arch::I686),
-            Platform::new(Os::Windows, Arch::Aarch64),
-            // Linux
-            Platform::new(Os::Linux, Arch::X86_64),
-            Platform::new(Os::Linux, Arch::I686),
-            Platform::new(Os::Linux, Arch::Aarch64),
-            Platform::new(Os::Linux, Arch::Armv7),
-            // macOS
-            Platform::new(Os::MacOS, Arch::X86_64),
-            Platform::new(Os::MacOS, Arch::Aarch64),
-            // FreeBSD
-            Platform::new(Os::FreeBSD, Arch::X86_64),
-            Platform::new(Os::FreeBSD, Arch::Aarch64),
-        ];
-
-        for platform in test_platforms {
+        for platform in Platform::matrix() {
             // Score every asset against this platform using the cached parse.
             // Mirrors score_asset + select_all_for_platform, but without re-parsing.
             let mut scored: Vec<(usize, &BinaryAsset, Option<Compiler>)> = Vec::new();
@@ -1136,13 +1381,14 @@ impl BinarySelector {
                     p.unsupported_arch,
                     p.unknown_arch_pattern,
                     platform,
+                    weights,
                 ) else {
                     continue;
                 };
                 scored.push((score, p.asset, p.parsed.compiler));
             }
-            // Sort by score (highest first) — matches select_all_for_platform ordering.
-            scored.sort_by(|a, b| b.0.cmp(&a.0));
+            // Best first — matches select_all_for_platform ordering.
+            scored.sort_by(|a, b| Self::rank_scored(a.0, a.1, b.0, b.1));
 
             for (_score, asset, compiler) in scored {
                 // Build platform identifier with compiler variant
@@ -1175,6 +1421,7 @@ impl BinarySelector {
         unsupported_arch: bool,
         unknown_arch_pattern: bool,
         platform: Platform,
+        weights: ScoringWeights,
     ) -> Option<usize> {
         // Exclude certain files
         if excluded {
@@ -1202,13 +1449,13 @@ impl BinarySelector {
         if !os_matches {
             return None;
         }
-        score += 100;
+        score += weights.os;
 
         // Architecture matching
         match parsed.arch {
             Some(arch) if arch == platform.arch => {
                 // Explicit architecture match
-                score += 50;
+                score += weights.arch;
             }
             Some(_) => {
                 // Explicit architecture mismatch - exclude
@@ -1227,7 +1474,7 @@ impl BinarySelector {
                 if let Some(default_arch) = platform.os.default_arch() {
                     if platform.arch == default_arch {
                         // Use default architecture (lower score than explicit)
-                        score += 25;
+                        score += weights.default_arch;
                     }
                     // If platform arch doesn't match default, still allow but no arch bonus
                 } else {
@@ -1239,12 +1486,12 @@ impl BinarySelector {
 
         // Compiler scoring based on OS-specific priority
         if let Some(compiler) = parsed.compiler {
-            let priority = compiler.priority(platform.os);
-            score += (priority as usize) * 10;
+            let priority = compiler.priority(platform.os, platform.compiler);
+            score += (priority as usize) * weights.compiler;
         }
 
         // File format preference
-        score += parsed.extension.format_score();
+        score += parsed.extension.format_score() * weights.format;
 
         // Suppress unused-variable warning for filename_lower: it is computed by
         // callers to drive the exclude/unsupported-arch flags above, and kept as a
@@ -1325,6 +1572,70 @@ mod tests {
         assert!(!BinarySelector::should_exclude("app-linux-x86_64.tar.gz"));
     }
 
+    #[test]
+    fn test_installer_for_platform() {
+        let asset = |name: &str| BinaryAsset {
+            name: name.to_string(),
+            url: format!("https://example.com/{}", name),
+            size: 1000,
+        };
+        let assets = vec![
+            asset("App-1.0-arm64.msi"),
+            asset("App-1.0-x64.msi"),
+            asset("app_1.0_amd64.deb"),
+            asset("App-1.0.dmg"),
+            asset("checksums.txt"),
+        ];
+
+        // Nothing is selectable, but each OS has an installer to point to
+        let windows = Platform::new(Os::Windows, Arch::X86_64);
+        assert!(BinarySelector::select_for_platform(&assets, windows).is_none());
+        let (ext, installer) = BinarySelector::installer_for_platform(&assets, windows).unwrap();
+        assert_eq!(ext, ".msi");
+        assert_eq!(installer.name, "App-1.0-x64.msi");
+
+        let linux = Platform::new(Os::Linux, Arch::X86_64);
+        let (ext, installer) = BinarySelector::installer_for_platform(&assets, linux).unwrap();
+        assert_eq!(ext, ".deb");
+        assert_eq!(installer.name, "app_1.0_amd64.deb");
+
+        let macos = Platform::new(Os::MacOS, Arch::Aarch64);
+        let (ext, _) = BinarySelector::installer_for_platform(&assets, macos).unwrap();
+        assert_eq!(ext, ".dmg");
+
+        // Another architecture's installer doesn't count
+        let linux_arm = Platform::new(Os::Linux, Arch::Aarch64);
+        assert!(BinarySelector::installer_for_platform(&assets, linux_arm).is_none());
+        let freebsd = Platform::new(Os::FreeBSD, Arch::X86_64);
+        assert!(BinarySelector::installer_for_platform(&assets, freebsd).is_none());
+    }
+
+    #[test]
+    fn test_split_parts_are_excluded() {
+        assert!(BinarySelector::should_exclude(
+            "app-linux-x86_64.tar.gz.001"
+        ));
+        assert!(BinarySelector::should_exclude("app-windows-x86_64.zip.002"));
+        assert!(BinarySelector::should_exclude("app-windows-x86_64.7z.001"));
+        assert!(!BinarySelector::should_exclude("app-linux-x86_64-1.0.001"));
+
+        let assets = vec![
+            BinaryAsset {
+                name: "app-linux-x86_64.tar.gz.001".to_string(),
+                url: "https://example.com/1".to_string(),
+                size: 1000,
+            },
+            BinaryAsset {
+                name: "app-linux-x86_64.tar.gz.002".to_string(),
+                url: "https://example.com/2".to_string(),
+                size: 1000,
+            },
+        ];
+        let linux = Platform::new(Os::Linux, Arch::X86_64);
+        assert!(BinarySelector::select_for_platform(&assets, linux).is_none());
+        assert!(BinarySelector::extract_platforms(&assets).is_empty());
+    }
+
     #[test]
     fn test_linux_prefers_musl_over_gnu() {
         let assets = vec![
@@ -1593,10 +1904,55 @@ mod tests {
 
     #[test]
     fn test_compiler_priority() {
-        assert_eq!(Compiler::Musl.priority(Os::Linux), 3);
-        assert_eq!(Compiler::Gnu.priority(Os::Linux), 2);
-        assert_eq!(Compiler::Msvc.priority(Os::Windows), 3);
-        assert_eq!(Compiler::Gnu.priority(Os::Windows), 2);
+        assert_eq!(Compiler::Musl.priority(Os::Linux, None), 3);
+        assert_eq!(Compiler::Gnu.priority(Os::Linux, None), 2);
+        assert_eq!(Compiler::Msvc.priority(Os::Windows, None), 3);
+        assert_eq!(Compiler::Gnu.priority(Os::Windows, None), 2);
+
+        // A glibc host (or preferred_compiler = "gnu") flips the Linux order
+        let gnu = Some(Compiler::Gnu);
+        assert_eq!(Compiler::Gnu.priority(Os::Linux, gnu), 3);
+        assert_eq!(Compiler::Musl.priority(Os::Linux, gnu), 2);
+        assert_eq!(Compiler::Musl.priority(Os::Linux, Some(Compiler::Musl)), 3);
+    }
+
+    #[test]
+    fn test_libc_detection_parsing() {
+        assert!(LibcType::is_musl_loader("ld-musl-x86_64.so.1"));
+        assert!(LibcType::is_musl_loader("ld-musl-riscv64.so.1"));
+        assert!(!LibcType::is_musl_loader("ld-linux-x86-64.so.2"));
+
+        let glibc = "ldd (Ubuntu GLIBC 2.35-0ubuntu3.8) 2.35\nCopyright (C) 2022 Free Software Foundation, Inc.";
+        assert_eq!(LibcType::from_ldd_output(glibc), Some(LibcType::Glibc));
+        let glibc = "ldd (GNU libc) 2.39";
+        assert_eq!(LibcType::from_ldd_output(glibc), Some(LibcType::Glibc));
+        let musl = "musl libc (x86_64)\nVersion 1.2.4\nDynamic Program Loader";
+        assert_eq!(LibcType::from_ldd_output(musl), Some(LibcType::Musl));
+        assert_eq!(LibcType::from_ldd_output("ldd: command not found"), None);
+
+        assert_eq!(LibcType::Glibc.compiler(), Some(Compiler::Gnu));
+        assert_eq!(LibcType::Musl.compiler(), Some(Compiler::Musl));
+        assert_eq!(LibcType::Unknown.compiler(), None);
+    }
+
+    #[test]
+    fn test_preferred_compiler_orders_identifiers() {
+        let gnu = Platform::with_compiler(Os::Linux, Arch::X86_64, Compiler::Gnu);
+        assert_eq!(
+            gnu.possible_identifiers(),
+            vec!["linux-x86_64-gnu", "linux-x86_64", "linux-x86_64-musl"]
+        );
+
+        let musl = gnu.prefer_compiler(Compiler::from_name("musl"));
+        assert_eq!(
+            musl.possible_identifiers(),
+            vec!["linux-x86_64-musl", "linux-x86_64", "linux-x86_64-gnu"]
+        );
+
+        // No override keeps the detected variant
+        assert_eq!(gnu.prefer_compiler(None), gnu);
+        assert_eq!(Compiler::from_name("GLIBC"), Some(Compiler::Gnu));
+        assert_eq!(Compiler::from_name("clang"), None);
     }
 
     #[test]
@@ -1644,6 +2000,91 @@ mod tests {
         assert_eq!(platforms.len(), 2, "Should have exactly 2 platforms");
     }
 
+    #[test]
+    fn test_custom_scoring_weights() {
+        let assets = vec![
+            BinaryAsset {
+                name: "app-linux-x86_64.zip".to_string(),
+                url: "https://example.com/app-linux-x86_64.zip".to_string(),
+                size: 1000000,
+            },
+            BinaryAsset {
+                name: "app-linux.tar.gz".to_string(),
+                url: "https://example.com/app-linux.tar.gz".to_string(),
+                size: 1000000,
+            },
+        ];
+        let first = |weights| {
+            BinarySelector::extract_platforms_with(&assets, weights)["linux-x86_64"][0]
+                .name
+                .clone()
+        };
+
+        // Default: the explicit arch outweighs the better format
+        assert_eq!(first(ScoringWeights::default()), "app-linux-x86_64.zip");
+        assert_eq!(
+            BinarySelector::extract_platforms(&assets)["linux-x86_64"][0].name,
+            "app-linux-x86_64.zip"
+        );
+
+        // Format first
+        let weights = ScoringWeights {
+            format: 100,
+            ..Default::default()
+        };
+        assert_eq!(first(weights), "app-linux.tar.gz");
+    }
+
+    #[test]
+    fn test_tied_scores_prefer_smaller_asset() {
+        let asset = |name: &str, size| BinaryAsset {
+            name: name.to_string(),
+            url: format!("https://example.com/{}", name),
+            size,
+        };
+        let linux = Platform::new(Os::Linux, Arch::X86_64);
+
+        // .zip and .tar.bz2 share a format score; the smaller one wins either way round
+        for assets in [
+            vec![
+                asset("app-linux-x86_64.zip", 3_000_000),
+                asset("app-linux-x86_64.tar.bz2", 2_000_000),
+            ],
+            vec![
+                asset("app-linux-x86_64.tar.bz2", 2_000_000),
+                asset("app-linux-x86_64.zip", 3_000_000),
+            ],
+        ] {
+            assert_eq!(
+                BinarySelector::select_for_platform(&assets, linux)
+                    .unwrap()
+                    .name,
+                "app-linux-x86_64.tar.bz2"
+            );
+            let names: Vec<_> = BinarySelector::select_all_for_platform(&assets, linux)
+                .into_iter()
+                .map(|(_, asset, _)| asset.name)
+                .collect();
+            assert_eq!(names, ["app-linux-x86_64.tar.bz2", "app-linux-x86_64.zip"]);
+            assert_eq!(
+                BinarySelector::extract_platforms(&assets)["linux-x86_64"][0].name,
+                "app-linux-x86_64.tar.bz2"
+            );
+        }
+
+        // Same size: name order decides
+        let assets = vec![
+            asset("app-linux-x86_64.zip", 1_000_000),
+            asset("app-linux-x86_64.tar.bz2", 1_000_000),
+        ];
+        assert_eq!(
+            BinarySelector::select_for_platform(&assets, linux)
+                .unwrap()
+                .name,
+            "app-linux-x86_64.tar.bz2"
+        );
+    }
+
     #[test]
     fn test_platform_fallback_matching() {
         use crate::core::manifest::PlatformBinary;
@@ -1764,6 +2205,88 @@ mod tests {
         assert_eq!(matches[0].fallback_type, Some(FallbackType::X64OnArm));
     }
 
+    #[test]
+    fn test_windows_arm_x64_fallback() {
+        use crate::core::manifest::PlatformBinary;
+
+        let bin = |name: &str| {
+            vec![PlatformBinary {
+                url: "test".to_string(),
+                size: 0,
+                checksum: None,
+                asset_name: name.to_string(),
+            }]
+        };
+
+        // No arm64 asset published: the x86_64 msvc build is picked via emulation
+        let assets: Vec<BinaryAsset> = [
+            "tool-x86_64-pc-windows-msvc.zip",
+            "tool-x86_64-pc-windows-gnu.zip",
+            "tool-i686-pc-windows-msvc.zip",
+            "tool-x86_64-unknown-linux-musl.tar.gz",
+        ]
+        .iter()
+        .map(|name| BinaryAsset {
+            name: name.to_string(),
+            url: "test".to_string(),
+            size: 0,
+        })
+        .collect();
+        let available: HashMap<String, Vec<PlatformBinary>> =
+            BinarySelector::extract_platforms(&assets)
+                .into_iter()
+                .map(|(id, assets)| {
+                    let binaries = assets.iter().flat_map(|a| bin(&a.name)).collect();
+                    (id, binaries)
+                })
+                .collect();
+
+        let platform = Platform::new(Os::Windows, Arch::Aarch64);
+        let matches = platform.find_best_match(&available);
+
+        assert!(!matches.is_empty());
+        assert_eq!(matches[0].platform_id, "windows-x86_64-msvc");
+        assert!(!matches[0].is_exact);
+        assert_eq!(matches[0].fallback_type, Some(FallbackType::X64OnArm));
+        assert_eq!(
+            available[&matches[0].platform_id][0].asset_name,
+            "tool-x86_64-pc-windows-msvc.zip"
+        );
+
+        // The i686 build ranks below every x86_64 build
+        let i686 = matches
+            .iter()
+            .position(|m| m.platform_id.starts_with("windows-i686"))
+            .unwrap();
+        assert!(matches[..i686]
+            .iter()
+            .all(|m| m.platform_id.starts_with("windows-x86_64")));
+
+        // A native arm64 build still wins when published
+        let mut available = available;
+        available.insert(
+            "windows-aarch64-msvc".to_string(),
+            bin("tool-aarch64-pc-windows-msvc.zip"),
+        );
+        let matches = platform.find_best_match(&available);
+        assert_eq!(matches[0].platform_id, "windows-aarch64-msvc");
+        assert!(matches[0].is_exact);
+    }
+
+    #[test]
+    fn test_arch_from_processor_architecture() {
+        assert_eq!(
+            Arch::from_processor_architecture("ARM64"),
+            Some(Arch::Aarch64)
+        );
+        assert_eq!(
+            Arch::from_processor_architecture("AMD64"),
+            Some(Arch::X86_64)
+        );
+        assert_eq!(Arch::from_processor_architecture("x86"), Some(Arch::I686));
+        assert_eq!(Arch::from_processor_architecture("IA64"), None);
+    }
+
     #[test]
     fn test_fallback_confirmation_required() {
         // Arch fallback should require confirmation
@@ -1908,6 +2431,16 @@ mod tests {
             FileExtension::from_filename("app-windows-x64.zip"),
             FileExtension::Zip
         );
+        assert_eq!(
+            FileExtension::from_filename("app-linux-x64.tar"),
+            FileExtension::Tar
+        );
+        assert!(FileExtension::Tar.format_score() > 0);
+        assert!(FileExtension::Tar.format_score() < FileExtension::TarGz.format_score());
+        assert_eq!(
+            FileExtension::from_filename("app-linux-x64.tar.lz4"),
+            FileExtension::TarLz4
+        );
     }
 
     #[test]
@@ -2,8 +2,10 @@
 //!
 //! This module handles persistent user configuration stored in config.toml
 
+use crate::core::ScoringWeights;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -17,14 +19,91 @@ pub struct Preferences {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub preferred_platform: Option<String>,
 
+    /// Preferred libc/compiler variant on Linux ("gnu" or "musl")
+    ///
+    /// When unset, the variant matching the detected host libc is preferred.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_compiler: Option<String>,
+
     /// Custom bin directory path
     ///
     /// When set, symlinks/shims will be created here instead of the default location.
     /// Useful for custom PATH setups or when ~/.wenget/bin cannot be added to PATH.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub custom_bin_path: Option<PathBuf>,
+
+    /// SOCKS5 proxy URL (e.g., "socks5://127.0.0.1:1080")
+    ///
+    /// Used for all HTTP traffic when set. `ALL_PROXY` takes precedence when it
+    /// also holds a `socks5://` URL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub socks_proxy: Option<String>,
+
+    /// HTTP(S) or SOCKS5 proxy URL for all requests
+    ///
+    /// Used when `HTTPS_PROXY` / `HTTP_PROXY` are not set. Hosts in `NO_PROXY`
+    /// are reached directly.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_url: Option<String>,
+
+    /// Network timeout in seconds for HTTP requests and downloads
+    ///
+    /// Overridden by `WENGET_TIMEOUT` and the `--timeout` flag.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+
+    /// Timeout in seconds for file downloads (default 300)
+    ///
+    /// Takes precedence over `timeout_secs` for downloads; `WENGET_TIMEOUT` and
+    /// `--timeout` still override it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_timeout_secs: Option<u64>,
+
+    /// Largest file a download may be, in bytes (default 2 GiB)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_download_bytes: Option<u64>,
+
+    /// How often a failed request is retried (network errors, HTTP 502/503/504/429; default 3)
+    ///
+    /// Overridden by `WENGET_RETRIES` and the `--retries` flag.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+
+    /// Download buffer size in KiB (default 64, allowed 4 to 4096)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_buffer_kib: Option<usize>,
+
+    /// Mirrors tried before the original URL for matching downloads
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub download_mirrors: Vec<DownloadMirror>,
+
+    /// Weights used to pick release assets; unset fields keep their defaults
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scoring: Option<ScoringWeights>,
+
+    /// Installed versions kept per package, counting the current one (default 1)
+    ///
+    /// Older versions are kept for `wenget rollback`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keep_versions: Option<usize>,
+
+    /// Extra launchers for installed commands, alias -> command (`wenget alias`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, String>,
+}
+
+/// A mirror for downloads whose URL starts with `prefix`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DownloadMirror {
+    /// URL prefix to rewrite (e.g. "https://github.com/")
+    pub prefix: String,
+    /// What the prefix is replaced with (e.g. "https://mirror.example.com/github/")
+    pub replace_with: String,
 }
 
+/// Allowed `download_buffer_kib` values, keeping per-download memory bounded
+pub const DOWNLOAD_BUFFER_KIB_RANGE: std::ops::RangeInclusive<usize> = 4..=4096;
+
 impl Preferences {
     /// Load preferences from config.toml
     ///
@@ -59,6 +138,72 @@ impl Preferences {
             .with_context(|| format!("Failed to write config file: {}", config_path.display()))
     }
 
+    /// Replace the `[aliases]` table of config.toml
+    ///
+    /// Only that table is rewritten; comments and other settings in the file are
+    /// kept as they are. An empty map removes the table.
+    pub fn save_aliases(config_path: &Path, aliases: &BTreeMap<String, String>) -> Result<()> {
+        Self::edit_file(config_path, |doc| {
+            if aliases.is_empty() {
+                doc.remove("aliases");
+            } else {
+                let mut table = toml_edit::Table::new();
+                for (alias, command) in aliases {
+                    table.insert(alias, toml_edit::value(command.as_str()));
+                }
+                doc.insert("aliases", toml_edit::Item::Table(table));
+            }
+        })
+    }
+
+    /// Set or remove (`None`) a top-level string setting of config.toml
+    ///
+    /// Like [`Self::save_aliases`], the rest of the file is kept as it is.
+    pub fn save_value(config_path: &Path, key: &str, value: Option<&str>) -> Result<()> {
+        Self::edit_file(config_path, |doc| match value {
+            Some(value) => {
+                // In a file holding only comments (the generated template) they
+                // are trailing text; keep them above the new setting
+                let comments = doc
+                    .is_empty()
+                    .then(|| doc.trailing().as_str().map(str::to_string));
+                doc.insert(key, toml_edit::value(value));
+                if let Some(Some(comments)) = comments {
+                    doc.set_trailing("");
+                    if let Some(mut key) = doc.key_mut(key) {
+                        key.leaf_decor_mut().set_prefix(comments);
+                    }
+                }
+            }
+            None => {
+                doc.remove(key);
+            }
+        })
+    }
+
+    /// Apply `edit` to config.toml, keeping its formatting and comments
+    fn edit_file(config_path: &Path, edit: impl FnOnce(&mut toml_edit::DocumentMut)) -> Result<()> {
+        let content = if config_path.exists() {
+            fs::read_to_string(config_path)
+                .with_context(|| format!("Failed to read config file: {}", config_path.display()))?
+        } else {
+            String::new()
+        };
+        let mut doc: toml_edit::DocumentMut = content
+            .parse()
+            .with_context(|| format!("Failed to parse config file: {}", config_path.display()))?;
+
+        edit(&mut doc);
+
+        if let Some(parent) = config_path.parent() {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create config directory: {}", parent.display())
+            })?;
+        }
+        fs::write(config_path, doc.to_string())
+            .with_context(|| format!("Failed to write config file: {}", config_path.display()))
+    }
+
     /// Generate a default config.toml with helpful comments
     pub fn generate_default_file(config_path: &Path) -> Result<()> {
         // Create parent directory if needed
@@ -94,6 +239,14 @@ impl Preferences {
 # Example:
 # preferred_platform = "x86_64-unknown-linux-musl"
 
+# Preferred libc variant on Linux: "gnu" or "musl"
+#
+# By default Wenget prefers the build matching the host libc: gnu builds on
+# glibc distributions, musl builds on musl distributions such as Alpine.
+#
+# Example:
+# preferred_compiler = "musl"
+
 # Custom bin directory (overrides default)
 #
 # When set, symlinks/shims will be created in this directory instead of:
@@ -104,6 +257,102 @@ impl Preferences {
 #
 # Example:
 # custom_bin_path = "/usr/local/bin"
+
+# SOCKS5 proxy (overridden by ALL_PROXY=socks5://...)
+#
+# Routes all downloads and GitHub API requests through a SOCKS5 proxy,
+# e.g. for Tor or an SSH dynamic tunnel. Use "socks5h://" to resolve
+# host names on the proxy side.
+#
+# Example:
+# socks_proxy = "socks5://127.0.0.1:1080"
+
+# Proxy for all requests, e.g. a corporate HTTP proxy
+#
+# HTTPS_PROXY / HTTP_PROXY take precedence when set. Hosts listed in NO_PROXY
+# (e.g. NO_PROXY=api.github.com) are reached directly.
+#
+# Example:
+# proxy_url = "http://proxy.example.com:3128"
+
+# Network timeout in seconds (overridden by WENGET_TIMEOUT and --timeout)
+#
+# Example:
+# timeout_secs = 120
+
+# Download limits
+#
+# A download taking longer than download_timeout_secs (default 300, or
+# timeout_secs when only that is set) or larger than max_download_bytes
+# (default 2 GiB) is aborted. WENGET_TIMEOUT and --timeout override the
+# download timeout too.
+#
+# Example:
+# download_timeout_secs = 600
+# max_download_bytes = 4294967296
+
+# Retries for failed requests (overridden by WENGET_RETRIES and --retries)
+#
+# Connection errors, timeouts and HTTP 502/503/504/429 responses are retried
+# after 1s, 2s, 4s, ... Other errors such as 404 fail at once. Defaults to 3;
+# 0 turns retries off.
+#
+# Example:
+# retries = 3
+
+# Download buffer size in KiB (4 to 4096, default 64)
+#
+# Larger buffers mean fewer system calls on fast connections. The buffer is
+# allocated once per download.
+#
+# Example:
+# download_buffer_kib = 256
+
+# Download mirrors (off unless configured)
+#
+# Downloads whose URL starts with `prefix` are first tried with the prefix
+# replaced by `replace_with`; mirrors are tried in order and the original URL
+# is the last resort. A mirror download whose checksum doesn't match the
+# bucket's is discarded. The log shows which URL was used.
+#
+# Example:
+# [[download_mirrors]]
+# prefix = "https://github.com/"
+# replace_with = "https://mirror.example.com/github/"
+
+# Asset scoring weights (advanced)
+#
+# When adding a package from a GitHub release, each asset is scored and the
+# highest score wins: os + arch (or default_arch when the name has no arch)
+# + compiler * priority (0-3) + format * format score (1 for bare binaries up
+# to 5 for .tar.gz). Unset fields keep the defaults shown below. Bucket
+# manifests are not affected.
+#
+# Example:
+# [scoring]
+# os = 100
+# arch = 50
+# default_arch = 25
+# compiler = 10
+# format = 1
+
+# Versions kept per package, counting the installed one (default 1)
+#
+# With a value above 1, updating a package keeps the previous versions so that
+# `wenget rollback <name>` can switch back to them. The oldest ones beyond
+# this number are removed.
+#
+# Example:
+# keep_versions = 3
+
+# Command aliases (managed with `wenget alias <alias> <command>`)
+#
+# Each alias gets its own launcher running the same executable as the
+# command. `wenget repair --shims` recreates them.
+#
+# Example:
+# [aliases]
+# k = "kubectl"
 "#;
 
         fs::write(config_path, template)
@@ -114,14 +363,25 @@ impl Preferences {
     ///
     /// Checks that:
     /// - Platform string is reasonable (contains expected separators)
+    /// - Compiler name is known
     /// - Custom bin path is absolute
+    /// - SOCKS proxy uses a socks5:// or socks5h:// URL, proxy URL an http(s) or SOCKS5 one
+    /// - Timeouts and the download size limit are not zero
+    /// - Download buffer size is within bounds
+    /// - Download mirrors are http(s) URLs
+    /// - At least one version is kept
     pub fn validate(&self) -> Result<()> {
         // Validate platform string format
         if let Some(ref platform) = self.preferred_platform {
-            if !platform.contains('-') {
+            validate_platform(platform)?;
+        }
+
+        // Validate compiler name
+        if let Some(ref compiler) = self.preferred_compiler {
+            if crate::core::Compiler::from_name(compiler).is_none() {
                 anyhow::bail!(
-                    "Invalid platform string: '{}' - Expected format: 'arch-vendor-os' or 'arch-vendor-os-abi'",
-                    platform
+                    "Invalid preferred_compiler: '{}' - Expected 'gnu' or 'musl'",
+                    compiler
                 );
             }
         }
@@ -133,8 +393,96 @@ impl Preferences {
             }
         }
 
+        // Validate SOCKS proxy scheme
+        if let Some(ref proxy) = self.socks_proxy {
+            if !crate::utils::proxy::is_socks_url(proxy) {
+                anyhow::bail!(
+                    "Invalid socks_proxy: '{}' - Expected a socks5:// or socks5h:// URL",
+                    proxy
+                );
+            }
+        }
+        if let Some(ref proxy) = self.proxy_url {
+            if !crate::utils::proxy::is_proxy_url(proxy) {
+                anyhow::bail!(
+                    "Invalid proxy_url: '{}' - Expected an http://, https:// or socks5:// URL",
+                    proxy
+                );
+            }
+        }
+
+        // Validate timeout
+        if self.timeout_secs == Some(0) {
+            anyhow::bail!("Invalid timeout_secs: 0 - Expected a number of seconds greater than 0");
+        }
+        if self.download_timeout_secs == Some(0) {
+            anyhow::bail!(
+                "Invalid download_timeout_secs: 0 - Expected a number of seconds greater than 0"
+            );
+        }
+        if self.max_download_bytes == Some(0) {
+            anyhow::bail!(
+                "Invalid max_download_bytes: 0 - Expected a size in bytes greater than 0"
+            );
+        }
+
+        // Validate download buffer size
+        if let Some(kib) = self.download_buffer_kib {
+            if !DOWNLOAD_BUFFER_KIB_RANGE.contains(&kib) {
+                anyhow::bail!(
+                    "Invalid download_buffer_kib: {} - Expected a value from {} to {}",
+                    kib,
+                    DOWNLOAD_BUFFER_KIB_RANGE.start(),
+                    DOWNLOAD_BUFFER_KIB_RANGE.end()
+                );
+            }
+        }
+
+        // Validate download mirrors
+        for mirror in &self.download_mirrors {
+            for url in [&mirror.prefix, &mirror.replace_with] {
+                if !url.starts_with("https://") && !url.starts_with("http://") {
+                    anyhow::bail!(
+                        "Invalid download mirror URL: '{}' - Expected an http:// or https:// URL",
+                        url
+                    );
+                }
+            }
+        }
+
+        // Validate kept versions
+        if self.keep_versions == Some(0) {
+            anyhow::bail!("Invalid keep_versions: 0 - Expected 1 or more");
+        }
+
         Ok(())
     }
+
+    /// The `preferred_platform` setting, unless it is malformed
+    ///
+    /// A malformed value is reported when config.toml is loaded and then ignored,
+    /// so installs fall back to auto-detection.
+    pub fn valid_preferred_platform(&self) -> Option<&str> {
+        self.preferred_platform
+            .as_deref()
+            .filter(|platform| validate_platform(platform).is_ok())
+    }
+
+    /// Versions kept per package, counting the installed one
+    pub fn keep_versions(&self) -> usize {
+        self.keep_versions.unwrap_or(1).max(1)
+    }
+}
+
+/// Check the format of a platform string
+fn validate_platform(platform: &str) -> Result<()> {
+    if !platform.contains('-') {
+        anyhow::bail!(
+            "Invalid platform string: '{}' - Expected format: 'arch-vendor-os' or 'arch-vendor-os-abi'",
+            platform
+        );
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -157,6 +505,7 @@ mod tests {
         let prefs = Preferences {
             preferred_platform: Some("x86_64-unknown-linux-musl".to_string()),
             custom_bin_path: Some(PathBuf::from("/usr/local/bin")),
+            ..Default::default()
         };
 
         prefs.save(&config_path).unwrap();
@@ -195,6 +544,7 @@ mod tests {
         let prefs = Preferences {
             preferred_platform: Some("x86_64-unknown-linux-gnu".to_string()),
             custom_bin_path: Some(PathBuf::from("/usr/local/bin")),
+            ..Default::default()
         };
         assert!(prefs.validate().is_ok());
     }
@@ -204,8 +554,16 @@ mod tests {
         let prefs = Preferences {
             preferred_platform: Some("invalid".to_string()),
             custom_bin_path: None,
+            ..Default::default()
         };
         assert!(prefs.validate().is_err());
+        assert_eq!(prefs.valid_preferred_platform(), None);
+
+        let prefs = Preferences {
+            preferred_platform: Some("linux-aarch64-musl".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(prefs.valid_preferred_platform(), Some("linux-aarch64-musl"));
     }
 
     #[test]
@@ -213,7 +571,213 @@ mod tests {
         let prefs = Preferences {
             preferred_platform: None,
             custom_bin_path: Some(PathBuf::from("relative/path")),
+            ..Default::default()
         };
         assert!(prefs.validate().is_err());
     }
+
+    #[test]
+    fn test_validate_download_limits() {
+        let prefs = Preferences {
+            download_timeout_secs: Some(600),
+            max_download_bytes: Some(1 << 32),
+            ..Default::default()
+        };
+        assert!(prefs.validate().is_ok());
+
+        let prefs = Preferences {
+            download_timeout_secs: Some(0),
+            ..Default::default()
+        };
+        assert!(prefs.validate().is_err());
+        let prefs = Preferences {
+            max_download_bytes: Some(0),
+            ..Default::default()
+        };
+        assert!(prefs.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_socks_proxy() {
+        let prefs = Preferences {
+            socks_proxy: Some("socks5://127.0.0.1:1080".to_string()),
+            ..Default::default()
+        };
+        assert!(prefs.validate().is_ok());
+
+        let prefs = Preferences {
+            socks_proxy: Some("http://proxy:8080".to_string()),
+            ..Default::default()
+        };
+        assert!(prefs.validate().is_err());
+
+        let prefs = Preferences {
+            proxy_url: Some("http://proxy.example.com:3128".to_string()),
+            ..Default::default()
+        };
+        assert!(prefs.validate().is_ok());
+        let prefs = Preferences {
+            proxy_url: Some("proxy.example.com:3128".to_string()),
+            ..Default::default()
+        };
+        assert!(prefs.validate().is_err());
+    }
+
+    #[test]
+    fn test_keep_versions() {
+        assert_eq!(Preferences::default().keep_versions(), 1);
+
+        let prefs = Preferences {
+            keep_versions: Some(3),
+            ..Default::default()
+        };
+        assert!(prefs.validate().is_ok());
+        assert_eq!(prefs.keep_versions(), 3);
+
+        let prefs = Preferences {
+            keep_versions: Some(0),
+            ..Default::default()
+        };
+        assert!(prefs.validate().is_err());
+    }
+
+    #[test]
+    fn test_download_mirrors() {
+        let prefs: Preferences = toml::from_str(
+            r#"
+            [[download_mirrors]]
+            prefix = "https://github.com/"
+            replace_with = "https://mirror.example.com/github/"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            prefs.download_mirrors,
+            vec![DownloadMirror {
+                prefix: "https://github.com/".to_string(),
+                replace_with: "https://mirror.example.com/github/".to_string(),
+            }]
+        );
+        assert!(prefs.validate().is_ok());
+
+        // Mirrors are opt-in
+        assert!(Preferences::default().download_mirrors.is_empty());
+
+        let prefs = Preferences {
+            download_mirrors: vec![DownloadMirror {
+                prefix: "github.com/".to_string(),
+                replace_with: "https://mirror.example.com/".to_string(),
+            }],
+            ..Default::default()
+        };
+        assert!(prefs.validate().is_err());
+    }
+
+    #[test]
+    fn test_scoring_weights() {
+        let prefs: Preferences = toml::from_str(
+            r#"
+            [scoring]
+            format = 100
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            prefs.scoring,
+            Some(ScoringWeights {
+                format: 100,
+                ..Default::default()
+            })
+        );
+        assert!(Preferences::default().scoring.is_none());
+    }
+
+    #[test]
+    fn test_validate_preferred_compiler() {
+        let prefs = Preferences {
+            preferred_compiler: Some("musl".to_string()),
+            ..Default::default()
+        };
+        assert!(prefs.validate().is_ok());
+
+        let prefs = Preferences {
+            preferred_compiler: Some("clang".to_string()),
+            ..Default::default()
+        };
+        assert!(prefs.validate().is_err());
+    }
+
+    #[test]
+    fn test_save_aliases_keeps_rest_of_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.toml");
+        Preferences::generate_default_file(&config_path).unwrap();
+        fs::write(
+            &config_path,
+            fs::read_to_string(&config_path).unwrap() + "retries = 3\n",
+        )
+        .unwrap();
+
+        let mut aliases = BTreeMap::new();
+        aliases.insert("k".to_string(), "kubectl".to_string());
+        aliases.insert("g".to_string(), "git".to_string());
+        Preferences::save_aliases(&config_path, &aliases).unwrap();
+
+        let content = fs::read_to_string(&config_path).unwrap();
+        assert!(content.starts_with("# Wenget Configuration File"));
+        let prefs = Preferences::load(&config_path).unwrap();
+        assert_eq!(prefs.aliases, aliases);
+        assert_eq!(prefs.retries, Some(3));
+
+        // Removing the last alias drops the table
+        Preferences::save_aliases(&config_path, &BTreeMap::new()).unwrap();
+        let prefs = Preferences::load(&config_path).unwrap();
+        assert!(prefs.aliases.is_empty());
+        assert_eq!(prefs.retries, Some(3));
+
+        // A missing file is created
+        let new_path = temp_dir.path().join("sub").join("config.toml");
+        Preferences::save_aliases(&new_path, &aliases).unwrap();
+        assert_eq!(Preferences::load(&new_path).unwrap().aliases, aliases);
+    }
+
+    #[test]
+    fn test_save_value_keeps_rest_of_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.toml");
+        fs::write(
+            &config_path,
+            "# my settings\nretries = 3\n\n[aliases]\nk = \"kubectl\"\n",
+        )
+        .unwrap();
+
+        Preferences::save_value(
+            &config_path,
+            "preferred_platform",
+            Some("x86_64-unknown-linux-musl"),
+        )
+        .unwrap();
+        let content = fs::read_to_string(&config_path).unwrap();
+        assert!(content.starts_with("# my settings"));
+        let prefs = Preferences::load(&config_path).unwrap();
+        assert_eq!(
+            prefs.preferred_platform.as_deref(),
+            Some("x86_64-unknown-linux-musl")
+        );
+        assert_eq!(prefs.retries, Some(3));
+        assert_eq!(prefs.aliases.len(), 1);
+
+        Preferences::save_value(&config_path, "preferred_platform", None).unwrap();
+        let prefs = Preferences::load(&config_path).unwrap();
+        assert!(prefs.preferred_platform.is_none());
+        assert_eq!(prefs.retries, Some(3));
+
+        // Settings added to the generated template go below its comments
+        let template = temp_dir.path().join("template.toml");
+        Preferences::generate_default_file(&template).unwrap();
+        Preferences::save_value(&template, "custom_bin_path", Some("/opt/bin")).unwrap();
+        let content = fs::read_to_string(&template).unwrap();
+        assert!(content.starts_with("# Wenget Configuration File"));
+        assert!(content.ends_with("\ncustom_bin_path = \"/opt/bin\"\n"));
+    }
 }
@@ -0,0 +1,164 @@
+//! Semantic version helpers for release tags and version constraints
+
+use anyhow::{Context, Result};
+use semver::{Version, VersionReq};
+
+/// Parse a release tag as a semantic version
+///
+/// Tolerates a leading `v` and missing minor/patch components (`v13`, `13.1`).
+/// Returns None for tags that aren't versions (e.g. `nightly`, `latest`).
+pub fn parse_tag(tag: &str) -> Option<Version> {
+    let tag = tag.trim();
+    let tag = tag
+        .strip_prefix('v')
+        .or_else(|| tag.strip_prefix('V'))
+        .unwrap_or(tag);
+
+    if let Ok(version) = Version::parse(tag) {
+        return Some(version);
+    }
+
+    // Pad short numeric versions ("13" / "13.1") to a full triple
+    let parts: Vec<&str> = tag.split('.').collect();
+    if parts.len() < 3 && parts.iter().all(|p| p.parse::<u64>().is_ok()) {
+        let mut padded = parts.join(".");
+        for _ in parts.len()..3 {
+            padded.push_str(".0");
+        }
+        return Version::parse(&padded).ok();
+    }
+
+    None
+}
+
+/// Parse a version constraint such as `^13`, `~1.2` or `>=1.0, <2.0`
+pub fn parse_constraint(constraint: &str) -> Result<VersionReq> {
+    VersionReq::parse(constraint.trim())
+        .with_context(|| format!("Invalid version constraint: {}", constraint))
+}
+
+/// Split a `name@constraint` input into its name and constraint parts
+///
+/// Returns None when the input carries no constraint.
+pub fn split_constraint(input: &str) -> Option<(&str, &str)> {
+    let (name, constraint) = input.rsplit_once('@')?;
+    // An '@' followed by a path is URL userinfo, not a constraint
+    if name.is_empty() || constraint.is_empty() || constraint.contains('/') {
+        return None;
+    }
+    Some((name, constraint))
+}
+
+/// Whether the part after `name@` names one release rather than a range
+///
+/// Full versions (`14.1.0`, `v14.1.0`, `2.0.0-rc.1`) and tags that aren't
+/// versions (`nightly`) are exact. Anything with an operator or wildcard, and
+/// partial versions (`13`, `1.2`), are ranges as before.
+pub fn is_exact_tag(spec: &str) -> bool {
+    let spec = spec.trim();
+    if spec.starts_with(['^', '~', '=', '>', '<', '*']) || spec.contains([',', '*', ' ']) {
+        return false;
+    }
+    let numeric = spec.strip_prefix('v').unwrap_or(spec);
+    let parts: Vec<&str> = numeric.split('.').collect();
+    let partial = parts.len() < 3
+        && parts
+            .iter()
+            .all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit() || c == 'x'));
+    !partial
+}
+
+/// Constraint that keeps `update` on exactly this tag, if it is a version
+pub fn pin_constraint(tag: &str) -> Option<String> {
+    parse_tag(tag).map(|version| format!("={}", version))
+}
+
+/// Pick the newest tag satisfying `req`, skipping tags that aren't versions
+///
+/// Pre-releases only match when the constraint itself names a pre-release.
+pub fn newest_matching<'a, I>(tags: I, req: &VersionReq) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    tags.into_iter()
+        .filter_map(|tag| parse_tag(tag).map(|v| (v, tag)))
+        .filter(|(v, _)| req.matches(v))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, tag)| tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tag() {
+        assert_eq!(parse_tag("v13.0.0"), Some(Version::new(13, 0, 0)));
+        assert_eq!(parse_tag("14.1.1"), Some(Version::new(14, 1, 1)));
+        assert_eq!(parse_tag("v13"), Some(Version::new(13, 0, 0)));
+        assert_eq!(parse_tag("1.2"), Some(Version::new(1, 2, 0)));
+        assert!(parse_tag("v2.0.0-rc.1").unwrap().pre.as_str() == "rc.1");
+        assert_eq!(parse_tag("nightly"), None);
+        assert_eq!(parse_tag("release-2024"), None);
+    }
+
+    #[test]
+    fn test_split_constraint() {
+        assert_eq!(split_constraint("ripgrep@^13"), Some(("ripgrep", "^13")));
+        assert_eq!(
+            split_constraint("bun::baseline@~1.1"),
+            Some(("bun::baseline", "~1.1"))
+        );
+        assert_eq!(split_constraint("ripgrep"), None);
+        assert_eq!(split_constraint("ripgrep@"), None);
+        assert_eq!(
+            split_constraint("https://github.com/BurntSushi/ripgrep@^13"),
+            Some(("https://github.com/BurntSushi/ripgrep", "^13"))
+        );
+        assert_eq!(split_constraint("https://user@github.com/a/b"), None);
+    }
+
+    #[test]
+    fn test_is_exact_tag() {
+        assert!(is_exact_tag("14.1.0"));
+        assert!(is_exact_tag("v14.1.0"));
+        assert!(is_exact_tag("2.0.0-rc.1"));
+        assert!(is_exact_tag("nightly"));
+        assert!(!is_exact_tag("^13"));
+        assert!(!is_exact_tag("~1.2"));
+        assert!(!is_exact_tag(">=1.0, <2.0"));
+        assert!(!is_exact_tag("13"));
+        assert!(!is_exact_tag("v1.2"));
+        assert!(!is_exact_tag("1.x"));
+
+        assert_eq!(pin_constraint("v14.1.0").as_deref(), Some("=14.1.0"));
+        assert_eq!(pin_constraint("nightly"), None);
+        let req = parse_constraint("=14.1.0").unwrap();
+        assert_eq!(
+            newest_matching(["v14.2.0", "v14.1.0"], &req),
+            Some("v14.1.0")
+        );
+    }
+
+    #[test]
+    fn test_newest_matching() {
+        let tags = [
+            "v14.1.0",
+            "v13.0.0",
+            "13.0.1",
+            "nightly",
+            "v13.1.0-beta.1",
+            "v12.1.1",
+        ];
+        let req = parse_constraint("^13").unwrap();
+        assert_eq!(newest_matching(tags, &req), Some("13.0.1"));
+
+        let req = parse_constraint(">=12, <13").unwrap();
+        assert_eq!(newest_matching(tags, &req), Some("v12.1.1"));
+
+        let req = parse_constraint("^15").unwrap();
+        assert_eq!(newest_matching(tags, &req), None);
+
+        assert!(parse_constraint("not a range").is_err());
+    }
+}
@@ -1,33 +1,66 @@
 //! Downloader module for WenPM
 
+use crate::utils::interrupt::CleanupGuard;
+use crate::utils::network::NetworkSettings;
+use crate::utils::proxy;
+use crate::utils::rate_limit;
 use anyhow::{Context, Result};
 use indicatif::{ProgressBar, ProgressStyle};
-use std::fs::File;
-use std::io::Write;
-use std::path::Path;
-use std::sync::OnceLock;
-
-fn shared_client() -> &'static reqwest::blocking::Client {
-    static CLIENT: OnceLock<reqwest::blocking::Client> = OnceLock::new();
-    CLIENT.get_or_init(|| {
-        reqwest::blocking::Client::builder()
-            .user_agent(format!("WenPM/{}", env!("CARGO_PKG_VERSION")))
-            .build()
-            .expect("Failed to create HTTP client")
-    })
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Build the download client for the given timeout settings
+fn client(settings: &NetworkSettings) -> Result<reqwest::blocking::Client> {
+    let mut builder = reqwest::blocking::Client::builder()
+        .user_agent(format!("WenPM/{}", env!("CARGO_PKG_VERSION")))
+        .timeout(settings.download_timeout);
+
+    match proxy::proxy() {
+        Ok(Some(proxy)) => builder = builder.proxy(proxy),
+        Ok(None) => {}
+        Err(e) => log::warn!("Ignoring proxy: {:#}", e),
+    }
+
+    builder.build().context("Failed to create HTTP client")
 }
 
 /// Download a file from URL to a local path with progress bar
-pub fn download_file(url: &str, dest: &Path) -> Result<()> {
+pub fn download_file(url: &str, dest: &Path, settings: &NetworkSettings) -> Result<()> {
+    download_request(client(settings)?.get(url), url, dest, settings)
+}
+
+/// Download a release asset of a private GitHub repository
+///
+/// `api_url` is the asset's API URL (`.../releases/assets/{id}`). GitHub answers with
+/// a redirect to the file; the token is not forwarded to the redirect target.
+pub fn download_github_asset(
+    api_url: &str,
+    dest: &Path,
+    token: &str,
+    settings: &NetworkSettings,
+) -> Result<()> {
+    let request = client(settings)?
+        .get(api_url)
+        .header(reqwest::header::ACCEPT, "application/octet-stream")
+        .bearer_auth(token);
+    download_request(request, api_url, dest, settings)
+}
+
+/// Send a prepared GET request and stream the response body to `dest`
+fn download_request(
+    request: reqwest::blocking::RequestBuilder,
+    url: &str,
+    dest: &Path,
+    settings: &NetworkSettings,
+) -> Result<()> {
     log::info!("Downloading: {}", url);
     log::debug!("Destination: {}", dest.display());
-
-    let client = shared_client();
+    settings.ensure_online(url)?;
 
     // Send GET request
-    let response = client
-        .get(url)
-        .send()
+    let response = rate_limit::send(request, settings.retries, url)
         .with_context(|| format!("Failed to download from {}", url))?;
 
     if !response.status().is_success() {
@@ -36,6 +69,7 @@ pub fn download_file(url: &str, dest: &Path) -> Result<()> {
 
     // Get file size for progress bar
     let total_size = response.content_length().unwrap_or(0);
+    check_download_size(total_size, url, settings)?;
 
     // Create progress bar
     let pb = if total_size > 0 {
@@ -51,15 +85,23 @@ pub fn download_file(url: &str, dest: &Path) -> Result<()> {
         None
     };
 
-    // Create destination file
-    let mut file =
-        File::create(dest).with_context(|| format!("Failed to create file: {}", dest.display()))?;
+    // Write to a temporary ".part" file first so an interrupted download never
+    // leaves a truncated file under the final name
+    let part_path = part_path(dest);
+    // Removed again if the download fails or is interrupted
+    let mut cleanup = CleanupGuard::new();
+    cleanup.track(&part_path);
+    let buffer_size = settings.download_buffer_size();
+    let file = File::create(&part_path)
+        .with_context(|| format!("Failed to create file: {}", part_path.display()))?;
+    // Network reads often return less than the buffer; batch them into full writes
+    let mut file = BufWriter::with_capacity(buffer_size, file);
 
     // Download and write with progress
     let mut downloaded = 0u64;
-    let mut buffer = vec![0; 65536];
+    let mut buffer = vec![0; buffer_size];
 
-    let mut reader = std::io::BufReader::new(response);
+    let mut reader = response;
     loop {
         let n = std::io::Read::read(&mut reader, &mut buffer).context("Failed to read response")?;
 
@@ -71,6 +113,7 @@ pub fn download_file(url: &str, dest: &Path) -> Result<()> {
             .context("Failed to write to file")?;
 
         downloaded += n as u64;
+        check_download_size(downloaded, url, settings)?;
 
         if let Some(pb) = &pb {
             pb.set_position(downloaded);
@@ -81,11 +124,211 @@ pub fn download_file(url: &str, dest: &Path) -> Result<()> {
         pb.finish_with_message("Download complete");
     }
 
+    // Flush explicitly: dropping a BufWriter ignores write errors
+    file.flush().context("Failed to write to file")?;
+    drop(file);
+    fs::rename(&part_path, dest)
+        .with_context(|| format!("Failed to move download to {}", dest.display()))?;
+    cleanup.disarm();
+
     log::info!("Downloaded {} bytes", downloaded);
 
     Ok(())
 }
 
+/// Fail when a download is larger than `max_download_bytes`
+fn check_download_size(bytes: u64, url: &str, settings: &NetworkSettings) -> Result<()> {
+    if bytes > settings.max_download_bytes {
+        anyhow::bail!(
+            "Download of {} exceeds the {} limit (max_download_bytes in config.toml)",
+            url,
+            crate::commands::cache::format_size(settings.max_download_bytes)
+        );
+    }
+    Ok(())
+}
+
+/// Download a file, reusing an identical copy already present at `dest`
+///
+/// An existing file is trusted only if it matches the expected SHA256 checksum
+/// or, without a checksum, the expected size. When the size is unknown (0), the
+/// server's Content-Length is used instead. Anything else is downloaded afresh.
+///
+/// Returns `true` when the existing file was reused.
+pub fn download_file_cached(
+    url: &str,
+    dest: &Path,
+    expected_size: u64,
+    checksum: Option<&str>,
+    settings: &NetworkSettings,
+) -> Result<bool> {
+    if dest.is_file() && is_reusable(url, dest, expected_size, checksum, settings) {
+        log::info!("Reusing cached download: {}", dest.display());
+        return Ok(true);
+    }
+    ensure_downloaded_offline(dest, settings)?;
+
+    download_with_mirrors(url, dest, checksum, settings)?;
+    Ok(false)
+}
+
+/// Download `url`, trying the configured mirrors first
+///
+/// A mirror that fails, or serves a file not matching a known checksum, is
+/// skipped with a warning. The original URL is the last resort.
+fn download_with_mirrors(
+    url: &str,
+    dest: &Path,
+    checksum: Option<&str>,
+    settings: &NetworkSettings,
+) -> Result<()> {
+    let mirrors = settings.mirror_urls(url);
+    for mirror in &mirrors {
+        let result = download_file(mirror, dest, settings).and_then(|()| match checksum {
+            Some(expected) if !checksum_matches(expected, &sha256_file(dest)?) => {
+                anyhow::bail!("checksum mismatch")
+            }
+            _ => Ok(()),
+        });
+        match result {
+            Ok(()) => {
+                log::info!("Downloaded {} from mirror {}", url, mirror);
+                return Ok(());
+            }
+            Err(e) => {
+                log::warn!("Mirror {} failed: {:#}", mirror, e);
+                fs::remove_file(dest).ok();
+            }
+        }
+    }
+
+    if !mirrors.is_empty() {
+        log::info!(
+            "All mirrors failed, downloading {} from the original URL",
+            url
+        );
+    }
+    download_file(url, dest, settings)
+}
+
+/// Like [`download_file_cached`], for a private GitHub release asset
+///
+/// The API URL can't be probed without authentication, so an existing file is only
+/// reused when a checksum or expected size is known.
+pub fn download_github_asset_cached(
+    api_url: &str,
+    dest: &Path,
+    token: &str,
+    expected_size: u64,
+    checksum: Option<&str>,
+    settings: &NetworkSettings,
+) -> Result<bool> {
+    if dest.is_file()
+        && (checksum.is_some() || expected_size > 0)
+        && is_reusable(api_url, dest, expected_size, checksum, settings)
+    {
+        log::info!("Reusing cached download: {}", dest.display());
+        return Ok(true);
+    }
+    ensure_downloaded_offline(dest, settings)?;
+
+    download_github_asset(api_url, dest, token, settings)?;
+    Ok(false)
+}
+
+/// In offline mode, fail when a download isn't already on disk
+fn ensure_downloaded_offline(dest: &Path, settings: &NetworkSettings) -> Result<()> {
+    if settings.offline {
+        let name = dest.file_name().unwrap_or_default().to_string_lossy();
+        anyhow::bail!(
+            "Offline mode: {} has not been downloaded yet (download it once with --keep-download)",
+            name
+        );
+    }
+    Ok(())
+}
+
+/// Check whether an existing download matches what the server would send
+fn is_reusable(
+    url: &str,
+    dest: &Path,
+    expected_size: u64,
+    checksum: Option<&str>,
+    settings: &NetworkSettings,
+) -> bool {
+    if let Some(expected) = checksum {
+        return match sha256_file(dest) {
+            Ok(actual) => checksum_matches(expected, &actual),
+            Err(e) => {
+                log::debug!("Failed to hash {}: {}", dest.display(), e);
+                false
+            }
+        };
+    }
+
+    let Ok(actual_size) = fs::metadata(dest).map(|m| m.len()) else {
+        return false;
+    };
+
+    let expected_size = if expected_size > 0 {
+        Some(expected_size)
+    } else {
+        remote_content_length(url, settings)
+    };
+
+    match expected_size {
+        Some(size) => size == actual_size,
+        // Unfinished downloads never have their final name, so offline the file is trusted
+        None if settings.offline => true,
+        None => {
+            log::debug!("Cannot verify {} without a known size", dest.display());
+            false
+        }
+    }
+}
+
+/// Ask the server for the size of a file via a HEAD request
+fn remote_content_length(url: &str, settings: &NetworkSettings) -> Option<u64> {
+    if settings.offline {
+        return None;
+    }
+    let response = client(settings).ok()?.head(url).send().ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    response
+        .headers()
+        .get(reqwest::header::CONTENT_LENGTH)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+}
+
+/// Compute the lowercase hex SHA256 digest of a file
+pub fn sha256_file(path: &Path) -> Result<String> {
+    let mut file =
+        File::open(path).with_context(|| format!("Failed to open file: {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Compare an expected checksum (optionally prefixed with "sha256:") to a hex digest
+fn checksum_matches(expected: &str, actual: &str) -> bool {
+    let expected = expected.trim();
+    let expected = expected.strip_prefix("sha256:").unwrap_or(expected);
+    expected.eq_ignore_ascii_case(actual)
+}
+
+/// Temporary path used while a download is in progress
+fn part_path(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    dest.with_file_name(name)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -98,8 +341,104 @@ mod tests {
         let dest = temp_dir.path().join("test.txt");
 
         // Download a small file
-        let result = download_file("https://httpbin.org/bytes/1024", &dest);
+        let result = download_file(
+            "https://httpbin.org/bytes/1024",
+            &dest,
+            &NetworkSettings::default(),
+        );
         assert!(result.is_ok());
         assert!(dest.exists());
     }
+
+    #[test]
+    fn test_cached_download_reused_when_size_matches() {
+        let temp_dir = TempDir::new().unwrap();
+        let dest = temp_dir.path().join("tool.tar.gz");
+        fs::write(&dest, b"cached archive").unwrap();
+
+        // The URL is never contacted because the recorded size matches
+        let reused = download_file_cached(
+            "http://invalid.invalid/tool.tar.gz",
+            &dest,
+            14,
+            None,
+            &NetworkSettings::default(),
+        )
+        .unwrap();
+        assert!(reused);
+    }
+
+    #[test]
+    fn test_check_download_size() {
+        let settings = NetworkSettings {
+            max_download_bytes: 1024,
+            ..Default::default()
+        };
+        assert!(check_download_size(1024, "https://example.com/x", &settings).is_ok());
+        let err = check_download_size(1025, "https://example.com/x", &settings).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Download of https://example.com/x exceeds the 1.0 KiB limit (max_download_bytes in config.toml)"
+        );
+    }
+
+    #[test]
+    fn test_cached_download_offline() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings = NetworkSettings {
+            offline: true,
+            ..Default::default()
+        };
+
+        // Without a known size an existing file is trusted
+        let dest = temp_dir.path().join("tool.tar.gz");
+        fs::write(&dest, b"cached archive").unwrap();
+        let url = "http://invalid.invalid/tool.tar.gz";
+        assert!(download_file_cached(url, &dest, 0, None, &settings).unwrap());
+
+        let missing = temp_dir.path().join("other.tar.gz");
+        let err = download_file_cached(url, &missing, 0, None, &settings).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("other.tar.gz has not been downloaded"));
+        assert!(!missing.exists());
+    }
+
+    #[test]
+    fn test_cached_download_rejected_on_mismatch() {
+        let temp_dir = TempDir::new().unwrap();
+        let dest = temp_dir.path().join("tool.tar.gz");
+        fs::write(&dest, b"partial").unwrap();
+
+        assert!(!is_reusable(
+            "http://invalid.invalid/x",
+            &dest,
+            14,
+            None,
+            &NetworkSettings::default()
+        ));
+
+        let digest = sha256_file(&dest).unwrap();
+        assert!(is_reusable(
+            "http://invalid.invalid/x",
+            &dest,
+            14,
+            Some(&digest),
+            &NetworkSettings::default()
+        ));
+        assert!(is_reusable(
+            "http://invalid.invalid/x",
+            &dest,
+            0,
+            Some(&format!("sha256:{}", digest.to_uppercase())),
+            &NetworkSettings::default()
+        ));
+        assert!(!is_reusable(
+            "http://invalid.invalid/x",
+            &dest,
+            7,
+            Some("0000000000000000000000000000000000000000000000000000000000000000"),
+            &NetworkSettings::default()
+        ));
+    }
 }
@@ -0,0 +1,104 @@
+//! Validation of user-supplied command names
+//!
+//! A custom command name (`add -c/--command`) becomes a file name in the bin
+//! directory and, for scripts and local files, the name of the app directory.
+//! Names that are not a single plain file name are rejected before anything is
+//! written, so they cannot create broken launchers or escape those directories.
+
+use anyhow::{bail, Result};
+
+/// Characters Windows does not allow in file names
+const WINDOWS_INVALID_CHARS: [char; 7] = ['<', '>', ':', '"', '|', '?', '*'];
+
+/// Device names Windows reserves regardless of extension
+const WINDOWS_RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Validate a custom command name and return it trimmed
+///
+/// Rejects empty names, path separators, `.`/`..`, whitespace, control
+/// characters and characters Windows forbids in file names. Windows reserved
+/// device names (CON, NUL, COM1, ...) are accepted with a warning, since they
+/// only break launchers on Windows.
+pub fn sanitize_command_name(name: &str) -> Result<String> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        bail!("Command name cannot be empty");
+    }
+    if name.contains(['/', '\\']) {
+        bail!(
+            "Invalid command name '{}': must not contain path separators",
+            name
+        );
+    }
+    if name == "." || name == ".." {
+        bail!("Invalid command name '{}'", name);
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| c.is_whitespace() || c.is_control() || WINDOWS_INVALID_CHARS.contains(c))
+    {
+        bail!(
+            "Invalid command name '{}': character {:?} is not allowed",
+            name,
+            c
+        );
+    }
+
+    if is_windows_reserved_name(name) {
+        log::warn!(
+            "Command name '{}' is a reserved device name on Windows; its launcher will not work there",
+            name
+        );
+    }
+
+    Ok(name.to_string())
+}
+
+/// Whether a name is a Windows reserved device name (e.g. `nul`, `CON.cmd`)
+fn is_windows_reserved_name(name: &str) -> bool {
+    let stem = name.split('.').next().unwrap_or(name);
+    WINDOWS_RESERVED_NAMES
+        .iter()
+        .any(|reserved| stem.eq_ignore_ascii_case(reserved))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_accepts_plain_names() {
+        assert_eq!(sanitize_command_name("rg").unwrap(), "rg");
+        assert_eq!(sanitize_command_name("  my-tool_2 ").unwrap(), "my-tool_2");
+        assert_eq!(sanitize_command_name("tool.v2").unwrap(), "tool.v2");
+    }
+
+    #[test]
+    fn test_sanitize_rejects_invalid_names() {
+        for name in [
+            "", "   ", ".", "..", "../evil", "bin/rg", "..\\evil", "my tool", "a:b", "a*", "a\tb",
+        ] {
+            assert!(
+                sanitize_command_name(name).is_err(),
+                "{:?} should be rejected",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_windows_reserved_names() {
+        assert!(is_windows_reserved_name("CON"));
+        assert!(is_windows_reserved_name("nul"));
+        assert!(is_windows_reserved_name("com1.cmd"));
+        assert!(!is_windows_reserved_name("console"));
+        assert!(!is_windows_reserved_name("com10"));
+
+        // Reserved names only warn
+        assert_eq!(sanitize_command_name("nul").unwrap(), "nul");
+    }
+}
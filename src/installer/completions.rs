@@ -0,0 +1,195 @@
+//! Shell completions shipped in release archives
+//!
+//! Many archives carry completion scripts in a `complete/` or `completions/`
+//! directory (e.g. ripgrep's `complete/_rg`, `complete/rg.bash`). They are not
+//! executables and are skipped when looking for commands; with
+//! `wenget add --completions` they are copied to `completions/<shell>/` under
+//! the wenget root instead. The copies are recorded in the package's `files`
+//! list with their absolute path, so `wenget delete` removes them again.
+
+use crate::core::{InstalledPackage, WenPaths};
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Shells completions are installed for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl Shell {
+    /// Directory name under `completions/`
+    pub fn name(self) -> &'static str {
+        match self {
+            Shell::Bash => "bash",
+            Shell::Zsh => "zsh",
+            Shell::Fish => "fish",
+        }
+    }
+}
+
+/// Shell of an archive file, if it is a completion script
+///
+/// Only files inside a directory named like `complete` or `completions` count.
+/// The shell comes from the extension (`.bash`, `.zsh`, `.fish`); zsh's
+/// `_<name>` files have none.
+pub fn detect_shell(file: &str) -> Option<Shell> {
+    let path = Path::new(file);
+    let in_completion_dir = path.parent()?.components().any(|c| {
+        c.as_os_str()
+            .to_string_lossy()
+            .to_lowercase()
+            .contains("complet")
+    });
+    if !in_completion_dir {
+        return None;
+    }
+
+    let name = path.file_name()?.to_str()?.to_lowercase();
+    if name.ends_with(".bash") {
+        Some(Shell::Bash)
+    } else if name.ends_with(".fish") {
+        Some(Shell::Fish)
+    } else if name.ends_with(".zsh") || (name.starts_with('_') && !name.contains('.')) {
+        Some(Shell::Zsh)
+    } else {
+        None
+    }
+}
+
+/// File name a completion is installed as
+///
+/// zsh only loads `_<command>` files from `fpath`, so `rg.zsh` becomes `_rg`.
+fn installed_name(shell: Shell, file_name: &str) -> String {
+    match (shell, file_name.strip_suffix(".zsh")) {
+        (Shell::Zsh, Some(stem)) if !stem.starts_with('_') => format!("_{}", stem),
+        (Shell::Zsh, Some(stem)) => stem.to_string(),
+        _ => file_name.to_string(),
+    }
+}
+
+/// Copy the completion scripts among `extracted_files` out of `app_dir`
+///
+/// Returns the absolute paths of the copies.
+pub fn install(
+    paths: &WenPaths,
+    app_dir: &Path,
+    extracted_files: &[String],
+) -> Result<Vec<String>> {
+    let mut installed = Vec::new();
+    for file in extracted_files {
+        let Some(shell) = detect_shell(file) else {
+            continue;
+        };
+        let Some(file_name) = Path::new(file).file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+
+        let dir = paths.completions_dir(shell.name());
+        fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        let dest = dir.join(installed_name(shell, file_name));
+        fs::copy(app_dir.join(file), &dest)
+            .with_context(|| format!("Failed to copy completion {}", file))?;
+        installed.push(dest.to_string_lossy().to_string());
+    }
+    Ok(installed)
+}
+
+/// Completion files recorded for a package
+pub fn recorded(paths: &WenPaths, pkg: &InstalledPackage) -> Vec<PathBuf> {
+    let root = paths.completions_root();
+    pkg.files
+        .iter()
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute() && path.starts_with(&root))
+        .collect()
+}
+
+/// Remove the recorded completion files of a package, except those in `keep`
+pub fn remove(paths: &WenPaths, pkg: &InstalledPackage, keep: &[String]) -> Result<()> {
+    for path in recorded(paths, pkg) {
+        if keep.iter().any(|k| Path::new(k) == path) || !path.exists() {
+            continue;
+        }
+        fs::remove_file(&path)
+            .with_context(|| format!("Failed to remove completion {}", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_shell() {
+        assert_eq!(detect_shell("rg-14/complete/rg.bash"), Some(Shell::Bash));
+        assert_eq!(detect_shell("rg-14/complete/_rg"), Some(Shell::Zsh));
+        assert_eq!(detect_shell("completions/fd.fish"), Some(Shell::Fish));
+        assert_eq!(detect_shell("autocomplete/tool.zsh"), Some(Shell::Zsh));
+        assert_eq!(detect_shell("complete/_rg.ps1"), None);
+        assert_eq!(detect_shell("tool.bash"), None);
+        assert_eq!(detect_shell("bin/_helper"), None);
+
+        assert_eq!(installed_name(Shell::Zsh, "rg.zsh"), "_rg");
+        assert_eq!(installed_name(Shell::Zsh, "_rg.zsh"), "_rg");
+        assert_eq!(installed_name(Shell::Zsh, "_rg"), "_rg");
+        assert_eq!(installed_name(Shell::Bash, "rg.bash"), "rg.bash");
+    }
+
+    #[test]
+    fn test_install_and_remove() {
+        let temp = TempDir::new().unwrap();
+        let paths = WenPaths::with_root(temp.path().to_path_buf(), false);
+        let app_dir = paths.app_dir("ripgrep");
+        let files: Vec<String> = ["rg", "complete/_rg", "complete/rg.bash", "doc/rg.1"]
+            .iter()
+            .map(|f| f.to_string())
+            .collect();
+        for file in &files {
+            let path = app_dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, file).unwrap();
+        }
+
+        let copied = install(&paths, &app_dir, &files).unwrap();
+        let zsh = paths.completions_dir("zsh").join("_rg");
+        let bash = paths.completions_dir("bash").join("rg.bash");
+        assert_eq!(copied, [zsh.to_string_lossy(), bash.to_string_lossy()]);
+        assert_eq!(fs::read_to_string(&zsh).unwrap(), "complete/_rg");
+
+        let mut pkg = crate::core::InstalledPackage {
+            repo_name: "ripgrep".to_string(),
+            variant: None,
+            version: "14.1.0".to_string(),
+            platform: "linux-x86_64".to_string(),
+            installed_at: chrono::Utc::now(),
+            install_path: app_dir.to_string_lossy().to_string(),
+            executables: Default::default(),
+            source: crate::core::manifest::PackageSource::Bucket {
+                name: "main".to_string(),
+            },
+            description: String::new(),
+            command_names: vec![],
+            command_name: None,
+            asset_name: String::new(),
+            parent_package: None,
+            download_url: None,
+            bin_dir: None,
+            version_constraint: None,
+            compiler: None,
+            checksum: None,
+            files,
+        };
+        pkg.files.extend(copied);
+        assert_eq!(recorded(&paths, &pkg), [zsh.clone(), bash.clone()]);
+
+        remove(&paths, &pkg, &[bash.to_string_lossy().to_string()]).unwrap();
+        assert!(!zsh.exists());
+        assert!(bash.exists());
+    }
+}
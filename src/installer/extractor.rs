@@ -3,16 +3,37 @@
 use anyhow::{Context, Result};
 use bzip2::read::BzDecoder;
 use flate2::read::GzDecoder;
+use indicatif::{ProgressBar, ProgressBarIter, ProgressStyle};
+use lz4_flex::frame::FrameDecoder;
 use std::fs::{self, File};
-use std::io::Read;
-use std::path::Path;
+use std::io::{IsTerminal, Read};
+use std::path::{Path, PathBuf};
 use tar::Archive;
 use xz2::read::XzDecoder;
 use zip::ZipArchive;
 
+/// How many archives packed inside the downloaded one are unpacked
+const MAX_NESTED_ARCHIVE_DEPTH: usize = 2;
+
+/// Limit on the total size of the files once nested archives are unpacked
+const MAX_NESTED_EXTRACTED_BYTES: u64 = 4 * 1024 * 1024 * 1024;
+
 /// Extract an archive file to a destination directory
 /// For standalone executables, copies them directly to the destination
-pub fn extract_archive(archive_path: &Path, dest_dir: &Path) -> Result<Vec<String>> {
+///
+/// When the only meaningful file extracted is itself an archive (a zip holding a
+/// `.tar.gz`, as some CI pipelines produce), it is unpacked in place as well, up
+/// to [`MAX_NESTED_ARCHIVE_DEPTH`] levels.
+///
+/// With `strip_top_level`, a single directory wrapping every entry (such as
+/// `ripgrep-14.1.0-x86_64-unknown-linux-musl/`) is removed like
+/// `tar --strip-components=1`, and the returned paths are relative to its
+/// contents. Archives with several top-level entries are left as they are.
+pub fn extract_archive(
+    archive_path: &Path,
+    dest_dir: &Path,
+    strip_top_level: bool,
+) -> Result<Vec<String>> {
     log::info!("Extracting: {}", archive_path.display());
     log::debug!("Destination: {}", dest_dir.display());
 
@@ -20,6 +41,206 @@ pub fn extract_archive(archive_path: &Path, dest_dir: &Path) -> Result<Vec<Strin
     fs::create_dir_all(dest_dir)
         .with_context(|| format!("Failed to create directory: {}", dest_dir.display()))?;
 
+    let extracted_files = extract_layer(archive_path, dest_dir, u64::MAX)?;
+    let extracted_files = extract_nested(
+        dest_dir,
+        extracted_files,
+        MAX_NESTED_ARCHIVE_DEPTH,
+        MAX_NESTED_EXTRACTED_BYTES,
+    )?;
+    let extracted_files = if strip_top_level {
+        strip_top_level_dir(dest_dir, extracted_files)?
+    } else {
+        extracted_files
+    };
+
+    log::info!("Extracted {} file(s)", extracted_files.len());
+
+    Ok(extracted_files)
+}
+
+/// Unpack archives packed inside an extracted archive
+///
+/// Each level replaces the nested archive in `files` with its contents, extracted
+/// next to it. Fails as soon as the extracted files would exceed `max_bytes`,
+/// while the nested archive is being written, which bounds what a crafted
+/// archive can expand to.
+fn extract_nested(
+    dest_dir: &Path,
+    mut files: Vec<String>,
+    max_depth: usize,
+    max_bytes: u64,
+) -> Result<Vec<String>> {
+    for _ in 0..max_depth {
+        let Some(inner) = nested_archive(&files).cloned() else {
+            break;
+        };
+        let inner_path = dest_dir.join(&inner);
+        let inner_dir = inner_path.parent().unwrap_or(dest_dir).to_path_buf();
+        let prefix = Path::new(&inner).parent().unwrap_or(Path::new(""));
+        log::info!("Extracting nested archive: {}", inner);
+
+        let budget = max_bytes.saturating_sub(files_size(dest_dir, &files));
+        let inner_files = extract_layer(&inner_path, &inner_dir, budget)
+            .with_context(|| format!("Failed to extract nested archive {}", inner))?;
+        let inner_files: Vec<String> = inner_files
+            .iter()
+            .map(|f| prefix.join(f).to_string_lossy().to_string())
+            .collect();
+
+        // An entry named like the archive has overwritten it already
+        if !inner_files.contains(&inner) {
+            fs::remove_file(&inner_path)
+                .with_context(|| format!("Failed to remove {}", inner_path.display()))?;
+        }
+        files.retain(|f| *f != inner);
+        for file in inner_files {
+            if !files.contains(&file) {
+                files.push(file);
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Total size of the extracted `files` on disk
+fn files_size(dest_dir: &Path, files: &[String]) -> u64 {
+    files
+        .iter()
+        .filter_map(|f| fs::symlink_metadata(dest_dir.join(f)).ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Reader that fails once more than `remaining` bytes have been read through it
+///
+/// Every extracted byte is read through it before being written, so it stops
+/// an archive layer mid-entry when the layer exceeds its size budget.
+struct BudgetReader<'a, R> {
+    inner: R,
+    remaining: &'a mut u64,
+}
+
+impl<R: Read> Read for BudgetReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        if read as u64 > *self.remaining {
+            return Err(std::io::Error::other(
+                "archive expands beyond the extraction size limit",
+            ));
+        }
+        *self.remaining -= read as u64;
+        Ok(read)
+    }
+}
+
+/// Move the contents of the directory wrapping all extracted files up into `dest_dir`
+///
+/// Nothing changes unless every file lies below the same top-level directory
+/// and that directory is the only entry of `dest_dir`. macOS `.app` bundles
+/// are kept, as the bundle directory is part of the application.
+fn strip_top_level_dir(dest_dir: &Path, files: Vec<String>) -> Result<Vec<String>> {
+    let Some(top) = common_top_level_dir(&files) else {
+        return Ok(files);
+    };
+    if top.to_lowercase().ends_with(".app") {
+        return Ok(files);
+    }
+    let entries = fs::read_dir(dest_dir)
+        .with_context(|| format!("Failed to read {}", dest_dir.display()))?
+        .count();
+    if entries != 1 {
+        return Ok(files);
+    }
+    log::debug!("Stripping top-level directory: {}", top);
+
+    // Rename the wrapper first, it may contain an entry of the same name
+    let wrapper = dest_dir.join(format!(".{}.strip", top));
+    fs::rename(dest_dir.join(&top), &wrapper).with_context(|| format!("Failed to move {}", top))?;
+    for entry in
+        fs::read_dir(&wrapper).with_context(|| format!("Failed to read {}", wrapper.display()))?
+    {
+        let entry = entry.with_context(|| format!("Failed to read {}", wrapper.display()))?;
+        let target = dest_dir.join(entry.file_name());
+        fs::rename(entry.path(), &target)
+            .with_context(|| format!("Failed to move {}", target.display()))?;
+    }
+    fs::remove_dir(&wrapper).with_context(|| format!("Failed to remove {}", wrapper.display()))?;
+
+    Ok(files
+        .iter()
+        .map(|file| {
+            Path::new(file)
+                .strip_prefix(&top)
+                .map(|rest| rest.to_string_lossy().to_string())
+                .unwrap_or_else(|_| file.clone())
+        })
+        .collect())
+}
+
+/// The top-level directory shared by all `files`, if there is one
+///
+/// A file at the top level (e.g. a README next to the directory) means there is none.
+fn common_top_level_dir(files: &[String]) -> Option<String> {
+    let mut top: Option<&std::ffi::OsStr> = None;
+    for file in files {
+        let mut components = Path::new(file).components();
+        let first = match components.next()? {
+            std::path::Component::Normal(name) => name,
+            _ => return None,
+        };
+        components.next()?;
+        match top {
+            Some(top) if top != first => return None,
+            _ => top = Some(first),
+        }
+    }
+    top.map(|top| top.to_string_lossy().to_string())
+}
+
+/// The extracted archive among `files`, when it is the only meaningful file
+///
+/// Documentation, license and checksum/signature files next to it are ignored.
+fn nested_archive(files: &[String]) -> Option<&String> {
+    let mut meaningful = files.iter().filter(|file| {
+        let filename = Path::new(file)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(file);
+        let lower = filename.to_lowercase();
+        let signature = [
+            ".sha256",
+            ".sha512",
+            ".sha256sum",
+            ".sig",
+            ".asc",
+            ".minisig",
+        ]
+        .iter()
+        .any(|ext| lower.ends_with(ext));
+        !signature && !is_excluded_file(filename, file)
+    });
+    let only = meaningful.next()?;
+    if meaningful.next().is_some() {
+        return None;
+    }
+    let filename = Path::new(only).file_name()?.to_str()?;
+    is_supported_archive(filename).then_some(only)
+}
+
+/// Whether [`extract_archive`] can unpack a file with this name
+fn is_supported_archive(filename: &str) -> bool {
+    [
+        ".tar.gz", ".tgz", ".tar.xz", ".tar.bz2", ".tbz", ".tar.lz4", ".tar", ".zip", ".7z",
+    ]
+    .iter()
+    .any(|ext| filename.ends_with(ext))
+}
+
+/// Extract one archive layer, dispatching on the file extension
+///
+/// Fails once more than `max_bytes` would be written.
+fn extract_layer(archive_path: &Path, dest_dir: &Path, max_bytes: u64) -> Result<Vec<String>> {
     // Determine archive type by extension
     let filename = archive_path
         .file_name()
@@ -28,26 +249,46 @@ pub fn extract_archive(archive_path: &Path, dest_dir: &Path) -> Result<Vec<Strin
 
     let extracted_files = if is_standalone_executable(filename) {
         // Handle standalone executable
-        extract_standalone_executable(archive_path, dest_dir)?
+        extract_standalone_executable(archive_path, dest_dir, max_bytes)?
     } else if filename.ends_with(".tar.gz") || filename.ends_with(".tgz") {
-        extract_tar_gz(archive_path, dest_dir)?
+        extract_tar_gz(archive_path, dest_dir, max_bytes)?
     } else if filename.ends_with(".tar.xz") {
-        extract_tar_xz(archive_path, dest_dir)?
+        extract_tar_xz(archive_path, dest_dir, max_bytes)?
     } else if filename.ends_with(".tar.bz2") || filename.ends_with(".tbz") {
-        extract_tar_bz2(archive_path, dest_dir)?
+        extract_tar_bz2(archive_path, dest_dir, max_bytes)?
+    } else if filename.ends_with(".tar.lz4") {
+        extract_tar_lz4(archive_path, dest_dir, max_bytes)?
+    } else if filename.ends_with(".tar") {
+        extract_tar(archive_path, dest_dir, max_bytes)?
     } else if filename.ends_with(".zip") {
-        extract_zip(archive_path, dest_dir)?
+        extract_zip(archive_path, dest_dir, max_bytes)?
     } else if filename.ends_with(".7z") {
-        extract_7z(archive_path, dest_dir)?
+        extract_7z(archive_path, dest_dir, max_bytes)?
     } else {
-        anyhow::bail!("Unsupported archive format: {}", filename);
+        anyhow::bail!(
+            "Unsupported archive format '{}' ({}). Extract it manually and install the \
+             executable with `wenget add <path>`, or request support at {}/issues",
+            archive_format(filename),
+            filename,
+            env!("CARGO_PKG_REPOSITORY")
+        );
     };
 
-    log::info!("Extracted {} file(s)", extracted_files.len());
-
     Ok(extracted_files)
 }
 
+/// Name the archive format of a file for error messages (e.g. ".tar.zst", ".rar")
+fn archive_format(filename: &str) -> &str {
+    let lower = filename.to_lowercase();
+    if let Some(pos) = lower.rfind(".tar.") {
+        return &filename[pos..];
+    }
+    filename
+        .rfind('.')
+        .map(|pos| &filename[pos..])
+        .unwrap_or(filename)
+}
+
 /// Check if a file is a standalone executable (not an archive)
 fn is_standalone_executable(filename: &str) -> bool {
     // Windows executables
@@ -62,7 +303,7 @@ fn is_standalone_executable(filename: &str) -> bool {
         }
         // Check if it has no common archive extension
         let archive_extensions = [
-            ".zip", ".tar", ".gz", ".xz", ".bz2", ".7z", ".rar", ".tbz", ".tgz",
+            ".zip", ".tar", ".gz", ".xz", ".bz2", ".lz4", ".7z", ".rar", ".tbz", ".tgz",
         ];
         if !archive_extensions.iter().any(|ext| filename.contains(ext)) {
             // Could be a standalone binary
@@ -74,10 +315,20 @@ fn is_standalone_executable(filename: &str) -> bool {
 }
 
 /// "Extract" a standalone executable by copying it to the destination directory
-fn extract_standalone_executable(executable_path: &Path, dest_dir: &Path) -> Result<Vec<String>> {
+fn extract_standalone_executable(
+    executable_path: &Path,
+    dest_dir: &Path,
+    max_bytes: u64,
+) -> Result<Vec<String>> {
     let filename = executable_path
         .file_name()
         .context("Invalid executable filename")?;
+    if fs::metadata(executable_path)?.len() > max_bytes {
+        anyhow::bail!(
+            "{} exceeds the extraction size limit",
+            executable_path.display()
+        );
+    }
 
     let dest_path = dest_dir.join(filename);
 
@@ -103,46 +354,133 @@ fn extract_standalone_executable(executable_path: &Path, dest_dir: &Path) -> Res
     Ok(vec![relative_path])
 }
 
-/// Extract a .tar.gz file
-fn extract_tar_gz(archive_path: &Path, dest_dir: &Path) -> Result<Vec<String>> {
+/// Create an extraction progress bar, hidden when stderr isn't a terminal
+fn extraction_progress(len: u64, template: &str) -> ProgressBar {
+    if !std::io::stderr().is_terminal() {
+        return ProgressBar::hidden();
+    }
+
+    let pb = ProgressBar::new(len);
+    pb.set_style(
+        ProgressStyle::with_template(template)
+            .unwrap()
+            .progress_chars("#>-"),
+    );
+    pb
+}
+
+/// Open an archive for reading, tracking how much of it has been consumed
+///
+/// Tar archives are streamed through a decompressor, so progress is reported as
+/// bytes of the (compressed) archive read rather than as entries.
+fn open_with_progress(archive_path: &Path) -> Result<(ProgressBarIter<File>, ProgressBar)> {
     let file = File::open(archive_path)
         .with_context(|| format!("Failed to open archive: {}", archive_path.display()))?;
+    let len = file.metadata().map(|m| m.len()).unwrap_or(0);
 
-    let decoder = GzDecoder::new(file);
-    let mut archive = Archive::new(decoder);
+    let pb = extraction_progress(
+        len,
+        "{spinner:.green} Extracting [{bar:40.cyan/blue}] {bytes}/{total_bytes}",
+    );
+    Ok((pb.wrap_read(file), pb))
+}
 
-    extract_tar_archive(&mut archive, dest_dir)
+/// Extract a .tar.gz file
+fn extract_tar_gz(archive_path: &Path, dest_dir: &Path, max_bytes: u64) -> Result<Vec<String>> {
+    let (file, pb) = open_with_progress(archive_path)?;
+
+    let decoder = GzDecoder::new(file);
+    let mut remaining = max_bytes;
+    let mut archive = Archive::new(BudgetReader {
+        inner: decoder,
+        remaining: &mut remaining,
+    });
+
+    let result = extract_tar_archive(&mut archive, dest_dir);
+    pb.finish_and_clear();
+    result
 }
 
 /// Extract a .tar.xz file
-fn extract_tar_xz(archive_path: &Path, dest_dir: &Path) -> Result<Vec<String>> {
-    let file = File::open(archive_path)
-        .with_context(|| format!("Failed to open archive: {}", archive_path.display()))?;
+fn extract_tar_xz(archive_path: &Path, dest_dir: &Path, max_bytes: u64) -> Result<Vec<String>> {
+    let (file, pb) = open_with_progress(archive_path)?;
 
     let decoder = XzDecoder::new(file);
-    let mut archive = Archive::new(decoder);
+    let mut remaining = max_bytes;
+    let mut archive = Archive::new(BudgetReader {
+        inner: decoder,
+        remaining: &mut remaining,
+    });
+
+    let result = extract_tar_archive(&mut archive, dest_dir);
+    pb.finish_and_clear();
+    result
+}
+
+/// Extract an uncompressed .tar file
+fn extract_tar(archive_path: &Path, dest_dir: &Path, max_bytes: u64) -> Result<Vec<String>> {
+    let (file, pb) = open_with_progress(archive_path)?;
+
+    let mut remaining = max_bytes;
+    let mut archive = Archive::new(BudgetReader {
+        inner: file,
+        remaining: &mut remaining,
+    });
+
+    let result = extract_tar_archive(&mut archive, dest_dir);
+    pb.finish_and_clear();
+    result
+}
+
+/// Extract a .tar.lz4 file (LZ4 frame format)
+fn extract_tar_lz4(archive_path: &Path, dest_dir: &Path, max_bytes: u64) -> Result<Vec<String>> {
+    let (file, pb) = open_with_progress(archive_path)?;
 
-    extract_tar_archive(&mut archive, dest_dir)
+    let decoder = FrameDecoder::new(file);
+    let mut remaining = max_bytes;
+    let mut archive = Archive::new(BudgetReader {
+        inner: decoder,
+        remaining: &mut remaining,
+    });
+
+    let result = extract_tar_archive(&mut archive, dest_dir);
+    pb.finish_and_clear();
+    result
 }
 
 /// Extract a .tar.bz2 or .tbz file
-fn extract_tar_bz2(archive_path: &Path, dest_dir: &Path) -> Result<Vec<String>> {
-    let file = File::open(archive_path)
-        .with_context(|| format!("Failed to open archive: {}", archive_path.display()))?;
+fn extract_tar_bz2(archive_path: &Path, dest_dir: &Path, max_bytes: u64) -> Result<Vec<String>> {
+    let (file, pb) = open_with_progress(archive_path)?;
 
     let decoder = BzDecoder::new(file);
-    let mut archive = Archive::new(decoder);
-
-    extract_tar_archive(&mut archive, dest_dir)
+    let mut remaining = max_bytes;
+    let mut archive = Archive::new(BudgetReader {
+        inner: decoder,
+        remaining: &mut remaining,
+    });
+
+    let result = extract_tar_archive(&mut archive, dest_dir);
+    pb.finish_and_clear();
+    result
 }
 
 /// Extract a .7z file
-fn extract_7z(archive_path: &Path, dest_dir: &Path) -> Result<Vec<String>> {
-    use sevenz_rust::decompress_file;
-
-    // Extract the 7z archive
-    decompress_file(archive_path, dest_dir)
-        .with_context(|| format!("Failed to extract 7z archive: {}", archive_path.display()))?;
+fn extract_7z(archive_path: &Path, dest_dir: &Path, max_bytes: u64) -> Result<Vec<String>> {
+    use sevenz_rust::{decompress_file_with_extract_fn, default_entry_extract_fn};
+
+    // Extract the 7z archive (no incremental progress available, so just spin)
+    let pb = extraction_progress(0, "{spinner:.green} Extracting...");
+    pb.enable_steady_tick(std::time::Duration::from_millis(100));
+    let mut remaining = max_bytes;
+    let result = decompress_file_with_extract_fn(archive_path, dest_dir, |entry, reader, dest| {
+        let mut limited = BudgetReader {
+            inner: reader,
+            remaining: &mut remaining,
+        };
+        default_entry_extract_fn(entry, &mut limited, dest)
+    });
+    pb.finish_and_clear();
+    result.with_context(|| format!("Failed to extract 7z archive: {}", archive_path.display()))?;
 
     // Collect all extracted files
     let mut extracted_files = Vec::new();
@@ -177,8 +515,8 @@ fn extract_7z(archive_path: &Path, dest_dir: &Path) -> Result<Vec<String>> {
     Ok(extracted_files)
 }
 
-/// Recursively collect all files in a directory (helper for 7z extraction)
-fn collect_files_recursively(
+/// Recursively collect all files in a directory as paths relative to `base_dir`
+pub(crate) fn collect_files_recursively(
     base_dir: &Path,
     current_dir: &Path,
     files: &mut Vec<String>,
@@ -203,11 +541,26 @@ fn collect_files_recursively(
 }
 
 /// Extract a tar archive (common logic for .tar.gz and .tar.xz)
+///
+/// Regular files keep the permission bits stored in the archive. Symlink and
+/// hardlink entries are recreated after all regular files have been written, so
+/// links pointing at entries later in the archive resolve correctly. Entries and
+/// link targets that lead outside `dest_dir`, also through links extracted
+/// earlier, are skipped.
 fn extract_tar_archive<R: std::io::Read>(
     archive: &mut Archive<R>,
     dest_dir: &Path,
 ) -> Result<Vec<String>> {
+    use tar::EntryType;
+
+    fs::create_dir_all(dest_dir)?;
+    let root = dest_dir
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", dest_dir.display()))?;
+
     let mut extracted_files = Vec::new();
+    // (link path relative to dest_dir, link target as stored in the archive, is_hardlink)
+    let mut links: Vec<(PathBuf, PathBuf, bool)> = Vec::new();
 
     for entry_result in archive
         .entries()
@@ -220,10 +573,7 @@ fn extract_tar_archive<R: std::io::Read>(
         // Tar archives commonly store entries as "./agd", which would otherwise
         // leak into the recorded relative path and the resulting symlink target
         // (e.g. /opt/wenget/apps/agd/./agd).
-        let path: std::path::PathBuf = raw_path
-            .components()
-            .filter(|c| !matches!(c, std::path::Component::CurDir))
-            .collect();
+        let path = strip_curdir(&raw_path);
         let path_str = path.to_string_lossy().to_string();
 
         // Skip directories (path normalization above strips trailing slashes,
@@ -233,6 +583,24 @@ fn extract_tar_archive<R: std::io::Read>(
             continue;
         }
 
+        if normalize_within_root(&path).is_none() {
+            log::warn!(
+                "Skipping {}: path escapes the extraction directory",
+                path_str
+            );
+            continue;
+        }
+
+        // Links are recreated once every regular file is in place
+        if matches!(entry_type, EntryType::Symlink | EntryType::Link) {
+            let target = entry
+                .link_name()
+                .with_context(|| format!("Failed to read link target: {}", path_str))?
+                .with_context(|| format!("Link entry has no target: {}", path_str))?;
+            links.push((path, target.into_owned(), entry_type == EntryType::Link));
+            continue;
+        }
+
         // Extract file
         let dest_path = dest_dir.join(&path);
 
@@ -253,17 +621,66 @@ fn extract_tar_archive<R: std::io::Read>(
             .unpack(&dest_path)
             .with_context(|| format!("Failed to extract: {}", path_str))?;
 
-        // Set executable permission on Unix
-        // Skip for symlinks (they inherit permissions from their target)
+        // Preserve the permission bits recorded in the archive on Unix
         #[cfg(unix)]
         {
-            use tar::EntryType;
-            if entry_type != EntryType::Symlink && is_executable(&mut entry)? {
-                use std::os::unix::fs::PermissionsExt;
-                let mut perms = fs::metadata(&dest_path)?.permissions();
-                perms.set_mode(0o755);
-                fs::set_permissions(&dest_path, perms)?;
-            }
+            use std::os::unix::fs::PermissionsExt;
+            let mode = entry.header().mode()? & 0o777;
+            fs::set_permissions(&dest_path, fs::Permissions::from_mode(mode))?;
+        }
+
+        extracted_files.push(path_str);
+    }
+
+    for (path, target, is_hardlink) in links {
+        let path_str = path.to_string_lossy().to_string();
+
+        // The link's directory may pass through a link extracted before it
+        let parent = path.parent().unwrap_or(Path::new(""));
+        let (Some(link_dir), Some(file_name)) = (resolve_within(&root, parent), path.file_name())
+        else {
+            log::warn!(
+                "Skipping link {}: path escapes the extraction directory",
+                path_str
+            );
+            continue;
+        };
+
+        // Hardlink targets are archive paths; symlink targets are relative to the link
+        let relative = if is_hardlink {
+            strip_curdir(&target)
+        } else {
+            parent.join(&target)
+        };
+        let Some(resolved) = resolve_within(&root, &relative) else {
+            log::warn!(
+                "Skipping link {} -> {}: target escapes the extraction directory",
+                path_str,
+                target.display()
+            );
+            continue;
+        };
+
+        fs::create_dir_all(&link_dir)?;
+        let dest_path = link_dir.join(file_name);
+        if dest_path.exists() || dest_path.is_symlink() {
+            fs::remove_file(&dest_path)?;
+        }
+
+        log::debug!(
+            "Linking: {} -> {} ({})",
+            path_str,
+            target.display(),
+            if is_hardlink { "hardlink" } else { "symlink" }
+        );
+
+        if is_hardlink {
+            fs::hard_link(&resolved, &dest_path)
+                .or_else(|_| fs::copy(&resolved, &dest_path).map(|_| ()))
+                .with_context(|| format!("Failed to create hardlink: {}", path_str))?;
+        } else {
+            create_tar_symlink(&target, &resolved, &dest_path)
+                .with_context(|| format!("Failed to create symlink: {}", path_str))?;
         }
 
         extracted_files.push(path_str);
@@ -272,23 +689,98 @@ fn extract_tar_archive<R: std::io::Read>(
     Ok(extracted_files)
 }
 
-/// Check if a tar entry is executable
+/// Drop "./" (CurDir) components from an archive path
+fn strip_curdir(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| !matches!(c, std::path::Component::CurDir))
+        .collect()
+}
+
+/// Lexically normalize a relative path, returning None if it is absolute or
+/// climbs above its root via ".."
+fn normalize_within_root(path: &Path) -> Option<PathBuf> {
+    use std::path::Component;
+
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => normalized.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    return None;
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(normalized)
+}
+
+/// Resolve a relative archive path against what is already on disk under `root`
+///
+/// Symlinks met on the way are followed, so a path through a link extracted
+/// earlier resolves to where that link points. Returns None when the path
+/// leaves `root` (which must be canonical) or passes through a dangling link.
+fn resolve_within(root: &Path, path: &Path) -> Option<PathBuf> {
+    use std::path::Component;
+
+    let mut resolved = root.to_path_buf();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => {
+                resolved.push(part);
+                if resolved.is_symlink() {
+                    resolved = resolved.canonicalize().ok()?;
+                }
+            }
+            Component::CurDir => {}
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            Component::RootDir | Component::Prefix(_) => return None,
+        }
+        if !resolved.starts_with(root) {
+            return None;
+        }
+    }
+    Some(resolved)
+}
+
+/// Recreate a symlink entry, keeping the archive's relative target
 #[cfg(unix)]
-fn is_executable<R: std::io::Read>(entry: &mut tar::Entry<R>) -> Result<bool> {
-    let mode = entry.header().mode()?;
-    Ok(mode & 0o111 != 0)
+fn create_tar_symlink(target: &Path, _resolved: &Path, link: &Path) -> Result<()> {
+    std::os::unix::fs::symlink(target, link)?;
+    Ok(())
+}
+
+/// Recreate a symlink entry by copying the file it points to
+///
+/// Creating symlinks on Windows requires extra privileges, so the link target
+/// is materialized as a regular file instead.
+#[cfg(not(unix))]
+fn create_tar_symlink(_target: &Path, resolved: &Path, link: &Path) -> Result<()> {
+    fs::copy(resolved, link)?;
+    Ok(())
 }
 
 /// Extract a .zip file
-fn extract_zip(archive_path: &Path, dest_dir: &Path) -> Result<Vec<String>> {
+fn extract_zip(archive_path: &Path, dest_dir: &Path, max_bytes: u64) -> Result<Vec<String>> {
     let file = File::open(archive_path)
         .with_context(|| format!("Failed to open archive: {}", archive_path.display()))?;
 
     let mut archive = ZipArchive::new(file).context("Failed to read ZIP archive")?;
 
     let mut extracted_files = Vec::new();
+    let mut remaining = max_bytes;
+
+    let pb = extraction_progress(
+        archive.len() as u64,
+        "{spinner:.green} Extracting [{bar:40.cyan/blue}] {pos}/{len} entries",
+    );
 
     for i in 0..archive.len() {
+        pb.set_position(i as u64);
         let mut file = archive.by_index(i).context("Failed to read ZIP entry")?;
 
         let file_path = file
@@ -312,7 +804,11 @@ fn extract_zip(archive_path: &Path, dest_dir: &Path) -> Result<Vec<String>> {
         let mut dest_file = File::create(&dest_path)
             .with_context(|| format!("Failed to create file: {}", dest_path.display()))?;
 
-        std::io::copy(&mut file, &mut dest_file).context("Failed to extract file")?;
+        let mut limited = BudgetReader {
+            inner: &mut file,
+            remaining: &mut remaining,
+        };
+        std::io::copy(&mut limited, &mut dest_file).context("Failed to extract file")?;
 
         // Set executable permission on Unix
         #[cfg(unix)]
@@ -330,6 +826,8 @@ fn extract_zip(archive_path: &Path, dest_dir: &Path) -> Result<Vec<String>> {
         extracted_files.push(file_path.to_string_lossy().to_string());
     }
 
+    pb.finish_and_clear();
+
     Ok(extracted_files)
 }
 
@@ -499,11 +997,9 @@ fn detect_script_type(file_path: &Path) -> Option<&'static str> {
 
     // Check shebang #!
     if buf[0] == b'#' && buf[1] == b'!' {
-        let line = std::str::from_utf8(&buf[..bytes_read])
-            .ok()?
-            .lines()
-            .next()?;
-        let lower = line.to_lowercase();
+        // Lossy: the buffer may end inside a multi-byte character
+        let text = String::from_utf8_lossy(&buf[..bytes_read]);
+        let lower = text.lines().next()?.to_lowercase();
         if lower.contains("python") {
             return Some("Python script");
         } else if lower.contains("bash") || lower.contains("/sh") {
@@ -521,6 +1017,70 @@ fn detect_script_type(file_path: &Path) -> Option<&'static str> {
     None
 }
 
+/// What the first bytes of a file say about it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ContentKind {
+    /// Native executable (ELF, PE, Mach-O)
+    Native(&'static str),
+    /// Script with a shebang line
+    Script(&'static str),
+    /// Non-empty file with neither, e.g. a data blob or a plain text file
+    Data,
+    /// Empty or unreadable
+    Unknown,
+}
+
+/// Classify a file by its magic bytes or shebang
+fn classify_content(file_path: &Path) -> ContentKind {
+    if let Some(exe_type) = detect_executable_type(file_path) {
+        return ContentKind::Native(exe_type);
+    }
+    if let Some(script_type) = detect_script_type(file_path) {
+        return ContentKind::Script(script_type);
+    }
+    match fs::metadata(file_path) {
+        Ok(meta) if meta.is_file() && meta.len() > 0 => ContentKind::Data,
+        _ => ContentKind::Unknown,
+    }
+}
+
+/// Whether a file name looks like a shared or static library
+fn is_library(filename: &str) -> bool {
+    let lower = filename.to_lowercase();
+    [".so", ".dylib", ".dll", ".a", ".o", ".node"]
+        .iter()
+        .any(|ext| lower.ends_with(ext))
+        || lower.contains(".so.")
+}
+
+/// Whether a file is a candidate only because its content is a native binary
+///
+/// On Unix a file with an unusual extension (`tool.real`) is normally skipped;
+/// binary magic shows it is a program after all. Libraries are left out.
+fn is_native_with_extension(filename: &str, file_path: &str, content: Option<ContentKind>) -> bool {
+    !cfg!(windows)
+        && matches!(content, Some(ContentKind::Native(_)))
+        && !is_library(filename)
+        && !could_be_executable(filename, file_path)
+}
+
+/// File name the command of an extracted executable is derived from
+///
+/// A native binary that is a candidate only because of its content, like the
+/// `tool.real` behind a `tool` wrapper script, is named without its extension.
+pub fn command_file_name<'a>(file: &'a str, extract_dir: &Path) -> &'a str {
+    let filename = Path::new(file)
+        .file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or(file);
+    let content = classify_content(&extract_dir.join(file));
+    if is_native_with_extension(filename, file, Some(content)) {
+        filename.split('.').next().unwrap_or(filename)
+    } else {
+        filename
+    }
+}
+
 /// Find all possible executables and rank them by priority
 /// `extract_dir` is the directory where files were extracted to (used for permission checks)
 pub fn find_executable_candidates(
@@ -529,6 +1089,10 @@ pub fn find_executable_candidates(
     extract_dir: Option<&Path>,
 ) -> Vec<ExecutableCandidate> {
     let mut candidates = Vec::new();
+    // Paths a wrapper script for a `<name>.<ext>` binary would have, and the
+    // indexes of script candidates
+    let mut wrapped_paths = Vec::new();
+    let mut scripts = Vec::new();
 
     log::debug!(
         "find_executable_candidates: package_name={}, extract_dir={:?}, files={}",
@@ -552,8 +1116,15 @@ pub fn find_executable_candidates(
             continue;
         }
 
+        // Look at the content when the files are on disk
+        let content = extract_dir.map(|dir| classify_content(&dir.join(file)));
+
+        // A native binary with an extension (e.g. `tool.real` behind a `tool`
+        // wrapper script) is still a candidate on Unix
+        let native_with_extension = is_native_with_extension(filename, file, content);
+
         // Check if this could be an executable
-        if !could_be_executable(filename, file) {
+        if !could_be_executable(filename, file) && !native_with_extension {
             log::trace!(
                 "Skipping {} - not executable candidate (filename: {})",
                 file,
@@ -573,12 +1144,6 @@ pub fn find_executable_candidates(
             continue;
         }
 
-        log::trace!("Evaluating candidate: {} (filename: {})", file, filename);
-
-        let name_without_ext = filename.trim_end_matches(".exe");
-        let mut score = 0u32;
-        let mut reasons = Vec::new();
-
         // Check executable permission (Unix only)
         #[cfg(unix)]
         let has_exec_perm = if let Some(dir) = extract_dir {
@@ -590,6 +1155,23 @@ pub fn find_executable_candidates(
         #[cfg(not(unix))]
         let has_exec_perm = false;
 
+        // Neither binary magic nor a shebang: data, unless marked executable
+        if content == Some(ContentKind::Data) && !has_exec_perm {
+            log::trace!("Skipping {} - no binary magic or shebang", file);
+            continue;
+        }
+
+        log::trace!("Evaluating candidate: {} (filename: {})", file, filename);
+
+        // `tool.real` is named like the package's `tool`
+        let name_without_ext = if native_with_extension {
+            filename.split('.').next().unwrap_or(filename)
+        } else {
+            filename.trim_end_matches(".exe")
+        };
+        let mut score = 0u32;
+        let mut reasons = Vec::new();
+
         // Rule 0: Has executable permission (Unix) - strong signal
         #[cfg(unix)]
         if has_exec_perm {
@@ -601,9 +1183,8 @@ pub fn find_executable_candidates(
         let _ = extract_dir;
 
         // Rule 0b: Content-based detection via magic bytes (strongest signal)
-        if let Some(dir) = extract_dir {
-            let full_path = dir.join(file);
-            if let Some(exe_type) = detect_executable_type(&full_path) {
+        match content {
+            Some(ContentKind::Native(exe_type)) => {
                 score += 60;
                 reasons.push(match exe_type {
                     "ELF" => "ELF binary",
@@ -611,7 +1192,8 @@ pub fn find_executable_candidates(
                     "Mach-O" | "Mach-O fat" => "Mach-O binary",
                     _ => "native binary",
                 });
-            } else if let Some(script_type) = detect_script_type(&full_path) {
+            }
+            Some(ContentKind::Script(script_type)) => {
                 score += 30;
                 reasons.push(match script_type {
                     "Shell script" => "shell script (shebang)",
@@ -622,6 +1204,7 @@ pub fn find_executable_candidates(
                     _ => "script (shebang)",
                 });
             }
+            _ => {}
         }
 
         // Rule 1: Exact match with package name (highest priority)
@@ -678,6 +1261,13 @@ pub fn find_executable_candidates(
                 reasons.join(", ")
             };
 
+            if native_with_extension {
+                let dir = &file[..file.len() - filename.len()];
+                wrapped_paths.push((format!("{}{}", dir, name_without_ext), file.clone()));
+            }
+            if matches!(content, Some(ContentKind::Script(_))) {
+                scripts.push(candidates.len());
+            }
             candidates.push(ExecutableCandidate {
                 path: file.clone(),
                 score,
@@ -686,8 +1276,21 @@ pub fn find_executable_candidates(
         }
     }
 
+    // A script next to the binary it wraps stays selectable, but the binary is
+    // what gets installed
+    for index in scripts {
+        let candidate = &mut candidates[index];
+        if let Some((_, binary)) = wrapped_paths
+            .iter()
+            .find(|(path, _)| *path == candidate.path)
+        {
+            candidate.score = 0;
+            candidate.reason = format!("wrapper script for {}", binary);
+        }
+    }
+
     // Sort by score (highest first)
-    candidates.sort_by(|a, b| b.score.cmp(&a.score));
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.score));
 
     candidates
 }
@@ -797,7 +1400,7 @@ mod tests {
         }
 
         let dest = dir.path().join("out");
-        let files = extract_archive(&archive_path, &dest).unwrap();
+        let files = extract_archive(&archive_path, &dest, false).unwrap();
 
         // Recorded relative paths must not contain a "./" component.
         assert!(
@@ -814,6 +1417,500 @@ mod tests {
         assert!(dest.join("Resources/cli-templates.toml").is_file());
     }
 
+    /// Write a .tar.gz holding `entries` (name, contents)
+    fn write_tar_gz(path: &Path, entries: &[(&str, &[u8])]) {
+        let file = File::create(path).unwrap();
+        let enc = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+        let mut builder = tar::Builder::new(enc);
+        for (name, data) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o755);
+            header.set_cksum();
+            builder.append_data(&mut header, name, *data).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    /// Write a .zip holding `entries` (name, contents)
+    fn write_zip(path: &Path, entries: &[(&str, &[u8])]) {
+        use std::io::Write;
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        for (name, data) in entries {
+            zip.start_file(*name, zip::write::FileOptions::default())
+                .unwrap();
+            zip.write_all(data).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn test_extract_tar_gz_strips_top_level_dir() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let archive = dir.path().join("rg.tar.gz");
+        write_tar_gz(
+            &archive,
+            &[
+                ("ripgrep-14.1.0-x86_64/rg", b"binary"),
+                ("ripgrep-14.1.0-x86_64/complete/_rg", b"#compdef rg"),
+                ("ripgrep-14.1.0-x86_64/README.md", b"readme"),
+            ],
+        );
+
+        let dest = dir.path().join("out");
+        let mut files = extract_archive(&archive, &dest, true).unwrap();
+        files.sort();
+        assert_eq!(files, ["README.md", "complete/_rg", "rg"]);
+        assert_eq!(fs::read(dest.join("rg")).unwrap(), b"binary");
+        assert!(dest.join("complete/_rg").is_file());
+        assert_eq!(fs::read_dir(&dest).unwrap().count(), 3);
+
+        // Without the option the wrapper is kept
+        let dest = dir.path().join("kept");
+        let files = extract_archive(&archive, &dest, false).unwrap();
+        assert!(files.contains(&"ripgrep-14.1.0-x86_64/rg".to_string()));
+    }
+
+    #[test]
+    fn test_extract_zip_strips_top_level_dir() {
+        let dir = tempfile::TempDir::new().unwrap();
+
+        // The wrapper may hold an entry with its own name
+        let archive = dir.path().join("tool.zip");
+        write_zip(
+            &archive,
+            &[("tool/tool", b"binary"), ("tool/doc/tool.1", b"man")],
+        );
+        let dest = dir.path().join("out");
+        let mut files = extract_archive(&archive, &dest, true).unwrap();
+        files.sort();
+        assert_eq!(files, ["doc/tool.1", "tool"]);
+        assert_eq!(fs::read(dest.join("tool")).unwrap(), b"binary");
+
+        // Several top-level entries are left untouched
+        let archive = dir.path().join("multi.zip");
+        write_zip(
+            &archive,
+            &[("tool-1.0/tool", b"binary"), ("LICENSE", b"MIT")],
+        );
+        let dest = dir.path().join("multi");
+        let mut files = extract_archive(&archive, &dest, true).unwrap();
+        files.sort();
+        assert_eq!(files, ["LICENSE", "tool-1.0/tool"]);
+        assert!(dest.join("tool-1.0/tool").is_file());
+
+        // So are app bundles
+        let archive = dir.path().join("app.zip");
+        write_zip(&archive, &[("Tool.app/Contents/MacOS/tool", b"binary")]);
+        let dest = dir.path().join("app");
+        let files = extract_archive(&archive, &dest, true).unwrap();
+        assert_eq!(files, ["Tool.app/Contents/MacOS/tool"]);
+    }
+
+    #[test]
+    fn test_extract_nested_archive() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let inner = dir.path().join("tool-linux-x86_64.tar.gz");
+        write_tar_gz(&inner, &[("tool-1.0/tool", b"\x7fELF binary")]);
+        let outer = dir.path().join("tool.zip");
+        write_zip(
+            &outer,
+            &[
+                ("dist/tool-linux-x86_64.tar.gz", &fs::read(&inner).unwrap()),
+                ("dist/tool-linux-x86_64.tar.gz.sha256", b"abc"),
+                ("README.md", b"docs"),
+            ],
+        );
+
+        let dest = dir.path().join("out");
+        let mut files = extract_archive(&outer, &dest, false).unwrap();
+        files.sort();
+        let tool = Path::new("dist")
+            .join("tool-1.0")
+            .join("tool")
+            .to_string_lossy()
+            .to_string();
+        assert!(files.contains(&tool), "{files:?}");
+        assert!(dest.join(&tool).is_file());
+        // The nested archive itself is gone
+        assert!(!files.iter().any(|f| f.ends_with("x86_64.tar.gz")));
+        assert!(!dest.join("dist/tool-linux-x86_64.tar.gz").exists());
+        assert_eq!(find_executable(&files, "tool"), Some(tool));
+    }
+
+    #[test]
+    fn test_extract_nested_archive_limits() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let level3 = dir.path().join("l3.tar.gz");
+        write_tar_gz(&level3, &[("tool", b"binary")]);
+        let level2 = dir.path().join("l2.zip");
+        write_zip(&level2, &[("l3.tar.gz", &fs::read(&level3).unwrap())]);
+        let level1 = dir.path().join("l1.tar.gz");
+        write_tar_gz(&level1, &[("l2.zip", &fs::read(&level2).unwrap())]);
+        let outer = dir.path().join("l0.zip");
+        write_zip(&outer, &[("l1.tar.gz", &fs::read(&level1).unwrap())]);
+
+        // Two nested levels are unpacked, the third archive is left alone
+        let dest = dir.path().join("out");
+        let files = extract_archive(&outer, &dest, false).unwrap();
+        assert_eq!(files, ["l3.tar.gz"]);
+
+        // Several meaningful files: nothing is unpacked
+        let dest = dir.path().join("several");
+        fs::create_dir_all(&dest).unwrap();
+        fs::copy(&level3, dest.join("l3.tar.gz")).unwrap();
+        fs::write(dest.join("tool"), "binary").unwrap();
+        let files = vec!["l3.tar.gz".to_string(), "tool".to_string()];
+        assert_eq!(
+            extract_nested(&dest, files.clone(), 2, u64::MAX).unwrap(),
+            files
+        );
+
+        // Expanding past the size limit fails
+        let dest = dir.path().join("bomb");
+        fs::create_dir_all(&dest).unwrap();
+        write_tar_gz(&dest.join("inner.tar.gz"), &[("big", &[0u8; 4096])]);
+        let err = extract_nested(&dest, vec!["inner.tar.gz".to_string()], 2, 1024).unwrap_err();
+        assert!(format!("{err:#}").contains("limit"), "{err:#}");
+    }
+
+    #[test]
+    fn test_extract_nested_archive_stops_mid_entry() {
+        const LIMIT: u64 = 64 * 1024;
+        let zeros = vec![0u8; 8 * 1024 * 1024];
+        let dir = tempfile::TempDir::new().unwrap();
+
+        // A few KiB compressed, 8 MiB once written: extraction must stop at the budget
+        for inner in ["inner.tar.gz", "inner.zip"] {
+            let dest = dir.path().join(inner.replace('.', "-"));
+            fs::create_dir_all(&dest).unwrap();
+            if inner.ends_with(".zip") {
+                write_zip(&dest.join(inner), &[("big", &zeros)]);
+            } else {
+                write_tar_gz(&dest.join(inner), &[("big", &zeros)]);
+            }
+            assert!(fs::metadata(dest.join(inner)).unwrap().len() < LIMIT / 2);
+
+            let err = extract_nested(&dest, vec![inner.to_string()], 2, LIMIT).unwrap_err();
+            assert!(format!("{err:#}").contains("limit"), "{err:#}");
+            let written = fs::metadata(dest.join("big")).map_or(0, |m| m.len());
+            assert!(written <= LIMIT, "{inner}: wrote {written} bytes");
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_extract_tar_preserves_modes_and_symlinks() {
+        use std::io::Write;
+        use std::os::unix::fs::PermissionsExt;
+        use tempfile::TempDir;
+
+        let dir = TempDir::new().unwrap();
+        let archive_path = dir.path().join("tool.tar.gz");
+        {
+            let file = File::create(&archive_path).unwrap();
+            let enc = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+            let mut builder = tar::Builder::new(enc);
+
+            // The symlink comes first so it is created before its target exists
+            let mut link = tar::Header::new_gnu();
+            link.set_entry_type(tar::EntryType::Symlink);
+            link.set_size(0);
+            link.set_mode(0o777);
+            builder
+                .append_link(&mut link, "tool/bin/tool", "../libexec/tool")
+                .unwrap();
+
+            let data = b"#!/bin/sh\necho tool\n";
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o750);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, "tool/libexec/tool", &data[..])
+                .unwrap();
+
+            let mut escaping = tar::Header::new_gnu();
+            escaping.set_entry_type(tar::EntryType::Symlink);
+            escaping.set_size(0);
+            builder
+                .append_link(&mut escaping, "tool/evil", "../../etc/passwd")
+                .unwrap();
+
+            builder.into_inner().unwrap().finish().unwrap().flush().ok();
+        }
+
+        let dest = dir.path().join("out");
+        let files = extract_archive(&archive_path, &dest, false).unwrap();
+
+        assert!(files.iter().any(|f| f == "tool/libexec/tool"), "{files:?}");
+        assert!(files.iter().any(|f| f == "tool/bin/tool"), "{files:?}");
+        assert!(!files.iter().any(|f| f == "tool/evil"), "{files:?}");
+
+        let real = dest.join("tool/libexec/tool");
+        let mode = fs::metadata(&real).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode, 0o750);
+
+        let link = dest.join("tool/bin/tool");
+        assert!(link.is_symlink());
+        assert_eq!(
+            fs::read_link(&link).unwrap(),
+            PathBuf::from("../libexec/tool")
+        );
+        assert_eq!(fs::read(&link).unwrap(), fs::read(&real).unwrap());
+        assert!(!dest.join("tool/evil").exists());
+    }
+
+    #[test]
+    fn test_extract_tar_lz4() {
+        use std::io::Write;
+        use tempfile::TempDir;
+
+        let dir = TempDir::new().unwrap();
+        let archive_path = dir.path().join("tool-linux-x86_64.tar.lz4");
+        {
+            let file = File::create(&archive_path).unwrap();
+            let enc = lz4_flex::frame::FrameEncoder::new(file);
+            let mut builder = tar::Builder::new(enc);
+
+            let data = b"binary";
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o755);
+            header.set_cksum();
+            builder.append_data(&mut header, "tool", &data[..]).unwrap();
+            builder
+                .into_inner()
+                .unwrap()
+                .finish()
+                .unwrap()
+                .flush()
+                .unwrap();
+        }
+
+        let dest = dir.path().join("out");
+        let files = extract_archive(&archive_path, &dest, false).unwrap();
+
+        assert_eq!(files, vec!["tool".to_string()]);
+        assert_eq!(fs::read(dest.join("tool")).unwrap(), b"binary");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_extract_tar_bz2_keeps_executable_bit() {
+        use std::io::Write;
+        use std::os::unix::fs::PermissionsExt;
+        use tempfile::TempDir;
+
+        let dir = TempDir::new().unwrap();
+        let archive_path = dir.path().join("tool-linux-x86_64.tar.bz2");
+        {
+            let file = File::create(&archive_path).unwrap();
+            let enc = bzip2::write::BzEncoder::new(file, bzip2::Compression::default());
+            let mut builder = tar::Builder::new(enc);
+
+            for (path, data, mode) in [
+                ("tool/bin/tool", &b"binary"[..], 0o755),
+                ("tool/README.md", &b"docs"[..], 0o644),
+            ] {
+                let mut header = tar::Header::new_gnu();
+                header.set_size(data.len() as u64);
+                header.set_mode(mode);
+                header.set_cksum();
+                builder.append_data(&mut header, path, data).unwrap();
+            }
+            builder
+                .into_inner()
+                .unwrap()
+                .finish()
+                .unwrap()
+                .flush()
+                .unwrap();
+        }
+
+        let dest = dir.path().join("out");
+        let mut files = extract_archive(&archive_path, &dest, false).unwrap();
+        files.sort();
+
+        assert_eq!(files, ["tool/README.md", "tool/bin/tool"]);
+        assert_eq!(fs::read(dest.join("tool/bin/tool")).unwrap(), b"binary");
+        let mode = |path: &str| fs::metadata(dest.join(path)).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode("tool/bin/tool"), 0o755);
+        assert_eq!(mode("tool/README.md"), 0o644);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_extract_7z_sets_executable_bit() {
+        use sevenz_rust::{SevenZArchiveEntry, SevenZWriter};
+        use std::os::unix::fs::PermissionsExt;
+        use tempfile::TempDir;
+
+        let dir = TempDir::new().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        let archive_path = dir.path().join("tool-linux-x86_64.7z");
+        {
+            let mut writer = SevenZWriter::create(&archive_path).unwrap();
+            for (path, data) in [
+                ("tool/bin/tool", &b"binary"[..]),
+                ("tool/README.md", &b"docs"[..]),
+            ] {
+                let file = src.join(path.replace('/', "_"));
+                fs::write(&file, data).unwrap();
+                // 7z stores no Unix mode, whatever the source file had
+                fs::set_permissions(&file, fs::Permissions::from_mode(0o755)).unwrap();
+                let entry = SevenZArchiveEntry::from_path(&file, path.to_string());
+                writer
+                    .push_archive_entry(entry, Some(File::open(&file).unwrap()))
+                    .unwrap();
+            }
+            writer.finish().unwrap();
+        }
+
+        let dest = dir.path().join("out");
+        let mut files = extract_archive(&archive_path, &dest, false).unwrap();
+        files.sort();
+
+        assert_eq!(files, ["tool/README.md", "tool/bin/tool"]);
+        assert_eq!(fs::read(dest.join("tool/bin/tool")).unwrap(), b"binary");
+        // The executable bit comes from the file name heuristic instead
+        let mode = |path: &str| fs::metadata(dest.join(path)).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode("tool/bin/tool"), 0o755);
+        assert_eq!(mode("tool/README.md") & 0o111, 0);
+    }
+
+    #[test]
+    fn test_unsupported_format_error() {
+        use tempfile::TempDir;
+
+        let dir = TempDir::new().unwrap();
+        let archive_path = dir.path().join("tool-linux-x86_64.tar.zst");
+        fs::write(&archive_path, b"data").unwrap();
+
+        let err = extract_archive(&archive_path, &dir.path().join("out"), false)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("'.tar.zst'"), "{err}");
+        assert!(err.contains("wenget add <path>"), "{err}");
+        assert!(err.contains("/issues"), "{err}");
+
+        assert_eq!(archive_format("tool.rar"), ".rar");
+        assert_eq!(archive_format("tool.TAR.ZST"), ".TAR.ZST");
+    }
+
+    #[test]
+    fn test_extract_uncompressed_tar() {
+        use tempfile::TempDir;
+
+        let dir = TempDir::new().unwrap();
+        let archive_path = dir.path().join("tool-linux-x86_64.tar");
+        {
+            let file = File::create(&archive_path).unwrap();
+            let mut builder = tar::Builder::new(file);
+
+            let data = b"binary";
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o755);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, "tool/bin/tool", &data[..])
+                .unwrap();
+            builder.finish().unwrap();
+        }
+
+        let dest = dir.path().join("out");
+        let files = extract_archive(&archive_path, &dest, false).unwrap();
+
+        assert_eq!(files, vec!["tool/bin/tool".to_string()]);
+        assert_eq!(fs::read(dest.join("tool/bin/tool")).unwrap(), b"binary");
+    }
+
+    /// Write a tar.gz of symlinks, storing paths verbatim (`tar::Builder` rejects "..")
+    #[cfg(unix)]
+    fn write_symlink_tar_gz(path: &Path, links: &[(&str, &str)]) {
+        use std::io::Write;
+
+        let file = File::create(path).unwrap();
+        let enc = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+        let mut builder = tar::Builder::new(enc);
+        for (link, target) in links {
+            let mut header = tar::Header::new_gnu();
+            header.set_entry_type(tar::EntryType::Symlink);
+            header.set_size(0);
+            header.set_mode(0o777);
+            header.as_old_mut().name[..link.len()].copy_from_slice(link.as_bytes());
+            header.set_link_name(target).unwrap();
+            header.set_cksum();
+            builder.append(&header, std::io::empty()).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap().flush().ok();
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_extract_tar_rejects_chained_symlinks() {
+        use tempfile::TempDir;
+
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("x"), "outside").unwrap();
+        let archive_path = dir.path().join("evil.tar.gz");
+        // a -> dest_dir is fine, but b -> a/.. is the parent of dest_dir, and
+        // b/x would then replace the file outside
+        write_symlink_tar_gz(&archive_path, &[("a", "."), ("b", "a/.."), ("b/x", "a")]);
+
+        let dest = dir.path().join("out");
+        let files = extract_archive(&archive_path, &dest, false).unwrap();
+
+        assert!(dest.join("a").is_symlink());
+        assert!(!dest.join("b").is_symlink());
+        assert!(!files.iter().any(|f| f == "b"), "{files:?}");
+        assert_eq!(fs::read_to_string(dir.path().join("x")).unwrap(), "outside");
+        assert!(!dir.path().join("x").is_symlink());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_extract_tar_rejects_link_through_link() {
+        use tempfile::TempDir;
+
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("victim"), "outside").unwrap();
+        let archive_path = dir.path().join("evil.tar.gz");
+        // sub/up points at dest_dir, so sub/up/../victim is outside it even
+        // though the path looks contained; the same goes for the target
+        write_symlink_tar_gz(
+            &archive_path,
+            &[
+                ("sub/up", ".."),
+                ("sub/up/../victim", "up"),
+                ("sub/out", "up/../x"),
+                ("sub/ok", "up/sub"),
+            ],
+        );
+
+        let dest = dir.path().join("out");
+        let files = extract_archive(&archive_path, &dest, false).unwrap();
+
+        assert_eq!(files, vec!["sub/up".to_string(), "sub/ok".to_string()]);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("victim")).unwrap(),
+            "outside"
+        );
+        assert!(!dest.join("sub/out").exists());
+    }
+
+    #[test]
+    fn test_normalize_within_root() {
+        assert_eq!(
+            normalize_within_root(Path::new("a/b/../c")),
+            Some(PathBuf::from("a/c"))
+        );
+        assert_eq!(normalize_within_root(Path::new("a/../../b")), None);
+        assert_eq!(normalize_within_root(Path::new("/etc/passwd")), None);
+    }
+
     #[test]
     fn test_find_executable() {
         let files = vec![
@@ -1028,5 +2125,56 @@ mod tests {
         let txt = dir.path().join("test.txt");
         fs::write(&txt, b"Just some text").unwrap();
         assert_eq!(detect_script_type(&txt), None);
+
+        // Multi-byte characters cut off by the read buffer
+        let long = dir.path().join("test_long");
+        let mut content = b"#!/bin/sh\n# ".to_vec();
+        content.extend("é".repeat(100).as_bytes());
+        fs::write(&long, content).unwrap();
+        assert_eq!(detect_script_type(&long), Some("Shell script"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_candidates_classified_by_content() {
+        use std::os::unix::fs::PermissionsExt;
+        use tempfile::TempDir;
+        let dir = TempDir::new().unwrap();
+        let write = |name: &str, content: &[u8], mode: u32| {
+            let path = dir.path().join(name);
+            fs::write(&path, content).unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(mode)).unwrap();
+        };
+        // Wrapper script next to the real binary
+        write(
+            "tool",
+            b"#!/bin/sh\nexec \"$(dirname \"$0\")/tool.real\" \"$@\"\n",
+            0o755,
+        );
+        write("tool.real", b"\x7fELF\x02\x01\x01\x00", 0o755);
+        // Extensionless data without exec permission
+        write("tooldata", b"\x00\x01binary blob", 0o644);
+        // Libraries are not commands even though they are ELF
+        write("libtool.so", b"\x7fELF\x02\x01\x01\x00", 0o755);
+
+        let files: Vec<String> = ["tool", "tool.real", "tooldata", "libtool.so"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let candidates = find_executable_candidates(&files, "tool", Some(dir.path()));
+        let paths: Vec<&str> = candidates.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, ["tool.real", "tool"]);
+        assert!(candidates[0].reason.contains("ELF binary"));
+        assert!(candidates[0].reason.contains("exact name match"));
+        // The wrapper is offered by --select but not installed by default
+        assert_eq!(candidates[1].score, 0);
+        assert_eq!(candidates[1].reason, "wrapper script for tool.real");
+        assert_eq!(command_file_name("tool.real", dir.path()), "tool");
+        assert_eq!(command_file_name("tool", dir.path()), "tool");
+
+        // Without the files on disk, only names count
+        let candidates = find_executable_candidates(&files, "tool", None);
+        let paths: Vec<&str> = candidates.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, ["tool", "tooldata"]);
     }
 }
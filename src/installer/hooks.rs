@@ -0,0 +1,78 @@
+//! Install hooks declared by bucket manifests
+//!
+//! Buckets may attach `pre_install` / `post_install` shell commands to a package.
+//! They run through the platform shell with the app directory as the working
+//! directory, so they can generate data files or fix up permissions.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::path::Path;
+use std::process::Command;
+
+/// Run a list of hook commands in `app_dir`
+///
+/// Each command and its exit status is printed. Stops at, and returns an error
+/// for, the first command that fails.
+pub fn run_hooks(stage: &str, commands: &[String], app_dir: &Path) -> Result<()> {
+    for command in commands {
+        println!("  Running {} hook: {}", stage, command.cyan());
+
+        let status = shell_command(command)
+            .current_dir(app_dir)
+            .env("WENGET_APP_DIR", app_dir)
+            .status()
+            .with_context(|| format!("Failed to run {} hook: {}", stage, command))?;
+
+        if status.success() {
+            println!("    {} exited with {}", "✓".green(), status);
+        } else {
+            println!("    {} exited with {}", "✗".red(), status);
+            anyhow::bail!("{} hook failed ({}): {}", stage, status, command);
+        }
+    }
+
+    Ok(())
+}
+
+/// Build a command that runs `command` through the platform shell
+fn shell_command(command: &str) -> Command {
+    #[cfg(windows)]
+    {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    }
+
+    #[cfg(not(windows))]
+    {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    #[cfg(unix)]
+    fn test_run_hooks_in_app_dir() {
+        let dir = TempDir::new().unwrap();
+        let commands = vec!["echo generated > data.txt".to_string()];
+
+        run_hooks("post-install", &commands, dir.path()).unwrap();
+        assert!(dir.path().join("data.txt").is_file());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_run_hooks_stops_on_failure() {
+        let dir = TempDir::new().unwrap();
+        let commands = vec!["exit 3".to_string(), "touch never".to_string()];
+
+        assert!(run_hooks("post-install", &commands, dir.path()).is_err());
+        assert!(!dir.path().join("never").exists());
+    }
+}
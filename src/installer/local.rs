@@ -11,7 +11,9 @@ use crate::installer::symlink::create_symlink;
 
 use crate::core::manifest::PackageSource;
 use crate::core::{InstalledPackage, WenPaths};
-use crate::installer::{extract_archive, find_executable_candidates, normalize_command_name};
+use crate::installer::{
+    command_file_name, extract_archive, find_executable_candidates, normalize_command_name,
+};
 
 #[cfg(windows)]
 use crate::installer::create_shim;
@@ -56,7 +58,7 @@ pub fn install_local_file(
 
     // Extract or copy file to app directory
     // extract_archive handles both archives and standalone executables
-    let extracted_files = extract_archive(file_path, &app_dir)?;
+    let extracted_files = extract_archive(file_path, &app_dir, true)?;
 
     // Find executable candidates
     let candidates = find_executable_candidates(&extracted_files, &name, Some(&app_dir));
@@ -93,11 +95,7 @@ pub fn install_local_file(
         custom.to_string()
     } else {
         // Use the executable name but normalized
-        let exe_filename = exe_path
-            .file_name()
-            .and_then(|s| s.to_str())
-            .unwrap_or(&name);
-        normalize_command_name(exe_filename)
+        normalize_command_name(command_file_name(exe_relative, &app_dir))
     };
 
     println!("  Command will be available as: {}", command_name);
@@ -151,5 +149,12 @@ pub fn install_local_file(
         asset_name: filename.to_string(),
         parent_package: None,
         download_url: None,
+        bin_dir: paths
+            .custom_bin_dir()
+            .map(|p| p.to_string_lossy().to_string()),
+        version_constraint: None,
+        compiler: None,
+        checksum: None,
+        files: extracted_files,
     })
 }
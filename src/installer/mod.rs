@@ -1,18 +1,24 @@
 //! Installer module for WenPM
 
+pub mod command_name;
+pub mod completions;
 pub mod extractor;
+pub mod hooks;
 pub mod input_detector;
 pub mod local;
 pub mod script;
 pub mod symlink;
+pub mod versions;
 
 // Re-export commonly used items
+pub use command_name::sanitize_command_name;
 pub use extractor::{
-    extract_archive, find_executable, find_executable_candidates, normalize_command_name,
+    command_file_name, extract_archive, find_executable, find_executable_candidates,
+    normalize_command_name,
 };
 pub use script::{
     create_script_shim, detect_script_type, download_script, extract_script_name, install_script,
-    read_local_script,
+    read_local_script, script_checksum,
 };
 
 #[cfg(windows)]
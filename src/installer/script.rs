@@ -7,6 +7,7 @@
 
 use crate::core::manifest::ScriptType;
 use crate::core::WenPaths;
+use crate::utils::network::NetworkSettings;
 use anyhow::{Context, Result};
 use std::fs;
 use std::path::Path;
@@ -175,10 +176,10 @@ pub fn extract_script_name(input: &str) -> Option<String> {
 }
 
 /// Download script content from URL
-pub fn download_script(url: &str) -> Result<String> {
+pub fn download_script(url: &str, settings: &NetworkSettings) -> Result<String> {
     use crate::utils::HttpClient;
 
-    let client = HttpClient::new()?;
+    let client = HttpClient::with_settings(None, settings)?;
     let content = client
         .get_text(url)
         .with_context(|| format!("Failed to download script from {}", url))?;
@@ -192,6 +193,15 @@ pub fn read_local_script(path: &Path) -> Result<String> {
         .with_context(|| format!("Failed to read script from {}", path.display()))
 }
 
+/// Checksum recorded for an installed script ("sha256:" + hex digest of its content)
+///
+/// `wenget update` compares it with the content at the script's origin to
+/// decide whether the script changed.
+pub fn script_checksum(content: &str) -> String {
+    use sha2::{Digest, Sha256};
+    format!("sha256:{:x}", Sha256::digest(content.as_bytes()))
+}
+
 /// Install a script to the apps directory
 pub fn install_script(
     paths: &WenPaths,
@@ -459,6 +469,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_script_checksum() {
+        // SHA256 of "hello"
+        assert_eq!(
+            script_checksum("hello"),
+            "sha256:2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        assert_ne!(script_checksum("echo 1\n"), script_checksum("echo 2\n"));
+    }
+
     #[test]
     fn test_get_powershell_command() {
         // Test that the function returns a valid PowerShell command
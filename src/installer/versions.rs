@@ -0,0 +1,322 @@
+//! Previous versions kept for rollback
+//!
+//! With `keep_versions` above 1, replacing an installed package moves its app
+//! directory to `versions/<name>/<version>/` instead of deleting it, next to its
+//! installed.json record (`<version>.json`). `wenget rollback` swaps one of
+//! them back in. The app directory itself stays at `apps/<name>/`, so
+//! launchers and everything else reading installed.json are unaffected.
+//!
+//! With the default of 1, only the version replaced last is kept, as a single
+//! snapshot in `cache/rollback/<name>/` (`app/` and `package.json`). Rolling
+//! back to it restores it and deletes the snapshot.
+
+use crate::core::paths::sanitize_path_component;
+use crate::core::{InstalledPackage, WenPaths};
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A previous version kept on disk
+#[derive(Debug, Clone)]
+pub struct KeptVersion {
+    /// Directory holding the version's files
+    pub dir: PathBuf,
+    /// Its installed.json record from when it was replaced
+    pub package: InstalledPackage,
+}
+
+/// Directory a package is installed in
+///
+/// The recorded install path survives `rename --package`, as long as it lives
+/// under the apps directory.
+pub fn installed_dir(paths: &WenPaths, name: &str, pkg: &InstalledPackage) -> PathBuf {
+    let recorded = Path::new(&pkg.install_path);
+    if recorded.starts_with(paths.apps_dir()) {
+        recorded.to_path_buf()
+    } else {
+        paths.app_dir(name)
+    }
+}
+
+/// Move the installed files of `pkg` out of the way, keeping them as a previous version
+///
+/// Replaces an earlier kept copy of the same version. Returns false when the
+/// package has no app directory to keep.
+pub fn keep(paths: &WenPaths, name: &str, pkg: &InstalledPackage) -> Result<bool> {
+    let app_dir = installed_dir(paths, name, pkg);
+    if !app_dir.exists() {
+        return Ok(false);
+    }
+
+    let versions_dir = paths.app_versions_dir(name);
+    fs::create_dir_all(&versions_dir)
+        .with_context(|| format!("Failed to create {}", versions_dir.display()))?;
+    let (dir, record) = version_paths(&versions_dir, &pkg.version);
+    if dir.exists() {
+        fs::remove_dir_all(&dir)?;
+    }
+
+    fs::rename(&app_dir, &dir)
+        .with_context(|| format!("Failed to move {} to {}", app_dir.display(), dir.display()))?;
+    fs::write(&record, serde_json::to_string_pretty(pkg)?)
+        .with_context(|| format!("Failed to write {}", record.display()))?;
+    Ok(true)
+}
+
+/// Previous versions kept of a package, most recently installed first
+pub fn kept_versions(paths: &WenPaths, name: &str) -> Result<Vec<KeptVersion>> {
+    let versions_dir = paths.app_versions_dir(name);
+    if !versions_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut kept = Vec::new();
+    for entry in fs::read_dir(&versions_dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let dir = path.with_extension("");
+        if !dir.is_dir() {
+            continue;
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let package: InstalledPackage = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        kept.push(KeptVersion { dir, package });
+    }
+    kept.sort_by_key(|k| std::cmp::Reverse(k.package.installed_at));
+    Ok(kept)
+}
+
+/// Drop the record of a kept version
+///
+/// The directory itself is left in place for the caller to move or remove.
+pub fn forget(kept: &KeptVersion) -> Result<()> {
+    let record = record_path(&kept.dir);
+    if record.exists() {
+        fs::remove_file(&record)
+            .with_context(|| format!("Failed to remove {}", record.display()))?;
+    }
+    Ok(())
+}
+
+/// Remove all but the `max` most recently installed previous versions
+pub fn prune(paths: &WenPaths, name: &str, max: usize) -> Result<()> {
+    for old in kept_versions(paths, name)?.into_iter().skip(max) {
+        log::debug!("Removing kept version {} of {}", old.package.version, name);
+        fs::remove_dir_all(&old.dir)
+            .with_context(|| format!("Failed to remove {}", old.dir.display()))?;
+        forget(&old)?;
+    }
+
+    let versions_dir = paths.app_versions_dir(name);
+    if versions_dir.exists() && fs::read_dir(&versions_dir)?.next().is_none() {
+        fs::remove_dir(&versions_dir)?;
+    }
+    Ok(())
+}
+
+/// Remove every kept version and the rollback snapshot of a package
+pub fn remove_all(paths: &WenPaths, name: &str) -> Result<()> {
+    let versions_dir = paths.app_versions_dir(name);
+    if versions_dir.exists() {
+        fs::remove_dir_all(&versions_dir)
+            .with_context(|| format!("Failed to remove {}", versions_dir.display()))?;
+    }
+    clear_snapshot(paths, name)
+}
+
+/// Move the installed files of `pkg` into its rollback snapshot
+///
+/// Replaces any earlier snapshot of the package, so there is at most one.
+/// Returns false when the package has no app directory to snapshot.
+pub fn snapshot(paths: &WenPaths, name: &str, pkg: &InstalledPackage) -> Result<bool> {
+    let app_dir = installed_dir(paths, name, pkg);
+    if !app_dir.exists() {
+        return Ok(false);
+    }
+
+    clear_snapshot(paths, name)?;
+    let snapshot_dir = paths.rollback_snapshot_dir(name);
+    fs::create_dir_all(&snapshot_dir)
+        .with_context(|| format!("Failed to create {}", snapshot_dir.display()))?;
+    let dir = snapshot_dir.join("app");
+    fs::rename(&app_dir, &dir)
+        .with_context(|| format!("Failed to move {} to {}", app_dir.display(), dir.display()))?;
+    let record = snapshot_dir.join("package.json");
+    fs::write(&record, serde_json::to_string_pretty(pkg)?)
+        .with_context(|| format!("Failed to write {}", record.display()))?;
+    Ok(true)
+}
+
+/// The rollback snapshot of a package, if there is a complete one
+pub fn rollback_snapshot(paths: &WenPaths, name: &str) -> Result<Option<KeptVersion>> {
+    let snapshot_dir = paths.rollback_snapshot_dir(name);
+    let dir = snapshot_dir.join("app");
+    let record = snapshot_dir.join("package.json");
+    if !dir.is_dir() || !record.is_file() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&record)
+        .with_context(|| format!("Failed to read {}", record.display()))?;
+    let package: InstalledPackage = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", record.display()))?;
+    Ok(Some(KeptVersion { dir, package }))
+}
+
+/// Remove the rollback snapshot of a package
+pub fn clear_snapshot(paths: &WenPaths, name: &str) -> Result<()> {
+    let snapshot_dir = paths.rollback_snapshot_dir(name);
+    if snapshot_dir.exists() {
+        fs::remove_dir_all(&snapshot_dir)
+            .with_context(|| format!("Failed to remove {}", snapshot_dir.display()))?;
+    }
+    Ok(())
+}
+
+/// Directory and record file of a kept version
+fn version_paths(versions_dir: &Path, version: &str) -> (PathBuf, PathBuf) {
+    let dir = versions_dir.join(sanitize_path_component(version));
+    let record = record_path(&dir);
+    (dir, record)
+}
+
+/// Record file next to a kept version's directory
+///
+/// Not `with_extension`, which would replace the last part of "1.2.0".
+fn record_path(dir: &Path) -> PathBuf {
+    let mut name = dir.file_name().unwrap_or_default().to_os_string();
+    name.push(".json");
+    dir.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::manifest::PackageSource;
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn install(paths: &WenPaths, version: &str, age_days: i64) -> InstalledPackage {
+        let app_dir = paths.app_dir("tool");
+        fs::create_dir_all(&app_dir).unwrap();
+        fs::write(app_dir.join("tool"), version).unwrap();
+        InstalledPackage {
+            repo_name: "tool".to_string(),
+            variant: None,
+            version: version.to_string(),
+            platform: "linux-x86_64".to_string(),
+            installed_at: Utc::now() - Duration::days(age_days),
+            install_path: app_dir.to_string_lossy().to_string(),
+            executables: HashMap::from([("tool".to_string(), "tool".to_string())]),
+            source: PackageSource::Bucket {
+                name: "main".to_string(),
+            },
+            description: String::new(),
+            command_names: vec![],
+            command_name: None,
+            asset_name: format!("tool-{}.tar.gz", version),
+            parent_package: None,
+            download_url: None,
+            bin_dir: None,
+            version_constraint: None,
+            compiler: None,
+            checksum: None,
+            files: Vec::new(),
+        }
+    }
+
+    fn kept(paths: &WenPaths) -> Vec<String> {
+        kept_versions(paths, "tool")
+            .unwrap()
+            .into_iter()
+            .map(|k| k.package.version)
+            .collect()
+    }
+
+    #[test]
+    fn test_keep_and_prune() {
+        let temp = TempDir::new().unwrap();
+        let paths = WenPaths::with_root(temp.path().to_path_buf(), false);
+
+        for (version, age) in [("1.0.0", 3), ("1.1.0", 2), ("1.2.0", 1)] {
+            let pkg = install(&paths, version, age);
+            assert!(keep(&paths, "tool", &pkg).unwrap());
+            assert!(!paths.app_dir("tool").exists());
+        }
+        assert_eq!(kept(&paths), ["1.2.0", "1.1.0", "1.0.0"]);
+
+        let newest = &kept_versions(&paths, "tool").unwrap()[0];
+        assert_eq!(
+            fs::read_to_string(newest.dir.join("tool")).unwrap(),
+            "1.2.0"
+        );
+
+        prune(&paths, "tool", 1).unwrap();
+        assert_eq!(kept(&paths), ["1.2.0"]);
+        assert!(!paths.app_versions_dir("tool").join("1.0.0").exists());
+
+        prune(&paths, "tool", 0).unwrap();
+        assert!(kept(&paths).is_empty());
+        assert!(!paths.app_versions_dir("tool").exists());
+    }
+
+    #[test]
+    fn test_keep_monorepo_tag() {
+        let temp = TempDir::new().unwrap();
+        let paths = WenPaths::with_root(temp.path().to_path_buf(), false);
+
+        // Tags like "cli/v1.2.3" must stay one directory below versions/<name>/
+        for version in ["cli/v1.2.3", ".."] {
+            let pkg = install(&paths, version, 1);
+            assert!(keep(&paths, "tool", &pkg).unwrap());
+        }
+        let kept = kept_versions(&paths, "tool").unwrap();
+        assert_eq!(kept.len(), 2);
+        for k in &kept {
+            assert_eq!(k.dir.parent().unwrap(), paths.app_versions_dir("tool"));
+        }
+        assert!(paths.app_versions_dir("tool").join("cli-v1.2.3").is_dir());
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let temp = TempDir::new().unwrap();
+        let paths = WenPaths::with_root(temp.path().to_path_buf(), false);
+        assert!(rollback_snapshot(&paths, "tool").unwrap().is_none());
+
+        // Only the version replaced last is kept
+        for (version, age) in [("1.0.0", 2), ("1.1.0", 1)] {
+            let pkg = install(&paths, version, age);
+            assert!(snapshot(&paths, "tool", &pkg).unwrap());
+            assert!(!paths.app_dir("tool").exists());
+        }
+        let snap = rollback_snapshot(&paths, "tool").unwrap().unwrap();
+        assert_eq!(snap.package.version, "1.1.0");
+        assert_eq!(fs::read_to_string(snap.dir.join("tool")).unwrap(), "1.1.0");
+        assert_eq!(
+            fs::read_dir(paths.rollback_snapshot_dir("tool"))
+                .unwrap()
+                .count(),
+            2
+        );
+
+        clear_snapshot(&paths, "tool").unwrap();
+        assert!(rollback_snapshot(&paths, "tool").unwrap().is_none());
+        assert!(!paths.rollback_snapshot_dir("tool").exists());
+    }
+
+    #[test]
+    fn test_keep_without_app_dir() {
+        let temp = TempDir::new().unwrap();
+        let paths = WenPaths::with_root(temp.path().to_path_buf(), false);
+        let pkg = install(&paths, "1.0.0", 1);
+        fs::remove_dir_all(paths.app_dir("tool")).unwrap();
+
+        assert!(!keep(&paths, "tool", &pkg).unwrap());
+        assert!(kept(&paths).is_empty());
+    }
+}
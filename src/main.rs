@@ -11,24 +11,51 @@ mod package_resolver;
 mod providers;
 mod utils;
 
+use anyhow::Context;
 use clap::CommandFactory;
-use cli::{BucketCommands, Cli, Commands};
+use cli::{BucketCommands, CacheCommands, Cli, Commands, ConfigCommands};
 use colored::Colorize;
+use commands::outcome::{EXIT_FAILURE, EXIT_SUCCESS};
+use core::events::TerminalSink;
+use std::ffi::OsStr;
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use utils::network::NetworkOverrides;
 
 fn main() {
-    // Initialize logger
-    env_logger::Builder::from_default_env()
-        .filter_level(log::LevelFilter::Info)
-        .init();
-
     // Parse CLI arguments
     let cli = Cli::parse_args();
 
-    // Set verbose logging if requested
-    if cli.verbose {
-        log::set_max_level(log::LevelFilter::Debug);
+    // Plain output for --no-color, NO_COLOR and pipes/files
+    let color = use_color(
+        cli.no_color,
+        std::env::var_os("NO_COLOR").as_deref(),
+        std::io::stdout().is_terminal(),
+    );
+    if !color {
+        colored::control::set_override(false);
+    }
+
+    // Initialize logger: --quiet logs errors only, --verbose adds debug output
+    let level = if cli.quiet {
+        log::LevelFilter::Error
+    } else if cli.verbose {
+        log::LevelFilter::Debug
+    } else {
+        log::LevelFilter::Info
+    };
+    if let Err(e) = init_logger(level, cli.log_file.as_deref(), color) {
+        eprintln!("{} {}", "Error:".red().bold(), e);
+        std::process::exit(EXIT_FAILURE);
     }
 
+    // --timeout / --retries / --offline apply to this invocation only
+    let network = NetworkOverrides {
+        timeout: cli.timeout,
+        retries: cli.retries,
+        offline: cli.offline,
+    };
+
     // Handle no command (show help and exit 0)
     let Some(command) = cli.command else {
         let _ = Cli::command().print_help();
@@ -36,16 +63,50 @@ fn main() {
         return;
     };
 
+    // Errors of --json runs are reported as JSON too
+    let json = wants_json(&command);
+
+    // Mutating commands hold the state lock until the process exits
+    let _lock = if needs_lock(&command) {
+        let lock = core::WenPaths::new()
+            .and_then(|paths| core::lock::StateLock::acquire(&paths.lock_file(), cli.wait));
+        match lock {
+            Ok(lock) => Some(lock),
+            Err(e) => {
+                eprintln!("{} {}", "Error:".red().bold(), e);
+                std::process::exit(EXIT_FAILURE);
+            }
+        }
+    } else {
+        None
+    };
+
     // Run the appropriate command
     let result = match command {
-        Commands::Init { yes } => commands::run_init(yes),
+        Commands::Init { yes, no_path } => {
+            commands::run_init(yes, no_path, network).map(|()| EXIT_SUCCESS)
+        }
 
         Commands::Bucket { command } => {
             let bucket_cmd = match command {
-                BucketCommands::Add { name, url } => {
-                    commands::bucket::BucketCommand::Add { name, url }
-                }
+                BucketCommands::Add {
+                    name,
+                    url,
+                    priority,
+                    disabled,
+                    force,
+                } => commands::bucket::BucketCommand::Add {
+                    name,
+                    url,
+                    priority,
+                    disabled,
+                    force,
+                },
                 BucketCommands::Del { names } => commands::bucket::BucketCommand::Del { names },
+                BucketCommands::Enable { name } => commands::bucket::BucketCommand::Enable { name },
+                BucketCommands::Disable { name } => {
+                    commands::bucket::BucketCommand::Disable { name }
+                }
                 BucketCommands::List => commands::bucket::BucketCommand::List,
                 BucketCommands::Refresh => commands::bucket::BucketCommand::Refresh,
                 BucketCommands::Create {
@@ -55,6 +116,7 @@ fn main() {
                     output,
                     token,
                     update_mode,
+                    platforms,
                 } => commands::bucket::BucketCommand::Create {
                     repos_src,
                     scripts_src,
@@ -62,11 +124,28 @@ fn main() {
                     output,
                     token,
                     update_mode,
+                    platforms,
                 },
+                BucketCommands::Validate { source, offline } => {
+                    commands::bucket::BucketCommand::Validate { source, offline }
+                }
             };
-            commands::run_bucket(bucket_cmd)
+            commands::run_bucket(bucket_cmd, network).map(|()| EXIT_SUCCESS)
         }
 
+        Commands::Cache { command } => {
+            let cache_cmd = match command {
+                CacheCommands::Info => commands::cache::CacheCommand::Info,
+                CacheCommands::Clear => commands::cache::CacheCommand::Clear,
+            };
+            commands::run_cache(cache_cmd).map(|()| EXIT_SUCCESS)
+        }
+
+        Commands::Clean {
+            downloads_only,
+            cache_only,
+        } => commands::run_clean(downloads_only, cache_only).map(|()| EXIT_SUCCESS),
+
         Commands::Add {
             names,
             yes,
@@ -74,53 +153,345 @@ fn main() {
             platform,
             pkg_version,
             variant,
+            compiler,
             no_suffix,
+            bin_dir,
+            keep_download,
+            force,
+            select,
+            exe,
+            all_bins,
+            asset,
+            pre,
+            emit_manifest,
+            append_manifest,
+            completions,
+            dry_run,
         } => commands::run_add(
             names,
+            commands::add::AddOptions {
+                yes,
+                script_name,
+                platform,
+                version: pkg_version,
+                pre,
+                variant,
+                compiler,
+                asset,
+                no_suffix,
+                bin_dir,
+                keep_download,
+                force,
+                dry_run,
+                completions,
+                exe_selection: commands::add::ExeSelection {
+                    interactive: select,
+                    paths: exe,
+                    all: all_bins,
+                },
+                emit_manifest: emit_manifest.map(|path| commands::install_manifest::EmitManifest {
+                    path,
+                    append: append_manifest,
+                }),
+                ..Default::default()
+            },
+            network,
+            &TerminalSink,
+        )
+        .map(|outcome| outcome.exit_code()),
+
+        Commands::List {
+            all,
+            by_source,
+            since,
+            before,
+            json,
+        } => {
+            commands::run_list(all, by_source, since, before, json, network).map(|()| EXIT_SUCCESS)
+        }
+
+        Commands::Info {
+            names,
+            all_platforms,
+            assets,
+        } => commands::run_info(names, all_platforms, assets, network).map(|()| EXIT_SUCCESS),
+
+        Commands::Export { output, text } => {
+            commands::run_export(output, text).map(|()| EXIT_SUCCESS)
+        }
+
+        Commands::Import {
+            source,
             yes,
-            script_name,
-            platform,
-            pkg_version,
-            variant,
-            no_suffix,
-            false,
-        ),
+            dry_run,
+        } => commands::run_import(source, yes, dry_run, network, &TerminalSink)
+            .map(|outcome| outcome.exit_code()),
+
+        Commands::Files { names } => commands::run_files(names).map(|()| EXIT_SUCCESS),
+
+        Commands::Log { limit } => commands::run_log(limit).map(|()| EXIT_SUCCESS),
 
-        Commands::List { all } => commands::run_list(all),
+        Commands::Outdated { json } => commands::run_outdated(json, network).map(|()| EXIT_SUCCESS),
 
-        Commands::Info { names } => commands::run_info(names),
+        Commands::Search {
+            names,
+            json,
+            limit,
+            interactive,
+        } => commands::run_search(names, json, limit, interactive, network).map(|()| EXIT_SUCCESS),
 
-        Commands::Search { names } => commands::run_search(names),
+        Commands::Try { name, args } => commands::run_try(name, args, network),
 
+        Commands::Update {
+            names,
+            pre,
+            check: true,
+            ..
+        } => commands::run_update_check(names, pre, network),
+
+        Commands::Update {
+            names,
+            metadata_only: true,
+            ..
+        } => commands::run_update_metadata(names, network).map(|outcome| outcome.exit_code()),
+
+        // `--all` is the same as naming no packages
         Commands::Update {
             names,
             yes,
             platform,
-        } => commands::run_update(names, yes, platform),
+            pre,
+            check: false,
+            metadata_only: false,
+            ..
+        } => commands::run_update(names, yes, platform, pre, network, &TerminalSink)
+            .map(|outcome| outcome.exit_code()),
 
         Commands::Del {
             names,
             yes,
             force,
             variant,
-        } => commands::run_delete(names, yes, force, variant),
+        } => commands::run_delete(names, yes, force, variant, &TerminalSink)
+            .map(|outcome| outcome.exit_code()),
+
+        Commands::Repair { force, shims } => {
+            commands::run_repair(force, shims, network).map(|()| EXIT_SUCCESS)
+        }
+
+        Commands::Verify { repair } => commands::run_verify(repair),
 
-        Commands::Repair { force } => commands::run_repair(force),
+        Commands::Config { command } => (|| {
+            let config = core::Config::new()?;
+            let cmd = match command {
+                None => commands::config::ConfigCommand::Edit,
+                Some(ConfigCommands::Get { key }) => commands::config::ConfigCommand::Get { key },
+                Some(ConfigCommands::Set { key, value }) => {
+                    commands::config::ConfigCommand::Set { key, value }
+                }
+            };
+            commands::run_config(&config, cmd)
+        })()
+        .map(|()| EXIT_SUCCESS),
 
-        Commands::Config => (|| {
+        Commands::Rename {
+            old_name,
+            new_name,
+            package,
+        } => (|| {
             let config = core::Config::new()?;
-            commands::run_config(&config)
-        })(),
+            commands::run_rename(old_name, new_name, package, &config)
+        })()
+        .map(|()| EXIT_SUCCESS),
+
+        Commands::Alias {
+            name,
+            command,
+            remove,
+        } => commands::run_alias(name, command, remove).map(|()| EXIT_SUCCESS),
 
-        Commands::Rename { old_name, new_name } => (|| {
+        Commands::Rollback { name, to } => (|| {
             let config = core::Config::new()?;
-            commands::run_rename(old_name, new_name, &config)
-        })(),
+            commands::run_rollback(name, to, &config)
+        })()
+        .map(|()| EXIT_SUCCESS),
     };
 
-    // Handle errors
-    if let Err(e) = result {
-        eprintln!("{} {}", "Error:".red().bold(), e);
-        std::process::exit(1);
+    // Exit with the command's code; see the exit code section of --help
+    match result {
+        Ok(EXIT_SUCCESS) => {}
+        Ok(code) => std::process::exit(code),
+        Err(e) if json => {
+            println!("{}", json_error(&e, EXIT_FAILURE));
+            std::process::exit(EXIT_FAILURE);
+        }
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            std::process::exit(EXIT_FAILURE);
+        }
+    }
+}
+
+/// Whether a command was asked for machine-readable output
+fn wants_json(command: &Commands) -> bool {
+    matches!(
+        command,
+        Commands::Search { json: true, .. }
+            | Commands::Outdated { json: true }
+            | Commands::List { json: true, .. }
+    )
+}
+
+/// Error reported on stdout in place of the JSON result of a `--json` run
+#[derive(serde::Serialize)]
+struct JsonError {
+    /// Error message including its causes
+    error: String,
+    /// Process exit code
+    code: i32,
+}
+
+/// Render an error as a JSON object, formatted like the JSON results
+fn json_error(error: &anyhow::Error, code: i32) -> String {
+    let envelope = JsonError {
+        error: format!("{:#}", error),
+        code,
+    };
+    serde_json::to_string_pretty(&envelope).expect("error envelope serializes")
+}
+
+/// Whether a command modifies wenget state and must hold the state lock
+fn needs_lock(command: &Commands) -> bool {
+    match command {
+        Commands::List { .. }
+        | Commands::Info { .. }
+        | Commands::Files { .. }
+        | Commands::Log { .. }
+        | Commands::Search { .. }
+        | Commands::Outdated { .. }
+        | Commands::Export { .. }
+        | Commands::Try { .. }
+        | Commands::Verify { repair: false } => false,
+        Commands::Config { command } => matches!(command, Some(ConfigCommands::Set { .. })),
+        Commands::Update { check: true, .. } | Commands::Add { dry_run: true, .. } => false,
+        Commands::Cache { command } => matches!(command, CacheCommands::Clear),
+        Commands::Bucket { command } => !matches!(
+            command,
+            BucketCommands::List | BucketCommands::Create { .. } | BucketCommands::Validate { .. }
+        ),
+        // Self-uninstall removes the wenget root, lock file included
+        Commands::Del { names, .. } => !(names.len() == 1 && names[0].eq_ignore_ascii_case("self")),
+        _ => true,
+    }
+}
+
+/// Whether styled output should use ANSI colors
+///
+/// Disabled by `--no-color`, a non-empty `NO_COLOR` (see no-color.org) or when
+/// stdout is not a terminal.
+fn use_color(no_color_flag: bool, no_color_env: Option<&OsStr>, stdout_is_tty: bool) -> bool {
+    let no_color_env = no_color_env.is_some_and(|v| !v.is_empty());
+    !no_color_flag && !no_color_env && stdout_is_tty
+}
+
+/// Initialize the logging backend
+///
+/// `WENGET_LOG_FORMAT=json` switches log records to line-delimited JSON for log
+/// aggregators. With `log_file`, records are written to stderr and appended to the
+/// file. Command output is unaffected.
+fn init_logger(
+    level: log::LevelFilter,
+    log_file: Option<&Path>,
+    color: bool,
+) -> anyhow::Result<()> {
+    let mut builder = env_logger::Builder::from_default_env();
+    builder.filter_level(level);
+    if !color {
+        builder.write_style(env_logger::WriteStyle::Never);
+    }
+
+    if let Some(path) = log_file {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open log file: {}", path.display()))?;
+        builder.target(env_logger::Target::Pipe(Box::new(TeeWriter { file })));
+    }
+
+    let json = std::env::var("WENGET_LOG_FORMAT").is_ok_and(|v| v.eq_ignore_ascii_case("json"));
+    if json {
+        builder.format(|buf, record| {
+            let line = serde_json::json!({
+                "timestamp": chrono::Utc::now().to_rfc3339(),
+                "level": record.level().as_str(),
+                "target": record.target(),
+                "message": record.args().to_string(),
+            });
+            writeln!(buf, "{}", line)
+        });
+    }
+
+    builder.init();
+    Ok(())
+}
+
+/// Log target duplicating everything to stderr and a log file
+struct TeeWriter {
+    file: std::fs::File,
+}
+
+impl Write for TeeWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        std::io::stderr().write_all(buf)?;
+        self.file.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        std::io::stderr().flush()?;
+        self.file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_use_color() {
+        assert!(use_color(false, None, true));
+        assert!(!use_color(true, None, true));
+        assert!(!use_color(false, Some(OsStr::new("1")), true));
+        assert!(!use_color(false, None, false));
+        // An empty NO_COLOR does not disable color
+        assert!(use_color(false, Some(OsStr::new("")), true));
+    }
+
+    #[test]
+    fn test_json_error() {
+        let error = anyhow::anyhow!("connection refused").context("Failed to load cache");
+        let value: serde_json::Value =
+            serde_json::from_str(&json_error(&error, EXIT_FAILURE)).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "error": "Failed to load cache: connection refused",
+                "code": 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_wants_json() {
+        use clap::Parser;
+        let cli = Cli::parse_from(["wenget", "search", "rg", "--json"]);
+        assert!(wants_json(&cli.command.unwrap()));
+        let cli = Cli::parse_from(["wenget", "search", "rg"]);
+        assert!(!wants_json(&cli.command.unwrap()));
+        let cli = Cli::parse_from(["wenget", "list", "--json"]);
+        assert!(wants_json(&cli.command.unwrap()));
+        let cli = Cli::parse_from(["wenget", "list"]);
+        assert!(!wants_json(&cli.command.unwrap()));
     }
 }
@@ -5,7 +5,7 @@
 //! - Fetching package information from cache or GitHub
 //! - Determining the bucket source of cached packages
 
-use crate::cache::ManifestCache;
+use crate::cache::{CachedPackage, ManifestCache};
 use crate::core::manifest::{Package, PackageSource};
 use crate::core::Config;
 use crate::providers::{GitHubProvider, SourceProvider};
@@ -16,6 +16,8 @@ use anyhow::{anyhow, Context, Result};
 pub enum PackageInput {
     /// Package name from cache (supports glob patterns)
     CacheName(String),
+    /// Package name qualified with the bucket to take it from (`bucket/name`)
+    BucketName { bucket: String, name: String },
     /// Direct GitHub repository URL
     DirectUrl(String),
 }
@@ -29,13 +31,80 @@ impl PackageInput {
             || input.starts_with("github.com/")
         {
             Self::DirectUrl(normalize_github_url(input))
+        } else if let Some((bucket, name)) = split_bucket_qualifier(input) {
+            Self::BucketName {
+                bucket: bucket.to_string(),
+                name: name.to_string(),
+            }
         } else {
             Self::CacheName(input.to_string())
         }
     }
+
+    /// The package name (or URL) without a bucket qualifier
+    pub fn name(&self) -> &str {
+        match self {
+            Self::CacheName(name) | Self::DirectUrl(name) => name,
+            Self::BucketName { name, .. } => name,
+        }
+    }
+
+    /// The bucket a qualified name is restricted to
+    pub fn bucket(&self) -> Option<&str> {
+        match self {
+            Self::BucketName { bucket, .. } => Some(bucket),
+            _ => None,
+        }
+    }
+}
+
+/// Split a `bucket/name` input into its bucket and package name
+///
+/// Package names never contain `/`, so a single slash with text on both
+/// sides selects a bucket.
+pub fn split_bucket_qualifier(input: &str) -> Option<(&str, &str)> {
+    let (bucket, name) = input.split_once('/')?;
+    if bucket.is_empty() || name.is_empty() || name.contains('/') || bucket.contains('*') {
+        return None;
+    }
+    Some((bucket, name))
+}
+
+/// Whether a cached entry comes from the given bucket (any bucket when `None`)
+pub fn from_bucket(source: &PackageSource, bucket: Option<&str>) -> bool {
+    match bucket {
+        None => true,
+        Some(bucket) => matches!(source, PackageSource::Bucket { name } if name == bucket),
+    }
+}
+
+/// The GitHub repository URL for an `owner/repo` shorthand
+///
+/// Only inputs that are valid GitHub owner and repository names qualify, so
+/// globs, variants (`::`) and other cache names with a slash are left alone.
+pub fn github_shorthand_url(owner: &str, repo: &str) -> Option<String> {
+    let repo = repo.strip_suffix(".git").unwrap_or(repo);
+
+    let valid_owner = !owner.is_empty()
+        && owner.len() <= 39
+        && !owner.starts_with('-')
+        && !owner.ends_with('-')
+        && owner.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    let valid_repo = !repo.is_empty()
+        && repo.len() <= 100
+        && repo != "."
+        && repo != ".."
+        && repo
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+
+    (valid_owner && valid_repo).then(|| format!("https://github.com/{}/{}", owner, repo))
 }
 
 /// Normalize GitHub URL to standard format
+///
+/// Deep links copied from the browser (`/tree/main`, `/releases`, `?tab=...`)
+/// are cut back to the `owner/repo` root.
 fn normalize_github_url(url: &str) -> String {
     let mut url = url.trim().to_string();
 
@@ -49,6 +118,15 @@ fn normalize_github_url(url: &str) -> String {
         url = format!("https://{}", url);
     }
 
+    // Keep only owner/repo, dropping deeper paths, queries and fragments
+    if let Some(path) = url.strip_prefix("https://github.com/") {
+        let path = path.split(['?', '#']).next().unwrap_or(path);
+        let mut segments = path.split('/').filter(|s| !s.is_empty());
+        if let (Some(owner), Some(repo)) = (segments.next(), segments.next()) {
+            url = format!("https://github.com/{}/{}", owner, repo);
+        }
+    }
+
     // Remove trailing slash
     while url.ends_with('/') {
         url.pop();
@@ -88,7 +166,7 @@ pub struct PackageResolver<'a> {
 impl<'a> PackageResolver<'a> {
     /// Create a new package resolver with pre-loaded cache
     pub fn new(config: &'a Config, cache: &'a ManifestCache) -> Result<Self> {
-        let github = GitHubProvider::new()?;
+        let github = GitHubProvider::from_config(config)?;
         Ok(Self {
             config,
             cache,
@@ -103,7 +181,16 @@ impl<'a> PackageResolver<'a> {
     /// For URLs, returns a single package.
     pub fn resolve(&self, input: &PackageInput) -> Result<Vec<ResolvedPackage>> {
         match input {
-            PackageInput::CacheName(name) => self.resolve_from_cache(name),
+            PackageInput::CacheName(name) => self.resolve_from_cache(name, None),
+            PackageInput::BucketName { bucket, name } => {
+                // `owner/repo` means a GitHub repository unless a bucket has that name
+                if !self.is_known_bucket(bucket)? {
+                    if let Some(url) = github_shorthand_url(bucket, name) {
+                        return Ok(vec![self.resolve_from_url(&url)?]);
+                    }
+                }
+                self.resolve_from_cache(name, Some(bucket))
+            }
             PackageInput::DirectUrl(url) => {
                 let pkg = self.resolve_from_url(url)?;
                 Ok(vec![pkg])
@@ -111,9 +198,28 @@ impl<'a> PackageResolver<'a> {
         }
     }
 
+    /// Whether a bucket with this name is configured or present in the cache
+    fn is_known_bucket(&self, bucket: &str) -> Result<bool> {
+        if self
+            .cache
+            .sources
+            .values()
+            .any(|info| from_bucket(&info.source, Some(bucket)))
+        {
+            return Ok(true);
+        }
+        Ok(self
+            .config
+            .get_or_create_buckets()?
+            .find_bucket(bucket)
+            .is_some())
+    }
+
     /// Resolve package from cache (supports glob patterns)
-    /// Falls back to checking installed packages if not found in cache
-    fn resolve_from_cache(&self, name: &str) -> Result<Vec<ResolvedPackage>> {
+    ///
+    /// With a `bucket`, only packages from that bucket match. Otherwise falls
+    /// back to checking installed packages if not found in cache.
+    fn resolve_from_cache(&self, name: &str, bucket: Option<&str>) -> Result<Vec<ResolvedPackage>> {
         // Handle repo::variant format - extract base name for cache lookup
         let base_name = if let Some(pos) = name.find("::") {
             &name[..pos]
@@ -121,22 +227,7 @@ impl<'a> PackageResolver<'a> {
             name
         };
 
-        // Filter packages by name pattern
-        let matches: Vec<_> = if base_name.contains('*') {
-            // Glob pattern matching
-            self.cache
-                .packages
-                .values()
-                .filter(|cached| glob_match(&cached.package.name, base_name))
-                .collect()
-        } else {
-            // Exact name matching
-            self.cache
-                .packages
-                .values()
-                .filter(|cached| cached.package.name == base_name)
-                .collect()
-        };
+        let matches = match_cached_packages(self.cache, base_name, bucket);
 
         if !matches.is_empty() {
             // Found in cache - return these matches
@@ -146,6 +237,17 @@ impl<'a> PackageResolver<'a> {
                 .collect());
         }
 
+        if let Some(bucket) = bucket {
+            return Err(if self.is_known_bucket(bucket)? {
+                anyhow!("Package '{}' not found in bucket '{}'", name, bucket)
+            } else {
+                anyhow!(
+                    "Bucket '{}' not found. Use 'wenget bucket list' to see configured buckets.",
+                    bucket
+                )
+            });
+        }
+
         // Not found in cache - check if it's an installed package from direct URL
         // Note: Only check for exact name match, not glob patterns
         if !name.contains('*') {
@@ -210,6 +312,41 @@ impl<'a> PackageResolver<'a> {
     }
 }
 
+/// Find cached packages whose name or one of its aliases matches `pattern`
+///
+/// Glob patterns match names and aliases alike. For exact lookups a package
+/// name wins over another package's alias. With a `bucket`, only packages
+/// from that bucket are considered.
+fn match_cached_packages<'c>(
+    cache: &'c ManifestCache,
+    pattern: &str,
+    bucket: Option<&str>,
+) -> Vec<&'c CachedPackage> {
+    let candidates = || {
+        cache
+            .packages
+            .values()
+            .filter(move |cached| from_bucket(&cached.source, bucket))
+    };
+
+    if pattern.contains('*') {
+        return candidates()
+            .filter(|cached| cached.package.names().any(|n| glob_match(n, pattern)))
+            .collect();
+    }
+
+    let by_name: Vec<_> = candidates()
+        .filter(|cached| cached.package.name == pattern)
+        .collect();
+    if !by_name.is_empty() {
+        return by_name;
+    }
+
+    candidates()
+        .filter(|cached| cached.package.aliases.iter().any(|a| a == pattern))
+        .collect()
+}
+
 /// Simple glob pattern matching (supports * wildcard)
 ///
 /// Examples:
@@ -343,6 +480,158 @@ mod tests {
             normalize_github_url("  https://github.com/user/repo  "),
             "https://github.com/user/repo"
         );
+
+        // Deep links from the browser
+        for deep in [
+            "https://github.com/user/repo/tree/main",
+            "https://github.com/user/repo/tree/main/src/",
+            "https://github.com/user/repo/releases",
+            "github.com/user/repo/releases/tag/v1.0.0",
+            "https://github.com/user/repo?tab=readme-ov-file",
+            "https://github.com/user/repo#installation",
+            "https://github.com/user/repo.git/",
+        ] {
+            assert_eq!(
+                normalize_github_url(deep),
+                "https://github.com/user/repo",
+                "{}",
+                deep
+            );
+        }
+    }
+
+    #[test]
+    fn test_github_shorthand_url() {
+        assert_eq!(
+            github_shorthand_url("BurntSushi", "ripgrep").as_deref(),
+            Some("https://github.com/BurntSushi/ripgrep")
+        );
+        assert_eq!(
+            github_shorthand_url("sharkdp", "fd.git").as_deref(),
+            Some("https://github.com/sharkdp/fd")
+        );
+        assert_eq!(
+            github_shorthand_url("owner", "my_repo.rs").as_deref(),
+            Some("https://github.com/owner/my_repo.rs")
+        );
+
+        // Globs, variants and names GitHub would not accept stay cache lookups
+        assert_eq!(github_shorthand_url("extra", "r*"), None);
+        assert_eq!(github_shorthand_url("extra", "bun::baseline"), None);
+        assert_eq!(github_shorthand_url("-owner", "repo"), None);
+        assert_eq!(github_shorthand_url("my_bucket", "repo"), None);
+        assert_eq!(github_shorthand_url("owner", ".."), None);
+    }
+
+    fn cached(name: &str, aliases: &[&str]) -> CachedPackage {
+        cached_in(name, aliases, "test")
+    }
+
+    fn cached_in(name: &str, aliases: &[&str], bucket: &str) -> CachedPackage {
+        CachedPackage {
+            package: Package {
+                name: name.to_string(),
+                aliases: aliases.iter().map(|a| a.to_string()).collect(),
+                description: String::new(),
+                repo: format!("https://github.com/test/{}", name),
+                homepage: None,
+                license: None,
+                version: None,
+                platforms: Default::default(),
+                pre_install: Vec::new(),
+                post_install: Vec::new(),
+            },
+            source: PackageSource::Bucket {
+                name: bucket.to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_parse_bucket_qualified_input() {
+        let input = PackageInput::parse("extra/fd");
+        assert!(matches!(
+            &input,
+            PackageInput::BucketName { bucket, name } if bucket == "extra" && name == "fd"
+        ));
+        assert_eq!(input.name(), "fd");
+        assert_eq!(input.bucket(), Some("extra"));
+
+        let input = PackageInput::parse("fd");
+        assert_eq!(input.name(), "fd");
+        assert_eq!(input.bucket(), None);
+
+        // Variants and globs keep working after the qualifier
+        assert_eq!(
+            split_bucket_qualifier("extra/bun::baseline"),
+            Some(("extra", "bun::baseline"))
+        );
+        assert_eq!(split_bucket_qualifier("extra/r*"), Some(("extra", "r*")));
+        assert_eq!(split_bucket_qualifier("/fd"), None);
+        assert_eq!(split_bucket_qualifier("extra/"), None);
+        assert_eq!(split_bucket_qualifier("a/b/c"), None);
+    }
+
+    #[test]
+    fn test_match_cached_packages_by_bucket() {
+        let mut cache = ManifestCache::new();
+        let mut main_fd = cached_in("fd", &[], "main");
+        main_fd.package.repo = "https://github.com/sharkdp/fd".to_string();
+        let mut extra_fd = cached_in("fd", &[], "extra");
+        extra_fd.package.repo = "https://github.com/fork/fd".to_string();
+        for pkg in [main_fd, extra_fd, cached_in("bat", &[], "main")] {
+            cache.packages.insert(pkg.package.repo.clone(), pkg);
+        }
+        let repos = |pattern: &str, bucket: Option<&str>| {
+            let mut repos: Vec<_> = match_cached_packages(&cache, pattern, bucket)
+                .into_iter()
+                .map(|c| c.package.repo.clone())
+                .collect();
+            repos.sort();
+            repos
+        };
+
+        // Unqualified names match across buckets
+        assert_eq!(repos("fd", None).len(), 2);
+        assert_eq!(
+            repos("fd", Some("extra")),
+            vec!["https://github.com/fork/fd"]
+        );
+        assert_eq!(
+            repos("fd", Some("main")),
+            vec!["https://github.com/sharkdp/fd"]
+        );
+        assert!(repos("bat", Some("extra")).is_empty());
+        assert_eq!(repos("*", Some("main")).len(), 2);
+    }
+
+    #[test]
+    fn test_match_cached_packages_aliases() {
+        let mut cache = ManifestCache::new();
+        for pkg in [
+            cached("ripgrep", &["rg"]),
+            cached("rg", &[]),
+            cached("fd", &["fd-find"]),
+        ] {
+            cache.packages.insert(pkg.package.repo.clone(), pkg);
+        }
+        let names = |pattern: &str| {
+            let mut names: Vec<_> = match_cached_packages(&cache, pattern, None)
+                .into_iter()
+                .map(|c| c.package.name.clone())
+                .collect();
+            names.sort();
+            names
+        };
+
+        // Alias lookup
+        assert_eq!(names("fd-find"), vec!["fd"]);
+        // A real package name takes precedence over another package's alias
+        assert_eq!(names("rg"), vec!["rg"]);
+        // Globs match aliases as well as names
+        assert_eq!(names("fd-*"), vec!["fd"]);
+        assert_eq!(names("r*"), vec!["rg", "ripgrep"]);
+        assert!(names("bat").is_empty());
     }
 
     #[test]
@@ -1,31 +1,84 @@
 //! GitHub provider implementation
 
 use super::base::SourceProvider;
-use crate::core::{BinaryAsset, BinarySelector, Package, PlatformBinary};
+use crate::core::platform::ParsedAsset;
+use crate::core::{
+    BinaryAsset, BinarySelector, Config, Package, Platform, PlatformBinary, ScoringWeights,
+};
+use crate::utils::network::NetworkSettings;
 use crate::utils::HttpClient;
 use anyhow::{Context, Result};
 use serde::Deserialize;
 use std::collections::HashMap;
 
+/// Releases fetched when listing tags (the API maximum per page)
+const RELEASES_PER_PAGE: usize = 100;
+
+/// Tags suggested when a requested tag doesn't exist
+const MISSING_TAG_SUGGESTIONS: usize = 5;
+
+/// Releases fetched when looking for the newest one, prereleases included
+const NEWEST_RELEASES_PER_PAGE: usize = 10;
+
 /// GitHub provider
 #[derive(Clone)]
 pub struct GitHubProvider {
     http: HttpClient,
+    scoring: ScoringWeights,
+    prereleases: bool,
 }
 
 impl GitHubProvider {
-    /// Create a new GitHub provider without authentication
+    /// Create a new GitHub provider, authenticated with a token from the environment when set
     pub fn new() -> Result<Self> {
-        Self::with_token(None)
+        Self::with_settings(&NetworkSettings::default())
+    }
+
+    /// Like [`GitHubProvider::new`], with the given timeout and retry settings
+    pub fn with_settings(settings: &NetworkSettings) -> Result<Self> {
+        Self::with_options(Self::token_from_env(), settings)
+    }
+
+    /// Read the GitHub token from `WENGET_GITHUB_TOKEN`, falling back to `GITHUB_TOKEN`
+    pub fn token_from_env() -> Option<String> {
+        crate::utils::http::github_token_from_env()
     }
 
     /// Create a new GitHub provider with optional token for authentication
+    #[allow(dead_code)]
     pub fn with_token(token: Option<String>) -> Result<Self> {
+        Self::with_options(token, &NetworkSettings::default())
+    }
+
+    /// Create a new GitHub provider with optional token and network settings
+    pub fn with_options(token: Option<String>, settings: &NetworkSettings) -> Result<Self> {
         Ok(Self {
-            http: HttpClient::with_token(token)?,
+            http: HttpClient::with_settings(token, settings)?,
+            scoring: ScoringWeights::default(),
+            prereleases: false,
         })
     }
 
+    /// Provider using the network settings, scoring weights and prerelease choice of `config`
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let scoring = config.preferences().scoring.unwrap_or_default();
+        Ok(Self::with_settings(config.network())?
+            .with_scoring(scoring)
+            .with_prereleases(config.prereleases()))
+    }
+
+    /// Use custom weights when picking release assets
+    pub fn with_scoring(mut self, scoring: ScoringWeights) -> Self {
+        self.scoring = scoring;
+        self
+    }
+
+    /// Treat prereleases as the latest release when they are newer (`--pre`)
+    pub fn with_prereleases(mut self, prereleases: bool) -> Self {
+        self.prereleases = prereleases;
+        self
+    }
+
     /// Parse GitHub URL to extract owner and repo
     ///
     /// Supports:
@@ -49,6 +102,37 @@ impl GitHubProvider {
         }
     }
 
+    /// Parse a release asset download URL
+    ///
+    /// Supports `https://github.com/owner/repo/releases/download/<tag>/<asset>`.
+    /// Query strings and fragments are ignored.
+    pub fn parse_release_asset_url(url: &str) -> Option<ReleaseAssetUrl> {
+        let url = url.split(['?', '#']).next().unwrap_or(url);
+        let path = url
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .strip_prefix("github.com/")?;
+
+        let (repo_path, rest) = path.split_once("/releases/download/")?;
+        let (owner, repo) = repo_path.split_once('/')?;
+        let (tag, asset_name) = rest.rsplit_once('/')?;
+
+        if [owner, repo, tag, asset_name]
+            .iter()
+            .any(|part| part.is_empty())
+            || repo.contains('/')
+        {
+            return None;
+        }
+
+        Some(ReleaseAssetUrl {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            tag: tag.to_string(),
+            asset_name: asset_name.to_string(),
+        })
+    }
+
     /// Fetch latest release from GitHub API
     pub fn fetch_latest_release(&self, owner: &str, repo: &str) -> Result<GitHubRelease> {
         let url = format!(
@@ -61,6 +145,54 @@ impl GitHubProvider {
             .with_context(|| format!("Failed to fetch latest release for {}/{}", owner, repo))
     }
 
+    /// Fetch the newest release, whether or not it is a prerelease
+    ///
+    /// `/releases/latest` never returns prereleases, so tools that only ship betas
+    /// have no latest release. Drafts are skipped.
+    pub fn fetch_latest_release_including_prereleases(
+        &self,
+        owner: &str,
+        repo: &str,
+    ) -> Result<GitHubRelease> {
+        let url = format!(
+            "https://api.github.com/repos/{}/{}/releases?per_page={}",
+            owner, repo, NEWEST_RELEASES_PER_PAGE
+        );
+
+        let releases: Vec<GitHubRelease> = self
+            .http
+            .get_json(&url)
+            .with_context(|| format!("Failed to fetch releases for {}/{}", owner, repo))?;
+        let release = Self::newest_release(releases)
+            .ok_or_else(|| anyhow::anyhow!("{}/{} has no releases", owner, repo))?;
+        log::debug!(
+            "Newest release of {}/{}: {}{}",
+            owner,
+            repo,
+            release.tag_name,
+            if release.prerelease {
+                " (prerelease)"
+            } else {
+                ""
+            }
+        );
+        Ok(release)
+    }
+
+    /// The newest published release of a list ordered newest first
+    fn newest_release(releases: Vec<GitHubRelease>) -> Option<GitHubRelease> {
+        releases.into_iter().find(|r| !r.draft)
+    }
+
+    /// The latest release, or the newest prerelease when prereleases are enabled
+    pub fn fetch_newest_release(&self, owner: &str, repo: &str) -> Result<GitHubRelease> {
+        if self.prereleases {
+            self.fetch_latest_release_including_prereleases(owner, repo)
+        } else {
+            self.fetch_latest_release(owner, repo)
+        }
+    }
+
     /// Fetch a specific release by tag from GitHub API
     pub fn fetch_release_by_tag(
         &self,
@@ -106,10 +238,117 @@ impl GitHubProvider {
     pub fn fetch_latest_version(&self, repo_url: &str) -> Result<String> {
         let (owner, repo) = Self::parse_github_url(repo_url)
             .ok_or_else(|| anyhow::anyhow!("Invalid GitHub URL: {}", repo_url))?;
-        let release = self.fetch_latest_release(&owner, &repo)?;
+        let release = self.fetch_newest_release(&owner, &repo)?;
         Ok(release.tag_name.trim_start_matches('v').to_string())
     }
 
+    /// Look up the API URL of a release asset in a private repository
+    ///
+    /// Private assets can't be fetched from their browser download URL; they must go
+    /// through the API with the token. Returns None without a token or when the repo
+    /// is public, so callers keep the plain download path.
+    pub fn private_asset_url(
+        &self,
+        repo_url: &str,
+        version: &str,
+        asset_name: &str,
+    ) -> Result<Option<String>> {
+        if self.http.token().is_none() {
+            return Ok(None);
+        }
+
+        let (owner, repo) = Self::parse_github_url(repo_url)
+            .ok_or_else(|| anyhow::anyhow!("Invalid GitHub URL: {}", repo_url))?;
+        if !self.fetch_repo_info(&owner, &repo)?.private {
+            return Ok(None);
+        }
+
+        let release = self.fetch_release_by_tag(&owner, &repo, version)?;
+        let asset = release
+            .assets
+            .into_iter()
+            .find(|a| a.name == asset_name)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Asset {} not found in release {} of {}/{}",
+                    asset_name,
+                    version,
+                    owner,
+                    repo
+                )
+            })?;
+        Ok(Some(asset.url))
+    }
+
+    /// Fetch the tags of a repository's most recent releases (newest first)
+    pub fn fetch_release_tags(&self, repo_url: &str) -> Result<Vec<String>> {
+        let (owner, repo) = Self::parse_github_url(repo_url)
+            .ok_or_else(|| anyhow::anyhow!("Invalid GitHub URL: {}", repo_url))?;
+        let url = format!(
+            "https://api.github.com/repos/{}/{}/releases?per_page={}",
+            owner, repo, RELEASES_PER_PAGE
+        );
+
+        let releases: Vec<GitHubRelease> = self
+            .http
+            .get_json(&url)
+            .with_context(|| format!("Failed to fetch releases for {}/{}", owner, repo))?;
+        Ok(releases.into_iter().map(|r| r.tag_name).collect())
+    }
+
+    /// Fetch the newest release tag satisfying a semver constraint
+    ///
+    /// Tags that aren't versions are skipped. Returns None when no release matches.
+    pub fn fetch_matching_version(
+        &self,
+        repo_url: &str,
+        req: &semver::VersionReq,
+    ) -> Result<Option<String>> {
+        let tags = self.fetch_release_tags(repo_url)?;
+        Ok(
+            crate::core::version::newest_matching(tags.iter().map(String::as_str), req)
+                .map(str::to_string),
+        )
+    }
+
+    /// Explain a release tag that doesn't exist, listing recent tags
+    ///
+    /// Returns None when the tag exists or the releases can't be listed (e.g.
+    /// rate limited), so callers keep their own error or fallback.
+    pub fn missing_tag_message(&self, repo_url: &str, tag: &str) -> Option<String> {
+        let tags = self.fetch_release_tags(repo_url).ok()?;
+        let repo = Self::parse_github_url(repo_url)
+            .map(|(owner, repo)| format!("{}/{}", owner, repo))
+            .unwrap_or_else(|| repo_url.to_string());
+        Self::missing_tag(&repo, tag, &tags)
+    }
+
+    /// [`Self::missing_tag_message`] for a list of the newest release tags
+    fn missing_tag(repo: &str, tag: &str, tags: &[String]) -> Option<String> {
+        let wanted = tag.trim_start_matches('v');
+        if tags.iter().any(|t| t.trim_start_matches('v') == wanted) {
+            return None;
+        }
+        // A full page may not reach back to older releases
+        if tags.len() >= RELEASES_PER_PAGE {
+            return None;
+        }
+        if tags.is_empty() {
+            return Some(format!("{} has no releases", repo));
+        }
+        let recent: Vec<&str> = tags
+            .iter()
+            .take(MISSING_TAG_SUGGESTIONS)
+            .map(String::as_str)
+            .collect();
+        Some(format!(
+            "{} has no release tagged '{}'. Recent tags: {}",
+            repo,
+            tag,
+            recent.join(", ")
+        ))
+    }
+
     /// Fetch package information for a specific version
     pub fn fetch_package_by_version(&self, url: &str, version: &str) -> Result<Package> {
         log::debug!("Fetching package from: {} (version: {})", url, version);
@@ -122,27 +361,39 @@ impl GitHubProvider {
         let repo_info = self.fetch_repo_info(&owner, &repo)?;
 
         // Fetch specific release by tag
-        let release = self.fetch_release_by_tag(&owner, &repo, version)
-            .with_context(|| {
-                format!(
+        let release = match self.fetch_release_by_tag(&owner, &repo, version) {
+            Ok(release) => release,
+            Err(e) => {
+                if let Some(message) = self.missing_tag_message(url, version) {
+                    anyhow::bail!(message);
+                }
+                return Err(e.context(format!(
                     "Version '{}' not found for {}/{}. Use 'wenget info {}' to see available versions.",
                     version, owner, repo, repo
-                )
-            })?;
+                )));
+            }
+        };
 
         if release.assets.is_empty() {
-            anyhow::bail!(
-                "No binary assets found in release {} for {}/{}",
-                version,
-                owner,
-                repo
-            );
+            anyhow::bail!(Self::source_only_message(&format!(
+                "{}/{} {}",
+                owner, repo, version
+            )));
         }
 
         // Use shared platform extraction logic
-        let platforms = Self::extract_platform_binaries(&release.assets);
+        let platforms = Self::extract_platform_binaries_with(&release.assets, self.scoring);
 
         if platforms.is_empty() {
+            if let Some(hint) = Self::installer_only_message(&release.assets, Platform::current()) {
+                anyhow::bail!("Cannot install {}/{} {}: {}", owner, repo, version, hint);
+            }
+            if Self::is_source_only(&release.assets) {
+                anyhow::bail!(Self::source_only_message(&format!(
+                    "{}/{} {}",
+                    owner, repo, version
+                )));
+            }
             anyhow::bail!(
                 "No matching binaries found for any platform in {}/{} (version: {})",
                 owner,
@@ -154,12 +405,15 @@ impl GitHubProvider {
         // Create package
         let package = Package {
             name: repo.clone(),
+            aliases: Vec::new(),
             description: repo_info.description.unwrap_or_else(|| repo.clone()),
             repo: url.to_string(),
             homepage: Some(repo_info.html_url),
             license: repo_info.license.map(|l| l.name),
             version: Some(release.tag_name.trim_start_matches('v').to_string()),
             platforms,
+            pre_install: Vec::new(),
+            post_install: Vec::new(),
         };
 
         let normalized_version = release.tag_name.trim_start_matches('v').to_string();
@@ -180,18 +434,18 @@ impl GitHubProvider {
     pub fn extract_platform_binaries(
         assets: &[GitHubAsset],
     ) -> HashMap<String, Vec<PlatformBinary>> {
-        // Convert GitHub assets to BinaryAsset
-        let binary_assets: Vec<BinaryAsset> = assets
-            .iter()
-            .map(|a| BinaryAsset {
-                name: a.name.clone(),
-                url: a.browser_download_url.clone(),
-                size: a.size,
-            })
-            .collect();
+        Self::extract_platform_binaries_with(assets, ScoringWeights::default())
+    }
+
+    /// [`Self::extract_platform_binaries`] with custom scoring weights
+    pub fn extract_platform_binaries_with(
+        assets: &[GitHubAsset],
+        scoring: ScoringWeights,
+    ) -> HashMap<String, Vec<PlatformBinary>> {
+        let binary_assets = Self::binary_assets(assets);
 
         // Extract platforms using BinarySelector (now returns Vec<BinaryAsset> per platform)
-        let platform_map = BinarySelector::extract_platforms(&binary_assets);
+        let platform_map = BinarySelector::extract_platforms_with(&binary_assets, scoring);
 
         // Convert to Vec<PlatformBinary> map
         platform_map
@@ -210,6 +464,63 @@ impl GitHubProvider {
             })
             .collect()
     }
+
+    /// Convert GitHub release assets to BinaryAsset
+    fn binary_assets(assets: &[GitHubAsset]) -> Vec<BinaryAsset> {
+        assets
+            .iter()
+            .map(|a| BinaryAsset {
+                name: a.name.clone(),
+                url: a.browser_download_url.clone(),
+                size: a.size,
+            })
+            .collect()
+    }
+
+    /// Whether release assets hold nothing built for any OS
+    ///
+    /// True without assets, and when no asset is an OS installer or has a name
+    /// mentioning or implying an OS (only source tarballs, checksums, docs, ...).
+    pub fn is_source_only(assets: &[GitHubAsset]) -> bool {
+        assets.iter().all(|asset| {
+            ParsedAsset::from_filename(&asset.name).os.is_none()
+                && !BinarySelector::is_installer(&asset.name)
+        })
+    }
+
+    /// Explain that a repository (or release) has nothing wenget can install
+    ///
+    /// Distinguishes source-only projects from packages that merely lack a
+    /// build for the current platform.
+    pub fn source_only_message(repo: &str) -> String {
+        format!(
+            "{} has no release binaries (source-only); wenget can't install it",
+            repo
+        )
+    }
+
+    /// Explain a release that only offers an OS installer for `platform`
+    ///
+    /// Installers (.msi, .deb, ...) are never selected, so such a release looks
+    /// unsupported; this points the user to the installer instead.
+    pub fn installer_only_message(assets: &[GitHubAsset], platform: Platform) -> Option<String> {
+        let assets = Self::binary_assets(assets);
+        let (ext, installer) = BinarySelector::installer_for_platform(&assets, platform)?;
+        Some(format!(
+            "only a {} installer is available for {}; run it manually: {}",
+            ext, platform, installer.url
+        ))
+    }
+
+    /// [`Self::installer_only_message`] for the latest release of a repository
+    ///
+    /// Used after a package turned out to have no build for `platform`; costs one
+    /// API call and returns None when the release can't be fetched.
+    pub fn installer_hint(&self, repo_url: &str, platform: Platform) -> Option<String> {
+        let (owner, repo) = Self::parse_github_url(repo_url)?;
+        let release = self.fetch_latest_release(&owner, &repo).ok()?;
+        Self::installer_only_message(&release.assets, platform)
+    }
 }
 
 impl SourceProvider for GitHubProvider {
@@ -224,20 +535,22 @@ impl SourceProvider for GitHubProvider {
         let repo_info = self.fetch_repo_info(&owner, &repo)?;
 
         // Fetch latest release
-        let release = self.fetch_latest_release(&owner, &repo)?;
+        let release = self.fetch_newest_release(&owner, &repo)?;
 
         if release.assets.is_empty() {
-            anyhow::bail!(
-                "No binary assets found in latest release for {}/{}",
-                owner,
-                repo
-            );
+            anyhow::bail!(Self::source_only_message(&format!("{}/{}", owner, repo)));
         }
 
         // Use shared platform extraction logic
-        let platforms = Self::extract_platform_binaries(&release.assets);
+        let platforms = Self::extract_platform_binaries_with(&release.assets, self.scoring);
 
         if platforms.is_empty() {
+            if let Some(hint) = Self::installer_only_message(&release.assets, Platform::current()) {
+                anyhow::bail!("Cannot install {}/{}: {}", owner, repo, hint);
+            }
+            if Self::is_source_only(&release.assets) {
+                anyhow::bail!(Self::source_only_message(&format!("{}/{}", owner, repo)));
+            }
             anyhow::bail!(
                 "No matching binaries found for any platform in {}/{}",
                 owner,
@@ -248,12 +561,15 @@ impl SourceProvider for GitHubProvider {
         // Create package
         let package = Package {
             name: repo.clone(),
+            aliases: Vec::new(),
             description: repo_info.description.unwrap_or_else(|| repo.clone()),
             repo: url.to_string(),
             homepage: Some(repo_info.html_url),
             license: repo_info.license.map(|l| l.name),
             version: Some(release.tag_name.trim_start_matches('v').to_string()),
             platforms,
+            pre_install: Vec::new(),
+            post_install: Vec::new(),
         };
 
         let version = release.tag_name.trim_start_matches('v').to_string();
@@ -285,6 +601,12 @@ impl Default for GitHubProvider {
 pub struct GitHubRelease {
     /// Release tag name (e.g., "v1.0.0")
     pub tag_name: String,
+    /// Whether the release is marked as a prerelease
+    #[serde(default)]
+    pub prerelease: bool,
+    /// Whether the release is an unpublished draft
+    #[serde(default)]
+    pub draft: bool,
     /// Release assets (downloadable files)
     pub assets: Vec<GitHubAsset>,
 }
@@ -296,6 +618,9 @@ pub struct GitHubAsset {
     pub name: String,
     /// Direct download URL
     pub browser_download_url: String,
+    /// API URL of the asset (needed to download assets of private repos)
+    #[serde(default)]
+    pub url: String,
     /// File size in bytes
     pub size: u64,
 }
@@ -313,6 +638,9 @@ pub struct GitHubRepo {
     pub homepage: Option<String>,
     /// License information
     pub license: Option<GitHubLicense>,
+    /// Whether the repository is private
+    #[serde(default)]
+    pub private: bool,
 }
 
 /// GitHub license information
@@ -324,10 +652,110 @@ pub struct GitHubLicense {
     pub spdx_id: Option<String>,
 }
 
+/// The parts of a GitHub release asset download URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseAssetUrl {
+    pub owner: String,
+    pub repo: String,
+    pub tag: String,
+    pub asset_name: String,
+}
+
+impl ReleaseAssetUrl {
+    /// URL of the repository the asset belongs to
+    pub fn repo_url(&self) -> String {
+        format!("https://github.com/{}/{}", self.owner, self.repo)
+    }
+
+    /// Release tag without a leading 'v'
+    pub fn version(&self) -> &str {
+        self.tag.trim_start_matches('v')
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_missing_tag() {
+        let tags: Vec<String> = ["v14.1.1", "v14.1.0", "14.0.3", "13.0.0", "12.1.1", "11.0.2"]
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+
+        assert_eq!(GitHubProvider::missing_tag("o/rg", "14.1.0", &tags), None);
+        assert_eq!(GitHubProvider::missing_tag("o/rg", "v14.0.3", &tags), None);
+        assert_eq!(
+            GitHubProvider::missing_tag("o/rg", "14.2.0", &tags).as_deref(),
+            Some(
+                "o/rg has no release tagged '14.2.0'. Recent tags: v14.1.1, v14.1.0, 14.0.3, 13.0.0, 12.1.1"
+            )
+        );
+        assert_eq!(
+            GitHubProvider::missing_tag("o/rg", "1.0.0", &[]).as_deref(),
+            Some("o/rg has no releases")
+        );
+
+        // Older releases may be past the first page
+        let page: Vec<String> = (0..RELEASES_PER_PAGE)
+            .map(|i| format!("v1.0.{}", i))
+            .collect();
+        assert_eq!(GitHubProvider::missing_tag("o/rg", "0.9.0", &page), None);
+    }
+
+    #[test]
+    fn test_newest_release() {
+        let releases: Vec<GitHubRelease> = serde_json::from_str(
+            r#"[
+                {"tag_name": "v2.0.0-rc.1", "draft": true, "prerelease": true, "assets": []},
+                {"tag_name": "v2.0.0-beta.3", "prerelease": true, "assets": []},
+                {"tag_name": "v1.9.0", "assets": []}
+            ]"#,
+        )
+        .unwrap();
+        let newest = GitHubProvider::newest_release(releases).unwrap();
+        assert_eq!(newest.tag_name, "v2.0.0-beta.3");
+        assert!(newest.prerelease);
+
+        assert!(GitHubProvider::newest_release(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn test_is_source_only() {
+        let assets = |names: &[&str]| -> Vec<GitHubAsset> {
+            names
+                .iter()
+                .map(|name| GitHubAsset {
+                    name: name.to_string(),
+                    browser_download_url: format!("https://example.com/{}", name),
+                    url: String::new(),
+                    size: 1000,
+                })
+                .collect()
+        };
+
+        assert!(GitHubProvider::is_source_only(&[]));
+        assert!(GitHubProvider::is_source_only(&assets(&[
+            "tool-1.0.0.tar.gz",
+            "tool-1.0.0.tar.gz.sha256",
+            "CHANGELOG.md"
+        ])));
+        // Binaries for some OS, even one that isn't supported here
+        assert!(!GitHubProvider::is_source_only(&assets(&[
+            "tool-1.0.0.tar.gz",
+            "tool-1.0.0-linux-riscv64.tar.gz"
+        ])));
+        assert!(!GitHubProvider::is_source_only(&assets(&[
+            "tool-setup.msi"
+        ])));
+
+        assert_eq!(
+            GitHubProvider::source_only_message("o/tool"),
+            "o/tool has no release binaries (source-only); wenget can't install it"
+        );
+    }
+
     #[test]
     fn test_parse_github_url() {
         let result = GitHubProvider::parse_github_url("https://github.com/user/repo");
@@ -353,6 +781,68 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_parse_release_asset_url() {
+        let asset = GitHubProvider::parse_release_asset_url(
+            "https://github.com/owner/tool/releases/download/v1.2.0/tool-special.tar.gz",
+        )
+        .unwrap();
+        assert_eq!(asset.owner, "owner");
+        assert_eq!(asset.repo, "tool");
+        assert_eq!(asset.tag, "v1.2.0");
+        assert_eq!(asset.asset_name, "tool-special.tar.gz");
+        assert_eq!(asset.repo_url(), "https://github.com/owner/tool");
+        assert_eq!(asset.version(), "1.2.0");
+
+        // Query strings are dropped, tags may contain slashes
+        let asset = GitHubProvider::parse_release_asset_url(
+            "https://github.com/owner/tool/releases/download/cli/v2/tool.zip?raw=1",
+        )
+        .unwrap();
+        assert_eq!(asset.tag, "cli/v2");
+        assert_eq!(asset.asset_name, "tool.zip");
+
+        // Not release assets
+        assert!(GitHubProvider::parse_release_asset_url("https://github.com/owner/tool").is_none());
+        assert!(GitHubProvider::parse_release_asset_url(
+            "https://example.com/owner/tool/releases/download/v1/tool.zip"
+        )
+        .is_none());
+        assert!(GitHubProvider::parse_release_asset_url(
+            "https://github.com/owner/tool/releases/download/v1/"
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_private_repo_fields() {
+        let repo: GitHubRepo = serde_json::from_str(
+            r#"{"name": "tool", "description": null, "html_url": "https://github.com/o/tool",
+                "homepage": null, "license": null, "private": true}"#,
+        )
+        .unwrap();
+        assert!(repo.private);
+
+        // Older payloads / fixtures without the field are treated as public
+        let repo: GitHubRepo = serde_json::from_str(
+            r#"{"name": "tool", "description": null, "html_url": "https://github.com/o/tool",
+                "homepage": null, "license": null}"#,
+        )
+        .unwrap();
+        assert!(!repo.private);
+
+        let asset: GitHubAsset = serde_json::from_str(
+            r#"{"name": "tool.tar.gz", "size": 1,
+                "browser_download_url": "https://github.com/o/tool/releases/download/v1/tool.tar.gz",
+                "url": "https://api.github.com/repos/o/tool/releases/assets/1"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            asset.url,
+            "https://api.github.com/repos/o/tool/releases/assets/1"
+        );
+    }
+
     #[test]
     #[ignore] // Requires network access
     fn test_fetch_package() {
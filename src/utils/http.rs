@@ -1,7 +1,9 @@
 //! HTTP client utilities for WenPM
 
+use super::network::NetworkSettings;
+use super::rate_limit;
 use anyhow::{Context, Result};
-use reqwest::blocking::Client;
+use reqwest::blocking::{Client, RequestBuilder};
 use serde::de::DeserializeOwned;
 use std::time::Duration;
 
@@ -10,49 +12,113 @@ use std::time::Duration;
 pub struct HttpClient {
     client: Client,
     token: Option<String>,
+    retries: u32,
+    /// Offline mode: every request fails without touching the network
+    offline: bool,
+}
+
+/// Timeout used when neither the CLI, the environment nor config.toml set one
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Environment variables read for a GitHub token, in order of precedence
+const TOKEN_VARS: [&str; 2] = ["WENGET_GITHUB_TOKEN", "GITHUB_TOKEN"];
+
+/// Read the GitHub token from `WENGET_GITHUB_TOKEN`, falling back to `GITHUB_TOKEN`
+pub fn github_token_from_env() -> Option<String> {
+    first_token(|var| std::env::var(var).ok())
+}
+
+/// The first non-empty token among [`TOKEN_VARS`]
+fn first_token(lookup: impl Fn(&str) -> Option<String>) -> Option<String> {
+    TOKEN_VARS
+        .iter()
+        .filter_map(|var| lookup(var))
+        .map(|token| token.trim().to_string())
+        .find(|token| !token.is_empty())
 }
 
 impl HttpClient {
-    /// Create a new HTTP client with default timeout (30 seconds) and no authentication
+    /// Create a new HTTP client with default timeout (30 seconds)
+    ///
+    /// GitHub API requests are authenticated with the token from the
+    /// environment, see [`github_token_from_env`].
     pub fn new() -> Result<Self> {
-        Self::with_options(None, Duration::from_secs(30))
+        Self::with_options(github_token_from_env(), DEFAULT_TIMEOUT)
     }
 
     /// Create a new HTTP client with optional GitHub token
+    #[allow(dead_code)]
     pub fn with_token(token: Option<String>) -> Result<Self> {
-        Self::with_options(token, Duration::from_secs(30))
+        Self::with_options(token, DEFAULT_TIMEOUT)
+    }
+
+    /// Create a new HTTP client with optional token and resolved network settings
+    pub fn with_settings(token: Option<String>, settings: &NetworkSettings) -> Result<Self> {
+        let mut http = Self::with_options(token, settings.timeout_or(DEFAULT_TIMEOUT))?;
+        http.retries = settings.retries;
+        http.offline = settings.offline;
+        Ok(http)
     }
 
     /// Create a new HTTP client with custom timeout
+    #[allow(dead_code)]
     pub fn with_timeout(timeout: Duration) -> Result<Self> {
         Self::with_options(None, timeout)
     }
 
     /// Create a new HTTP client with optional token and custom timeout
     pub fn with_options(token: Option<String>, timeout: Duration) -> Result<Self> {
-        let client = Client::builder()
+        let mut builder = Client::builder()
             .user_agent(format!("WenPM/{}", env!("CARGO_PKG_VERSION")))
-            .timeout(timeout)
-            .build()
-            .context("Failed to create HTTP client")?;
+            .timeout(timeout);
 
-        Ok(Self { client, token })
+        if let Some(proxy) = super::proxy::proxy()? {
+            builder = builder.proxy(proxy);
+        }
+
+        let client = builder.build().context("Failed to create HTTP client")?;
+
+        Ok(Self {
+            client,
+            token,
+            retries: super::network::DEFAULT_RETRIES,
+            offline: false,
+        })
+    }
+
+    /// The GitHub token used for authentication, if any
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    /// Fail instead of sending a request in offline mode
+    fn ensure_online(&self, url: &str) -> Result<()> {
+        if self.offline {
+            anyhow::bail!("Offline mode: not fetching {}", url);
+        }
+        Ok(())
+    }
+
+    /// Add the token to requests for api.github.com
+    ///
+    /// Other hosts (bucket manifests, mirrors, release downloads) never see it.
+    fn authorize(&self, request: RequestBuilder, url: &str) -> RequestBuilder {
+        match &self.token {
+            Some(token) if rate_limit::is_github_api(url) => {
+                request.header("Authorization", format!("Bearer {}", token))
+            }
+            _ => request,
+        }
     }
 
     /// Send a GET request and return the response as text
     pub fn get_text(&self, url: &str) -> Result<String> {
         log::debug!("GET {}", url);
+        self.ensure_online(url)?;
 
-        let mut request = self.client.get(url);
+        let request = self.authorize(self.client.get(url), url);
 
-        // Add authorization header if token is available
-        if let Some(ref token) = self.token {
-            request = request.header("Authorization", format!("Bearer {}", token));
-        }
-
-        let response = request
-            .send()
-            .with_context(|| format!("Failed to send GET request to {}", url))?;
+        let response = rate_limit::send(request, self.retries, url)?;
 
         if !response.status().is_success() {
             anyhow::bail!("HTTP {} for {}", response.status(), url);
@@ -68,17 +134,14 @@ impl HttpClient {
     /// Send a GET request and parse JSON response
     pub fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T> {
         log::debug!("GET {} (JSON)", url);
+        self.ensure_online(url)?;
 
-        let mut request = self.client.get(url).header("Accept", "application/json");
-
-        // Add authorization header if token is available
-        if let Some(ref token) = self.token {
-            request = request.header("Authorization", format!("Bearer {}", token));
-        }
+        let request = self.authorize(
+            self.client.get(url).header("Accept", "application/json"),
+            url,
+        );
 
-        let response = request
-            .send()
-            .with_context(|| format!("Failed to send GET request to {}", url))?;
+        let response = rate_limit::send(request, self.retries, url)?;
 
         if !response.status().is_success() {
             anyhow::bail!("HTTP {} for {}", response.status(), url);
@@ -91,6 +154,20 @@ impl HttpClient {
         Ok(data)
     }
 
+    /// Check whether a URL answers a HEAD request successfully
+    pub fn is_reachable(&self, url: &str) -> bool {
+        log::debug!("HEAD {}", url);
+        if self.offline {
+            return false;
+        }
+
+        let request = self.authorize(self.client.head(url), url);
+
+        rate_limit::send(request, self.retries, url)
+            .map(|response| response.status().is_success())
+            .unwrap_or(false)
+    }
+
     /// Check GitHub API rate limit
     #[allow(dead_code)]
     pub fn check_rate_limit(&self) -> Result<RateLimit> {
@@ -157,6 +234,57 @@ mod tests {
         assert!(client.is_ok());
     }
 
+    #[test]
+    fn test_first_token_precedence() {
+        let vars = |pairs: &'static [(&'static str, &'static str)]| {
+            move |var: &str| {
+                pairs
+                    .iter()
+                    .find(|(name, _)| *name == var)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        assert_eq!(
+            first_token(vars(&[("GITHUB_TOKEN", "b"), ("WENGET_GITHUB_TOKEN", "a")])),
+            Some("a".to_string())
+        );
+        assert_eq!(
+            first_token(vars(&[
+                ("WENGET_GITHUB_TOKEN", " "),
+                ("GITHUB_TOKEN", "b\n")
+            ])),
+            Some("b".to_string())
+        );
+        assert_eq!(first_token(vars(&[])), None);
+    }
+
+    #[test]
+    fn test_token_only_sent_to_github_api() {
+        let client = HttpClient::with_token(Some("secret".to_string())).unwrap();
+        let auth = |url: &str| {
+            client
+                .authorize(client.client.get(url), url)
+                .build()
+                .unwrap()
+                .headers()
+                .get("Authorization")
+                .map(|value| value.to_str().unwrap().to_string())
+        };
+        assert_eq!(
+            auth("https://api.github.com/repos/o/r/releases"),
+            Some("Bearer secret".to_string())
+        );
+        assert_eq!(
+            auth("https://raw.githubusercontent.com/o/r/main/m.json"),
+            None
+        );
+        assert_eq!(
+            auth("https://github.com/o/r/releases/download/v1/a.zip"),
+            None
+        );
+        assert_eq!(auth("https://api.github.com.evil.example/x"), None);
+    }
+
     #[test]
     #[ignore] // Requires network access
     fn test_rate_limit_check() {
@@ -0,0 +1,177 @@
+//! Cleanup of half-written files when an operation fails or is interrupted
+//!
+//! A [`CleanupGuard`] tracks paths (a `.part` download, a half-extracted app
+//! directory, fresh launchers) until the operation that creates them finishes.
+//! If the guard is dropped before [`CleanupGuard::disarm`] - on an error, or
+//! when Ctrl-C arrives - the paths are removed, so the next run starts from a
+//! clean state.
+//!
+//! The Ctrl-C handler is installed the first time a guard is created. It
+//! removes every path still tracked and exits with status 130, like a shell
+//! reports a process killed by SIGINT. Guards and the handler take the same
+//! lock while removing, so a signal arriving between steps never races with a
+//! guard cleaning up, and removing an already missing path is not an error.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, Once};
+
+/// Exit status after a Ctrl-C interruption (128 + SIGINT)
+pub const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// Paths tracked by live guards, removed by the Ctrl-C handler
+static PENDING: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+static HANDLER: Once = Once::new();
+
+/// Removes its tracked paths when dropped, unless disarmed
+#[derive(Debug, Default)]
+pub struct CleanupGuard {
+    paths: Vec<PathBuf>,
+}
+
+impl CleanupGuard {
+    /// Create an empty guard, installing the Ctrl-C handler if needed
+    pub fn new() -> Self {
+        HANDLER.call_once(install_handler);
+        Self::default()
+    }
+
+    /// Remove `path` (file or directory) unless the guard is disarmed
+    pub fn track(&mut self, path: impl Into<PathBuf>) {
+        let path = path.into();
+        pending().push(path.clone());
+        self.paths.push(path);
+    }
+
+    /// Keep the tracked paths: the operation completed
+    pub fn disarm(mut self) {
+        let mut pending = pending();
+        for path in self.paths.drain(..) {
+            untrack(&mut pending, &path);
+        }
+    }
+}
+
+impl Drop for CleanupGuard {
+    fn drop(&mut self) {
+        if self.paths.is_empty() {
+            return;
+        }
+        // Hold the lock while removing so the Ctrl-C handler waits for us
+        let mut pending = pending();
+        for path in self.paths.drain(..).rev() {
+            untrack(&mut pending, &path);
+            remove_path(&path);
+        }
+    }
+}
+
+/// Lock the pending list, recovering it if a panicking thread held the lock
+fn pending() -> MutexGuard<'static, Vec<PathBuf>> {
+    PENDING.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Drop one occurrence of `path` from the pending list
+fn untrack(pending: &mut Vec<PathBuf>, path: &Path) {
+    if let Some(pos) = pending.iter().rposition(|p| p == path) {
+        pending.remove(pos);
+    }
+}
+
+/// Remove a file, symlink or directory; a missing path is not an error
+fn remove_path(path: &Path) {
+    let result = match fs::symlink_metadata(path) {
+        Ok(meta) if meta.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path),
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(()) => log::debug!("Removed incomplete {}", path.display()),
+        Err(e) if e.kind() == ErrorKind::NotFound => {}
+        Err(e) => log::warn!("Failed to remove {}: {}", path.display(), e),
+    }
+}
+
+/// Wait for Ctrl-C on a background thread, then clean up and exit
+fn install_handler() {
+    let spawned = std::thread::Builder::new()
+        .name("wenget-ctrl-c".to_string())
+        .spawn(|| {
+            let runtime = match tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+            {
+                Ok(runtime) => runtime,
+                Err(e) => {
+                    log::warn!("Ctrl-C cleanup unavailable: {}", e);
+                    return;
+                }
+            };
+            if let Err(e) = runtime.block_on(tokio::signal::ctrl_c()) {
+                log::warn!("Ctrl-C cleanup unavailable: {}", e);
+                return;
+            }
+
+            // Keep the lock until exit so no guard can start or finish meanwhile
+            let mut pending = pending();
+            if !pending.is_empty() {
+                eprintln!("\nInterrupted, removing incomplete files...");
+            }
+            for path in pending.drain(..).rev() {
+                remove_path(&path);
+            }
+            std::process::exit(INTERRUPTED_EXIT_CODE);
+        });
+    if let Err(e) = spawned {
+        log::warn!("Ctrl-C cleanup unavailable: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn is_pending(path: &Path) -> bool {
+        pending().iter().any(|p| p == path)
+    }
+
+    #[test]
+    fn test_guard_removes_on_drop() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("app");
+        let file = temp.path().join("tool.tar.gz.part");
+        fs::create_dir_all(dir.join("bin")).unwrap();
+        fs::write(dir.join("bin/tool"), "x").unwrap();
+        fs::write(&file, "partial").unwrap();
+
+        let mut guard = CleanupGuard::new();
+        guard.track(&dir);
+        guard.track(&file);
+        // Never created: removing it must not fail
+        guard.track(temp.path().join("missing"));
+        assert!(is_pending(&dir));
+
+        drop(guard);
+        assert!(!dir.exists());
+        assert!(!file.exists());
+        assert!(!is_pending(&dir));
+        assert!(!is_pending(&file));
+    }
+
+    #[test]
+    fn test_disarmed_guard_keeps_paths() {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join("tool");
+        fs::write(&file, "x").unwrap();
+
+        let mut guard = CleanupGuard::new();
+        guard.track(&file);
+        guard.disarm();
+
+        assert!(file.exists());
+        assert!(!is_pending(&file));
+    }
+}
@@ -1,7 +1,11 @@
 //! Utility modules for WenPM
 
 pub mod http;
+pub mod interrupt;
+pub mod network;
 pub mod prompt;
+pub mod proxy;
+pub mod rate_limit;
 
 // Re-export commonly used items
 pub use http::HttpClient;
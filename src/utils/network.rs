@@ -0,0 +1,490 @@
+//! Network timeout, retry and download buffer settings for WenPM
+//!
+//! Timeout and retries are resolved with the precedence: CLI flag (`--timeout`,
+//! `--retries`) > environment variable (`WENGET_TIMEOUT`, `WENGET_RETRIES`) >
+//! config.toml (`timeout_secs`, `retries`) > built-in default. Downloads use
+//! `download_timeout_secs` ahead of `timeout_secs`. The download size limit,
+//! buffer size and mirrors only come from config.toml (`max_download_bytes`,
+//! `download_buffer_kib`, `download_mirrors`).
+//!
+//! Offline mode (`--offline` or `WENGET_OFFLINE=1`) makes every request fail
+//! at once instead of waiting for a timeout; callers fall back to what is
+//! already on disk.
+
+use crate::core::preferences::{DownloadMirror, DOWNLOAD_BUFFER_KIB_RANGE};
+use crate::core::Preferences;
+use anyhow::{Context, Result};
+use reqwest::blocking::{RequestBuilder, Response};
+use reqwest::StatusCode;
+use std::time::Duration;
+
+/// Environment variable overriding the network timeout (seconds)
+pub const TIMEOUT_ENV: &str = "WENGET_TIMEOUT";
+
+/// Environment variable overriding the number of retries
+pub const RETRIES_ENV: &str = "WENGET_RETRIES";
+
+/// Environment variable enabling offline mode when set to 1/true/yes/on
+pub const OFFLINE_ENV: &str = "WENGET_OFFLINE";
+
+/// Values given on the command line for a single invocation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NetworkOverrides {
+    /// `--timeout <secs>`
+    pub timeout: Option<u64>,
+    /// `--retries <n>`
+    pub retries: Option<u32>,
+    /// `--offline`
+    pub offline: bool,
+}
+
+/// Retries when neither the CLI, the environment nor config.toml set a number
+pub const DEFAULT_RETRIES: u32 = 3;
+
+/// Download timeout when neither the CLI, the environment nor config.toml set one
+pub const DEFAULT_DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Largest download accepted when `max_download_bytes` is unset (2 GiB)
+pub const DEFAULT_MAX_DOWNLOAD_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+/// Default download buffer size
+///
+/// For a 256 MiB download, 64 KiB needs about 4,500 reads where 8 KiB needs
+/// 32,800; larger buffers showed no consistent gain in local measurements.
+pub const DEFAULT_DOWNLOAD_BUFFER: usize = 64 * 1024;
+
+/// Resolved timeout, retry, buffer and mirror settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkSettings {
+    /// Request timeout; `None` keeps the caller's default
+    pub timeout: Option<Duration>,
+    /// Timeout for file downloads
+    pub download_timeout: Duration,
+    /// Downloads larger than this are aborted
+    pub max_download_bytes: u64,
+    /// Number of retries after the first attempt
+    pub retries: u32,
+    /// Download buffer size in bytes; `None` uses [`DEFAULT_DOWNLOAD_BUFFER`]
+    pub download_buffer: Option<usize>,
+    /// Mirrors tried before the original URL of a download
+    pub mirrors: Vec<DownloadMirror>,
+    /// Never touch the network
+    pub offline: bool,
+}
+
+impl Default for NetworkSettings {
+    fn default() -> Self {
+        Self {
+            timeout: None,
+            download_timeout: DEFAULT_DOWNLOAD_TIMEOUT,
+            max_download_bytes: DEFAULT_MAX_DOWNLOAD_BYTES,
+            retries: DEFAULT_RETRIES,
+            download_buffer: None,
+            mirrors: Vec::new(),
+            offline: false,
+        }
+    }
+}
+
+impl NetworkSettings {
+    /// Resolve settings from CLI overrides, the environment and preferences
+    ///
+    /// `env` looks up an environment variable; invalid values are ignored with a warning.
+    pub fn resolve(
+        overrides: NetworkOverrides,
+        env: impl Fn(&str) -> Option<String>,
+        preferences: &Preferences,
+    ) -> Self {
+        let override_timeout = overrides
+            .timeout
+            .or_else(|| parse_env(&env, TIMEOUT_ENV))
+            .filter(|secs| *secs > 0);
+        let timeout = override_timeout
+            .or(preferences.timeout_secs)
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs);
+        let download_timeout = override_timeout
+            .or(preferences.download_timeout_secs)
+            .or(preferences.timeout_secs)
+            .filter(|secs| *secs > 0)
+            .map_or(DEFAULT_DOWNLOAD_TIMEOUT, Duration::from_secs);
+        let max_download_bytes = preferences
+            .max_download_bytes
+            .filter(|bytes| *bytes > 0)
+            .unwrap_or(DEFAULT_MAX_DOWNLOAD_BYTES);
+
+        let retries = overrides
+            .retries
+            .or_else(|| parse_env(&env, RETRIES_ENV))
+            .or(preferences.retries)
+            .unwrap_or(DEFAULT_RETRIES);
+
+        let download_buffer = preferences.download_buffer_kib.map(|kib| {
+            kib.clamp(
+                *DOWNLOAD_BUFFER_KIB_RANGE.start(),
+                *DOWNLOAD_BUFFER_KIB_RANGE.end(),
+            ) * 1024
+        });
+
+        let offline = overrides.offline || env(OFFLINE_ENV).is_some_and(|value| is_truthy(&value));
+
+        Self {
+            timeout,
+            download_timeout,
+            max_download_bytes,
+            retries,
+            download_buffer,
+            mirrors: preferences.download_mirrors.clone(),
+            offline,
+        }
+    }
+
+    /// Resolve settings against the process environment
+    pub fn from_env(overrides: NetworkOverrides, preferences: &Preferences) -> Self {
+        Self::resolve(overrides, |var| std::env::var(var).ok(), preferences)
+    }
+
+    /// Fail with a clear error instead of fetching `url` in offline mode
+    pub fn ensure_online(&self, url: &str) -> Result<()> {
+        if self.offline {
+            anyhow::bail!("Offline mode: not fetching {}", url);
+        }
+        Ok(())
+    }
+
+    /// The configured timeout, or `default` when none is set
+    pub fn timeout_or(&self, default: Duration) -> Duration {
+        self.timeout.unwrap_or(default)
+    }
+
+    /// Buffer size for reading and writing downloads
+    pub fn download_buffer_size(&self) -> usize {
+        self.download_buffer.unwrap_or(DEFAULT_DOWNLOAD_BUFFER)
+    }
+
+    /// Mirror URLs for a download, in configured order
+    ///
+    /// Each mirror whose prefix matches `url` contributes `url` with that prefix
+    /// replaced. The original URL is not included.
+    pub fn mirror_urls(&self, url: &str) -> Vec<String> {
+        self.mirrors
+            .iter()
+            .filter_map(|mirror| {
+                let rest = url.strip_prefix(&mirror.prefix)?;
+                Some(format!("{}{}", mirror.replace_with, rest))
+            })
+            .collect()
+    }
+}
+
+/// Parse a numeric environment variable, warning about unusable values
+fn parse_env<T: std::str::FromStr>(env: &impl Fn(&str) -> Option<String>, var: &str) -> Option<T> {
+    let value = env(var)?;
+    match value.trim().parse() {
+        Ok(parsed) => Some(parsed),
+        Err(_) => {
+            log::warn!("Ignoring {}={:?}: expected a whole number", var, value);
+            None
+        }
+    }
+}
+
+/// Whether an environment flag is switched on
+fn is_truthy(value: &str) -> bool {
+    matches!(
+        value.trim().to_lowercase().as_str(),
+        "1" | "true" | "yes" | "on"
+    )
+}
+
+/// Send a request, retrying network errors and HTTP 502/503/504/429 responses
+///
+/// Waits 1s, 2s, 4s, ... (capped at 8s) between attempts. Other statuses such
+/// as 404 or 401 are returned at once. The last response is returned as is, so
+/// callers still see the final HTTP status.
+pub fn send_with_retries(request: RequestBuilder, retries: u32, url: &str) -> Result<Response> {
+    let mut attempt = 0;
+    loop {
+        let current = request
+            .try_clone()
+            .context("Request body cannot be retried")?;
+
+        let failure = match current.send() {
+            Ok(response) if attempt < retries && is_transient_status(response.status()) => {
+                format!("HTTP {}", response.status())
+            }
+            Ok(response) => return Ok(response),
+            Err(e) if attempt < retries && is_transient_error(&e) => e.to_string(),
+            Err(e) => return Err(super::proxy::request_error(e, url)),
+        };
+
+        attempt += 1;
+        let delay = retry_delay(attempt);
+        log::debug!(
+            "Request to {} failed ({}), retrying in {}s ({}/{})",
+            url,
+            failure,
+            delay.as_secs(),
+            attempt,
+            retries
+        );
+        std::thread::sleep(delay);
+    }
+}
+
+/// Whether an HTTP status is worth retrying
+///
+/// Gateway errors and overload usually clear up; other server errors, like
+/// client errors, tend to repeat.
+fn is_transient_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+            | StatusCode::TOO_MANY_REQUESTS
+    )
+}
+
+/// Whether a failed request is worth retrying: refused or reset connections and timeouts
+fn is_transient_error(error: &reqwest::Error) -> bool {
+    error.is_connect() || error.is_timeout() || error.is_request()
+}
+
+/// Backoff before the given retry attempt (1-based)
+fn retry_delay(attempt: u32) -> Duration {
+    Duration::from_secs(1u64 << attempt.saturating_sub(1).min(3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env_from(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |var| vars.get(var).cloned()
+    }
+
+    #[test]
+    fn test_resolve_precedence() {
+        let prefs = Preferences {
+            timeout_secs: Some(60),
+            retries: Some(1),
+            ..Default::default()
+        };
+
+        // Default
+        let settings = NetworkSettings::resolve(
+            NetworkOverrides::default(),
+            env_from(&[]),
+            &Default::default(),
+        );
+        assert_eq!(settings, NetworkSettings::default());
+        assert_eq!(settings.retries, DEFAULT_RETRIES);
+
+        // Retries can be turned off
+        let env = env_from(&[(RETRIES_ENV, "0")]);
+        let settings = NetworkSettings::resolve(NetworkOverrides::default(), env, &prefs);
+        assert_eq!(settings.retries, 0);
+
+        // Preference
+        let settings = NetworkSettings::resolve(NetworkOverrides::default(), env_from(&[]), &prefs);
+        assert_eq!(settings.timeout, Some(Duration::from_secs(60)));
+        assert_eq!(settings.retries, 1);
+
+        // Env beats preference
+        let env = env_from(&[(TIMEOUT_ENV, "90"), (RETRIES_ENV, "2")]);
+        let settings = NetworkSettings::resolve(NetworkOverrides::default(), env, &prefs);
+        assert_eq!(settings.timeout, Some(Duration::from_secs(90)));
+        assert_eq!(settings.retries, 2);
+
+        // CLI beats env
+        let env = env_from(&[(TIMEOUT_ENV, "90"), (RETRIES_ENV, "2")]);
+        let overrides = NetworkOverrides {
+            timeout: Some(5),
+            retries: Some(4),
+            offline: false,
+        };
+        let settings = NetworkSettings::resolve(overrides, env, &prefs);
+        assert_eq!(settings.timeout, Some(Duration::from_secs(5)));
+        assert_eq!(settings.retries, 4);
+    }
+
+    #[test]
+    fn test_resolve_ignores_invalid_env() {
+        let prefs = Preferences {
+            timeout_secs: Some(60),
+            ..Default::default()
+        };
+        let env = env_from(&[(TIMEOUT_ENV, "soon"), (RETRIES_ENV, "-1")]);
+        let settings = NetworkSettings::resolve(NetworkOverrides::default(), env, &prefs);
+        assert_eq!(settings.timeout, Some(Duration::from_secs(60)));
+        assert_eq!(settings.retries, DEFAULT_RETRIES);
+    }
+
+    #[test]
+    fn test_resolve_offline() {
+        let prefs = Preferences::default();
+        let offline = |overrides, env: &[(&str, &str)]| {
+            NetworkSettings::resolve(overrides, env_from(env), &prefs).offline
+        };
+        let flag = NetworkOverrides {
+            offline: true,
+            ..Default::default()
+        };
+
+        assert!(!offline(NetworkOverrides::default(), &[]));
+        assert!(offline(flag, &[]));
+        assert!(offline(NetworkOverrides::default(), &[(OFFLINE_ENV, "1")]));
+        assert!(offline(
+            NetworkOverrides::default(),
+            &[(OFFLINE_ENV, "TRUE")]
+        ));
+        assert!(!offline(NetworkOverrides::default(), &[(OFFLINE_ENV, "0")]));
+
+        let settings = NetworkSettings {
+            offline: true,
+            ..Default::default()
+        };
+        let err = settings.ensure_online("https://example.com/x").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Offline mode: not fetching https://example.com/x"
+        );
+        assert!(NetworkSettings::default()
+            .ensure_online("https://example.com/x")
+            .is_ok());
+    }
+
+    #[test]
+    fn test_resolve_download_limits() {
+        let resolve = |overrides, env: &[(&str, &str)], prefs: &Preferences| {
+            NetworkSettings::resolve(overrides, env_from(env), prefs)
+        };
+        let none = Preferences::default();
+        let settings = resolve(NetworkOverrides::default(), &[], &none);
+        assert_eq!(settings.download_timeout, DEFAULT_DOWNLOAD_TIMEOUT);
+        assert_eq!(settings.max_download_bytes, DEFAULT_MAX_DOWNLOAD_BYTES);
+
+        // timeout_secs applies to downloads when it is the only setting
+        let general = Preferences {
+            timeout_secs: Some(60),
+            ..Default::default()
+        };
+        let settings = resolve(NetworkOverrides::default(), &[], &general);
+        assert_eq!(settings.download_timeout, Duration::from_secs(60));
+
+        let prefs = Preferences {
+            timeout_secs: Some(60),
+            download_timeout_secs: Some(600),
+            max_download_bytes: Some(1024),
+            ..Default::default()
+        };
+        let settings = resolve(NetworkOverrides::default(), &[], &prefs);
+        assert_eq!(settings.timeout, Some(Duration::from_secs(60)));
+        assert_eq!(settings.download_timeout, Duration::from_secs(600));
+        assert_eq!(settings.max_download_bytes, 1024);
+
+        // The environment and CLI override both
+        let settings = resolve(NetworkOverrides::default(), &[(TIMEOUT_ENV, "90")], &prefs);
+        assert_eq!(settings.download_timeout, Duration::from_secs(90));
+        let flag = NetworkOverrides {
+            timeout: Some(5),
+            ..Default::default()
+        };
+        assert_eq!(
+            resolve(flag, &[], &prefs).download_timeout,
+            Duration::from_secs(5)
+        );
+    }
+
+    #[test]
+    fn test_download_buffer_size() {
+        let settings = NetworkSettings::default();
+        assert_eq!(settings.download_buffer_size(), DEFAULT_DOWNLOAD_BUFFER);
+
+        let resolve = |kib| {
+            let prefs = Preferences {
+                download_buffer_kib: Some(kib),
+                ..Default::default()
+            };
+            NetworkSettings::resolve(NetworkOverrides::default(), env_from(&[]), &prefs)
+                .download_buffer_size()
+        };
+        assert_eq!(resolve(256), 256 * 1024);
+        // Out-of-range values are clamped so memory stays bounded
+        assert_eq!(resolve(0), 4 * 1024);
+        assert_eq!(resolve(1 << 20), 4096 * 1024);
+    }
+
+    #[test]
+    fn test_mirror_urls() {
+        let mirror = |prefix: &str, replace_with: &str| DownloadMirror {
+            prefix: prefix.to_string(),
+            replace_with: replace_with.to_string(),
+        };
+        let prefs = Preferences {
+            download_mirrors: vec![
+                mirror("https://github.com/", "https://m1.example.com/gh/"),
+                mirror("https://example.org/", "https://m2.example.com/"),
+                mirror("https://github.com/", "https://m3.example.com/"),
+            ],
+            ..Default::default()
+        };
+        let settings = NetworkSettings::resolve(NetworkOverrides::default(), env_from(&[]), &prefs);
+
+        assert_eq!(
+            settings.mirror_urls("https://github.com/o/r/releases/download/v1/r.tar.gz"),
+            vec![
+                "https://m1.example.com/gh/o/r/releases/download/v1/r.tar.gz",
+                "https://m3.example.com/o/r/releases/download/v1/r.tar.gz",
+            ]
+        );
+        assert!(settings
+            .mirror_urls("https://gitlab.com/o/r/r.tar.gz")
+            .is_empty());
+        assert!(NetworkSettings::default()
+            .mirror_urls("https://github.com/o/r")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_timeout_or() {
+        let settings = NetworkSettings::default();
+        assert_eq!(
+            settings.timeout_or(Duration::from_secs(30)),
+            Duration::from_secs(30)
+        );
+
+        let settings = NetworkSettings {
+            timeout: Some(Duration::from_secs(5)),
+            ..Default::default()
+        };
+        assert_eq!(
+            settings.timeout_or(Duration::from_secs(30)),
+            Duration::from_secs(5)
+        );
+    }
+
+    #[test]
+    fn test_transient_status() {
+        for code in [502, 503, 504, 429] {
+            assert!(is_transient_status(StatusCode::from_u16(code).unwrap()));
+        }
+        for code in [200, 401, 403, 404, 500, 501] {
+            assert!(!is_transient_status(StatusCode::from_u16(code).unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        assert_eq!(retry_delay(1), Duration::from_secs(1));
+        assert_eq!(retry_delay(2), Duration::from_secs(2));
+        assert_eq!(retry_delay(4), Duration::from_secs(8));
+        assert_eq!(retry_delay(10), Duration::from_secs(8));
+    }
+}
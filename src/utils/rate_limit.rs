@@ -0,0 +1,313 @@
+//! Pacing of GitHub API requests
+//!
+//! All requests to api.github.com share one budget per process, taken from the
+//! `x-ratelimit-remaining` and `x-ratelimit-reset` headers of the responses.
+//! While plenty of it is left, requests go out immediately. When it runs low,
+//! the remaining requests are spread over the time until the reset. Once it is
+//! exhausted, requests wait for the reset instead of failing. A response saying
+//! the limit was hit (including GitHub's secondary limit, which sends
+//! `retry-after`) is retried after the wait GitHub asks for.
+//!
+//! Requests to other hosts (bucket manifests, release downloads) are sent as is.
+
+use super::network::send_with_retries;
+use anyhow::{Context, Result};
+use reqwest::blocking::{RequestBuilder, Response};
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Remaining requests below which requests are spread until the reset
+const LOW_BUDGET: u64 = 50;
+
+/// Requests answered with "rate limit exceeded" are retried this many times
+const MAX_LIMIT_RETRIES: u32 = 3;
+
+/// Longest wait for a reset; GitHub's windows are one hour
+const MAX_WAIT: Duration = Duration::from_secs(3600);
+
+/// Slack after the reset time, for clock differences with GitHub
+const RESET_MARGIN: Duration = Duration::from_secs(1);
+
+/// Remaining requests below which a warning is logged
+const WARN_REMAINING: u64 = 5;
+
+/// Waits at least this long are announced, shorter ones only logged at debug level
+const ANNOUNCE_WAIT: Duration = Duration::from_secs(5);
+
+/// Budget shared by every GitHub API request of the process
+static BUDGET: Mutex<ApiBudget> = Mutex::new(ApiBudget::new());
+
+/// What is known about the current rate limit window
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ApiBudget {
+    /// Requests left, as last reported minus the requests sent since
+    remaining: Option<u64>,
+    /// When the window resets, since the Unix epoch
+    reset: Option<Duration>,
+    /// Earliest time the next paced request may go out, since the Unix epoch
+    next_slot: Option<Duration>,
+}
+
+impl ApiBudget {
+    const fn new() -> Self {
+        Self {
+            remaining: None,
+            reset: None,
+            next_slot: None,
+        }
+    }
+
+    /// Reserve a request and return how long to wait before sending it
+    fn reserve(&mut self, now: Duration) -> Duration {
+        let (Some(remaining), Some(reset)) = (self.remaining, self.reset) else {
+            return Duration::ZERO;
+        };
+        if now >= reset {
+            // A new window started; its budget is learned from the next response
+            *self = Self::new();
+            return Duration::ZERO;
+        }
+
+        if remaining == 0 {
+            return (reset + RESET_MARGIN - now).min(MAX_WAIT);
+        }
+        self.remaining = Some(remaining - 1);
+        if remaining > LOW_BUDGET {
+            return Duration::ZERO;
+        }
+
+        // Spread what is left evenly over the rest of the window
+        let interval = (reset - now) / remaining as u32;
+        let slot = self.next_slot.map_or(now, |slot| slot.max(now));
+        self.next_slot = Some(slot + interval);
+        slot - now
+    }
+
+    /// Take the rate limit reported by a response
+    fn observe(&mut self, remaining: u64, reset: Duration) {
+        if self.reset == Some(reset) {
+            // Responses of one window can arrive out of order
+            self.remaining = Some(self.remaining.map_or(remaining, |r| r.min(remaining)));
+        } else if self.reset.is_none_or(|current| reset > current) {
+            *self = Self {
+                remaining: Some(remaining),
+                reset: Some(reset),
+                next_slot: None,
+            };
+        }
+    }
+}
+
+/// Whether a URL is a GitHub API request
+pub(crate) fn is_github_api(url: &str) -> bool {
+    url.strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .is_some_and(|rest| {
+            rest.split(['/', '?'])
+                .next()
+                .is_some_and(|host| host.eq_ignore_ascii_case("api.github.com"))
+        })
+}
+
+/// The `x-ratelimit-remaining` and `x-ratelimit-reset` values of a response
+fn rate_limit_headers(headers: &HeaderMap) -> Option<(u64, Duration)> {
+    let number = |name: &str| headers.get(name)?.to_str().ok()?.trim().parse::<u64>().ok();
+    Some((
+        number("x-ratelimit-remaining")?,
+        Duration::from_secs(number("x-ratelimit-reset")?),
+    ))
+}
+
+/// How long to wait before retrying a response that hit the rate limit
+///
+/// None when the response isn't a rate limit error.
+fn limit_wait(status: StatusCode, headers: &HeaderMap, now: Duration) -> Option<Duration> {
+    if status != StatusCode::FORBIDDEN && status != StatusCode::TOO_MANY_REQUESTS {
+        return None;
+    }
+    let retry_after = headers
+        .get("retry-after")
+        .and_then(|value| value.to_str().ok()?.trim().parse::<u64>().ok());
+    if let Some(secs) = retry_after {
+        return Some(Duration::from_secs(secs).min(MAX_WAIT));
+    }
+    match rate_limit_headers(headers) {
+        Some((0, reset)) => Some((reset + RESET_MARGIN).saturating_sub(now).min(MAX_WAIT)),
+        _ => None,
+    }
+}
+
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+fn wait(duration: Duration, url: &str) {
+    if duration.is_zero() {
+        return;
+    }
+    if duration >= ANNOUNCE_WAIT {
+        log::warn!(
+            "Waiting {}s for the GitHub API rate limit before continuing",
+            duration.as_secs()
+        );
+    } else {
+        log::debug!("Pacing GitHub API request to {} by {:?}", url, duration);
+    }
+    std::thread::sleep(duration);
+}
+
+/// Send a request like [`send_with_retries`], pacing GitHub API requests
+///
+/// Requests to other hosts are passed straight through.
+pub fn send(request: RequestBuilder, retries: u32, url: &str) -> Result<Response> {
+    if !is_github_api(url) {
+        return send_with_retries(request, retries, url);
+    }
+
+    let mut attempt = 0;
+    loop {
+        let delay = BUDGET.lock().unwrap().reserve(now());
+        wait(delay, url);
+
+        let current = request
+            .try_clone()
+            .context("Request body cannot be retried")?;
+        let response = send_with_retries(current, retries, url)?;
+
+        if let Some((remaining, reset)) = rate_limit_headers(response.headers()) {
+            BUDGET.lock().unwrap().observe(remaining, reset);
+            if remaining < WARN_REMAINING {
+                log::warn!(
+                    "Only {} GitHub API request(s) left in this rate limit window; set WENGET_GITHUB_TOKEN for a higher limit",
+                    remaining
+                );
+            }
+        }
+
+        match limit_wait(response.status(), response.headers(), now()) {
+            Some(delay) if attempt < MAX_LIMIT_RETRIES => {
+                attempt += 1;
+                wait(delay.max(RESET_MARGIN), url);
+            }
+            _ => return Ok(response),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(s: u64) -> Duration {
+        Duration::from_secs(s)
+    }
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, value.parse().unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn test_is_github_api() {
+        assert!(is_github_api(
+            "https://api.github.com/repos/o/r/releases/latest"
+        ));
+        assert!(is_github_api("https://API.github.com/rate_limit"));
+        assert!(!is_github_api(
+            "https://github.com/o/r/releases/download/v1/r.tar.gz"
+        ));
+        assert!(!is_github_api(
+            "https://raw.githubusercontent.com/o/r/main/manifest.json"
+        ));
+        assert!(!is_github_api("https://api.github.com.example.com/x"));
+    }
+
+    #[test]
+    fn test_budget_paces_when_low() {
+        let mut budget = ApiBudget::new();
+        // Unknown budget: no waiting
+        assert_eq!(budget.reserve(secs(1000)), Duration::ZERO);
+
+        // Plenty left
+        budget.observe(4000, secs(2000));
+        assert_eq!(budget.reserve(secs(1000)), Duration::ZERO);
+        assert_eq!(budget.remaining, Some(3999));
+
+        // Low: 10 requests over 1000s are spaced 100s apart
+        budget.observe(10, secs(2000));
+        assert_eq!(budget.reserve(secs(1000)), Duration::ZERO);
+        assert_eq!(budget.reserve(secs(1000)), secs(100));
+        assert_eq!(budget.remaining, Some(8));
+
+        // Exhausted: wait for the reset
+        budget.observe(0, secs(2000));
+        assert_eq!(budget.reserve(secs(1500)), secs(501));
+        assert_eq!(budget.remaining, Some(0));
+
+        // After the reset the budget is unknown again
+        assert_eq!(budget.reserve(secs(2001)), Duration::ZERO);
+        assert_eq!(budget, ApiBudget::new());
+    }
+
+    #[test]
+    fn test_budget_observe() {
+        let mut budget = ApiBudget::new();
+        budget.observe(100, secs(2000));
+        // A late response of the same window doesn't raise the budget
+        budget.observe(120, secs(2000));
+        assert_eq!(budget.remaining, Some(100));
+        // A stale response of the previous window is ignored
+        budget.observe(5, secs(1000));
+        assert_eq!(budget.remaining, Some(100));
+        // A new window replaces it
+        budget.observe(5000, secs(5600));
+        assert_eq!(budget.remaining, Some(5000));
+        assert_eq!(budget.reset, Some(secs(5600)));
+    }
+
+    #[test]
+    fn test_limit_wait() {
+        let now = secs(1000);
+        let exhausted = headers(&[
+            ("x-ratelimit-remaining", "0"),
+            ("x-ratelimit-reset", "1060"),
+        ]);
+        assert_eq!(
+            limit_wait(StatusCode::FORBIDDEN, &exhausted, now),
+            Some(secs(61))
+        );
+        assert_eq!(
+            limit_wait(StatusCode::TOO_MANY_REQUESTS, &exhausted, now),
+            Some(secs(61))
+        );
+        // Secondary limit
+        assert_eq!(
+            limit_wait(
+                StatusCode::FORBIDDEN,
+                &headers(&[("retry-after", "30")]),
+                now
+            ),
+            Some(secs(30))
+        );
+        // Plain 403s and successful responses are not rate limit errors
+        let left = headers(&[
+            ("x-ratelimit-remaining", "12"),
+            ("x-ratelimit-reset", "1060"),
+        ]);
+        assert_eq!(limit_wait(StatusCode::FORBIDDEN, &left, now), None);
+        assert_eq!(limit_wait(StatusCode::OK, &exhausted, now), None);
+        // Waits are capped
+        let far = headers(&[
+            ("x-ratelimit-remaining", "0"),
+            ("x-ratelimit-reset", "999999"),
+        ]);
+        assert_eq!(limit_wait(StatusCode::FORBIDDEN, &far, now), Some(MAX_WAIT));
+    }
+}